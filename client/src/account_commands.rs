@@ -20,6 +20,8 @@ impl Command for AccountCommand {
             Box::new(AccountCommandRecoverWallet {}),
             Box::new(AccountCommandWriteRecovery {}),
             Box::new(AccountCommandMint {}),
+            Box::new(AccountCommandEnableNonces {}),
+            Box::new(AccountCommandAcceptCurrency {}),
         ];
 
         subcommand_execute(&params[0], commands, client, &params[1..]);
@@ -124,13 +126,15 @@ impl Command for AccountCommandMint {
         vec!["mint", "mintb", "m", "mb"]
     }
     fn get_params_help(&self) -> &'static str {
-        "<receiver_account_ref_id>|<receiver_account_address> <number_of_coins>"
+        "<receiver_account_ref_id>|<receiver_account_address> <number_of_coins> \
+         [currency_code (default=LBR)]"
     }
     fn get_description(&self) -> &'static str {
-        "Mint coins to the account. Suffix 'b' is for blocking"
+        "Mint coins to the account. Currencies other than LBR need a faucet account that is their \
+         minter. Suffix 'b' is for blocking"
     }
     fn execute(&self, client: &mut ClientProxy, params: &[&str]) {
-        if params.len() != 3 {
+        if params.len() != 3 && params.len() != 4 {
             println!("Invalid number of arguments for mint");
             return;
         }
@@ -150,3 +154,69 @@ impl Command for AccountCommandMint {
         }
     }
 }
+
+/// Sub command to let an account accept nonce transactions.
+pub struct AccountCommandEnableNonces {}
+
+impl Command for AccountCommandEnableNonces {
+    fn get_aliases(&self) -> Vec<&'static str> {
+        vec!["enable_nonces", "enable_noncesb", "en", "enb"]
+    }
+    fn get_params_help(&self) -> &'static str {
+        "<account_ref_id>|<account_address>"
+    }
+    fn get_description(&self) -> &'static str {
+        "Let the account send nonce transactions with transfer_nonce. Suffix 'b' is for blocking"
+    }
+    fn execute(&self, client: &mut ClientProxy, params: &[&str]) {
+        if params.len() != 2 {
+            println!("Invalid number of arguments for enabling nonces");
+            return;
+        }
+        println!(">> Enabling nonces");
+        let is_blocking = blocking_cmd(params[0]);
+        match client.enable_nonces(&params, is_blocking) {
+            Ok(_) => {
+                if is_blocking {
+                    println!("Finished enabling nonces!");
+                } else {
+                    println!("Enable nonces request submitted");
+                }
+            }
+            Err(e) => report_error("Error enabling nonces", e),
+        }
+    }
+}
+
+/// Sub command to let an account accept coins of a currency other than LBR.
+pub struct AccountCommandAcceptCurrency {}
+
+impl Command for AccountCommandAcceptCurrency {
+    fn get_aliases(&self) -> Vec<&'static str> {
+        vec!["accept_currency", "accept_currencyb", "ac", "acb"]
+    }
+    fn get_params_help(&self) -> &'static str {
+        "<account_ref_id>|<account_address> <currency_code>"
+    }
+    fn get_description(&self) -> &'static str {
+        "Let the account receive coins of a registered currency. Suffix 'b' is for blocking"
+    }
+    fn execute(&self, client: &mut ClientProxy, params: &[&str]) {
+        if params.len() != 3 {
+            println!("Invalid number of arguments for accepting a currency");
+            return;
+        }
+        println!(">> Accepting currency");
+        let is_blocking = blocking_cmd(params[0]);
+        match client.accept_currency(&params, is_blocking) {
+            Ok(_) => {
+                if is_blocking {
+                    println!("Finished accepting currency!");
+                } else {
+                    println!("Accept currency request submitted");
+                }
+            }
+            Err(e) => report_error("Error accepting currency", e),
+        }
+    }
+}
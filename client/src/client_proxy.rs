@@ -10,14 +10,17 @@ use libra_types::{
     access_path::AccessPath,
     account_address::{AccountAddress, ADDRESS_LENGTH},
     account_config::{
-        association_address, core_code_address, get_account_resource_or_default, AccountResource,
-        ACCOUNT_RECEIVED_EVENT_PATH, ACCOUNT_SENT_EVENT_PATH,
+        association_address, core_code_address, get_account_resource_or_default, AccountEvent,
+        AccountResource, CapabilityEvent, CreateAccountEvent, CurrencyBalancesResource,
+        KeyRotationEvent, ACCOUNT_CREATION_EVENT_PATH, ACCOUNT_KEY_ROTATION_CAPABILITY_EVENT_PATH,
+        ACCOUNT_KEY_ROTATION_EVENT_PATH, ACCOUNT_RECEIVED_EVENT_PATH, ACCOUNT_SENT_EVENT_PATH,
+        ACCOUNT_WITHDRAWAL_CAPABILITY_EVENT_PATH,
     },
     account_state_blob::{AccountStateBlob, AccountStateWithProof},
     contract_event::{ContractEvent, EventWithProof},
     transaction::{
-        parse_as_transaction_argument, RawTransaction, Script, SignedTransaction,
-        TransactionPayload, Version,
+        parse_as_transaction_argument, sequence_number_from_nonce, RawTransaction, Script,
+        SignedTransaction, Transaction, TransactionInfo, TransactionPayload, Version, NONCE_FLAG,
     },
     transaction_helpers::{create_signed_txn, create_unsigned_txn, TransactionSigner},
 };
@@ -28,11 +31,13 @@ use num_traits::{
     identities::Zero,
 };
 use reqwest;
+use resource_viewer::{AnnotatedAccountState, MoveValueAnnotator};
 use rust_decimal::Decimal;
+use serde::Deserialize;
 use serde_json;
 use std::{
     collections::{BTreeMap, HashMap},
-    convert::TryFrom,
+    convert::{TryFrom, TryInto},
     fmt, fs,
     io::{stdout, Write},
     path::{Display, Path, PathBuf},
@@ -47,6 +52,9 @@ const CLIENT_WALLET_MNEMONIC_FILE: &str = "client.mnemonic";
 const GAS_UNIT_PRICE: u64 = 0;
 const MAX_GAS_AMOUNT: u64 = 140_000;
 const TX_EXPIRATION: i64 = 100;
+/// The currency code the client uses for LibraCoin. Any other code refers to a currency
+/// registered with the Currency module.
+const LIBRA_CURRENCY_CODE: &str = "LBR";
 
 /// Enum used for error formatting.
 #[derive(Debug)]
@@ -56,6 +64,19 @@ enum InputType {
     Usize,
 }
 
+/// A module of a bundle written by the compiler's package build.
+#[derive(Debug, Deserialize)]
+struct BundleEntry {
+    address: String,
+    path: PathBuf,
+}
+
+/// The modules of a package build, in the order they have to be published.
+#[derive(Debug, Deserialize)]
+struct Bundle {
+    modules: Vec<BundleEntry>,
+}
+
 /// Account data is stored in a map and referenced by an index.
 #[derive(Debug)]
 pub struct AddressAndIndex {
@@ -230,18 +251,25 @@ impl ClientProxy {
         ret
     }
 
-    /// Get balance from validator for the account specified.
+    /// Get balance from validator for the account specified, in LBR unless a currency code is
+    /// given.
     pub fn get_balance(&mut self, space_delim_strings: &[&str]) -> Result<String> {
         ensure!(
-            space_delim_strings.len() == 2,
+            space_delim_strings.len() == 2 || space_delim_strings.len() == 3,
             "Invalid number of arguments for getting balance"
         );
         let address = self.get_account_address_from_parameter(space_delim_strings[1])?;
-        self.get_account_resource_and_update(address).map(|res| {
-            let whole_num = res.balance() / 1_000_000;
-            let remainder = res.balance() % 1_000_000;
-            format!("{}.{:0>6}", whole_num.to_string(), remainder.to_string())
-        })
+        let balance = match Self::parse_currency_code(space_delim_strings.get(2)) {
+            Some(currency_code) => self.get_currency_balance(address, currency_code)?,
+            None => self.get_account_resource_and_update(address)?.balance(),
+        };
+        let whole_num = balance / 1_000_000;
+        let remainder = balance % 1_000_000;
+        Ok(format!(
+            "{}.{:0>6}",
+            whole_num.to_string(),
+            remainder.to_string()
+        ))
     }
 
     /// Get the latest sequence number from validator for the account specified.
@@ -275,18 +303,28 @@ impl ClientProxy {
         Ok(sequence_number)
     }
 
-    /// Mints coins for the receiver specified.
+    /// Mints coins for the receiver specified, in LBR unless a currency code is given. Currencies
+    /// other than LBR can only be minted with a local faucet account that is their minter.
     pub fn mint_coins(&mut self, space_delim_strings: &[&str], is_blocking: bool) -> Result<()> {
         ensure!(
-            space_delim_strings.len() == 3,
+            space_delim_strings.len() == 3 || space_delim_strings.len() == 4,
             "Invalid number of arguments for mint"
         );
         let receiver = self.get_account_address_from_parameter(space_delim_strings[1])?;
         let num_coins = Self::convert_to_micro_libras(space_delim_strings[2])?;
-
-        match self.faucet_account {
-            Some(_) => self.mint_coins_with_local_faucet_account(&receiver, num_coins, is_blocking),
-            None => self.mint_coins_with_faucet_service(&receiver, num_coins, is_blocking),
+        let currency_code = Self::parse_currency_code(space_delim_strings.get(3));
+
+        match (self.faucet_account.is_some(), currency_code) {
+            (true, _) => self.mint_coins_with_local_faucet_account(
+                &receiver,
+                num_coins,
+                currency_code,
+                is_blocking,
+            ),
+            (false, None) => self.mint_coins_with_faucet_service(&receiver, num_coins, is_blocking),
+            (false, Some(currency_code)) => {
+                bail!("Minting {} requires a local faucet account", currency_code)
+            }
         }
     }
 
@@ -298,7 +336,7 @@ impl ClientProxy {
             stdout().flush().unwrap();
             max_iterations -= 1;
 
-            if let Ok(Some((_, Some(events)))) =
+            if let Ok(Some((_, _, Some(events)))) =
                 self.client
                     .get_txn_by_acc_seq(account, sequence_number - 1, true)
             {
@@ -316,28 +354,42 @@ impl ClientProxy {
         }
     }
 
-    /// Transfer num_coins from sender account to receiver. If is_blocking = true,
-    /// it will keep querying validator till the sequence number is bumped up in validator.
+    /// Transfer num_coins from sender account to receiver, in LBR unless a `currency_code` is
+    /// given. If is_blocking = true, it will keep querying validator till the sequence number is
+    /// bumped up in validator.
+    /// With a `nonce`, the transfer is a nonce transaction that doesn't use the next sequence
+    /// number of the sender.
     pub fn transfer_coins_int(
         &mut self,
         sender_account_ref_id: usize,
         receiver_address: &AccountAddress,
         num_coins: u64,
+        currency_code: Option<&str>,
         gas_unit_price: Option<u64>,
         max_gas_amount: Option<u64>,
+        nonce: Option<u64>,
         is_blocking: bool,
     ) -> Result<IndexAndSequence> {
         let sender_address;
-        let sender_sequence;
+        let txn_sequence_number;
         {
             let sender = self.accounts.get(sender_account_ref_id).ok_or_else(|| {
                 format_err!("Unable to find sender account: {}", sender_account_ref_id)
             })?;
-
-            let program = transaction_builder::encode_transfer_script(&receiver_address, num_coins);
-            let req = self.create_submit_transaction_req(
+            txn_sequence_number = nonce.map_or(sender.sequence_number, sequence_number_from_nonce);
+
+            let program = match currency_code {
+                Some(currency_code) => transaction_builder::encode_currency_transfer_script(
+                    &receiver_address,
+                    currency_code,
+                    num_coins,
+                ),
+                None => transaction_builder::encode_transfer_script(&receiver_address, num_coins),
+            };
+            let req = self.create_submit_transaction_req_with_sequence_number(
                 TransactionPayload::Script(program),
                 sender,
+                txn_sequence_number,
                 max_gas_amount, /* max_gas_amount */
                 gas_unit_price, /* gas_unit_price */
             )?;
@@ -347,18 +399,23 @@ impl ClientProxy {
                 .ok_or_else(|| {
                     format_err!("Unable to find sender account: {}", sender_account_ref_id)
                 })?;
-            self.client.submit_transaction(Some(sender_mut), &req)?;
             sender_address = sender_mut.address;
-            sender_sequence = sender_mut.sequence_number;
+            // Nonce transactions don't bump up the sequence number of the sender
+            let sender_opt = if nonce.is_none() {
+                Some(sender_mut)
+            } else {
+                None
+            };
+            self.client.submit_transaction(sender_opt, &req)?;
         }
 
         if is_blocking {
-            self.wait_for_transaction(sender_address, sender_sequence);
+            self.wait_for_transaction(sender_address, txn_sequence_number + 1);
         }
 
         Ok(IndexAndSequence {
             account_index: AccountEntry::Index(sender_account_ref_id),
-            sequence_number: sender_sequence - 1,
+            sequence_number: txn_sequence_number,
         })
     }
 
@@ -389,9 +446,48 @@ impl ClientProxy {
         &mut self,
         space_delim_strings: &[&str],
         is_blocking: bool,
+    ) -> Result<IndexAndSequence> {
+        self.transfer_coins_impl(space_delim_strings, None, is_blocking)
+    }
+
+    /// Transfers coins from sender to receiver with a nonce transaction, which can be executed
+    /// independently of the other transactions of the sender.
+    pub fn transfer_coins_with_nonce(
+        &mut self,
+        space_delim_strings: &[&str],
+        is_blocking: bool,
+    ) -> Result<IndexAndSequence> {
+        ensure!(
+            space_delim_strings.len() >= 5 && space_delim_strings.len() <= 8,
+            "Invalid number of arguments for transfer with nonce"
+        );
+        let nonce = space_delim_strings[4].parse::<u64>().map_err(|error| {
+            format_parse_data_error(
+                "nonce",
+                InputType::UnsignedInt,
+                space_delim_strings[4],
+                error,
+            )
+        })?;
+        ensure!(
+            nonce < NONCE_FLAG,
+            "Nonce must be lower than {}",
+            NONCE_FLAG
+        );
+
+        let mut transfer_params = space_delim_strings.to_vec();
+        transfer_params.remove(4);
+        self.transfer_coins_impl(&transfer_params, Some(nonce), is_blocking)
+    }
+
+    fn transfer_coins_impl(
+        &mut self,
+        space_delim_strings: &[&str],
+        nonce: Option<u64>,
+        is_blocking: bool,
     ) -> Result<IndexAndSequence> {
         ensure!(
-            space_delim_strings.len() >= 4 && space_delim_strings.len() <= 6,
+            space_delim_strings.len() >= 4 && space_delim_strings.len() <= 7,
             "Invalid number of arguments for transfer"
         );
 
@@ -401,12 +497,25 @@ impl ClientProxy {
 
         let num_coins = Self::convert_to_micro_libras(space_delim_strings[3])?;
 
-        let gas_unit_price = if space_delim_strings.len() > 4 {
-            Some(space_delim_strings[4].parse::<u64>().map_err(|error| {
+        // The currency code is optional and, unlike the gas parameters, isn't a number
+        let (currency_code, gas_params) = match space_delim_strings.get(4) {
+            Some(param) if param.parse::<u64>().is_err() => (
+                Self::parse_currency_code(Some(param)),
+                &space_delim_strings[5..],
+            ),
+            _ => (None, &space_delim_strings[4..]),
+        };
+        ensure!(
+            gas_params.len() <= 2,
+            "Invalid number of arguments for transfer"
+        );
+
+        let gas_unit_price = if !gas_params.is_empty() {
+            Some(gas_params[0].parse::<u64>().map_err(|error| {
                 format_parse_data_error(
                     "gas_unit_price",
                     InputType::UnsignedInt,
-                    space_delim_strings[4],
+                    gas_params[0],
                     error,
                 )
             })?)
@@ -414,12 +523,12 @@ impl ClientProxy {
             None
         };
 
-        let max_gas_amount = if space_delim_strings.len() > 5 {
-            Some(space_delim_strings[5].parse::<u64>().map_err(|error| {
+        let max_gas_amount = if gas_params.len() > 1 {
+            Some(gas_params[1].parse::<u64>().map_err(|error| {
                 format_parse_data_error(
                     "max_gas_amount",
                     InputType::UnsignedInt,
-                    space_delim_strings[5],
+                    gas_params[1],
                     error,
                 )
             })?)
@@ -433,12 +542,73 @@ impl ClientProxy {
             sender_account_ref_id,
             &receiver_address,
             num_coins,
+            currency_code,
             gas_unit_price,
             max_gas_amount,
+            nonce,
             is_blocking,
         )
     }
 
+    /// Lets the account accept nonce transactions.
+    pub fn enable_nonces(&mut self, space_delim_strings: &[&str], is_blocking: bool) -> Result<()> {
+        ensure!(
+            space_delim_strings.len() == 2,
+            "Invalid number of arguments for enabling nonces"
+        );
+        let program = transaction_builder::encode_enable_nonces_script();
+        self.submit_program_from_account(space_delim_strings[1], program, is_blocking)
+    }
+
+    /// Lets the account accept coins of a currency other than LBR.
+    pub fn accept_currency(
+        &mut self,
+        space_delim_strings: &[&str],
+        is_blocking: bool,
+    ) -> Result<()> {
+        ensure!(
+            space_delim_strings.len() == 3,
+            "Invalid number of arguments for accepting a currency"
+        );
+        let currency_code = Self::parse_currency_code(Some(&space_delim_strings[2]))
+            .ok_or_else(|| format_err!("Every account accepts {}", LIBRA_CURRENCY_CODE))?;
+        let program = transaction_builder::encode_accept_currency_script(currency_code);
+        self.submit_program_from_account(space_delim_strings[1], program, is_blocking)
+    }
+
+    /// Submit `program` from the account given as `account_param`, which is bumped to its next
+    /// sequence number.
+    fn submit_program_from_account(
+        &mut self,
+        account_param: &str,
+        program: Script,
+        is_blocking: bool,
+    ) -> Result<()> {
+        let address = self.get_account_address_from_parameter(account_param)?;
+        let account_ref_id = self.get_account_ref_id(&address)?;
+        let sender = self
+            .accounts
+            .get(account_ref_id)
+            .ok_or_else(|| format_err!("Unable to find sender account: {}", account_ref_id))?;
+
+        let req = self.create_submit_transaction_req(
+            TransactionPayload::Script(program),
+            sender,
+            None, /* max_gas_amount */
+            None, /* gas_unit_price */
+        )?;
+        let sender_mut = self
+            .accounts
+            .get_mut(account_ref_id)
+            .ok_or_else(|| format_err!("Unable to find sender account: {}", account_ref_id))?;
+        self.client.submit_transaction(Some(sender_mut), &req)?;
+        if is_blocking {
+            let sequence_number = self.accounts[account_ref_id].sequence_number;
+            self.wait_for_transaction(address, sequence_number);
+        }
+        Ok(())
+    }
+
     /// Compile move program
     pub fn compile_program(&mut self, space_delim_strings: &[&str]) -> Result<String> {
         let address = self.get_account_address_from_parameter(space_delim_strings[1])?;
@@ -568,6 +738,20 @@ impl ClientProxy {
         self.submit_program(space_delim_strings, TransactionPayload::Module(module))
     }
 
+    /// Publish the modules of a package bundle in order, each from the account it is built for
+    pub fn publish_bundle(&mut self, space_delim_strings: &[&str]) -> Result<usize> {
+        let bundle: Bundle = serde_json::from_slice(&fs::read(space_delim_strings[1])?)?;
+        for entry in &bundle.modules {
+            let path = entry
+                .path
+                .to_str()
+                .ok_or_else(|| format_err!("Invalid module path {}", entry.path.display()))?;
+            self.publish_module(&[space_delim_strings[0], &entry.address, path])
+                .map_err(|err| format_err!("Failed to publish {}: {}", path, err))?;
+        }
+        Ok(bundle.modules.len())
+    }
+
     /// Execute custom script
     pub fn execute_script(&mut self, space_delim_strings: &[&str]) -> Result<()> {
         let script: Script = serde_json::from_slice(&fs::read(space_delim_strings[2])?)?;
@@ -595,11 +779,51 @@ impl ClientProxy {
         self.get_account_state_and_update(account)
     }
 
+    /// Get the latest account state from validator, with its resources decoded using the modules
+    /// published under the core code address, the account itself and the optional extra addresses.
+    pub fn get_latest_annotated_account_state(
+        &mut self,
+        space_delim_strings: &[&str],
+    ) -> Result<(Option<AnnotatedAccountState>, Version)> {
+        ensure!(
+            space_delim_strings.len() >= 2,
+            "Invalid number of arguments to get latest account state"
+        );
+        let account = self.get_account_address_from_parameter(space_delim_strings[1])?;
+        let mut module_addresses = vec![core_code_address(), account];
+        for param in &space_delim_strings[2..] {
+            module_addresses.push(self.get_account_address_from_parameter(param)?);
+        }
+        module_addresses.sort();
+        module_addresses.dedup();
+
+        let (account_state, version) = self.get_account_state_and_update(account)?;
+        let account_state = match account_state {
+            Some(account_state) => account_state,
+            None => return Ok((None, version)),
+        };
+        let mut annotator = MoveValueAnnotator::new();
+        for address in module_addresses {
+            if address == account {
+                annotator.add_modules_from_account_state(&account_state)?;
+            } else if let (Some(modules_state), _) = self.client.get_account_blob(address)? {
+                annotator.add_modules_from_account_state(&modules_state)?;
+            }
+        }
+        Ok((Some(annotator.view_account_state(&account_state)?), version))
+    }
+
     /// Get committed txn by account and sequence number.
     pub fn get_committed_txn_by_acc_seq(
         &mut self,
         space_delim_strings: &[&str],
-    ) -> Result<Option<(SignedTransaction, Option<Vec<ContractEvent>>)>> {
+    ) -> Result<
+        Option<(
+            SignedTransaction,
+            TransactionInfo,
+            Option<Vec<ContractEvent>>,
+        )>,
+    > {
         ensure!(
             space_delim_strings.len() == 4,
             "Invalid number of arguments to get transaction by account and sequence number"
@@ -631,7 +855,7 @@ impl ClientProxy {
     pub fn get_committed_txn_by_range(
         &mut self,
         space_delim_strings: &[&str],
-    ) -> Result<Vec<(SignedTransaction, Option<Vec<ContractEvent>>)>> {
+    ) -> Result<Vec<(Transaction, TransactionInfo, Option<Vec<ContractEvent>>)>> {
         ensure!(
             space_delim_strings.len() == 4,
             "Invalid number of arguments to get transaction by range"
@@ -704,8 +928,13 @@ impl ClientProxy {
         let path = match space_delim_strings[2] {
             "sent" => ACCOUNT_SENT_EVENT_PATH.to_vec(),
             "received" => ACCOUNT_RECEIVED_EVENT_PATH.to_vec(),
+            "created" => ACCOUNT_CREATION_EVENT_PATH.to_vec(),
+            "key_rotation" => ACCOUNT_KEY_ROTATION_EVENT_PATH.to_vec(),
+            "key_rotation_capability" => ACCOUNT_KEY_ROTATION_CAPABILITY_EVENT_PATH.to_vec(),
+            "withdrawal_capability" => ACCOUNT_WITHDRAWAL_CAPABILITY_EVENT_PATH.to_vec(),
             _ => bail!(
-                "Unknown event type: {:?}, only sent and received are supported",
+                "Unknown event type: {:?}, only sent, received, created, key_rotation, \
+                 key_rotation_capability and withdrawal_capability are supported",
                 space_delim_strings[2]
             ),
         };
@@ -733,6 +962,20 @@ impl ClientProxy {
             .get_events_by_access_path(access_path, start_seq_number, ascending, limit)
     }
 
+    /// Decode the data of an event of the given type, as accepted by
+    /// `get_events_by_account_and_type`, into a human readable form.
+    pub fn decode_event_data(event_type: &str, event_data: &[u8]) -> Result<String> {
+        Ok(match event_type {
+            "sent" | "received" => format!("{:?}", AccountEvent::try_from(event_data)?),
+            "created" => format!("{:?}", CreateAccountEvent::try_from(event_data)?),
+            "key_rotation" => format!("{:?}", KeyRotationEvent::try_from(event_data)?),
+            "key_rotation_capability" | "withdrawal_capability" => {
+                format!("{:?}", CapabilityEvent::try_from(event_data)?)
+            }
+            _ => bail!("Unknown event type: {:?}", event_type),
+        })
+    }
+
     /// Write mnemonic recover to the file specified.
     pub fn write_recovery(&self, space_delim_strings: &[&str]) -> Result<()> {
         ensure!(
@@ -820,6 +1063,31 @@ impl ClientProxy {
         get_account_resource_or_default(&account_state.0)
     }
 
+    /// Get the balance of the account at `address` in `currency_code`, which is 0 if the account
+    /// doesn't accept the currency.
+    fn get_currency_balance(
+        &mut self,
+        address: AccountAddress,
+        currency_code: &str,
+    ) -> Result<u64> {
+        let balance = match self.get_account_state_and_update(address)?.0 {
+            Some(blob) => {
+                let account_btree = (&blob).try_into()?;
+                CurrencyBalancesResource::make_from(&account_btree)?
+                    .and_then(|balances| balances.balance(currency_code.as_bytes()))
+            }
+            None => None,
+        };
+        Ok(balance.unwrap_or(0))
+    }
+
+    /// Return the currency code given as `param`, or None for LBR.
+    fn parse_currency_code<'a>(param: Option<&&'a str>) -> Option<&'a str> {
+        param
+            .copied()
+            .filter(|currency_code| *currency_code != LIBRA_CURRENCY_CODE)
+    }
+
     /// Get account using specific address.
     /// Sync with validator for account sequence number in case it is already created on chain.
     /// This assumes we have a very low probability of mnemonic word conflict.
@@ -916,12 +1184,20 @@ impl ClientProxy {
         &mut self,
         receiver: &AccountAddress,
         num_coins: u64,
+        currency_code: Option<&str>,
         is_blocking: bool,
     ) -> Result<()> {
         ensure!(self.faucet_account.is_some(), "No faucet account loaded");
         let sender = self.faucet_account.as_ref().unwrap();
         let sender_address = sender.address;
-        let program = transaction_builder::encode_mint_script(&receiver, num_coins);
+        let program = match currency_code {
+            Some(currency_code) => transaction_builder::encode_mint_currency_script(
+                &receiver,
+                currency_code,
+                num_coins,
+            ),
+            None => transaction_builder::encode_mint_script(&receiver, num_coins),
+        };
         let req = self.create_submit_transaction_req(
             TransactionPayload::Script(program),
             sender,
@@ -1004,6 +1280,23 @@ impl ClientProxy {
         sender_account: &AccountData,
         max_gas_amount: Option<u64>,
         gas_unit_price: Option<u64>,
+    ) -> Result<SubmitTransactionRequest> {
+        self.create_submit_transaction_req_with_sequence_number(
+            program,
+            sender_account,
+            sender_account.sequence_number,
+            max_gas_amount,
+            gas_unit_price,
+        )
+    }
+
+    fn create_submit_transaction_req_with_sequence_number(
+        &self,
+        program: TransactionPayload,
+        sender_account: &AccountData,
+        sequence_number: u64,
+        max_gas_amount: Option<u64>,
+        gas_unit_price: Option<u64>,
     ) -> Result<SubmitTransactionRequest> {
         let signer: Box<&dyn TransactionSigner> = match &sender_account.key_pair {
             Some(key_pair) => Box::new(key_pair),
@@ -1013,7 +1306,7 @@ impl ClientProxy {
             *signer,
             program,
             sender_account.address,
-            sender_account.sequence_number,
+            sequence_number,
             max_gas_amount.unwrap_or(MAX_GAS_AMOUNT),
             gas_unit_price.unwrap_or(GAS_UNIT_PRICE),
             TX_EXPIRATION,
@@ -2,8 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    account_commands::AccountCommand, client_proxy::ClientProxy, dev_commands::DevCommand,
-    query_commands::QueryCommand, transfer_commands::TransferCommand,
+    account_commands::AccountCommand,
+    client_proxy::ClientProxy,
+    dev_commands::DevCommand,
+    query_commands::QueryCommand,
+    transfer_commands::{TransferCommand, TransferWithNonceCommand},
 };
 
 use failure::prelude::*;
@@ -63,6 +66,7 @@ pub fn get_commands(
         Arc::new(AccountCommand {}),
         Arc::new(QueryCommand {}),
         Arc::new(TransferCommand {}),
+        Arc::new(TransferWithNonceCommand {}),
     ];
     if include_dev {
         commands.push(Arc::new(DevCommand {}));
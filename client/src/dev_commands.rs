@@ -17,6 +17,7 @@ impl Command for DevCommand {
         let commands: Vec<Box<dyn Command>> = vec![
             Box::new(DevCommandCompile {}),
             Box::new(DevCommandPublish {}),
+            Box::new(DevCommandPublishBundle {}),
             Box::new(DevCommandExecute {}),
         ];
         subcommand_execute(&params[0], commands, client, &params[1..]);
@@ -77,6 +78,34 @@ impl Command for DevCommandPublish {
     }
 }
 
+/// Sub command to publish the modules of a package bundle
+pub struct DevCommandPublishBundle {}
+
+impl Command for DevCommandPublishBundle {
+    fn get_aliases(&self) -> Vec<&'static str> {
+        vec!["publish_bundle", "pb"]
+    }
+
+    fn get_params_help(&self) -> &'static str {
+        "<bundle_path>"
+    }
+
+    fn get_description(&self) -> &'static str {
+        "Publish the modules of a package bundle on-chain, each from the account it is built for"
+    }
+
+    fn execute(&self, client: &mut ClientProxy, params: &[&str]) {
+        if params.len() != 2 {
+            println!("Invalid number of arguments to publish bundle");
+            return;
+        }
+        match client.publish_bundle(params) {
+            Ok(count) => println!("Successfully published {} modules", count),
+            Err(e) => println!("{}", e),
+        }
+    }
+}
+
 /// Sub command to execute custom move script
 pub struct DevCommandExecute {}
 
@@ -23,7 +23,7 @@ use libra_types::{
     get_with_proof::{
         RequestItem, ResponseItem, UpdateToLatestLedgerRequest, UpdateToLatestLedgerResponse,
     },
-    transaction::{SignedTransaction, Version},
+    transaction::{SignedTransaction, Transaction, TransactionInfo, Version},
     vm_error::StatusCode,
 };
 use logger::prelude::*;
@@ -85,12 +85,12 @@ impl GRPCClient {
                     sender_account.sequence_number =
                         self.get_sequence_number(sender_account.address)?;
                     bail!(
-                        "Transaction failed with vm status: {:?}, please retry your transaction.",
+                        "Transaction failed with vm status: {}, please retry your transaction.",
                         vm_error
                     );
                 }
             }
-            bail!("Transaction failed with vm status: {:?}", vm_error);
+            bail!("Transaction failed with vm status: {}", vm_error);
         } else if let Some(mempool_error) = completed_resp.mempool_error {
             bail!(
                 "Transaction failed with mempool status: {:?}",
@@ -214,7 +214,13 @@ impl GRPCClient {
         account: AccountAddress,
         sequence_number: u64,
         fetch_events: bool,
-    ) -> Result<Option<(SignedTransaction, Option<Vec<ContractEvent>>)>> {
+    ) -> Result<
+        Option<(
+            SignedTransaction,
+            TransactionInfo,
+            Option<Vec<ContractEvent>>,
+        )>,
+    > {
         let req_item = RequestItem::GetAccountTransactionBySequenceNumber {
             account,
             sequence_number,
@@ -227,7 +233,10 @@ impl GRPCClient {
             .remove(0)
             .into_get_account_txn_by_seq_num_response()?;
 
-        Ok(signed_txn_with_proof.map(|t| (t.signed_transaction, t.events)))
+        Ok(signed_txn_with_proof.map(|t| {
+            let txn_info = t.proof.transaction_info().clone();
+            (t.signed_transaction, txn_info, t.events)
+        }))
     }
 
     /// Get transactions in range (start_version..start_version + limit - 1) from validator.
@@ -236,7 +245,7 @@ impl GRPCClient {
         start_version: u64,
         limit: u64,
         fetch_events: bool,
-    ) -> Result<Vec<(SignedTransaction, Option<Vec<ContractEvent>>)>> {
+    ) -> Result<Vec<(Transaction, TransactionInfo, Option<Vec<ContractEvent>>)>> {
         // Make the request.
         let req_item = RequestItem::GetTransactions {
             start_version,
@@ -257,7 +266,7 @@ impl GRPCClient {
             .unwrap_or_else(|| vec![None; num_txns]);
 
         let res = itertools::zip_eq(txn_list_with_proof.transaction_and_infos, event_lists)
-            .map(|((signed_txn, _), events)| (signed_txn, events))
+            .map(|((txn, txn_info), events)| (txn, txn_info, events))
             .collect();
         Ok(res)
     }
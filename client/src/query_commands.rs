@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{client_proxy::ClientProxy, commands::*};
-use libra_types::account_config::get_account_resource_or_default;
+use libra_types::transaction::{Transaction, TransactionInfo};
 use transaction_builder::get_transaction_name;
 
 /// Major command for query operations.
@@ -37,13 +37,13 @@ impl Command for QueryCommandGetBalance {
         vec!["balance", "b"]
     }
     fn get_params_help(&self) -> &'static str {
-        "<account_ref_id>|<account_address>"
+        "<account_ref_id>|<account_address> [currency_code (default=LBR)]"
     }
     fn get_description(&self) -> &'static str {
         "Get the current balance of an account"
     }
     fn execute(&self, client: &mut ClientProxy, params: &[&str]) {
-        if params.len() != 2 {
+        if params.len() != 2 && params.len() != 3 {
             println!("Invalid number of arguments for balance query");
             return;
         }
@@ -85,28 +85,37 @@ impl Command for QueryCommandGetLatestAccountState {
         vec!["account_state", "as"]
     }
     fn get_params_help(&self) -> &'static str {
-        "<account_ref_id>|<account_address>"
+        "<account_ref_id>|<account_address> [module_address ...]"
     }
     fn get_description(&self) -> &'static str {
-        "Get the latest state for an account"
+        "Get the latest state for an account, with the resources declared by the modules \
+         published under 0x0, the account and the optional module addresses decoded"
     }
     fn execute(&self, client: &mut ClientProxy, params: &[&str]) {
+        if params.len() < 2 {
+            println!("Invalid number of arguments for account state query");
+            return;
+        }
         println!(">> Getting latest account state");
-        match client.get_latest_account_state(&params) {
-            Ok((acc, version)) => match get_account_resource_or_default(&acc) {
-                Ok(_) => println!(
+        match client.get_latest_annotated_account_state(&params) {
+            Ok((acc, version)) => {
+                let state = match acc {
+                    Some(acc) => serde_json::to_string_pretty(&acc.to_json())
+                        .expect("JSON values must serialize"),
+                    None => "None".to_string(),
+                };
+                println!(
                     "Latest account state is: \n \
                      Account: {:#?}\n \
-                     State: {:#?}\n \
+                     State: {}\n \
                      Blockchain Version: {}\n",
                     client
                         .get_account_address_from_parameter(params[1])
                         .expect("Unable to parse account parameter"),
-                    acc,
+                    state,
                     version,
-                ),
-                Err(e) => report_error("Error converting account blob to account resource", e),
-            },
+                )
+            }
             Err(e) => report_error("Error getting latest account state", e),
         }
     }
@@ -131,11 +140,12 @@ impl Command for QueryCommandGetTxnByAccountSeq {
         match client.get_committed_txn_by_acc_seq(&params) {
             Ok(txn_and_events) => {
                 match txn_and_events {
-                    Some((comm_txn, events)) => {
+                    Some((comm_txn, txn_info, events)) => {
                         println!(
                             "Committed transaction: {}",
                             comm_txn.format_for_client(get_transaction_name)
                         );
+                        print_txn_status(&txn_info);
                         if let Some(events_inner) = &events {
                             println!("Events: ");
                             for event in events_inner {
@@ -175,12 +185,16 @@ impl Command for QueryCommandGetTxnByRange {
                 // Note that this should never panic because we shouldn't return items
                 // if the version wasn't able to be parsed in the first place
                 let mut cur_version = params[1].parse::<u64>().expect("Unable to parse version");
-                for (txn, opt_events) in comm_txns_and_events {
-                    println!(
-                        "Transaction at version {}: {}",
-                        cur_version,
-                        txn.format_for_client(get_transaction_name)
-                    );
+                for (txn, txn_info, opt_events) in comm_txns_and_events {
+                    match txn {
+                        Transaction::UserTransaction(signed_txn) => println!(
+                            "Transaction at version {}: {}",
+                            cur_version,
+                            signed_txn.format_for_client(get_transaction_name)
+                        ),
+                        txn => println!("Transaction at version {}: {:?}", cur_version, txn),
+                    }
+                    print_txn_status(&txn_info);
                     if let Some(events) = opt_events {
                         if events.is_empty() {
                             println!("No events returned");
@@ -198,6 +212,15 @@ impl Command for QueryCommandGetTxnByRange {
     }
 }
 
+/// Print the status a committed transaction finished with and, if the status has one, where in
+/// Move code execution stopped.
+fn print_txn_status(txn_info: &TransactionInfo) {
+    match txn_info.location() {
+        Some(location) => println!("Status: {:?} in {}", txn_info.major_status(), location),
+        None => println!("Status: {:?}", txn_info.major_status()),
+    }
+}
+
 /// Sub command to query events from validator.
 pub struct QueryCommandGetEvent {}
 
@@ -206,10 +229,11 @@ impl Command for QueryCommandGetEvent {
         vec!["event", "ev"]
     }
     fn get_params_help(&self) -> &'static str {
-        "<account_ref_id>|<account_address> <sent|received> <start_sequence_number> <ascending=true|false> <limit>"
+        "<account_ref_id>|<account_address> <sent|received|created|key_rotation|key_rotation_capability|withdrawal_capability> <start_sequence_number> <ascending=true|false> <limit>"
     }
     fn get_description(&self) -> &'static str {
-        "Get events by account and event type (sent|received)."
+        "Get events by account and event type \
+         (sent|received|created|key_rotation|key_rotation_capability|withdrawal_capability)."
     }
     fn execute(&self, client: &mut ClientProxy, params: &[&str]) {
         println!(">> Getting events by account and event type.");
@@ -220,6 +244,10 @@ impl Command for QueryCommandGetEvent {
                 } else {
                     for event in events {
                         println!("{}", event);
+                        match ClientProxy::decode_event_data(params[2], event.event.event_data()) {
+                            Ok(data) => println!("Decoded event data: {}", data),
+                            Err(e) => report_error("Failed to decode event data", e),
+                        }
                     }
                 }
                 println!("Last event state: {:#?}", last_event_state);
@@ -13,14 +13,15 @@ impl Command for TransferCommand {
     fn get_params_help(&self) -> &'static str {
         "\n\t<sender_account_address>|<sender_account_ref_id> \
          <receiver_account_address>|<receiver_account_ref_id> <number_of_coins> \
+         [currency_code (default=LBR)] \
          [gas_unit_price_in_micro_libras (default=0)] [max_gas_amount_in_micro_libras (default 140000)] \
          Suffix 'b' is for blocking. "
     }
     fn get_description(&self) -> &'static str {
-        "Transfer coins (in libra) from account to another."
+        "Transfer coins (in libra unless another currency is given) from account to another."
     }
     fn execute(&self, client: &mut ClientProxy, params: &[&str]) {
-        if params.len() < 4 || params.len() > 6 {
+        if params.len() < 4 || params.len() > 7 {
             println!("Invalid number of arguments for transfer");
             println!(
                 "{} {}",
@@ -49,3 +50,52 @@ impl Command for TransferCommand {
         }
     }
 }
+
+/// Command to transfer coins with a nonce transaction, which doesn't have to wait for the previous
+/// transactions of the sender.
+pub struct TransferWithNonceCommand {}
+
+impl Command for TransferWithNonceCommand {
+    fn get_aliases(&self) -> Vec<&'static str> {
+        vec!["transfer_nonce", "transfer_nonceb", "tn", "tnb"]
+    }
+    fn get_params_help(&self) -> &'static str {
+        "\n\t<sender_account_address>|<sender_account_ref_id> \
+         <receiver_account_address>|<receiver_account_ref_id> <number_of_coins> <nonce> \
+         [currency_code (default=LBR)] [gas_unit_price_in_micro_libras (default=0)] [max_gas_amount_in_micro_libras (default 140000)] \
+         Suffix 'b' is for blocking. "
+    }
+    fn get_description(&self) -> &'static str {
+        "Transfer coins (in libra) from account to another with a nonce instead of the next \
+         sequence number. Nonces must be enabled for the sender account."
+    }
+    fn execute(&self, client: &mut ClientProxy, params: &[&str]) {
+        if params.len() < 5 || params.len() > 8 {
+            println!("Invalid number of arguments for transfer with nonce");
+            println!(
+                "{} {}",
+                self.get_aliases().join(" | "),
+                self.get_params_help()
+            );
+            return;
+        }
+
+        println!(">> Transferring with nonce");
+        let is_blocking = blocking_cmd(&params[0]);
+        match client.transfer_coins_with_nonce(&params, is_blocking) {
+            Ok(index_and_seq) => {
+                if is_blocking {
+                    println!("Finished transaction!");
+                } else {
+                    println!("Transaction submitted to validator");
+                }
+                println!(
+                    "To query for transaction status, run: query txn_acc_seq {} {} \
+                     <fetch_events=true|false>",
+                    index_and_seq.account_index, index_and_seq.sequence_number
+                );
+            }
+            Err(e) => report_error("Failed to perform transaction", e),
+        }
+    }
+}
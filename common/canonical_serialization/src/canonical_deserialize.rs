@@ -39,6 +39,8 @@ pub trait CanonicalDeserializer {
 
     fn decode_u64(&mut self) -> Result<u64>;
 
+    fn decode_u128(&mut self) -> Result<u128>;
+
     fn decode_tuple2<T0, T1>(&mut self) -> Result<(T0, T1)>
     where
         Self: Sized,
@@ -118,6 +120,7 @@ impl_canonical_deserialize!(decode_u8, u8);
 impl_canonical_deserialize!(decode_u16, u16);
 impl_canonical_deserialize!(decode_u32, u32);
 impl_canonical_deserialize!(decode_u64, u64);
+impl_canonical_deserialize!(decode_u128, u128);
 
 impl<T> CanonicalDeserialize for Option<T>
 where
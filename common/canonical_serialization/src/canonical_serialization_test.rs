@@ -186,6 +186,11 @@ proptest! {
     fn serialize_u64(value in any::<u64>()) {
         test_helper::assert_canonical_encode_decode(&value);
     }
+
+    #[test]
+    fn serialize_u128(value in any::<u128>()) {
+        test_helper::assert_canonical_encode_decode(&value);
+    }
 }
 
 #[test]
@@ -252,6 +257,36 @@ fn test_btreemap_lexicographic_order() {
     assert_eq!(deserializer.decode_bytes().unwrap(), value);
 }
 
+#[test]
+fn test_btreemap_non_canonical_order() {
+    let encode_entries = |keys: &[Vec<u8>]| {
+        let mut serializer = SimpleSerializer::<Vec<u8>>::new();
+        serializer.encode_u32(keys.len() as u32).unwrap();
+        for key in keys {
+            serializer
+                .encode_bytes(key)
+                .unwrap()
+                .encode_bytes(&[7])
+                .unwrap();
+        }
+        serializer.get_output()
+    };
+
+    let sorted = encode_entries(&[vec![0], vec![1], vec![0, 6]]);
+    let map: BTreeMap<Vec<u8>, Vec<u8>> = SimpleDeserializer::deserialize(&sorted).unwrap();
+    assert_eq!(
+        SimpleSerializer::<Vec<u8>>::serialize(&map).unwrap(),
+        sorted
+    );
+
+    // Entries sorted by the value of the keys rather than by their encoding.
+    let unsorted = encode_entries(&[vec![0], vec![0, 6], vec![1]]);
+    assert!(SimpleDeserializer::deserialize::<BTreeMap<Vec<u8>, Vec<u8>>>(&unsorted).is_err());
+
+    let duplicated = encode_entries(&[vec![0], vec![0]]);
+    assert!(SimpleDeserializer::deserialize::<BTreeMap<Vec<u8>, Vec<u8>>>(&duplicated).is_err());
+}
+
 #[test]
 fn test_serialization_optional() {
     let bar1: Option<u32> = Some(42);
@@ -37,6 +37,8 @@ pub trait CanonicalSerializer {
 
     fn encode_u64(&mut self, v: u64) -> Result<&mut Self>;
 
+    fn encode_u128(&mut self, v: u128) -> Result<&mut Self>;
+
     fn encode_tuple2<T0, T1>(&mut self, v: &(T0, T1)) -> Result<&mut Self>
     where
         Self: Sized,
@@ -137,6 +139,7 @@ impl_canonical_serialize_for_primitive!(encode_u8, u8);
 impl_canonical_serialize_for_primitive!(encode_u16, u16);
 impl_canonical_serialize_for_primitive!(encode_u32, u32);
 impl_canonical_serialize_for_primitive!(encode_u64, u64);
+impl_canonical_serialize_for_primitive!(encode_u128, u128);
 
 impl<T> CanonicalSerialize for &T
 where
@@ -11,6 +11,7 @@ mod simple_serializer;
 pub mod test_helper;
 
 pub use canonical_deserialize::{CanonicalDeserialize, CanonicalDeserializer};
+pub use canonical_serialization_derive::{CanonicalDeserialize, CanonicalSerialize};
 pub use canonical_serialize::{CanonicalSerialize, CanonicalSerializer};
 pub use simple_deserializer::SimpleDeserializer;
 pub use simple_serializer::SimpleSerializer;
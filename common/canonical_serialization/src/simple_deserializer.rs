@@ -113,15 +113,33 @@ impl<'a> CanonicalDeserializer for SimpleDeserializer<'a> {
         Ok(self.raw_bytes.read_u64::<Endianness>()?)
     }
 
+    fn decode_u128(&mut self) -> Result<u128> {
+        Ok(self.raw_bytes.read_u128::<Endianness>()?)
+    }
+
     fn decode_btreemap<K: CanonicalDeserialize + std::cmp::Ord, V: CanonicalDeserialize>(
         &mut self,
     ) -> Result<BTreeMap<K, V>> {
         let len = self.decode_u32()?;
         ensure_max_length!(len as usize);
 
+        // Only one encoding of a map is canonical: the entries must be sorted by the bytes of
+        // their serialized keys, which must be unique.
+        let raw_bytes: &'a [u8] = *self.raw_bytes.get_ref();
+        let mut previous_key: Option<&'a [u8]> = None;
         let mut map = BTreeMap::new();
         for _i in 0..len {
+            let key_start = self.raw_bytes.position() as usize;
             let key = K::deserialize(self)?;
+            let key_bytes = &raw_bytes[key_start..self.raw_bytes.position() as usize];
+            ensure!(
+                previous_key.map_or(true, |previous| previous < key_bytes),
+                "map keys must be unique and in increasing lexicographic order, found {:?} after \
+                 {:?}",
+                key_bytes,
+                previous_key.unwrap_or_default(),
+            );
+            previous_key = Some(key_bytes);
             let value = V::deserialize(self)?;
             map.insert(key, value);
         }
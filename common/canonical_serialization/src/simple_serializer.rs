@@ -112,6 +112,11 @@ where
         Ok(self)
     }
 
+    fn encode_u128(&mut self, v: u128) -> Result<&mut Self> {
+        self.output.write_u128::<Endianness>(v)?;
+        Ok(self)
+    }
+
     fn encode_tuple_iterator<K: CanonicalSerialize, V: CanonicalSerialize, I>(
         &mut self,
         iter: I,
@@ -0,0 +1,260 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This crate derives `CanonicalSerialize` and `CanonicalDeserialize` for structs and enums whose
+//! fields all implement these traits, following the encoding described in the
+//! `canonical_serialization` README:
+//! - the fields of a struct, or of an enum variant, are encoded in declaration order,
+//! - an enum variant is prefixed with its index as a 32-bit unsigned integer. The index is the
+//!   position of the variant in the declaration, unless given with `#[canonical(index = N)]`.
+//!
+//! The derived code refers to the `canonical_serialization` and `failure` crates, which must be
+//! dependencies of the crate using the derives under these names. The derives are re-exported by
+//! `canonical_serialization` next to the traits they implement.
+//!
+//! ## Example
+//!
+//! ```
+//! use canonical_serialization::{
+//!     CanonicalDeserialize, CanonicalSerialize, SimpleDeserializer, SimpleSerializer,
+//! };
+//!
+//! #[derive(CanonicalSerialize, CanonicalDeserialize, Debug, PartialEq)]
+//! struct Payment {
+//!     amount: u64,
+//!     memo: Option<String>,
+//! }
+//!
+//! #[derive(CanonicalSerialize, CanonicalDeserialize, Debug, PartialEq)]
+//! enum Instruction {
+//!     // Encoded with index 1 to match a previous version of the protocol.
+//!     #[canonical(index = 1)]
+//!     Pay(Payment),
+//!     #[canonical(index = 0)]
+//!     Noop,
+//! }
+//!
+//! let instruction = Instruction::Pay(Payment { amount: 5, memo: None });
+//! let bytes = SimpleSerializer::<Vec<u8>>::serialize(&instruction).unwrap();
+//! assert_eq!(bytes, vec![1, 0, 0, 0, 5, 0, 0, 0, 0, 0, 0, 0, 0]);
+//! let decoded: Instruction = SimpleDeserializer::deserialize(&bytes).unwrap();
+//! assert_eq!(decoded, instruction);
+//! ```
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use std::collections::BTreeSet;
+use syn::{
+    parse_macro_input, parse_quote, Data, DataEnum, DeriveInput, Fields, Generics, Ident, Lit,
+    Meta, NestedMeta, Variant,
+};
+
+#[proc_macro_derive(CanonicalSerialize, attributes(canonical))]
+pub fn derive_canonical_serialize(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    let name = &ast.ident;
+    let generics = add_trait_bounds(
+        ast.generics.clone(),
+        parse_quote!(::canonical_serialization::CanonicalSerialize),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let body = match ast.data {
+        Data::Struct(ref data) => {
+            let (pattern, encode_fields) = destructure_fields(&data.fields);
+            quote! {
+                let #name #pattern = self;
+                #encode_fields
+            }
+        }
+        Data::Enum(ref data) => {
+            let arms = variant_indexes(data).into_iter().map(|(variant, index)| {
+                let variant_ident = &variant.ident;
+                let (pattern, encode_fields) = destructure_fields(&variant.fields);
+                quote! {
+                    #name::#variant_ident #pattern => {
+                        ::canonical_serialization::CanonicalSerializer::encode_u32(
+                            serializer,
+                            #index,
+                        )?;
+                        #encode_fields
+                    }
+                }
+            });
+            quote! {
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+        Data::Union(_) => panic!("#[derive(CanonicalSerialize)] is not defined for unions"),
+    };
+
+    quote!(
+        impl #impl_generics ::canonical_serialization::CanonicalSerialize for #name #ty_generics
+        #where_clause
+        {
+            #[allow(unused_variables)]
+            fn serialize(
+                &self,
+                serializer: &mut impl ::canonical_serialization::CanonicalSerializer,
+            ) -> ::failure::Result<()> {
+                #body
+                Ok(())
+            }
+        }
+    )
+    .into()
+}
+
+#[proc_macro_derive(CanonicalDeserialize, attributes(canonical))]
+pub fn derive_canonical_deserialize(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    let name = &ast.ident;
+    let generics = add_trait_bounds(
+        ast.generics.clone(),
+        parse_quote!(::canonical_serialization::CanonicalDeserialize),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let body = match ast.data {
+        Data::Struct(ref data) => {
+            let fields = decode_fields(&data.fields);
+            quote! {
+                Ok(#name #fields)
+            }
+        }
+        Data::Enum(ref data) => {
+            let arms = variant_indexes(data).into_iter().map(|(variant, index)| {
+                let variant_ident = &variant.ident;
+                let fields = decode_fields(&variant.fields);
+                quote! {
+                    #index => Ok(#name::#variant_ident #fields),
+                }
+            });
+            let error = format!("ParseError: Unable to decode {} variant, found {{}}", name);
+            quote! {
+                let index =
+                    ::canonical_serialization::CanonicalDeserializer::decode_u32(deserializer)?;
+                match index {
+                    #(#arms)*
+                    _ => Err(::failure::format_err!(#error, index)),
+                }
+            }
+        }
+        Data::Union(_) => panic!("#[derive(CanonicalDeserialize)] is not defined for unions"),
+    };
+
+    quote!(
+        impl #impl_generics ::canonical_serialization::CanonicalDeserialize for #name #ty_generics
+        #where_clause
+        {
+            #[allow(unused_variables)]
+            fn deserialize(
+                deserializer: &mut impl ::canonical_serialization::CanonicalDeserializer,
+            ) -> ::failure::Result<Self> {
+                #body
+            }
+        }
+    )
+    .into()
+}
+
+/// Requires every type parameter to implement `bound`.
+fn add_trait_bounds(mut generics: Generics, bound: syn::TypeParamBound) -> Generics {
+    for param in generics.type_params_mut() {
+        param.bounds.push(bound.clone());
+    }
+    generics
+}
+
+/// Returns a pattern binding the fields to `field_<i>` variables, and the code encoding them.
+fn destructure_fields(fields: &Fields) -> (TokenStream2, TokenStream2) {
+    let bindings: Vec<_> = (0..fields.len())
+        .map(|i| Ident::new(&format!("field_{}", i), Span::call_site()))
+        .collect();
+    let pattern = match fields {
+        Fields::Named(named) => {
+            let names = named.named.iter().map(|field| &field.ident);
+            quote!({ #(#names: #bindings),* })
+        }
+        Fields::Unnamed(_) => quote!(( #(#bindings),* )),
+        Fields::Unit => quote!(),
+    };
+    let encode = quote! {
+        #(::canonical_serialization::CanonicalSerializer::encode_struct(serializer, #bindings)?;)*
+    };
+    (pattern, encode)
+}
+
+/// Returns the fields of a struct or variant expression, each decoded in declaration order.
+fn decode_fields(fields: &Fields) -> TokenStream2 {
+    let decode =
+        quote!(::canonical_serialization::CanonicalDeserializer::decode_struct(deserializer)?);
+    match fields {
+        Fields::Named(named) => {
+            let names = named.named.iter().map(|field| &field.ident);
+            quote!({ #(#names: #decode),* })
+        }
+        Fields::Unnamed(unnamed) => {
+            let decodes = unnamed.unnamed.iter().map(|_| &decode);
+            quote!(( #(#decodes),* ))
+        }
+        Fields::Unit => quote!(),
+    }
+}
+
+/// Pairs the variants of an enum with their index.
+fn variant_indexes(data: &DataEnum) -> Vec<(&Variant, u32)> {
+    let mut seen = BTreeSet::new();
+    data.variants
+        .iter()
+        .enumerate()
+        .map(|(position, variant)| {
+            let index = explicit_index(variant).unwrap_or(position as u32);
+            if !seen.insert(index) {
+                panic!(
+                    "Variant {} has the same canonical index {} as another variant",
+                    variant.ident, index
+                );
+            }
+            (variant, index)
+        })
+        .collect()
+}
+
+/// Parses `#[canonical(index = N)]`.
+fn explicit_index(variant: &Variant) -> Option<u32> {
+    let mut index = None;
+    for attr in variant
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("canonical"))
+    {
+        let list = match attr.parse_meta() {
+            Ok(Meta::List(list)) => list,
+            _ => panic!("Expected #[canonical(index = N)]"),
+        };
+        for nested in list.nested {
+            match nested {
+                NestedMeta::Meta(Meta::NameValue(ref name_value))
+                    if name_value.path.is_ident("index") =>
+                {
+                    if let Lit::Int(ref lit) = name_value.lit {
+                        index = Some(
+                            lit.base10_parse::<u32>()
+                                .expect("The canonical index must be a u32"),
+                        );
+                        continue;
+                    }
+                    panic!("The canonical index must be an integer")
+                }
+                _ => panic!("Expected #[canonical(index = N)]"),
+            }
+        }
+    }
+    index
+}
@@ -0,0 +1,146 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use canonical_serialization::{
+    CanonicalDeserialize, CanonicalDeserializer, CanonicalSerialize, CanonicalSerializer,
+    SimpleDeserializer, SimpleSerializer,
+};
+use failure::prelude::*;
+use std::{collections::BTreeMap, fmt::Debug};
+
+/// The same fields as `Derived`, serialized by hand.
+struct HandWritten {
+    flag: bool,
+    bytes: Vec<u8>,
+    label: String,
+    numbers: Vec<u64>,
+    map: BTreeMap<Vec<u8>, Vec<u8>>,
+    optional: Option<u32>,
+}
+
+impl CanonicalSerialize for HandWritten {
+    fn serialize(&self, serializer: &mut impl CanonicalSerializer) -> Result<()> {
+        serializer
+            .encode_bool(self.flag)?
+            .encode_bytes(&self.bytes)?
+            .encode_string(&self.label)?
+            .encode_vec(&self.numbers)?
+            .encode_btreemap(&self.map)?
+            .encode_optional(&self.optional)?;
+        Ok(())
+    }
+}
+
+#[derive(CanonicalSerialize, CanonicalDeserialize, Debug, PartialEq)]
+struct Derived {
+    flag: bool,
+    bytes: Vec<u8>,
+    label: String,
+    numbers: Vec<u64>,
+    map: BTreeMap<Vec<u8>, Vec<u8>>,
+    optional: Option<u32>,
+}
+
+#[derive(CanonicalSerialize, CanonicalDeserialize, Debug, PartialEq)]
+struct Wrapper<T> {
+    inner: T,
+    pair: (u8, i64),
+}
+
+#[derive(CanonicalSerialize, CanonicalDeserialize, Debug, PartialEq)]
+struct Unit;
+
+#[derive(CanonicalSerialize, CanonicalDeserialize, Debug, PartialEq)]
+struct Newtype(u16);
+
+#[derive(CanonicalSerialize, CanonicalDeserialize, Debug, PartialEq)]
+enum Message {
+    Empty,
+    Single(u64),
+    Pair(u8, String),
+    Named { id: u32, payload: Vec<u8> },
+}
+
+#[derive(CanonicalSerialize, CanonicalDeserialize, Debug, PartialEq)]
+enum Reordered {
+    #[canonical(index = 3)]
+    Script(u8),
+    #[canonical(index = 0)]
+    Program,
+    Module,
+}
+
+fn assert_round_trip<T>(value: &T, expected_hex: &str)
+where
+    T: CanonicalSerialize + CanonicalDeserialize + Debug + PartialEq,
+{
+    let bytes = SimpleSerializer::<Vec<u8>>::serialize(value).unwrap();
+    assert_eq!(hex::encode(&bytes), expected_hex);
+    let decoded: T = SimpleDeserializer::deserialize(&bytes).unwrap();
+    assert_eq!(&decoded, value);
+}
+
+#[test]
+fn struct_matches_hand_written_serialization() {
+    let mut map = BTreeMap::new();
+    map.insert(vec![2], vec![3, 4]);
+    map.insert(vec![1], vec![]);
+    let derived = Derived {
+        flag: true,
+        bytes: vec![0xca, 0xfe],
+        label: "lcs".to_string(),
+        numbers: vec![1, 2],
+        map: map.clone(),
+        optional: Some(7),
+    };
+    let hand_written = HandWritten {
+        flag: true,
+        bytes: vec![0xca, 0xfe],
+        label: "lcs".to_string(),
+        numbers: vec![1, 2],
+        map,
+        optional: Some(7),
+    };
+    assert_eq!(
+        SimpleSerializer::<Vec<u8>>::serialize(&derived).unwrap(),
+        SimpleSerializer::<Vec<u8>>::serialize(&hand_written).unwrap(),
+    );
+
+    let bytes = SimpleSerializer::<Vec<u8>>::serialize(&derived).unwrap();
+    let decoded: Derived = SimpleDeserializer::deserialize(&bytes).unwrap();
+    assert_eq!(decoded, derived);
+}
+
+#[test]
+fn generic_and_tuple_structs() {
+    assert_round_trip(
+        &Wrapper {
+            inner: Newtype(0x1234),
+            pair: (1, -1),
+        },
+        "341201ffffffffffffffff",
+    );
+    assert_round_trip(&Unit, "");
+}
+
+#[test]
+fn enum_variants_are_prefixed_with_their_index() {
+    assert_round_trip(&Message::Empty, "00000000");
+    assert_round_trip(&Message::Single(6), "010000000600000000000000");
+    assert_round_trip(&Message::Pair(1, "a".to_string()), "02000000010100000061");
+    assert_round_trip(
+        &Message::Named {
+            id: 5,
+            payload: vec![0xff],
+        },
+        "030000000500000001000000ff",
+    );
+
+    assert_round_trip(&Reordered::Script(9), "0300000009");
+    assert_round_trip(&Reordered::Program, "00000000");
+    // Variants without an explicit index keep their position.
+    assert_round_trip(&Reordered::Module, "02000000");
+
+    let error = SimpleDeserializer::deserialize::<Message>(&[4, 0, 0, 0]).unwrap_err();
+    assert!(error.to_string().contains("Message"));
+}
@@ -123,11 +123,7 @@ impl<T: Payload> BlockStore<T> {
         for block in blocks {
             assert!(!block.is_genesis_block());
             let compute_res = state_computer
-                .compute(
-                    block.parent_id(),
-                    block.id(),
-                    block.payload().unwrap_or(&T::default()),
-                )
+                .compute(&block)
                 .await
                 .expect("fail to rebuild scratchpad");
             // if this block is certified, ensure we agree with the certified state.
@@ -191,23 +187,16 @@ impl<T: Payload> BlockStore<T> {
         if let Some(existing_block) = self.get_block(block.id()) {
             return Ok(existing_block);
         }
-        let parent_id = match self.verify_and_get_parent_id(&block) {
-            Ok(t) => t,
-            Err(e) => {
-                security_log(SecurityEvent::InvalidBlock)
-                    .error(&e)
-                    .data(&block)
-                    .log();
-                return Err(e);
-            }
-        };
+        if let Err(e) = self.verify_and_get_parent_id(&block) {
+            security_log(SecurityEvent::InvalidBlock)
+                .error(&e)
+                .data(&block)
+                .log();
+            return Err(e);
+        }
         let compute_res = self
             .state_computer
-            .compute(
-                parent_id,
-                block.id(),
-                block.payload().unwrap_or(&T::default()),
-            )
+            .compute(&block)
             .await
             .with_context(|e| format!("Execution failure for block {}: {:?}", block, e))?;
 
@@ -8,7 +8,7 @@ mod safety;
 
 mod block_storage;
 pub mod chained_bft_consensus_provider;
-pub use consensus_types::quorum_cert::QuorumCert;
+pub use consensus_types::{block::Block, quorum_cert::QuorumCert};
 mod chained_bft_smr;
 mod network;
 
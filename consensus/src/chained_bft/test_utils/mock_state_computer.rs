@@ -3,12 +3,12 @@
 
 use crate::{
     chained_bft::{
-        consensus_types::quorum_cert::QuorumCert,
+        consensus_types::{block::Block, quorum_cert::QuorumCert},
         test_utils::{mock_storage::MockStorage, TestPayload},
     },
     state_replication::StateComputer,
 };
-use crypto::{hash::ACCUMULATOR_PLACEHOLDER_HASH, HashValue};
+use crypto::hash::ACCUMULATOR_PLACEHOLDER_HASH;
 use executor::{ExecutedState, StateComputeResult};
 use failure::Result;
 use futures::{channel::mpsc, future, Future, FutureExt};
@@ -38,9 +38,7 @@ impl StateComputer for MockStateComputer {
     type Payload = Vec<usize>;
     fn compute(
         &self,
-        _parent_id: HashValue,
-        _block_id: HashValue,
-        _transactions: &Self::Payload,
+        _block: &Block<Self::Payload>,
     ) -> Pin<Box<dyn Future<Output = Result<StateComputeResult>> + Send>> {
         future::ok(StateComputeResult {
             executed_state: ExecutedState {
@@ -88,9 +86,7 @@ impl StateComputer for EmptyStateComputer {
     type Payload = TestPayload;
     fn compute(
         &self,
-        _parent_id: HashValue,
-        _block_id: HashValue,
-        _transactions: &Self::Payload,
+        _block: &Block<Self::Payload>,
     ) -> Pin<Box<dyn Future<Output = Result<StateComputeResult>> + Send>> {
         future::ok(StateComputeResult {
             executed_state: ExecutedState {
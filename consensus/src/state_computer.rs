@@ -1,12 +1,20 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{chained_bft::QuorumCert, counters, state_replication::StateComputer};
-use crypto::HashValue;
+use crate::{
+    chained_bft::{Block, QuorumCert},
+    counters,
+    state_replication::StateComputer,
+};
 use executor::{Executor, StateComputeResult};
 use failure::Result;
 use futures::{Future, FutureExt};
-use libra_types::{crypto_proxies::LedgerInfoWithSignatures, transaction::SignedTransaction};
+use libra_types::{
+    account_address::AccountAddress,
+    block_metadata::BlockMetadata,
+    crypto_proxies::LedgerInfoWithSignatures,
+    transaction::{SignedTransaction, Transaction},
+};
 use logger::prelude::*;
 use state_synchronizer::StateSyncClient;
 use std::{
@@ -38,22 +46,37 @@ impl StateComputer for ExecutionProxy {
 
     fn compute(
         &self,
-        // The id of a parent block, on top of which the given transactions should be executed.
-        parent_block_id: HashValue,
-        // The id of a current block.
-        block_id: HashValue,
-        // Transactions to execute.
-        transactions: &Self::Payload,
+        // The block to execute: its payload as well as the metadata of the block.
+        block: &Block<Self::Payload>,
     ) -> Pin<Box<dyn Future<Output = Result<StateComputeResult>> + Send>> {
         let pre_execution_instant = Instant::now();
+        // The block metadata runs the block prologue before the user transactions of the block.
+        // Nil blocks have no proposer, so the fees of the previous blocks are left in the pool.
+        let block_metadata = BlockMetadata::new(
+            block.id(),
+            block.timestamp_usecs(),
+            block.quorum_cert().ledger_info().signatures().clone(),
+            block.author().unwrap_or_else(AccountAddress::default),
+        );
+        let transactions = std::iter::once(Transaction::BlockMetadata(block_metadata))
+            .chain(
+                block
+                    .payload()
+                    .into_iter()
+                    .flatten()
+                    .cloned()
+                    .map(Transaction::UserTransaction),
+            )
+            .collect();
         let execute_future =
             self.executor
-                .execute_block(transactions.clone(), parent_block_id, block_id);
+                .execute_block(transactions, block.parent_id(), block.id());
         async move {
             match execute_future.await {
                 Ok(Ok(state_compute_result)) => {
                     let execution_duration = pre_execution_instant.elapsed();
-                    let num_txns = state_compute_result.compute_status.len();
+                    // Don't count the block metadata txn.
+                    let num_txns = state_compute_result.compute_status.len().saturating_sub(1);
                     if num_txns == 0 {
                         // no txns in that block
                         counters::EMPTY_BLOCK_EXECUTION_DURATION_S
@@ -1,8 +1,7 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::chained_bft::QuorumCert;
-use crypto::HashValue;
+use crate::chained_bft::{Block, QuorumCert};
 use executor::StateComputeResult;
 use failure::Result;
 use futures::Future;
@@ -39,19 +38,16 @@ pub trait TxnManager: Send + Sync {
 pub trait StateComputer: Send + Sync {
     type Payload;
 
-    /// How to execute a sequence of transactions and obtain the next state. While some of the
+    /// How to execute the transactions of a block and obtain the next state. While some of the
     /// transactions succeed, some of them can fail.
     /// In case all the transactions are failed, new_state_id is equal to the previous state id.
+    /// The block is executed on top of its parent, identified by `block.parent_id()`. We're going
+    /// to use a special GENESIS_BLOCK_ID constant defined in crypto::hash module to refer to the
+    /// block id of the Genesis block, which is executed in a special way.
     fn compute(
         &self,
-        // The id of a parent block, on top of which the given transactions should be executed.
-        // We're going to use a special GENESIS_BLOCK_ID constant defined in crypto::hash module to
-        // refer to the block id of the Genesis block, which is executed in a special way.
-        parent_block_id: HashValue,
-        // The id of a current block.
-        block_id: HashValue,
-        // Transactions to execute.
-        transactions: &Self::Payload,
+        // The block to execute: its payload as well as the metadata of the block.
+        block: &Block<Self::Payload>,
     ) -> Pin<Box<dyn Future<Output = Result<StateComputeResult>> + Send>>;
 
     /// Send a successful commit. A future is fulfilled when the state is finalized.
@@ -32,8 +32,10 @@ impl MempoolProxy {
         timestamp_usecs: u64,
     ) -> CommitTransactionsRequest {
         let mut all_updates = Vec::new();
-        assert_eq!(txns.len(), compute_result.compute_status.len());
-        for (txn, status) in txns.iter().zip(compute_result.compute_status.iter()) {
+        assert_eq!(txns.len() + 1, compute_result.compute_status.len());
+        // skip the block metadata txn result
+        let statuses = compute_result.compute_status.iter().skip(1);
+        for (txn, status) in txns.iter().zip(statuses) {
             let mut transaction = CommittedTransaction::default();
             transaction.sender = txn.sender().as_ref().to_vec();
             transaction.sequence_number = txn.sequence_number();
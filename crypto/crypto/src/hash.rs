@@ -522,6 +522,11 @@ define_hasher! {
     )
 }
 
+define_hasher! {
+    /// The hasher used to compute the hash of a Transaction that is not signed by a user.
+    (TransactionHasher, TRANSACTION_HASHER, b"Transaction")
+}
+
 define_hasher! {
     /// The hasher used to compute the hash (block_id) of a Block object.
     (BlockHasher, BLOCK_HASHER, b"BlockId")
@@ -0,0 +1,506 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module provides an API for K-of-N multi-signatures built on top of the
+//! [`ed25519`](crate::ed25519) signature scheme.
+//!
+//! A `MultiEd25519PublicKey` is made of up to 32 Ed25519 public keys and a threshold K. A
+//! `MultiEd25519Signature` carries the Ed25519 signatures of some of these keys, along with a 32 bit
+//! bitmap telling which keys produced them. It is valid if at least K of the signatures are valid
+//! for their respective keys.
+//!
+//! The byte encodings match the ones expected by the `Signature.ed25519_multisig_verify` Move
+//! native:
+//! - a public key is the concatenation of the Ed25519 public keys followed by the threshold byte,
+//! - a signature is the concatenation of the Ed25519 signatures, in key order, followed by the
+//!   four bytes of the bitmap. Bit `i`, counting from the most significant bit of the first byte,
+//!   is set if the `i`-th key signed.
+//!
+//! # Examples
+//!
+//! ```
+//! use crypto::hash::{CryptoHasher, TestOnlyHasher};
+//! use crypto::{
+//!     ed25519::*,
+//!     multi_ed25519::*,
+//!     traits::{Signature, SigningKey, Uniform},
+//! };
+//! use rand::{rngs::StdRng, SeedableRng};
+//!
+//! let mut hasher = TestOnlyHasher::default();
+//! hasher.write("Test message".as_bytes());
+//! let hashed_message = hasher.finish();
+//!
+//! let mut rng: StdRng = SeedableRng::from_seed([0; 32]);
+//! let private_keys: Vec<_> = (0..3)
+//!     .map(|_| Ed25519PrivateKey::generate_for_testing(&mut rng))
+//!     .collect();
+//! let public_key = MultiEd25519PublicKey::new(
+//!     private_keys.iter().map(|key| key.into()).collect(),
+//!     2,
+//! )
+//! .unwrap();
+//!
+//! // The first and the last key sign.
+//! let signature = MultiEd25519Signature::new(vec![
+//!     (private_keys[0].sign_message(&hashed_message), 0),
+//!     (private_keys[2].sign_message(&hashed_message), 2),
+//! ])
+//! .unwrap();
+//! assert!(signature.verify(&hashed_message, &public_key).is_ok());
+//! ```
+//! **Note**: The above example generates private keys using a private function intended only for
+//! testing purposes. Production code should find an alternate means for secure key generation.
+
+use crate::{
+    ed25519::{
+        Ed25519PrivateKey, Ed25519PublicKey, Ed25519Signature, ED25519_PRIVATE_KEY_LENGTH,
+        ED25519_PUBLIC_KEY_LENGTH, ED25519_SIGNATURE_LENGTH,
+    },
+    traits::*,
+    HashValue,
+};
+use canonical_serialization::{
+    CanonicalDeserialize, CanonicalDeserializer, CanonicalSerialize, CanonicalSerializer,
+};
+use core::convert::TryFrom;
+use crypto_derive::{SilentDebug, SilentDisplay};
+use failure::prelude::*;
+use serde::{de, export, ser};
+use std::fmt;
+
+/// The maximum number of keys of a multi-signature key.
+pub const MAX_NUM_OF_KEYS: usize = 32;
+/// The length of the bitmap of a `MultiEd25519Signature`.
+pub const BITMAP_NUM_OF_BYTES: usize = 4;
+
+/// A K-of-N Ed25519 private key. Only meant for tests: in practice, every signer holds their own
+/// `Ed25519PrivateKey` and signatures are put together with [`MultiEd25519Signature::new`].
+#[derive(SilentDisplay, SilentDebug)]
+pub struct MultiEd25519PrivateKey {
+    private_keys: Vec<Ed25519PrivateKey>,
+    threshold: u8,
+}
+
+/// A K-of-N Ed25519 public key.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct MultiEd25519PublicKey {
+    public_keys: Vec<Ed25519PublicKey>,
+    threshold: u8,
+}
+
+/// A K-of-N Ed25519 signature.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct MultiEd25519Signature {
+    signatures: Vec<Ed25519Signature>,
+    bitmap: [u8; BITMAP_NUM_OF_BYTES],
+}
+
+/// Checks that `threshold` keys out of `num_of_keys` can sign.
+fn check_threshold(
+    num_of_keys: usize,
+    threshold: u8,
+) -> std::result::Result<(), CryptoMaterialError> {
+    if threshold == 0 || num_of_keys > MAX_NUM_OF_KEYS || usize::from(threshold) > num_of_keys {
+        return Err(CryptoMaterialError::ValidationError);
+    }
+    Ok(())
+}
+
+/// Splits the trailing threshold byte from the concatenated keys, which must be `key_length`
+/// bytes long each.
+fn split_threshold(
+    bytes: &[u8],
+    key_length: usize,
+) -> std::result::Result<(&[u8], u8), CryptoMaterialError> {
+    match bytes.split_last() {
+        Some((threshold, keys)) if !keys.is_empty() && keys.len() % key_length == 0 => {
+            check_threshold(keys.len() / key_length, *threshold)?;
+            Ok((keys, *threshold))
+        }
+        _ => Err(CryptoMaterialError::WrongLengthError),
+    }
+}
+
+fn bitmap_get_bit(bitmap: [u8; BITMAP_NUM_OF_BYTES], index: usize) -> bool {
+    bitmap[index / 8] & (128 >> (index % 8)) != 0
+}
+
+fn bitmap_count_ones(bitmap: [u8; BITMAP_NUM_OF_BYTES]) -> u32 {
+    bitmap.iter().map(|byte| byte.count_ones()).sum()
+}
+
+impl MultiEd25519PrivateKey {
+    /// Builds a private key out of the given keys, any `threshold` of which can sign.
+    pub fn new(
+        private_keys: Vec<Ed25519PrivateKey>,
+        threshold: u8,
+    ) -> std::result::Result<Self, CryptoMaterialError> {
+        check_threshold(private_keys.len(), threshold)?;
+        Ok(MultiEd25519PrivateKey {
+            private_keys,
+            threshold,
+        })
+    }
+}
+
+impl MultiEd25519PublicKey {
+    /// Builds a public key out of the given keys, `threshold` of which must sign.
+    pub fn new(
+        public_keys: Vec<Ed25519PublicKey>,
+        threshold: u8,
+    ) -> std::result::Result<Self, CryptoMaterialError> {
+        check_threshold(public_keys.len(), threshold)?;
+        Ok(MultiEd25519PublicKey {
+            public_keys,
+            threshold,
+        })
+    }
+
+    /// The keys, in the order signatures refer to them.
+    pub fn public_keys(&self) -> &[Ed25519PublicKey] {
+        &self.public_keys
+    }
+
+    /// The minimum number of valid signatures.
+    pub fn threshold(&self) -> u8 {
+        self.threshold
+    }
+}
+
+impl MultiEd25519Signature {
+    /// Puts together signatures of a message, each tagged with the index of the key that produced
+    /// it. Fails if an index is out of range or appears twice.
+    pub fn new(
+        mut signatures: Vec<(Ed25519Signature, u8)>,
+    ) -> std::result::Result<Self, CryptoMaterialError> {
+        signatures.sort_by_key(|(_, index)| *index);
+        let mut bitmap = [0u8; BITMAP_NUM_OF_BYTES];
+        for (_, index) in &signatures {
+            let index = usize::from(*index);
+            if index >= MAX_NUM_OF_KEYS || bitmap_get_bit(bitmap, index) {
+                return Err(CryptoMaterialError::ValidationError);
+            }
+            bitmap[index / 8] |= 128 >> (index % 8);
+        }
+        Ok(MultiEd25519Signature {
+            signatures: signatures.into_iter().map(|(sig, _)| sig).collect(),
+            bitmap,
+        })
+    }
+
+    /// The signatures, in key order.
+    pub fn signatures(&self) -> &[Ed25519Signature] {
+        &self.signatures
+    }
+
+    /// The bitmap of the keys that signed.
+    pub fn bitmap(&self) -> [u8; BITMAP_NUM_OF_BYTES] {
+        self.bitmap
+    }
+}
+
+///////////////////////
+// PrivateKey Traits //
+///////////////////////
+
+impl PrivateKey for MultiEd25519PrivateKey {
+    type PublicKeyMaterial = MultiEd25519PublicKey;
+}
+
+impl SigningKey for MultiEd25519PrivateKey {
+    type VerifyingKeyMaterial = MultiEd25519PublicKey;
+    type SignatureMaterial = MultiEd25519Signature;
+
+    /// Signs with the first `threshold` keys.
+    fn sign_message(&self, message: &HashValue) -> MultiEd25519Signature {
+        let signatures = self
+            .private_keys
+            .iter()
+            .take(usize::from(self.threshold))
+            .enumerate()
+            .map(|(index, key)| (key.sign_message(message), index as u8))
+            .collect();
+        MultiEd25519Signature::new(signatures).expect("Indexes are distinct and in range")
+    }
+}
+
+impl PartialEq<Self> for MultiEd25519PrivateKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_bytes() == other.to_bytes()
+    }
+}
+
+impl Eq for MultiEd25519PrivateKey {}
+
+impl TryFrom<&[u8]> for MultiEd25519PrivateKey {
+    type Error = CryptoMaterialError;
+
+    fn try_from(bytes: &[u8]) -> std::result::Result<MultiEd25519PrivateKey, CryptoMaterialError> {
+        let (keys, threshold) = split_threshold(bytes, ED25519_PRIVATE_KEY_LENGTH)?;
+        let private_keys = keys
+            .chunks(ED25519_PRIVATE_KEY_LENGTH)
+            .map(Ed25519PrivateKey::try_from)
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        MultiEd25519PrivateKey::new(private_keys, threshold)
+    }
+}
+
+impl ValidKey for MultiEd25519PrivateKey {
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes: Vec<u8> = self
+            .private_keys
+            .iter()
+            .flat_map(|key| key.to_bytes().to_vec())
+            .collect();
+        bytes.push(self.threshold);
+        bytes
+    }
+}
+
+//////////////////////
+// PublicKey Traits //
+//////////////////////
+
+impl From<&MultiEd25519PrivateKey> for MultiEd25519PublicKey {
+    fn from(private_key: &MultiEd25519PrivateKey) -> Self {
+        MultiEd25519PublicKey {
+            public_keys: private_key
+                .private_keys
+                .iter()
+                .map(|key| key.into())
+                .collect(),
+            threshold: private_key.threshold,
+        }
+    }
+}
+
+impl PublicKey for MultiEd25519PublicKey {
+    type PrivateKeyMaterial = MultiEd25519PrivateKey;
+}
+
+impl VerifyingKey for MultiEd25519PublicKey {
+    type SigningKeyMaterial = MultiEd25519PrivateKey;
+    type SignatureMaterial = MultiEd25519Signature;
+}
+
+impl fmt::Display for MultiEd25519PublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", hex::encode(&self.to_bytes()))
+    }
+}
+
+impl TryFrom<&[u8]> for MultiEd25519PublicKey {
+    type Error = CryptoMaterialError;
+
+    /// Deserialize a MultiEd25519PublicKey, checking every key as
+    /// [`Ed25519PublicKey::try_from`] does.
+    fn try_from(bytes: &[u8]) -> std::result::Result<MultiEd25519PublicKey, CryptoMaterialError> {
+        let (keys, threshold) = split_threshold(bytes, ED25519_PUBLIC_KEY_LENGTH)?;
+        let public_keys = keys
+            .chunks(ED25519_PUBLIC_KEY_LENGTH)
+            .map(Ed25519PublicKey::try_from)
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        MultiEd25519PublicKey::new(public_keys, threshold)
+    }
+}
+
+impl ValidKey for MultiEd25519PublicKey {
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes: Vec<u8> = self
+            .public_keys
+            .iter()
+            .flat_map(|key| key.to_bytes().to_vec())
+            .collect();
+        bytes.push(self.threshold);
+        bytes
+    }
+}
+
+//////////////////////
+// Signature Traits //
+//////////////////////
+
+impl Signature for MultiEd25519Signature {
+    type VerifyingKeyMaterial = MultiEd25519PublicKey;
+    type SigningKeyMaterial = MultiEd25519PrivateKey;
+
+    /// Checks that `self` is valid for `message` using `public_key`.
+    fn verify(&self, message: &HashValue, public_key: &MultiEd25519PublicKey) -> Result<()> {
+        self.verify_arbitrary_msg(message.as_ref(), public_key)
+    }
+
+    /// Checks that at least `threshold` signatures were provided, that they all refer to keys of
+    /// `public_key` and that they are all valid for an arbitrary &[u8] `message`.
+    fn verify_arbitrary_msg(
+        &self,
+        message: &[u8],
+        public_key: &MultiEd25519PublicKey,
+    ) -> Result<()> {
+        let num_of_keys = public_key.public_keys.len();
+        if (num_of_keys..MAX_NUM_OF_KEYS).any(|index| bitmap_get_bit(self.bitmap, index)) {
+            bail!("Signature bitmap refers to a non-existent key");
+        }
+        if bitmap_count_ones(self.bitmap) as usize != self.signatures.len() {
+            bail!("Signature bitmap does not match the number of signatures");
+        }
+        if self.signatures.len() < usize::from(public_key.threshold) {
+            bail!(
+                "{} signatures provided, {} required",
+                self.signatures.len(),
+                public_key.threshold
+            );
+        }
+        let keys = (0..num_of_keys)
+            .filter(|index| bitmap_get_bit(self.bitmap, *index))
+            .map(|index| &public_key.public_keys[index]);
+        for (key, signature) in keys.zip(self.signatures.iter()) {
+            signature.verify_arbitrary_msg(message, key)?;
+        }
+        Ok(())
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes: Vec<u8> = self
+            .signatures
+            .iter()
+            .flat_map(|sig| sig.to_bytes().to_vec())
+            .collect();
+        bytes.extend_from_slice(&self.bitmap);
+        bytes
+    }
+}
+
+impl TryFrom<&[u8]> for MultiEd25519Signature {
+    type Error = CryptoMaterialError;
+
+    fn try_from(bytes: &[u8]) -> std::result::Result<MultiEd25519Signature, CryptoMaterialError> {
+        if bytes.len() < BITMAP_NUM_OF_BYTES
+            || (bytes.len() - BITMAP_NUM_OF_BYTES) % ED25519_SIGNATURE_LENGTH != 0
+        {
+            return Err(CryptoMaterialError::WrongLengthError);
+        }
+        let (signatures, bitmap_bytes) = bytes.split_at(bytes.len() - BITMAP_NUM_OF_BYTES);
+        let mut bitmap = [0u8; BITMAP_NUM_OF_BYTES];
+        bitmap.copy_from_slice(bitmap_bytes);
+        let signatures = signatures
+            .chunks(ED25519_SIGNATURE_LENGTH)
+            .map(Ed25519Signature::try_from)
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        if bitmap_count_ones(bitmap) as usize != signatures.len() {
+            return Err(CryptoMaterialError::ValidationError);
+        }
+        Ok(MultiEd25519Signature { signatures, bitmap })
+    }
+}
+
+//////////////////////////////
+// Canonical Serialization  //
+//////////////////////////////
+
+impl CanonicalSerialize for MultiEd25519PublicKey {
+    fn serialize(&self, serializer: &mut impl CanonicalSerializer) -> Result<()> {
+        serializer.encode_bytes(&self.to_bytes())?;
+        Ok(())
+    }
+}
+
+impl CanonicalDeserialize for MultiEd25519PublicKey {
+    fn deserialize(deserializer: &mut impl CanonicalDeserializer) -> Result<Self>
+    where
+        Self: Sized,
+    {
+        let public_key_bytes = deserializer.decode_bytes()?;
+        Ok(MultiEd25519PublicKey::try_from(&public_key_bytes[..])?)
+    }
+}
+
+impl CanonicalSerialize for MultiEd25519Signature {
+    fn serialize(&self, serializer: &mut impl CanonicalSerializer) -> Result<()> {
+        serializer.encode_bytes(&self.to_bytes())?;
+        Ok(())
+    }
+}
+
+impl CanonicalDeserialize for MultiEd25519Signature {
+    fn deserialize(deserializer: &mut impl CanonicalDeserializer) -> Result<Self>
+    where
+        Self: Sized,
+    {
+        let signature_bytes = deserializer.decode_bytes()?;
+        Ok(MultiEd25519Signature::try_from(&signature_bytes[..])?)
+    }
+}
+
+//////////////////////////////
+// Compact Serialization    //
+//////////////////////////////
+
+impl ser::Serialize for MultiEd25519PublicKey {
+    fn serialize<S>(&self, serializer: S) -> export::Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serializer.serialize_bytes(&self.to_bytes())
+    }
+}
+
+impl ser::Serialize for MultiEd25519Signature {
+    fn serialize<S>(&self, serializer: S) -> export::Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serializer.serialize_bytes(&self.to_bytes())
+    }
+}
+
+struct MultiEd25519PublicKeyVisitor;
+struct MultiEd25519SignatureVisitor;
+
+impl<'de> de::Visitor<'de> for MultiEd25519PublicKeyVisitor {
+    type Value = MultiEd25519PublicKey;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("multi-signature public key in bytes")
+    }
+
+    fn visit_bytes<E>(self, value: &[u8]) -> export::Result<MultiEd25519PublicKey, E>
+    where
+        E: de::Error,
+    {
+        MultiEd25519PublicKey::try_from(value).map_err(E::custom)
+    }
+}
+
+impl<'de> de::Visitor<'de> for MultiEd25519SignatureVisitor {
+    type Value = MultiEd25519Signature;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("multi-signature in bytes")
+    }
+
+    fn visit_bytes<E>(self, value: &[u8]) -> export::Result<MultiEd25519Signature, E>
+    where
+        E: de::Error,
+    {
+        MultiEd25519Signature::try_from(value).map_err(E::custom)
+    }
+}
+
+impl<'de> de::Deserialize<'de> for MultiEd25519PublicKey {
+    fn deserialize<D>(deserializer: D) -> export::Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_bytes(MultiEd25519PublicKeyVisitor {})
+    }
+}
+
+impl<'de> de::Deserialize<'de> for MultiEd25519Signature {
+    fn deserialize<D>(deserializer: D) -> export::Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_bytes(MultiEd25519SignatureVisitor {})
+    }
+}
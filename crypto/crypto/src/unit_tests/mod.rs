@@ -5,6 +5,7 @@ mod bls12381_test;
 mod cross_test;
 mod ed25519_test;
 mod hkdf_test;
+mod multi_ed25519_test;
 mod slip0010_test;
 mod x25519_test;
 
@@ -0,0 +1,143 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    ed25519::{Ed25519PrivateKey, Ed25519PublicKey},
+    multi_ed25519::{MultiEd25519PrivateKey, MultiEd25519PublicKey, MultiEd25519Signature},
+    traits::*,
+    unit_tests::uniform_keypair_strategy,
+};
+
+use crate::hash::HashValue;
+use core::convert::TryFrom;
+use proptest::prelude::*;
+use rand::{rngs::StdRng, SeedableRng};
+
+fn private_keys(num_of_keys: usize) -> Vec<Ed25519PrivateKey> {
+    let mut rng = StdRng::from_seed([7u8; 32]);
+    (0..num_of_keys)
+        .map(|_| Ed25519PrivateKey::generate_for_testing(&mut rng))
+        .collect()
+}
+
+fn multi_public_key(private_keys: &[Ed25519PrivateKey], threshold: u8) -> MultiEd25519PublicKey {
+    MultiEd25519PublicKey::new(
+        private_keys.iter().map(|key| key.into()).collect(),
+        threshold,
+    )
+    .unwrap()
+}
+
+proptest! {
+    #[test]
+    fn test_k_of_n_verification(
+        hash in any::<HashValue>(),
+        keypairs in proptest::array::uniform5(uniform_keypair_strategy::<Ed25519PrivateKey, Ed25519PublicKey>())
+    ) {
+        let public_key = MultiEd25519PublicKey::new(
+            keypairs.iter().map(|keypair| keypair.public_key.clone()).collect(),
+            3,
+        ).unwrap();
+        let sign = |indexes: &[u8]| {
+            MultiEd25519Signature::new(
+                indexes
+                    .iter()
+                    .map(|i| (keypairs[*i as usize].private_key.sign_message(&hash), *i))
+                    .collect(),
+            )
+            .unwrap()
+        };
+
+        prop_assert!(sign(&[0, 2, 4]).verify(&hash, &public_key).is_ok());
+        // Signatures may be given out of order.
+        prop_assert!(sign(&[4, 1, 3, 0]).verify(&hash, &public_key).is_ok());
+        prop_assert!(sign(&[1, 3]).verify(&hash, &public_key).is_err());
+    }
+}
+
+#[test]
+fn test_keys_and_signature_serialization() {
+    let keys = private_keys(3);
+    let private_key = MultiEd25519PrivateKey::new(private_keys(3), 2).unwrap();
+    let public_key: MultiEd25519PublicKey = (&private_key).into();
+    assert_eq!(public_key, multi_public_key(&keys, 2));
+
+    let serialized = public_key.to_bytes();
+    assert_eq!(serialized.len(), 3 * 32 + 1);
+    assert_eq!(serialized[96], 2);
+    assert_eq!(
+        MultiEd25519PublicKey::try_from(serialized.as_slice()),
+        Ok(public_key.clone())
+    );
+    let serialized = private_key.to_bytes();
+    assert_eq!(
+        MultiEd25519PrivateKey::try_from(serialized.as_slice()),
+        Ok(private_key)
+    );
+
+    let hash = HashValue::random();
+    let signature = MultiEd25519Signature::new(vec![
+        (keys[2].sign_message(&hash), 2),
+        (keys[0].sign_message(&hash), 0),
+    ])
+    .unwrap();
+    let serialized = signature.to_bytes();
+    assert_eq!(serialized.len(), 2 * 64 + 4);
+    // The most significant bit of the first byte is the first key.
+    assert_eq!(serialized[128..], [0b1010_0000, 0, 0, 0]);
+    let deserialized = MultiEd25519Signature::try_from(serialized.as_slice()).unwrap();
+    assert_eq!(deserialized, signature);
+    assert!(deserialized.verify(&hash, &public_key).is_ok());
+}
+
+#[test]
+fn test_invalid_keys_and_signatures() {
+    let keys = private_keys(3);
+    let public_keys: Vec<Ed25519PublicKey> = keys.iter().map(|key| key.into()).collect();
+    assert_eq!(
+        MultiEd25519PublicKey::new(public_keys.clone(), 0),
+        Err(CryptoMaterialError::ValidationError)
+    );
+    assert_eq!(
+        MultiEd25519PublicKey::new(public_keys.clone(), 4),
+        Err(CryptoMaterialError::ValidationError)
+    );
+    let public_key = multi_public_key(&keys, 2);
+    let mut serialized = public_key.to_bytes();
+    serialized.pop();
+    assert!(MultiEd25519PublicKey::try_from(serialized.as_slice()).is_err());
+
+    let hash = HashValue::random();
+    let sig = keys[0].sign_message(&hash);
+    assert_eq!(
+        MultiEd25519Signature::new(vec![(sig.clone(), 1), (sig.clone(), 1)]),
+        Err(CryptoMaterialError::ValidationError)
+    );
+    assert_eq!(
+        MultiEd25519Signature::new(vec![(sig.clone(), 32)]),
+        Err(CryptoMaterialError::ValidationError)
+    );
+    // Signatures referring to keys out of range.
+    let signature = MultiEd25519Signature::new(vec![
+        (keys[0].sign_message(&hash), 0),
+        (keys[1].sign_message(&hash), 3),
+    ])
+    .unwrap();
+    assert!(signature.verify(&hash, &public_key).is_err());
+    // Signatures attributed to the wrong keys.
+    let signature = MultiEd25519Signature::new(vec![
+        (keys[0].sign_message(&hash), 1),
+        (keys[1].sign_message(&hash), 0),
+    ])
+    .unwrap();
+    assert!(signature.verify(&hash, &public_key).is_err());
+    // A bitmap not matching the signatures.
+    let mut serialized = MultiEd25519Signature::new(vec![(sig, 0)])
+        .unwrap()
+        .to_bytes();
+    serialized[64] = 0b1100_0000;
+    assert_eq!(
+        MultiEd25519Signature::try_from(serialized.as_slice()),
+        Err(CryptoMaterialError::ValidationError)
+    );
+}
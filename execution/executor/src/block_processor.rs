@@ -20,7 +20,7 @@ use libra_types::{
     crypto_proxies::LedgerInfoWithSignatures,
     proof::{accumulator::Accumulator, definition::LeafCount, SparseMerkleProof},
     transaction::{
-        SignedTransaction, TransactionInfo, TransactionListWithProof, TransactionOutput,
+        Transaction, TransactionInfo, TransactionListWithProof, TransactionOutput,
         TransactionPayload, TransactionStatus, TransactionToCommit, Version,
     },
     write_set::{WriteOp, WriteSet},
@@ -367,6 +367,7 @@ where
                 txn_data.events().to_vec(),
                 txn_data.gas_used(),
                 txn_data.status().vm_status().major_status,
+                txn_data.status().vm_status().location.clone(),
             ));
         }
 
@@ -504,6 +505,7 @@ where
                         txn_data.events().to_vec(),
                         txn_data.gas_used(),
                         txn_data.status().vm_status().major_status,
+                        txn_data.status().vm_status().location.clone(),
                     ));
                     num_accounts_created += txn_data.num_account_created();
                 }
@@ -679,7 +681,7 @@ where
     fn process_vm_outputs(
         mut account_to_btree: HashMap<AccountAddress, BTreeMap<Vec<u8>, Vec<u8>>>,
         account_to_proof: HashMap<HashValue, SparseMerkleProof>,
-        transactions: &[SignedTransaction],
+        transactions: &[Transaction],
         vm_outputs: Vec<TransactionOutput>,
         parent_trees: &ExecutedTrees,
     ) -> Result<ProcessedVMOutput> {
@@ -694,11 +696,9 @@ where
         let mut txn_info_hashes = vec![];
 
         let proof_reader = ProofReader::new(account_to_proof);
-        for (vm_output, signed_txn) in
-            itertools::zip_eq(vm_outputs.into_iter(), transactions.iter())
-        {
+        for (vm_output, txn) in itertools::zip_eq(vm_outputs.into_iter(), transactions.iter()) {
             let (blobs, state_tree, num_accounts_created) = Self::process_write_set(
-                signed_txn,
+                txn,
                 &mut account_to_btree,
                 &proof_reader,
                 vm_output.write_set().clone(),
@@ -717,11 +717,12 @@ where
                     // Compute hash for the TransactionInfo object. We need the hash of the
                     // transaction itself, the state root hash as well as the event root hash.
                     let txn_info = TransactionInfo::new(
-                        signed_txn.hash(),
+                        txn.hash(),
                         state_tree.root_hash(),
                         event_tree.root_hash(),
                         vm_output.gas_used(),
                         status.major_status,
+                        status.location.clone(),
                     );
                     txn_info_hashes.push(txn_info.hash());
                 }
@@ -764,7 +765,7 @@ where
     /// on the write set. Returns the blob value of all these accounts as well as the newly
     /// constructed state tree.
     fn process_write_set(
-        transaction: &SignedTransaction,
+        transaction: &Transaction,
         account_to_btree: &mut HashMap<AccountAddress, BTreeMap<Vec<u8>, Vec<u8>>>,
         proof_reader: &ProofReader,
         write_set: WriteSet,
@@ -796,13 +797,19 @@ where
                     // Before writing to an account, VM should always read that account. So we
                     // should not reach this code path. The exception is genesis transaction (and
                     // maybe other FTVM transactions).
-                    match transaction.payload() {
-                        TransactionPayload::Program(_)
-                        | TransactionPayload::Module(_)
-                        | TransactionPayload::Script(_) => {
+                    match transaction {
+                        Transaction::UserTransaction(txn) => match txn.payload() {
+                            TransactionPayload::Program(_)
+                            | TransactionPayload::Module(_)
+                            | TransactionPayload::Script(_) => {
+                                bail!("Write set should be a subset of read set.")
+                            }
+                            TransactionPayload::WriteSet(_) => (),
+                        },
+                        Transaction::WriteSet(_) => (),
+                        Transaction::BlockMetadata(_) => {
                             bail!("Write set should be a subset of read set.")
                         }
-                        TransactionPayload::WriteSet(_) => (),
                     }
 
                     let mut account_btree = BTreeMap::new();
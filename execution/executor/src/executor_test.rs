@@ -15,7 +15,7 @@ use libra_types::{
     account_address::{AccountAddress, ADDRESS_LENGTH},
     crypto_proxies::LedgerInfoWithSignatures,
     ledger_info::LedgerInfo,
-    transaction::{SignedTransaction, TransactionListWithProof, Version},
+    transaction::{Transaction, TransactionListWithProof, Version},
 };
 use proptest::prelude::*;
 use prost_ext::MessageExt;
@@ -468,7 +468,7 @@ fn test_executor_execute_chunk_restart() {
 }
 
 struct TestBlock {
-    txns: Vec<SignedTransaction>,
+    txns: Vec<Transaction>,
     parent_id: HashValue,
     id: HashValue,
 }
@@ -492,7 +492,7 @@ impl TestBlock {
 
 // Executes a list of transactions by executing and immediately commtting one at a time. Returns
 // the root hash after all transactions are committed.
-fn run_transactions_naive(transactions: Vec<SignedTransaction>) -> HashValue {
+fn run_transactions_naive(transactions: Vec<Transaction>) -> HashValue {
     let executor = TestExecutor::new();
     let mut iter = transactions.into_iter();
     let first_txn = iter.next();
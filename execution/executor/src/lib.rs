@@ -27,7 +27,9 @@ use libra_types::{
     crypto_proxies::LedgerInfoWithSignatures,
     ledger_info::LedgerInfo,
     proof::accumulator::Accumulator,
-    transaction::{SignedTransaction, TransactionListWithProof, TransactionStatus, Version},
+    transaction::{
+        SignedTransaction, Transaction, TransactionListWithProof, TransactionStatus, Version,
+    },
     validator_set::ValidatorSet,
 };
 use logger::prelude::*;
@@ -212,7 +214,7 @@ where
         // immediately.
         // We create `PRE_GENESIS_BLOCK_ID` as the parent of the genesis block.
         let state_compute_result = block_on(self.execute_block(
-            vec![genesis_txn],
+            vec![Transaction::UserTransaction(genesis_txn)],
             *PRE_GENESIS_BLOCK_ID,
             *GENESIS_BLOCK_ID,
         ))
@@ -236,10 +238,12 @@ where
         info!("GENESIS transaction is committed.")
     }
 
-    /// Executes a block.
+    /// Executes a block. Except for the genesis block, the transactions proposed by consensus are
+    /// preceded by the `Transaction::BlockMetadata` of the block, which is stored in the ledger
+    /// like any other transaction.
     pub fn execute_block(
         &self,
-        transactions: Vec<SignedTransaction>,
+        transactions: Vec<Transaction>,
         parent_id: HashValue,
         id: HashValue,
     ) -> oneshot::Receiver<Result<StateComputeResult>> {
@@ -355,7 +359,7 @@ impl<V> Drop for Executor<V> {
 #[derive(Debug)]
 enum Command {
     ExecuteBlock {
-        transactions: Vec<SignedTransaction>,
+        transactions: Vec<Transaction>,
         parent_id: HashValue,
         id: HashValue,
         resp_sender: oneshot::Sender<Result<StateComputeResult>>,
@@ -1,15 +1,22 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use super::{balance_ap, encode_mint_transaction, encode_transfer_transaction, seqnum_ap, MockVM};
+use super::{
+    balance_ap, encode_mint_transaction, encode_transfer_transaction, seqnum_ap, MockVM,
+    BLOCK_METADATA_STATUS,
+};
 use config::config::VMConfig;
+use crypto::HashValue;
 use failure::Result;
 use libra_types::{
     access_path::AccessPath,
     account_address::{AccountAddress, ADDRESS_LENGTH},
+    block_metadata::BlockMetadata,
+    transaction::Transaction,
     write_set::WriteOp,
 };
 use state_view::StateView;
+use std::collections::BTreeMap;
 use vm_runtime::VMExecutor;
 
 fn gen_address(index: u8) -> AccountAddress {
@@ -47,7 +54,7 @@ fn test_mock_vm_different_senders() {
     );
 
     for (output, txn) in itertools::zip_eq(outputs.iter(), txns.iter()) {
-        let sender = txn.sender();
+        let sender = txn.as_signed_user_txn().unwrap().sender();
         assert_eq!(
             output.write_set().iter().cloned().collect::<Vec<_>>(),
             vec![
@@ -140,3 +147,31 @@ fn test_mock_vm_payment() {
         ]
     );
 }
+
+#[test]
+fn test_mock_vm_block_metadata() {
+    let txns = vec![
+        Transaction::BlockMetadata(BlockMetadata::new(
+            HashValue::zero(),
+            0,
+            BTreeMap::new(),
+            gen_address(0),
+        )),
+        encode_mint_transaction(gen_address(1), 100),
+    ];
+
+    let outputs = MockVM::execute_block(
+        txns,
+        &VMConfig::empty_whitelist_FOR_TESTING(),
+        &MockStateView,
+    );
+
+    assert_eq!(outputs.len(), 2);
+    assert!(outputs[0].write_set().is_empty());
+    assert_eq!(outputs[0].status(), &*BLOCK_METADATA_STATUS);
+    assert_eq!(
+        outputs[1].write_set().iter().count(),
+        2,
+        "The mint after the block metadata should still be executed."
+    );
+}
@@ -13,7 +13,7 @@ use libra_types::{
     contract_event::ContractEvent,
     event::EventKey,
     transaction::{
-        RawTransaction, Script, SignedTransaction, TransactionArgument, TransactionOutput,
+        RawTransaction, Script, Transaction, TransactionArgument, TransactionOutput,
         TransactionPayload, TransactionStatus,
     },
     vm_error::{StatusCode, VMStatus},
@@ -24,7 +24,7 @@ use std::collections::HashMap;
 use vm_runtime::VMExecutor;
 
 #[derive(Debug)]
-enum Transaction {
+enum MockVMTransaction {
     Mint {
         sender: AccountAddress,
         amount: u64,
@@ -34,6 +34,7 @@ enum Transaction {
         recipient: AccountAddress,
         amount: u64,
     },
+    BlockMetadata,
 }
 
 lazy_static! {
@@ -43,13 +44,17 @@ lazy_static! {
     // We use 10 as the assertion error code for insufficient balance within the Libra coin contract.
     pub static ref DISCARD_STATUS: TransactionStatus =
         TransactionStatus::Discard(VMStatus::new(StatusCode::ABORTED).with_sub_status(10));
+
+    // MockVM doesn't model the block prologue, so block metadata transactions have no effect.
+    pub static ref BLOCK_METADATA_STATUS: TransactionStatus =
+        TransactionStatus::Discard(VMStatus::new(StatusCode::EXECUTED));
 }
 
 pub struct MockVM;
 
 impl VMExecutor for MockVM {
     fn execute_block(
-        transactions: Vec<SignedTransaction>,
+        transactions: Vec<Transaction>,
         _config: &VMConfig,
         state_view: &dyn StateView,
     ) -> Vec<TransactionOutput> {
@@ -71,7 +76,7 @@ impl VMExecutor for MockVM {
 
         for txn in transactions {
             match decode_transaction(&txn) {
-                Transaction::Mint { sender, amount } => {
+                MockVMTransaction::Mint { sender, amount } => {
                     let old_balance = read_balance(&output_cache, state_view, sender);
                     let new_balance = old_balance + amount;
                     let old_seqnum = read_seqnum(&output_cache, state_view, sender);
@@ -89,7 +94,15 @@ impl VMExecutor for MockVM {
                         KEEP_STATUS.clone(),
                     ));
                 }
-                Transaction::Payment {
+                MockVMTransaction::BlockMetadata => {
+                    outputs.push(TransactionOutput::new(
+                        WriteSet::default(),
+                        vec![],
+                        0,
+                        BLOCK_METADATA_STATUS.clone(),
+                    ));
+                }
+                MockVMTransaction::Payment {
                     sender,
                     recipient,
                     amount,
@@ -260,7 +273,7 @@ pub fn encode_transfer_program(recipient: AccountAddress, amount: u64) -> Script
     Script::new(vec![], vec![argument1, argument2])
 }
 
-pub fn encode_mint_transaction(sender: AccountAddress, amount: u64) -> SignedTransaction {
+pub fn encode_mint_transaction(sender: AccountAddress, amount: u64) -> Transaction {
     encode_transaction(sender, encode_mint_program(amount))
 }
 
@@ -268,36 +281,43 @@ pub fn encode_transfer_transaction(
     sender: AccountAddress,
     recipient: AccountAddress,
     amount: u64,
-) -> SignedTransaction {
+) -> Transaction {
     encode_transaction(sender, encode_transfer_program(recipient, amount))
 }
 
-fn encode_transaction(sender: AccountAddress, program: Script) -> SignedTransaction {
+fn encode_transaction(sender: AccountAddress, program: Script) -> Transaction {
     let raw_transaction =
         RawTransaction::new_script(sender, 0, program, 0, 0, std::time::Duration::from_secs(0));
 
     let (privkey, pubkey) = compat::generate_keypair(None);
-    raw_transaction
-        .sign(&privkey, pubkey)
-        .expect("Failed to sign raw transaction.")
-        .into_inner()
+    Transaction::UserTransaction(
+        raw_transaction
+            .sign(&privkey, pubkey)
+            .expect("Failed to sign raw transaction.")
+            .into_inner(),
+    )
 }
 
-fn decode_transaction(txn: &SignedTransaction) -> Transaction {
+fn decode_transaction(txn: &Transaction) -> MockVMTransaction {
+    let txn = match txn {
+        Transaction::UserTransaction(txn) => txn,
+        Transaction::BlockMetadata(_) => return MockVMTransaction::BlockMetadata,
+        Transaction::WriteSet(_) => unimplemented!("MockVM does not support WriteSet transaction."),
+    };
     let sender = txn.sender();
     match txn.payload() {
         TransactionPayload::Script(script) => {
             assert!(script.code().is_empty(), "Code should be empty.");
             match script.args().len() {
                 1 => match script.args()[0] {
-                    TransactionArgument::U64(amount) => Transaction::Mint { sender, amount },
+                    TransactionArgument::U64(amount) => MockVMTransaction::Mint { sender, amount },
                     _ => unimplemented!(
                         "Only one integer argument is allowed for mint transactions."
                     ),
                 },
                 2 => match (&script.args()[0], &script.args()[1]) {
                     (TransactionArgument::Address(recipient), TransactionArgument::U64(amount)) => {
-                        Transaction::Payment {
+                        MockVMTransaction::Payment {
                             sender,
                             recipient: *recipient,
                             amount: *amount,
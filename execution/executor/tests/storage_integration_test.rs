@@ -19,7 +19,8 @@ use libra_types::{
     ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
     test_helpers::transaction_test_helpers::get_test_signed_txn,
     transaction::{
-        Script, SignedTransaction, SignedTransactionWithProof, TransactionListWithProof,
+        Script, SignedTransaction, SignedTransactionWithProof, Transaction,
+        TransactionListWithProof,
     },
 };
 use rand::SeedableRng;
@@ -184,10 +185,19 @@ fn test_execution_with_storage() {
         ));
     }
 
-    let state_compute_result =
-        block_on(executor.execute_block(block1.clone(), *GENESIS_BLOCK_ID, block1_id))
-            .unwrap()
-            .unwrap();
+    let state_compute_result = block_on(
+        executor.execute_block(
+            block1
+                .iter()
+                .cloned()
+                .map(Transaction::UserTransaction)
+                .collect(),
+            *GENESIS_BLOCK_ID,
+            block1_id,
+        ),
+    )
+    .unwrap()
+    .unwrap();
     let ledger_info_with_sigs =
         gen_ledger_info_with_sigs(6, state_compute_result.root_hash(), block1_id);
     block_on(executor.commit_block(ledger_info_with_sigs))
@@ -421,10 +431,19 @@ fn test_execution_with_storage() {
     assert_eq!(account3_received_events.len(), 3);
 
     // Execution the 2nd block.
-    let state_compute_result =
-        block_on(executor.execute_block(block2.clone(), block1_id, block2_id))
-            .unwrap()
-            .unwrap();
+    let state_compute_result = block_on(
+        executor.execute_block(
+            block2
+                .iter()
+                .cloned()
+                .map(Transaction::UserTransaction)
+                .collect(),
+            block1_id,
+            block2_id,
+        ),
+    )
+    .unwrap()
+    .unwrap();
     let ledger_info_with_sigs =
         gen_ledger_info_with_sigs(20, state_compute_result.root_hash(), block2_id);
     block_on(executor.commit_block(ledger_info_with_sigs))
@@ -584,9 +603,8 @@ fn verify_transactions(
     let txns = txn_list_with_proof
         .transaction_and_infos
         .iter()
-        .map(|(txn, _)| txn)
-        .cloned()
-        .collect::<Vec<_>>();
+        .map(|(txn, _)| txn.as_signed_user_txn().map(Clone::clone))
+        .collect::<Result<Vec<_>>>()?;
     ensure!(
         expected_txns == &txns[..],
         "expected txns {:?} doesn't equal to returned txns {:?}",
@@ -44,3 +44,50 @@ fn valid_fallthrough_abort() {
     let errors = CodeUnitVerifier::verify(&module);
     assert!(errors.is_empty());
 }
+
+#[test]
+fn valid_u8_u128_arithmetic() {
+    let module = file_format::dummy_procedure_module(vec![
+        Bytecode::LdU8(1),
+        Bytecode::LdU8(2),
+        Bytecode::Add,
+        Bytecode::CastU128,
+        Bytecode::LdU128(3),
+        Bytecode::Lt,
+        Bytecode::Pop,
+        Bytecode::Ret,
+    ]);
+    let errors = CodeUnitVerifier::verify(&module);
+    assert!(errors.is_empty());
+}
+
+#[test]
+fn invalid_mixed_integer_arithmetic() {
+    let module = file_format::dummy_procedure_module(vec![
+        Bytecode::LdU8(1),
+        Bytecode::LdConst(2),
+        Bytecode::Add,
+        Bytecode::Pop,
+        Bytecode::Ret,
+    ]);
+    let errors = CodeUnitVerifier::verify(&module);
+    assert_eq!(
+        errors[0].major_status,
+        StatusCode::INTEGER_OP_TYPE_MISMATCH_ERROR
+    );
+}
+
+#[test]
+fn invalid_cast_of_non_integer() {
+    let module = file_format::dummy_procedure_module(vec![
+        Bytecode::LdTrue,
+        Bytecode::CastU64,
+        Bytecode::Pop,
+        Bytecode::Ret,
+    ]);
+    let errors = CodeUnitVerifier::verify(&module);
+    assert_eq!(
+        errors[0].major_status,
+        StatusCode::INTEGER_OP_TYPE_MISMATCH_ERROR
+    );
+}
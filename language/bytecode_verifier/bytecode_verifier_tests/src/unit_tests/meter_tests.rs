@@ -0,0 +1,51 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use bytecode_verifier::{CodeUnitVerifier, Meter, VerifiedModule, VerifierConfig};
+use libra_types::vm_error::StatusCode;
+use vm::file_format::{self, Bytecode, CompiledModule};
+
+// A function that loads and pops a value `count` times.
+fn long_procedure_module(count: usize) -> CompiledModule {
+    let mut code = vec![];
+    for _ in 0..count {
+        code.push(Bytecode::LdTrue);
+        code.push(Bytecode::Pop);
+    }
+    code.push(Bytecode::Ret);
+    file_format::dummy_procedure_module(code)
+}
+
+fn config(budget: u64) -> VerifierConfig {
+    VerifierConfig {
+        meter_budget: Some(budget),
+    }
+}
+
+#[test]
+fn module_within_budget_verifies() {
+    let module = long_procedure_module(100);
+    assert!(VerifiedModule::new_with_config(module.clone(), &config(1_000)).is_ok());
+    assert!(VerifiedModule::new(module.clone()).is_ok());
+    assert!(VerifiedModule::new_with_config(module, &VerifierConfig::unbounded()).is_ok());
+}
+
+#[test]
+fn module_over_budget_is_rejected() {
+    let module = long_procedure_module(100);
+    let (_, errors) = VerifiedModule::new_with_config(module, &config(100)).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].major_status, StatusCode::VERIFIER_BUDGET_EXCEEDED);
+}
+
+#[test]
+fn meter_counts_steps_and_borrow_graph_units() {
+    let module = long_procedure_module(10);
+    let mut meter = Meter::new(&VerifierConfig::unbounded());
+    assert!(CodeUnitVerifier::verify_with_meter(&module, &mut meter).is_empty());
+    // Every instruction is a step, and each `Pop` and `Ret` touches the borrow graph. The function
+    // has no locals, so the graph is empty and those operations are free.
+    assert_eq!(meter.steps(), 21);
+    assert_eq!(meter.borrow_graph_units(), 0);
+    assert!(!meter.is_exhausted());
+}
@@ -389,6 +389,20 @@ impl AbstractState {
         }
     }
 
+    /// returns the number of locals, globals and borrow edges in self, which bounds the work done
+    /// by an operation on the borrow graph
+    pub fn borrow_graph_size(&self) -> usize {
+        let borrow_edges: usize = self
+            .borrows
+            .values()
+            .map(|borrow_info| match borrow_info {
+                BorrowInfo::BorrowedBy(nonces) => nonces.len(),
+                BorrowInfo::FieldsBorrowedBy(fields) => fields.values().map(BTreeSet::len).sum(),
+            })
+            .sum();
+        self.locals.len() + self.globals.len() + borrow_edges
+    }
+
     /// checks if self is canonical
     pub fn is_canonical(&self) -> bool {
         let mut values = BTreeMap::new();
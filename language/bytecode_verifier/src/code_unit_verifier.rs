@@ -4,7 +4,14 @@
 //! This module implements the checker for verifying correctness of function bodies.
 //! The overall verification is split between stack_usage_verifier.rs and
 //! abstract_interpreter.rs. CodeUnitVerifier simply orchestrates calls into these two files.
-use crate::control_flow_graph::VMControlFlowGraph;
+use crate::{
+    control_flow_graph::VMControlFlowGraph,
+    counters::{
+        time_pass, ACQUIRES_PASS, CONTROL_FLOW_GRAPH_PASS, STACK_USAGE_PASS,
+        TYPE_MEMORY_SAFETY_PASS,
+    },
+    meter::{Meter, VerifierConfig},
+};
 use libra_types::vm_error::{StatusCode, VMStatus};
 use vm::{
     access::ModuleAccess,
@@ -20,26 +27,35 @@ use crate::{
 
 pub struct CodeUnitVerifier<'a> {
     module: &'a CompiledModule,
+    meter: &'a mut Meter,
 }
 
 impl<'a> CodeUnitVerifier<'a> {
+    /// Verifies the function bodies of `module` within the default budget.
     pub fn verify(module: &'a CompiledModule) -> Vec<VMStatus> {
-        let verifier = Self { module };
-        verifier
-            .module
-            .function_defs()
-            .iter()
-            .enumerate()
-            .flat_map(move |(idx, function_definition)| {
+        CodeUnitVerifier::verify_with_meter(module, &mut Meter::new(&VerifierConfig::default()))
+    }
+
+    /// Verifies the function bodies of `module`, charging the work done to `meter`. Stops at the
+    /// first function that exhausts the budget of `meter`.
+    pub fn verify_with_meter(module: &'a CompiledModule, meter: &'a mut Meter) -> Vec<VMStatus> {
+        let mut verifier = Self { module, meter };
+        let mut errors = vec![];
+        for (idx, function_definition) in module.function_defs().iter().enumerate() {
+            errors.extend(
                 verifier
                     .verify_function(function_definition)
                     .into_iter()
-                    .map(move |err| append_err_info(err, IndexKind::FunctionDefinition, idx))
-            })
-            .collect()
+                    .map(|err| append_err_info(err, IndexKind::FunctionDefinition, idx)),
+            );
+            if verifier.meter.is_exhausted() {
+                break;
+            }
+        }
+        errors
     }
 
-    fn verify_function(&self, function_definition: &FunctionDefinition) -> Vec<VMStatus> {
+    fn verify_function(&mut self, function_definition: &FunctionDefinition) -> Vec<VMStatus> {
         if function_definition.is_native() {
             return vec![];
         }
@@ -55,22 +71,31 @@ impl<'a> CodeUnitVerifier<'a> {
             return vec![VMStatus::new(StatusCode::INVALID_FALL_THROUGH)];
         }
 
-        self.verify_function_inner(function_definition, &VMControlFlowGraph::new(code))
+        let cfg = time_pass(CONTROL_FLOW_GRAPH_PASS, || VMControlFlowGraph::new(code));
+        self.verify_function_inner(function_definition, &cfg)
     }
 
     fn verify_function_inner(
-        &self,
+        &mut self,
         function_definition: &FunctionDefinition,
         cfg: &VMControlFlowGraph,
     ) -> Vec<VMStatus> {
-        let errors = StackUsageVerifier::verify(self.module, function_definition, cfg);
+        let module = self.module;
+        let errors = time_pass(STACK_USAGE_PASS, || {
+            StackUsageVerifier::verify(module, function_definition, cfg)
+        });
         if !errors.is_empty() {
             return errors;
         }
-        let errors = AcquiresVerifier::verify(self.module, function_definition);
+        let errors = time_pass(ACQUIRES_PASS, || {
+            AcquiresVerifier::verify(module, function_definition)
+        });
         if !errors.is_empty() {
             return errors;
         }
-        TypeAndMemorySafetyAnalysis::verify(self.module, function_definition, cfg)
+        let meter = &mut *self.meter;
+        time_pass(TYPE_MEMORY_SAFETY_PASS, || {
+            TypeAndMemorySafetyAnalysis::verify(module, function_definition, cfg, meter)
+        })
     }
 }
@@ -0,0 +1,266 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module provides a checker for whether a new version of a module can replace the version
+//! already published under the same `ModuleId`.
+//!
+//! A new version is compatible if every module and script linked against the old version still
+//! links against it, and if every value of its types already stored on chain can still be read.
+//! Since struct values of any type can be stored inside resources, including resources declared
+//! by other modules, the layout of every struct is preserved, not only the layout of resources.
+//! Types are compared by the name of the module and struct they refer to, so the tables of the
+//! two versions are free to differ.
+//!
+//! The VM runs this checker when a transaction publishes a module under a `ModuleId` that is
+//! already taken, and rejects the new version if it finds any breaking change.
+use libra_types::{
+    identifier::{IdentStr, Identifier},
+    language_storage::ModuleId,
+};
+use std::{collections::BTreeSet, fmt};
+use vm::{
+    access::ModuleAccess,
+    file_format::{Kind, SignatureToken},
+    views::{FunctionDefinitionView, ModuleView, StructDefinitionView, ViewInternals},
+};
+
+/// A change between two versions of a module that breaks code or data depending on the old one.
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum CompatibilityError {
+    /// The new version is published under a different address or name.
+    ModuleIdMismatch { old: ModuleId, new: ModuleId },
+    /// A public function was removed or made private.
+    PublicFunctionRemoved(Identifier),
+    /// The type parameters, arguments or return types of a public function changed.
+    PublicFunctionSignatureChanged(Identifier),
+    /// A public function no longer acquires a resource it acquired before.
+    AcquiresRemoved {
+        function: Identifier,
+        resource: Identifier,
+    },
+    /// A struct was removed.
+    StructRemoved(Identifier),
+    /// A struct changed between resource and value, or the kinds of its type parameters changed.
+    StructKindChanged(Identifier),
+    /// The fields of a struct changed, or it changed between native and declared.
+    StructLayoutChanged(Identifier),
+}
+
+impl fmt::Display for CompatibilityError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CompatibilityError::ModuleIdMismatch { old, new } => write!(
+                f,
+                "module {}.{} is replaced by {}.{}",
+                old.address(),
+                old.name(),
+                new.address(),
+                new.name()
+            ),
+            CompatibilityError::PublicFunctionRemoved(name) => {
+                write!(f, "public function {} was removed or made private", name)
+            }
+            CompatibilityError::PublicFunctionSignatureChanged(name) => {
+                write!(f, "signature of public function {} changed", name)
+            }
+            CompatibilityError::AcquiresRemoved { function, resource } => write!(
+                f,
+                "public function {} no longer acquires {}",
+                function, resource
+            ),
+            CompatibilityError::StructRemoved(name) => write!(f, "struct {} was removed", name),
+            CompatibilityError::StructKindChanged(name) => {
+                write!(f, "kind of struct {} changed", name)
+            }
+            CompatibilityError::StructLayoutChanged(name) => {
+                write!(f, "layout of struct {} changed", name)
+            }
+        }
+    }
+}
+
+pub struct CompatibilityChecker<'a, T: ModuleAccess> {
+    old: ModuleView<'a, T>,
+    new: ModuleView<'a, T>,
+}
+
+impl<'a, T: ModuleAccess> CompatibilityChecker<'a, T> {
+    pub fn new(old: &'a T, new: &'a T) -> Self {
+        Self {
+            old: ModuleView::new(old),
+            new: ModuleView::new(new),
+        }
+    }
+
+    /// Returns the breaking changes from the old to the new version, sorted.
+    pub fn check(self) -> Vec<CompatibilityError> {
+        let (old_id, new_id) = (self.old.id(), self.new.id());
+        if old_id != new_id {
+            return vec![CompatibilityError::ModuleIdMismatch {
+                old: old_id,
+                new: new_id,
+            }];
+        }
+
+        let mut errors = vec![];
+        for old_struct in self.old.structs() {
+            let name = old_struct.name();
+            match self.new.struct_definition(name) {
+                None => errors.push(CompatibilityError::StructRemoved(name.to_owned())),
+                Some(new_struct) => self.check_struct(&old_struct, new_struct, &mut errors),
+            }
+        }
+        for old_function in self.old.functions().filter(|function| function.is_public()) {
+            let name = old_function.name();
+            match self.new.function_definition(name) {
+                Some(new_function) if new_function.is_public() => {
+                    self.check_function(&old_function, new_function, &mut errors)
+                }
+                _ => errors.push(CompatibilityError::PublicFunctionRemoved(name.to_owned())),
+            }
+        }
+        errors.sort();
+        errors
+    }
+
+    fn check_struct(
+        &self,
+        old: &StructDefinitionView<'a, T>,
+        new: &StructDefinitionView<'a, T>,
+        errors: &mut Vec<CompatibilityError>,
+    ) {
+        let name = old.name().to_owned();
+        if old.is_nominal_resource() != new.is_nominal_resource()
+            || old.type_formals() != new.type_formals()
+        {
+            errors.push(CompatibilityError::StructKindChanged(name.clone()));
+        }
+        if struct_layout(old, self.old.module()) != struct_layout(new, self.new.module()) {
+            errors.push(CompatibilityError::StructLayoutChanged(name));
+        }
+    }
+
+    fn check_function(
+        &self,
+        old: &FunctionDefinitionView<'a, T>,
+        new: &FunctionDefinitionView<'a, T>,
+        errors: &mut Vec<CompatibilityError>,
+    ) {
+        let name = old.name();
+        if function_signature(old, self.old.module()) != function_signature(new, self.new.module())
+        {
+            errors.push(CompatibilityError::PublicFunctionSignatureChanged(
+                name.to_owned(),
+            ));
+        }
+
+        let new_acquires = acquired_resources(new, self.new.module());
+        for resource in acquired_resources(old, self.old.module()) {
+            if !new_acquires.contains(resource) {
+                errors.push(CompatibilityError::AcquiresRemoved {
+                    function: name.to_owned(),
+                    resource: resource.to_owned(),
+                });
+            }
+        }
+    }
+}
+
+// The field names and types of a struct, or `None` for native structs.
+fn struct_layout<T: ModuleAccess>(
+    def: &StructDefinitionView<T>,
+    module: &T,
+) -> Option<Vec<(Identifier, NormalizedType)>> {
+    Some(
+        def.fields()?
+            .map(|field| {
+                (
+                    field.name().to_owned(),
+                    NormalizedType::new(module, field.signature_token()),
+                )
+            })
+            .collect(),
+    )
+}
+
+fn function_signature<T: ModuleAccess>(
+    def: &FunctionDefinitionView<T>,
+    module: &T,
+) -> (Vec<Kind>, Vec<NormalizedType>, Vec<NormalizedType>) {
+    let signature = def.signature().as_inner();
+    let normalize = |tokens: &[SignatureToken]| {
+        tokens
+            .iter()
+            .map(|token| NormalizedType::new(module, token))
+            .collect::<Vec<_>>()
+    };
+    (
+        signature.type_formals.clone(),
+        normalize(&signature.arg_types),
+        normalize(&signature.return_types),
+    )
+}
+
+fn acquired_resources<'a, T: ModuleAccess>(
+    def: &FunctionDefinitionView<'a, T>,
+    module: &'a T,
+) -> BTreeSet<&'a IdentStr> {
+    def.as_inner()
+        .acquires_global_resources
+        .iter()
+        .map(|idx| {
+            let handle = module.struct_handle_at(module.struct_def_at(*idx).struct_handle);
+            module.identifier_at(handle.name)
+        })
+        .collect()
+}
+
+/// A `SignatureToken` with struct handles resolved to the module and name of the struct, so that
+/// types of different modules can be compared.
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum NormalizedType {
+    Bool,
+    U8,
+    U64,
+    U128,
+    String,
+    ByteArray,
+    Address,
+    Struct(ModuleId, Identifier, Vec<NormalizedType>),
+    Reference(Box<NormalizedType>),
+    MutableReference(Box<NormalizedType>),
+    TypeParameter(u16),
+}
+
+impl NormalizedType {
+    fn new(module: &impl ModuleAccess, token: &SignatureToken) -> Self {
+        match token {
+            SignatureToken::Bool => NormalizedType::Bool,
+            SignatureToken::U8 => NormalizedType::U8,
+            SignatureToken::U64 => NormalizedType::U64,
+            SignatureToken::U128 => NormalizedType::U128,
+            SignatureToken::String => NormalizedType::String,
+            SignatureToken::ByteArray => NormalizedType::ByteArray,
+            SignatureToken::Address => NormalizedType::Address,
+            SignatureToken::Struct(idx, actuals) => {
+                let handle = module.struct_handle_at(*idx);
+                let module_id = module.module_id_for_handle(module.module_handle_at(handle.module));
+                NormalizedType::Struct(
+                    module_id,
+                    module.identifier_at(handle.name).to_owned(),
+                    actuals
+                        .iter()
+                        .map(|actual| NormalizedType::new(module, actual))
+                        .collect(),
+                )
+            }
+            SignatureToken::Reference(inner) => {
+                NormalizedType::Reference(Box::new(NormalizedType::new(module, inner)))
+            }
+            SignatureToken::MutableReference(inner) => {
+                NormalizedType::MutableReference(Box::new(NormalizedType::new(module, inner)))
+            }
+            SignatureToken::TypeParameter(idx) => NormalizedType::TypeParameter(*idx),
+        }
+    }
+}
@@ -0,0 +1,44 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module defines the metrics recorded by the verifier: the time taken by each pass and the
+//! units charged by the `Meter` for each module.
+use crate::meter::Meter;
+use lazy_static::lazy_static;
+use metrics::OpMetrics;
+
+// names of the timed passes
+pub const DUPLICATION_PASS: &str = "verifier.pass.duplication";
+pub const SIGNATURE_PASS: &str = "verifier.pass.signature";
+pub const RESOURCE_TRANSITIVE_PASS: &str = "verifier.pass.resource_transitive";
+pub const RECURSIVE_STRUCT_DEF_PASS: &str = "verifier.pass.recursive_struct_def";
+pub const INSTANTIATION_LOOP_PASS: &str = "verifier.pass.instantiation_loop";
+pub const CODE_UNIT_PASS: &str = "verifier.pass.code_unit";
+pub const CONTROL_FLOW_GRAPH_PASS: &str = "verifier.pass.control_flow_graph";
+pub const STACK_USAGE_PASS: &str = "verifier.pass.stack_usage";
+pub const ACQUIRES_PASS: &str = "verifier.pass.acquires";
+pub const TYPE_MEMORY_SAFETY_PASS: &str = "verifier.pass.type_memory_safety";
+
+const METER_STEPS: &str = "verifier.meter.steps";
+const METER_BORROW_GRAPH_UNITS: &str = "verifier.meter.borrow_graph_units";
+const METER_BUDGET_EXCEEDED: &str = "verifier.meter.budget_exceeded";
+
+lazy_static! {
+    pub static ref VERIFIER_COUNTERS: OpMetrics =
+        OpMetrics::new_and_registered("bytecode_verifier");
+}
+
+/// Runs `pass` and records the time it took under the `name` histogram.
+pub fn time_pass<T>(name: &str, pass: impl FnOnce() -> T) -> T {
+    let _timer = VERIFIER_COUNTERS.timer(name);
+    pass()
+}
+
+/// Records the units charged by `meter` for a module.
+pub fn report_meter(meter: &Meter) {
+    VERIFIER_COUNTERS.observe(METER_STEPS, meter.steps() as f64);
+    VERIFIER_COUNTERS.observe(METER_BORROW_GRAPH_UNITS, meter.borrow_graph_units() as f64);
+    if meter.is_exhausted() {
+        VERIFIER_COUNTERS.inc(METER_BUDGET_EXCEEDED);
+    }
+}
@@ -9,8 +9,11 @@ pub mod abstract_state;
 pub mod acquires_list_verifier;
 pub mod check_duplication;
 pub mod code_unit_verifier;
+pub mod compatibility;
 pub mod control_flow_graph;
+pub mod counters;
 pub mod instantiation_loops;
+pub mod meter;
 pub mod nonce;
 pub mod partition;
 pub mod resources;
@@ -24,6 +27,8 @@ pub mod verifier;
 
 pub use check_duplication::DuplicationChecker;
 pub use code_unit_verifier::CodeUnitVerifier;
+pub use compatibility::{CompatibilityChecker, CompatibilityError};
+pub use meter::{Meter, VerifierConfig};
 pub use resources::ResourceTransitiveChecker;
 pub use signature::SignatureChecker;
 pub use stack_usage_verifier::StackUsageVerifier;
@@ -0,0 +1,110 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module bounds the work the verifier does on a single module.
+//!
+//! The abstract interpreter in type_memory_safety.rs runs until it reaches a fixed point, and
+//! every instruction it executes may rewrite the borrow graph of the abstract state. A module with
+//! deeply nested loops and many locals can therefore take a long time to verify. The `Meter`
+//! charges one unit per instruction executed and, for every operation on the borrow graph, one unit
+//! per local, global and borrow edge in the graph. Verification fails with
+//! `VERIFIER_BUDGET_EXCEEDED` once the units charged exceed the budget. The units only depend on
+//! the module, so every node reaches the same verdict.
+use crate::abstract_state::AbstractState;
+use libra_types::vm_error::{StatusCode, VMStatus};
+use vm::errors::VMResult;
+
+/// The number of units the verifier may charge for a script or a module published by a
+/// transaction. It is part of the protocol: changing it changes which transactions are accepted.
+pub const DEFAULT_METER_BUDGET: u64 = 10_000_000;
+
+/// Configuration for the bytecode verifier.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct VerifierConfig {
+    /// The number of units the verifier may charge for a single module, or `None` for no limit.
+    pub meter_budget: Option<u64>,
+}
+
+impl VerifierConfig {
+    /// Returns a configuration that does not bound the work done by the verifier.
+    pub fn unbounded() -> Self {
+        Self { meter_budget: None }
+    }
+}
+
+impl Default for VerifierConfig {
+    fn default() -> Self {
+        Self {
+            meter_budget: Some(DEFAULT_METER_BUDGET),
+        }
+    }
+}
+
+/// Counts the work done by the verifier against a budget.
+#[derive(Clone, Debug)]
+pub struct Meter {
+    budget: Option<u64>,
+    steps: u64,
+    borrow_graph_units: u64,
+}
+
+impl Meter {
+    pub fn new(config: &VerifierConfig) -> Self {
+        Self {
+            budget: config.meter_budget,
+            steps: 0,
+            borrow_graph_units: 0,
+        }
+    }
+
+    /// Charges for executing one instruction in the abstract interpreter.
+    pub fn charge_step(&mut self) -> VMResult<()> {
+        self.steps = self.steps.saturating_add(1);
+        self.check()
+    }
+
+    /// Charges for an operation on the borrow graph of `state`.
+    pub fn charge_borrow_graph(&mut self, state: &AbstractState) -> VMResult<()> {
+        self.borrow_graph_units = self
+            .borrow_graph_units
+            .saturating_add(state.borrow_graph_size() as u64);
+        self.check()
+    }
+
+    /// Returns the number of instructions executed in the abstract interpreter.
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    /// Returns the number of units charged for operations on borrow graphs.
+    pub fn borrow_graph_units(&self) -> u64 {
+        self.borrow_graph_units
+    }
+
+    /// Returns the total number of units charged.
+    pub fn units(&self) -> u64 {
+        self.steps.saturating_add(self.borrow_graph_units)
+    }
+
+    /// Returns true if the units charged exceed the budget.
+    pub fn is_exhausted(&self) -> bool {
+        match self.budget {
+            Some(budget) => self.units() > budget,
+            None => false,
+        }
+    }
+
+    /// Returns a `VERIFIER_BUDGET_EXCEEDED` error if the units charged exceed the budget.
+    pub fn check(&self) -> VMResult<()> {
+        match self.budget {
+            Some(budget) if self.units() > budget => Err(VMStatus::new(
+                StatusCode::VERIFIER_BUDGET_EXCEEDED,
+            )
+            .with_message(format!(
+                "{} steps and {} borrow graph units exceed the budget of {} units",
+                self.steps, self.borrow_graph_units, budget
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
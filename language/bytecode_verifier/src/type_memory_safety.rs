@@ -7,6 +7,7 @@ use crate::{
     absint::{AbstractInterpreter, BlockPrecondition, TransferFunctions},
     abstract_state::{AbstractState, AbstractValue},
     control_flow_graph::VMControlFlowGraph,
+    meter::Meter,
     nonce::Nonce,
 };
 use libra_types::vm_error::{StatusCode, VMStatus};
@@ -38,6 +39,7 @@ pub struct TypeAndMemorySafetyAnalysis<'a> {
     stack: Vec<StackAbstractValue>,
     next_nonce: usize,
     errors: Vec<VMStatus>,
+    meter: &'a mut Meter,
 }
 
 impl<'a> TypeAndMemorySafetyAnalysis<'a> {
@@ -45,6 +47,7 @@ impl<'a> TypeAndMemorySafetyAnalysis<'a> {
         module: &'a CompiledModule,
         function_definition: &'a FunctionDefinition,
         cfg: &'a VMControlFlowGraph,
+        meter: &'a mut Meter,
     ) -> Vec<VMStatus> {
         let module_view = ModuleView::new(module);
         let function_definition_view = FunctionDefinitionView::new(module, function_definition);
@@ -100,9 +103,15 @@ impl<'a> TypeAndMemorySafetyAnalysis<'a> {
             stack: vec![],
             next_nonce,
             errors: vec![],
+            meter,
         };
 
         let inv_map = verifier.analyze_function(initial_state, &function_definition_view, cfg);
+        // Once the budget is exhausted every block fails without being analyzed, so the other
+        // errors are not meaningful.
+        if let Err(err) = verifier.meter.check() {
+            return vec![err];
+        }
         // Report all the join failures
         for (block_id, inv) in inv_map.iter() {
             match inv.pre() {
@@ -500,6 +509,14 @@ impl<'a> TypeAndMemorySafetyAnalysis<'a> {
                 self.borrow_field(state, offset, false, *field_definition_index)
             }
 
+            Bytecode::LdU8(_) => {
+                self.stack.push(StackAbstractValue {
+                    signature: SignatureToken::U8,
+                    value: AbstractValue::full_value(Kind::Unrestricted),
+                });
+                Ok(())
+            }
+
             Bytecode::LdConst(_) => {
                 self.stack.push(StackAbstractValue {
                     signature: SignatureToken::U64,
@@ -508,6 +525,34 @@ impl<'a> TypeAndMemorySafetyAnalysis<'a> {
                 Ok(())
             }
 
+            Bytecode::LdU128(_) => {
+                self.stack.push(StackAbstractValue {
+                    signature: SignatureToken::U128,
+                    value: AbstractValue::full_value(Kind::Unrestricted),
+                });
+                Ok(())
+            }
+
+            Bytecode::CastU8 | Bytecode::CastU64 | Bytecode::CastU128 => {
+                let operand = self.stack.pop().unwrap();
+                if !operand.signature.is_integer() {
+                    return Err(err_at_offset(
+                        StatusCode::INTEGER_OP_TYPE_MISMATCH_ERROR,
+                        offset,
+                    ));
+                }
+                let signature = match bytecode {
+                    Bytecode::CastU8 => SignatureToken::U8,
+                    Bytecode::CastU64 => SignatureToken::U64,
+                    _ => SignatureToken::U128,
+                };
+                self.stack.push(StackAbstractValue {
+                    signature,
+                    value: AbstractValue::full_value(Kind::Unrestricted),
+                });
+                Ok(())
+            }
+
             Bytecode::LdAddr(_) => {
                 self.stack.push(StackAbstractValue {
                     signature: SignatureToken::Address,
@@ -840,11 +885,9 @@ impl<'a> TypeAndMemorySafetyAnalysis<'a> {
             | Bytecode::Xor => {
                 let operand1 = self.stack.pop().unwrap();
                 let operand2 = self.stack.pop().unwrap();
-                if operand1.signature == SignatureToken::U64
-                    && operand2.signature == SignatureToken::U64
-                {
+                if operand1.signature.is_integer() && operand1.signature == operand2.signature {
                     self.stack.push(StackAbstractValue {
-                        signature: SignatureToken::U64,
+                        signature: operand1.signature,
                         value: AbstractValue::full_value(Kind::Unrestricted),
                     });
                     Ok(())
@@ -934,9 +977,7 @@ impl<'a> TypeAndMemorySafetyAnalysis<'a> {
             Bytecode::Lt | Bytecode::Gt | Bytecode::Le | Bytecode::Ge => {
                 let operand1 = self.stack.pop().unwrap();
                 let operand2 = self.stack.pop().unwrap();
-                if operand1.signature == SignatureToken::U64
-                    && operand2.signature == SignatureToken::U64
-                {
+                if operand1.signature.is_integer() && operand1.signature == operand2.signature {
                     self.stack.push(StackAbstractValue {
                         signature: SignatureToken::Bool,
                         value: AbstractValue::full_value(Kind::Unrestricted),
@@ -1102,6 +1143,11 @@ impl<'a> TransferFunctions for TypeAndMemorySafetyAnalysis<'a> {
         index: usize,
         last_index: usize,
     ) -> Result<(), Self::AnalysisError> {
+        // Budget errors are reported once by `verify`, not for every block they end.
+        self.meter.charge_step()?;
+        if touches_borrow_graph(bytecode) {
+            self.meter.charge_borrow_graph(state)?;
+        }
         match self.execute_inner(state, bytecode, index) {
             Err(err) => {
                 self.errors.push(err.clone());
@@ -1109,6 +1155,9 @@ impl<'a> TransferFunctions for TypeAndMemorySafetyAnalysis<'a> {
             }
             Ok(()) => {
                 if index == last_index {
+                    // Canonicalizing the state and joining it into the successors of the block
+                    // both walk the whole borrow graph.
+                    self.meter.charge_borrow_graph(state)?;
                     *state = state.construct_canonical_state()
                 }
                 Ok(())
@@ -1118,3 +1167,28 @@ impl<'a> TransferFunctions for TypeAndMemorySafetyAnalysis<'a> {
 }
 
 impl<'a> AbstractInterpreter for TypeAndMemorySafetyAnalysis<'a> {}
+
+// Returns true if executing `bytecode` may query or update the borrow graph of the state.
+fn touches_borrow_graph(bytecode: &Bytecode) -> bool {
+    match bytecode {
+        Bytecode::Pop
+        | Bytecode::StLoc(_)
+        | Bytecode::Ret
+        | Bytecode::FreezeRef
+        | Bytecode::MutBorrowField(_)
+        | Bytecode::ImmBorrowField(_)
+        | Bytecode::CopyLoc(_)
+        | Bytecode::MoveLoc(_)
+        | Bytecode::MutBorrowLoc(_)
+        | Bytecode::ImmBorrowLoc(_)
+        | Bytecode::Call(_, _)
+        | Bytecode::ReadRef
+        | Bytecode::WriteRef
+        | Bytecode::Eq
+        | Bytecode::Neq
+        | Bytecode::MutBorrowGlobal(_, _)
+        | Bytecode::ImmBorrowGlobal(_, _)
+        | Bytecode::MoveFrom(_, _) => true,
+        _ => false,
+    }
+}
@@ -3,9 +3,17 @@
 
 //! This module contains the public APIs supported by the bytecode verifier.
 use crate::{
-    check_duplication::DuplicationChecker, code_unit_verifier::CodeUnitVerifier,
-    instantiation_loops::InstantiationLoopChecker, resources::ResourceTransitiveChecker,
-    signature::SignatureChecker, struct_defs::RecursiveStructDefChecker,
+    check_duplication::DuplicationChecker,
+    code_unit_verifier::CodeUnitVerifier,
+    counters::{
+        report_meter, time_pass, CODE_UNIT_PASS, DUPLICATION_PASS, INSTANTIATION_LOOP_PASS,
+        RECURSIVE_STRUCT_DEF_PASS, RESOURCE_TRANSITIVE_PASS, SIGNATURE_PASS,
+    },
+    instantiation_loops::InstantiationLoopChecker,
+    meter::{Meter, VerifierConfig},
+    resources::ResourceTransitiveChecker,
+    signature::SignatureChecker,
+    struct_defs::RecursiveStructDefChecker,
 };
 use failure::Error;
 use libra_types::{
@@ -152,22 +160,47 @@ impl VerifiedModule {
     ///
     /// There is a partial order on the checks. For example, the duplication check must precede the
     /// structural recursion check. In general, later checks are more expensive.
+    ///
+    /// The work done is bounded by the default `VerifierConfig`.
     pub fn new(module: CompiledModule) -> Result<Self, (CompiledModule, Vec<VMStatus>)> {
+        Self::new_with_config(module, &VerifierConfig::default())
+    }
+
+    /// Verifies this `CompiledModule` like `new`, failing with `VERIFIER_BUDGET_EXCEEDED` if the
+    /// verifier does more work than `config` allows.
+    pub fn new_with_config(
+        module: CompiledModule,
+        config: &VerifierConfig,
+    ) -> Result<Self, (CompiledModule, Vec<VMStatus>)> {
         // All CompiledModule instances are statically guaranteed to be bounds checked, so there's
         // no need for more checking.
-        let mut errors = DuplicationChecker::new(&module).verify();
+        let mut errors = time_pass(DUPLICATION_PASS, || {
+            DuplicationChecker::new(&module).verify()
+        });
         if errors.is_empty() {
-            errors.append(&mut SignatureChecker::new(&module).verify());
-            errors.append(&mut ResourceTransitiveChecker::new(&module).verify());
+            errors.append(&mut time_pass(SIGNATURE_PASS, || {
+                SignatureChecker::new(&module).verify()
+            }));
+            errors.append(&mut time_pass(RESOURCE_TRANSITIVE_PASS, || {
+                ResourceTransitiveChecker::new(&module).verify()
+            }));
         }
         if errors.is_empty() {
-            errors.append(&mut RecursiveStructDefChecker::new(&module).verify());
+            errors.append(&mut time_pass(RECURSIVE_STRUCT_DEF_PASS, || {
+                RecursiveStructDefChecker::new(&module).verify()
+            }));
         }
         if errors.is_empty() {
-            errors.append(&mut InstantiationLoopChecker::new(&module).verify())
+            errors.append(&mut time_pass(INSTANTIATION_LOOP_PASS, || {
+                InstantiationLoopChecker::new(&module).verify()
+            }));
         }
         if errors.is_empty() {
-            errors.append(&mut CodeUnitVerifier::verify(&module));
+            let mut meter = Meter::new(config);
+            errors.append(&mut time_pass(CODE_UNIT_PASS, || {
+                CodeUnitVerifier::verify_with_meter(&module, &mut meter)
+            }));
+            report_meter(&meter);
         }
         if errors.is_empty() {
             Ok(VerifiedModule(module))
@@ -241,8 +274,17 @@ impl VerifiedScript {
     /// of struct definitions, the bounds checker will catch any occurrences of these illegal
     /// operations.
     pub fn new(script: CompiledScript) -> Result<Self, (CompiledScript, Vec<VMStatus>)> {
+        Self::new_with_config(script, &VerifierConfig::default())
+    }
+
+    /// Verifies this `CompiledScript` like `new`, failing with `VERIFIER_BUDGET_EXCEEDED` if the
+    /// verifier does more work than `config` allows.
+    pub fn new_with_config(
+        script: CompiledScript,
+        config: &VerifierConfig,
+    ) -> Result<Self, (CompiledScript, Vec<VMStatus>)> {
         let fake_module = script.into_module();
-        let (fake_module, mut errors) = match VerifiedModule::new(fake_module) {
+        let (fake_module, mut errors) = match VerifiedModule::new_with_config(fake_module, config) {
             Ok(module) => (module.into_inner(), vec![]),
             Err((module, errors)) => (module, errors),
         };
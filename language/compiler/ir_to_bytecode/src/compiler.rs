@@ -3,15 +3,17 @@
 
 use crate::{
     context::{Context, MaterializedPools},
+    diagnostics::at_loc,
     errors::*,
     parser::ast::{
         self, BinOp, Block, Builtin, Cmd, Cmd_, CopyableVal, Exp, Exp_, Function, FunctionBody,
         FunctionCall, FunctionName, FunctionSignature as AstFunctionSignature, FunctionVisibility,
-        IfElse, ImportDefinition, LValue, LValue_, Loop, ModuleDefinition, ModuleIdent, ModuleName,
-        Program, QualifiedModuleIdent, QualifiedStructIdent, Script, Statement,
+        IfElse, ImportDefinition, LValue, LValue_, Loc, Loop, ModuleDefinition, ModuleIdent,
+        ModuleName, Program, QualifiedModuleIdent, QualifiedStructIdent, Script, Statement,
         StructDefinition as MoveStruct, StructDefinitionFields, Type, TypeVar, TypeVar_, UnaryOp,
         Var, Var_, While,
     },
+    source_map::{FunctionSourceMap, ModuleSourceMap, ProgramSourceMap},
 };
 
 use failure::*;
@@ -26,7 +28,7 @@ use std::{
 use vm::{
     access::ModuleAccess,
     file_format::{
-        self, Bytecode, CodeUnit, CompiledModule, CompiledModuleMut, CompiledProgram,
+        self, Bytecode, CodeOffset, CodeUnit, CompiledModule, CompiledModuleMut, CompiledProgram,
         CompiledScript, CompiledScriptMut, FieldDefinition, FieldDefinitionIndex,
         FunctionDefinition, FunctionSignature, Kind, LocalsSignature, MemberCount, SignatureToken,
         StructDefinition, StructFieldInformation, StructHandleIndex, TableIndex,
@@ -77,7 +79,9 @@ enum InferredType {
 
     // Signature tokens
     Bool,
+    U8,
     U64,
+    U128,
     String,
     ByteArray,
     Address,
@@ -93,7 +97,9 @@ impl InferredType {
         use SignatureToken as S;
         match sig_token {
             S::Bool => I::Bool,
+            S::U8 => I::U8,
             S::U64 => I::U64,
+            S::U128 => I::U128,
             S::String => I::String,
             S::ByteArray => I::ByteArray,
             S::Address => I::Address,
@@ -114,7 +120,9 @@ impl InferredType {
         match self {
             InferredType::Anything => bail!("could not infer struct type"),
             InferredType::Bool => bail!("no struct type for Bool"),
+            InferredType::U8 => bail!("no struct type for U8"),
             InferredType::U64 => bail!("no struct type for U64"),
+            InferredType::U128 => bail!("no struct type for U128"),
             InferredType::String => bail!("no struct type for String"),
             InferredType::ByteArray => bail!("no struct type for ByteArray"),
             InferredType::Address => bail!("no struct type for Address"),
@@ -141,6 +149,7 @@ struct FunctionFrame {
     max_stack_depth: i64,
     cur_stack_depth: i64,
     loops: Vec<LoopInfo>,
+    source_map: FunctionSourceMap,
 }
 
 impl FunctionFrame {
@@ -199,6 +208,10 @@ impl FunctionFrame {
         Ok(cur_loc_idx)
     }
 
+    fn record_location(&mut self, offset: usize, loc: Loc) {
+        self.source_map.add_code_mapping(offset as CodeOffset, loc);
+    }
+
     fn push_loop(&mut self, start_loc: usize) -> Result<()> {
         self.loops.push(LoopInfo {
             start_loc,
@@ -245,23 +258,39 @@ pub fn compile_program<'a, T: 'a + ModuleAccess>(
     program: Program,
     deps: impl IntoIterator<Item = &'a T>,
 ) -> Result<CompiledProgram> {
+    Ok(compile_program_with_source_map(address, program, deps)?.0)
+}
+
+/// Compile a transaction program, also returning the source maps of its modules and script.
+pub fn compile_program_with_source_map<'a, T: 'a + ModuleAccess>(
+    address: AccountAddress,
+    program: Program,
+    deps: impl IntoIterator<Item = &'a T>,
+) -> Result<(CompiledProgram, ProgramSourceMap)> {
     let deps = deps
         .into_iter()
         .map(|dep| dep.as_module())
         .collect::<Vec<_>>();
     // This is separate to avoid unnecessary code gen due to monomorphization.
     let mut modules = vec![];
+    let mut module_source_maps = vec![];
     for m in program.modules {
-        let module = {
+        let (module, source_map) = {
             let deps = deps.iter().copied().chain(&modules);
-            compile_module(address, m, deps)?
+            compile_module_with_source_map(address, m, deps)?
         };
         modules.push(module);
+        module_source_maps.push(source_map);
     }
 
     let deps = deps.into_iter().chain(modules.iter());
-    let script = compile_script(address, program.script, deps)?;
-    Ok(CompiledProgram { modules, script })
+    let (script, script_source_map) =
+        compile_script_with_source_map(address, program.script, deps)?;
+    let source_map = ProgramSourceMap {
+        modules: module_source_maps,
+        script: script_source_map,
+    };
+    Ok((CompiledProgram { modules, script }, source_map))
 }
 
 /// Compile a transaction script.
@@ -270,6 +299,15 @@ pub fn compile_script<'a, T: 'a + ModuleAccess>(
     script: Script,
     dependencies: impl IntoIterator<Item = &'a T>,
 ) -> Result<CompiledScript> {
+    Ok(compile_script_with_source_map(address, script, dependencies)?.0)
+}
+
+/// Compile a transaction script, also returning its source map.
+pub fn compile_script_with_source_map<'a, T: 'a + ModuleAccess>(
+    address: AccountAddress,
+    script: Script,
+    dependencies: impl IntoIterator<Item = &'a T>,
+) -> Result<(CompiledScript, ModuleSourceMap)> {
     let current_module = QualifiedModuleIdent {
         address,
         name: ModuleName::new(file_format::self_module_name().to_owned()),
@@ -283,7 +321,10 @@ pub fn compile_script<'a, T: 'a + ModuleAccess>(
 
     let sig = function_signature(&mut context, &function.signature)?;
     context.declare_function(self_name.clone(), main_name.clone(), sig)?;
-    let main = compile_function(&mut context, &self_name, main_name, function)?;
+    let (main, main_source_map) =
+        compile_function(&mut context, &self_name, main_name.clone(), function)?;
+    let mut source_map = ModuleSourceMap::new();
+    source_map.add_function(main_name.into_inner(), main_source_map);
 
     let MaterializedPools {
         module_handles,
@@ -310,9 +351,10 @@ pub fn compile_script<'a, T: 'a + ModuleAccess>(
         address_pool,
         main,
     };
-    compiled_script
+    let compiled_script = compiled_script
         .freeze()
-        .map_err(|errs| InternalCompilerError::BoundsCheckErrors(errs).into())
+        .map_err(InternalCompilerError::BoundsCheckErrors)?;
+    Ok((compiled_script, source_map))
 }
 
 /// Compile a module.
@@ -321,6 +363,15 @@ pub fn compile_module<'a, T: 'a + ModuleAccess>(
     module: ModuleDefinition,
     dependencies: impl IntoIterator<Item = &'a T>,
 ) -> Result<CompiledModule> {
+    Ok(compile_module_with_source_map(address, module, dependencies)?.0)
+}
+
+/// Compile a module, also returning its source map.
+pub fn compile_module_with_source_map<'a, T: 'a + ModuleAccess>(
+    address: AccountAddress,
+    module: ModuleDefinition,
+    dependencies: impl IntoIterator<Item = &'a T>,
+) -> Result<(CompiledModule, ModuleSourceMap)> {
     let current_module = QualifiedModuleIdent {
         address,
         name: module.name,
@@ -349,7 +400,8 @@ pub fn compile_module<'a, T: 'a + ModuleAccess>(
 
     let (struct_defs, field_defs) = compile_structs(&mut context, &self_name, module.structs)?;
 
-    let function_defs = compile_functions(&mut context, &self_name, module.functions)?;
+    let (function_defs, source_map) =
+        compile_functions(&mut context, &self_name, module.functions)?;
 
     let MaterializedPools {
         module_handles,
@@ -378,9 +430,10 @@ pub fn compile_module<'a, T: 'a + ModuleAccess>(
         field_defs,
         function_defs,
     };
-    compiled_module
+    let compiled_module = compiled_module
         .freeze()
-        .map_err(|errs| InternalCompilerError::BoundsCheckErrors(errs).into())
+        .map_err(InternalCompilerError::BoundsCheckErrors)?;
+    Ok((compiled_module, source_map))
 }
 
 fn compile_imports(
@@ -428,7 +481,9 @@ fn compile_types(context: &mut Context, tys: &[Type]) -> Result<Vec<SignatureTok
 fn compile_type(context: &mut Context, ty: &Type) -> Result<SignatureToken> {
     Ok(match ty {
         Type::Address => SignatureToken::Address,
+        Type::U8 => SignatureToken::U8,
         Type::U64 => SignatureToken::U64,
+        Type::U128 => SignatureToken::U128,
         Type::Bool => SignatureToken::Bool,
         Type::ByteArray => SignatureToken::ByteArray,
         Type::Reference(is_mutable, inner_type) => {
@@ -533,11 +588,16 @@ fn compile_functions(
     context: &mut Context,
     self_name: &ModuleName,
     functions: Vec<(FunctionName, Function)>,
-) -> Result<Vec<FunctionDefinition>> {
-    functions
-        .into_iter()
-        .map(|(name, ast_function)| compile_function(context, self_name, name, ast_function))
-        .collect()
+) -> Result<(Vec<FunctionDefinition>, ModuleSourceMap)> {
+    let mut function_defs = vec![];
+    let mut source_map = ModuleSourceMap::new();
+    for (name, ast_function) in functions {
+        let (function_def, function_source_map) =
+            compile_function(context, self_name, name.clone(), ast_function)?;
+        function_defs.push(function_def);
+        source_map.add_function(name.into_inner(), function_source_map);
+    }
+    Ok((function_defs, source_map))
 }
 
 fn compile_function(
@@ -545,7 +605,7 @@ fn compile_function(
     self_name: &ModuleName,
     name: FunctionName,
     ast_function: Function,
-) -> Result<FunctionDefinition> {
+) -> Result<(FunctionDefinition, FunctionSourceMap)> {
     let fh_idx = context.function_handle(self_name.clone(), name)?.1;
 
     let flags = match ast_function.visibility {
@@ -561,20 +621,21 @@ fn compile_function(
         .map(|name| context.struct_definition_index(name))
         .collect::<Result<_>>()?;
 
-    let code = match ast_function.body {
+    let (code, source_map) = match ast_function.body {
         FunctionBody::Move { locals, code } => {
             let (m, _) = type_formals(&ast_function.signature.type_formals)?;
             context.bind_type_formals(m)?;
             compile_function_body(context, ast_function.signature.formals, locals, code)?
         }
-        FunctionBody::Native => CodeUnit::default(),
+        FunctionBody::Native => (CodeUnit::default(), FunctionSourceMap::new()),
     };
-    Ok(FunctionDefinition {
+    let function_def = FunctionDefinition {
         function: fh_idx,
         flags,
         acquires_global_resources,
         code,
-    })
+    };
+    Ok((function_def, source_map))
 }
 
 fn compile_function_body(
@@ -582,17 +643,23 @@ fn compile_function_body(
     formals: Vec<(Var_, Type)>,
     locals: Vec<(Var_, Type)>,
     block: Block,
-) -> Result<CodeUnit> {
+) -> Result<(CodeUnit, FunctionSourceMap)> {
     let mut function_frame = FunctionFrame::new();
     let mut locals_signature = LocalsSignature(vec![]);
     for (var, t) in formals {
         let sig = compile_type(context, &t)?;
         function_frame.define_local(&var, sig.clone())?;
+        function_frame
+            .source_map
+            .add_local_mapping(var.value.to_string(), var.span);
         locals_signature.0.push(sig);
     }
     for (var_, t) in locals {
         let sig = compile_type(context, &t)?;
         function_frame.define_local(&var_.value, sig.clone())?;
+        function_frame
+            .source_map
+            .add_local_mapping(var_.value.to_string(), var_.span);
         locals_signature.0.push(sig);
     }
     let sig_idx = context.locals_signature_index(locals_signature)?;
@@ -606,11 +673,12 @@ fn compile_function_body(
     } else {
         function_frame.max_stack_depth as u16
     };
-    Ok(CodeUnit {
+    let code_unit = CodeUnit {
         locals: sig_idx,
         max_stack_size,
         code,
-    })
+    };
+    Ok((code_unit, function_frame.source_map))
 }
 
 fn compile_block(
@@ -626,7 +694,10 @@ fn compile_block(
     for stmt in block.stmts {
         let stmt_info = match stmt {
             Statement::CommandStatement(command) => {
-                compile_command(context, function_frame, code, command)?
+                let loc = command.span;
+                function_frame.record_location(code.len(), loc);
+                compile_command(context, function_frame, code, command)
+                    .map_err(|err| at_loc(err, loc))?
             }
             Statement::WhileStatement(while_) => {
                 // always assume the loop might not be taken
@@ -649,6 +720,8 @@ fn compile_if_else(
     code: &mut Vec<Bytecode>,
     if_else: IfElse,
 ) -> Result<ControlFlowInfo> {
+    let cond_loc = if_else.cond.span;
+    function_frame.record_location(code.len(), cond_loc);
     compile_expression(context, function_frame, code, if_else.cond)?;
 
     let brfalse_ins_loc = code.len();
@@ -666,6 +739,7 @@ fn compile_if_else(
         Some(else_block) => {
             let branch_ins_loc = code.len();
             if !if_cf_info.terminal_node {
+                function_frame.record_location(code.len(), cond_loc);
                 code.push(Bytecode::Branch(0)); // placeholder, final branch target replaced later
                 else_block_location += 1;
             }
@@ -691,6 +765,8 @@ fn compile_while(
 ) -> Result<ControlFlowInfo> {
     let loop_start_loc = code.len();
     function_frame.push_loop(loop_start_loc)?;
+    let cond_loc = while_.cond.span;
+    function_frame.record_location(code.len(), cond_loc);
     compile_expression(context, function_frame, code, while_.cond)?;
 
     let brfalse_loc = code.len();
@@ -698,6 +774,7 @@ fn compile_while(
     function_frame.pop()?;
 
     compile_block(context, function_frame, code, while_.block)?;
+    function_frame.record_location(code.len(), cond_loc);
     code.push(Bytecode::Branch(loop_start_loc as u16));
 
     let loop_end_loc = code.len() as u16;
@@ -864,7 +941,18 @@ fn compile_expression(
     code: &mut Vec<Bytecode>,
     exp: Exp_,
 ) -> Result<VecDeque<InferredType>> {
-    Ok(match exp.value {
+    let loc = exp.span;
+    compile_expression_inner(context, function_frame, code, exp.value)
+        .map_err(|err| at_loc(err, loc))
+}
+
+fn compile_expression_inner(
+    context: &mut Context,
+    function_frame: &mut FunctionFrame,
+    code: &mut Vec<Bytecode>,
+    exp: Exp,
+) -> Result<VecDeque<InferredType>> {
+    Ok(match exp {
         Exp::Move(v) => {
             let loc_idx = function_frame.get_local(&v.value)?;
             let load_loc = Bytecode::MoveLoc(loc_idx);
@@ -902,11 +990,21 @@ fn compile_expression(
                 function_frame.push()?;
                 vec_deque![InferredType::Address]
             }
+            CopyableVal::U8(i) => {
+                code.push(Bytecode::LdU8(i));
+                function_frame.push()?;
+                vec_deque![InferredType::U8]
+            }
             CopyableVal::U64(i) => {
                 code.push(Bytecode::LdConst(i));
                 function_frame.push()?;
                 vec_deque![InferredType::U64]
             }
+            CopyableVal::U128(i) => {
+                code.push(Bytecode::LdU128(i));
+                function_frame.push()?;
+                vec_deque![InferredType::U128]
+            }
             CopyableVal::ByteArray(buf) => {
                 let buf_idx = context.byte_array_index(&buf)?;
                 code.push(Bytecode::LdByteArray(buf_idx));
@@ -964,41 +1062,43 @@ fn compile_expression(
             }
         }
         Exp::BinopExp(e1, op, e2) => {
-            compile_expression(context, function_frame, code, *e1)?;
+            let lhs_type = compile_expression(context, function_frame, code, *e1)?
+                .pop_front()
+                .unwrap_or(InferredType::Anything);
             compile_expression(context, function_frame, code, *e2)?;
             function_frame.pop()?;
             match op {
                 BinOp::Add => {
                     code.push(Bytecode::Add);
-                    vec_deque![InferredType::U64]
+                    vec_deque![lhs_type]
                 }
                 BinOp::Sub => {
                     code.push(Bytecode::Sub);
-                    vec_deque![InferredType::U64]
+                    vec_deque![lhs_type]
                 }
                 BinOp::Mul => {
                     code.push(Bytecode::Mul);
-                    vec_deque![InferredType::U64]
+                    vec_deque![lhs_type]
                 }
                 BinOp::Mod => {
                     code.push(Bytecode::Mod);
-                    vec_deque![InferredType::U64]
+                    vec_deque![lhs_type]
                 }
                 BinOp::Div => {
                     code.push(Bytecode::Div);
-                    vec_deque![InferredType::U64]
+                    vec_deque![lhs_type]
                 }
                 BinOp::BitOr => {
                     code.push(Bytecode::BitOr);
-                    vec_deque![InferredType::U64]
+                    vec_deque![lhs_type]
                 }
                 BinOp::BitAnd => {
                     code.push(Bytecode::BitAnd);
-                    vec_deque![InferredType::U64]
+                    vec_deque![lhs_type]
                 }
                 BinOp::Xor => {
                     code.push(Bytecode::Xor);
-                    vec_deque![InferredType::U64]
+                    vec_deque![lhs_type]
                 }
                 BinOp::Or => {
                     code.push(Bytecode::Or);
@@ -1202,6 +1302,24 @@ fn compile_call(
                     };
                     vec_deque![InferredType::Reference(inner_token)]
                 }
+                Builtin::ToU8 => {
+                    code.push(Bytecode::CastU8);
+                    function_frame.pop()?;
+                    function_frame.push()?;
+                    vec_deque![InferredType::U8]
+                }
+                Builtin::ToU64 => {
+                    code.push(Bytecode::CastU64);
+                    function_frame.pop()?;
+                    function_frame.push()?;
+                    vec_deque![InferredType::U64]
+                }
+                Builtin::ToU128 => {
+                    code.push(Bytecode::CastU128);
+                    function_frame.pop()?;
+                    function_frame.push()?;
+                    vec_deque![InferredType::U128]
+                }
             }
         }
         FunctionCall::ModuleFunctionCall {
@@ -0,0 +1,163 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Structured, human readable diagnostics for parse, compile and verification errors.
+//!
+//! A `Diagnostic` carries an error code, the span of the IR it refers to, secondary labels and
+//! notes. It is returned as a `failure::Error` by the parser and compiler and can be rendered
+//! against the IR source with `Diagnostic::render`.
+
+use crate::{parser::ast::Loc, source_map::ModuleSourceMap};
+use codespan::{ByteOffset, CodeMap, Span};
+use codespan_reporting::{
+    emit, termcolor::Buffer, Diagnostic as CodespanDiagnostic, Label, Severity,
+};
+use failure::{Error, Fail};
+use libra_types::vm_error::{StatusCode, VMStatus};
+use std::fmt;
+use vm::errors::err_code_location;
+
+/// The class of problem reported by a `Diagnostic`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DiagnosticCode {
+    /// The source could not be parsed.
+    ParserError,
+    /// The source parsed but could not be compiled, e.g. a name failed to resolve.
+    CompilerError,
+    /// The compiled bytecode was rejected by the bytecode verifier.
+    VerificationError(StatusCode),
+}
+
+/// An error located in the Move IR source.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Diagnostic {
+    pub code: DiagnosticCode,
+    pub message: String,
+    /// The span the error is reported at, with an optional label message.
+    pub primary: Option<(Loc, String)>,
+    /// Related spans, e.g. the declaration of a local the error is about.
+    pub labels: Vec<(Loc, String)>,
+    pub notes: Vec<String>,
+}
+
+impl Diagnostic {
+    pub fn new(code: DiagnosticCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            primary: None,
+            labels: vec![],
+            notes: vec![],
+        }
+    }
+
+    pub fn with_primary(mut self, loc: Loc, message: impl Into<String>) -> Self {
+        self.primary = Some((loc, message.into()));
+        self
+    }
+
+    pub fn with_label(mut self, loc: Loc, message: impl Into<String>) -> Self {
+        self.labels.push((loc, message.into()));
+        self
+    }
+
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.notes.push(note.into());
+        self
+    }
+
+    /// Renders the diagnostic with snippets of `source`, the contents of the file `file_name`.
+    pub fn render(&self, file_name: &str, source: &str) -> String {
+        let mut code_map = CodeMap::new();
+        let file_map = code_map.add_filemap(file_name.to_string().into(), source.to_string());
+        // Spans are relative to the start of the source, the code map places files at an offset.
+        let file_start = file_map.span().start();
+        let to_span = |loc: &Loc| {
+            Span::new(
+                file_start + ByteOffset(i64::from(loc.start().0)),
+                file_start + ByteOffset(i64::from(loc.end().0)),
+            )
+        };
+
+        let mut diagnostic = CodespanDiagnostic::new(Severity::Error, self.message.clone())
+            .with_code(self.code.to_string());
+        if let Some((loc, message)) = &self.primary {
+            diagnostic = diagnostic.with_label(label(Label::new_primary(to_span(loc)), message));
+        }
+        for (loc, message) in &self.labels {
+            diagnostic = diagnostic.with_label(label(Label::new_secondary(to_span(loc)), message));
+        }
+
+        let mut buffer = Buffer::no_color();
+        emit(&mut buffer, &code_map, &diagnostic).unwrap();
+        let mut rendered = String::from_utf8_lossy(buffer.as_slice()).into_owned();
+        for note in &self.notes {
+            rendered.push_str(&format!("  = note: {}\n", note));
+        }
+        rendered
+    }
+}
+
+fn label(label: Label, message: &str) -> Label {
+    if message.is_empty() {
+        label
+    } else {
+        label.with_message(message)
+    }
+}
+
+impl fmt::Display for DiagnosticCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DiagnosticCode::ParserError => write!(f, "ParserError"),
+            DiagnosticCode::CompilerError => write!(f, "CompilerError"),
+            DiagnosticCode::VerificationError(status) => write!(f, "{:?}", status),
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.code, self.message)
+    }
+}
+
+impl Fail for Diagnostic {}
+
+/// Turns an error raised while compiling the IR at `loc` into a `CompilerError` diagnostic,
+/// unless it already is a diagnostic pointing at a more precise location.
+pub(crate) fn at_loc(error: Error, loc: Loc) -> Error {
+    if error.downcast_ref::<Diagnostic>().is_some() || loc == Loc::default() {
+        return error;
+    }
+    Diagnostic::new(DiagnosticCode::CompilerError, error.to_string())
+        .with_primary(loc, "")
+        .into()
+}
+
+/// Converts bytecode verifier errors for a module or script into diagnostics, locating errors
+/// inside function bodies through the unit's source map.
+pub fn verification_diagnostics(
+    errors: &[VMStatus],
+    source_map: &ModuleSourceMap,
+) -> Vec<Diagnostic> {
+    errors
+        .iter()
+        .map(|status| {
+            let code = DiagnosticCode::VerificationError(status.major_status);
+            let mut diagnostic = Diagnostic::new(code, "bytecode verification failed");
+            if let Some(loc) = source_map.resolve_verification_error(status) {
+                diagnostic = diagnostic.with_primary(loc, "rejected by the bytecode verifier");
+            }
+            if let Some((idx, _)) = err_code_location(status) {
+                if let Some(name) = source_map.get_function_name(idx) {
+                    diagnostic = diagnostic.with_note(format!("in function `{}`", name));
+                }
+            }
+            if let Some(message) = &status.message {
+                diagnostic = diagnostic.with_note(message.clone());
+            }
+            diagnostic
+        })
+        .collect()
+}
@@ -5,7 +5,9 @@ extern crate log;
 
 pub mod compiler;
 mod context;
+pub mod diagnostics;
 pub mod errors;
 pub mod parser;
+pub mod source_map;
 
 // Unit tests for this crate are in the parent "compiler" crate.
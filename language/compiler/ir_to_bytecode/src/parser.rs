@@ -1,16 +1,12 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use codespan::{ByteIndex, CodeMap, Span};
-use codespan_reporting::{emit, termcolor::Buffer, Diagnostic, Label, Severity};
+use crate::diagnostics::{Diagnostic, DiagnosticCode};
+use codespan::{ByteIndex, Span};
 use failure::*;
 use ir_to_bytecode_syntax::syntax;
 use lalrpop_util::ParseError;
 use libra_types::account_address::AccountAddress;
-use std::{
-    collections::hash_map::DefaultHasher,
-    hash::{Hash, Hasher},
-};
 
 // Re-export this to make it convenient for other crates.
 pub use ir_to_bytecode_syntax::ast;
@@ -85,9 +81,7 @@ fn strip_comments_and_verify(string: &str) -> Result<String> {
 pub fn parse_script_or_module(s: &str) -> Result<ast::ScriptOrModule> {
     let stripped_string = &strip_comments(s);
     let parser = syntax::ScriptOrModuleParser::new();
-    parser
-        .parse(stripped_string)
-        .or_else(|e| handle_error(e, s))
+    parser.parse(stripped_string).or_else(handle_error)
 }
 
 /// Given the raw input of a file, creates a `Program` struct
@@ -95,9 +89,7 @@ pub fn parse_script_or_module(s: &str) -> Result<ast::ScriptOrModule> {
 pub fn parse_program(program_str: &str) -> Result<ast::Program> {
     let stripped_string = &strip_comments_and_verify(program_str)?;
     let parser = syntax::ProgramParser::new();
-    parser
-        .parse(stripped_string)
-        .or_else(|e| handle_error(e, stripped_string))
+    parser.parse(stripped_string).or_else(handle_error)
 }
 
 /// Given the raw input of a file, creates a `Script` struct
@@ -105,9 +97,7 @@ pub fn parse_program(program_str: &str) -> Result<ast::Program> {
 pub fn parse_script(script_str: &str) -> Result<ast::Script> {
     let stripped_string = &strip_comments_and_verify(script_str)?;
     let parser = syntax::ScriptParser::new();
-    parser
-        .parse(stripped_string)
-        .or_else(|e| handle_error(e, stripped_string))
+    parser.parse(stripped_string).or_else(handle_error)
 }
 
 /// Given the raw input of a file, creates a single `ModuleDefinition` struct
@@ -115,9 +105,7 @@ pub fn parse_script(script_str: &str) -> Result<ast::Script> {
 pub fn parse_module(modules_str: &str) -> Result<ast::ModuleDefinition> {
     let stripped_string = &strip_comments_and_verify(modules_str)?;
     let parser = syntax::ModuleParser::new();
-    parser
-        .parse(stripped_string)
-        .or_else(|e| handle_error(e, stripped_string))
+    parser.parse(stripped_string).or_else(handle_error)
 }
 
 /// Given the raw input of a file, creates a single `Cmd` struct
@@ -125,54 +113,34 @@ pub fn parse_module(modules_str: &str) -> Result<ast::ModuleDefinition> {
 pub fn parse_cmd(cmd_str: &str, _sender_address: AccountAddress) -> Result<ast::Cmd> {
     let stripped_string = &strip_comments_and_verify(cmd_str)?;
     let parser = syntax::CmdParser::new();
-    parser
-        .parse(stripped_string)
-        .or_else(|e| handle_error(e, stripped_string))
+    parser.parse(stripped_string).or_else(handle_error)
 }
 
-fn handle_error<'input, T, Token>(
-    e: lalrpop_util::ParseError<usize, Token, failure::Error>,
-    code_str: &'input str,
-) -> Result<T>
+fn handle_error<T, Token>(e: lalrpop_util::ParseError<usize, Token, failure::Error>) -> Result<T>
 where
     Token: std::fmt::Display,
 {
-    let mut s = DefaultHasher::new();
-    code_str.hash(&mut s);
-    let mut code = CodeMap::new();
-    code.add_filemap(s.finish().to_string().into(), code_str.to_string());
-    let msg = match &e {
+    let span = |l: usize, r: usize| Span::new(ByteIndex(l as u32), ByteIndex(r as u32));
+    let diagnostic = match &e {
         ParseError::InvalidToken { location } => {
-            let error =
-                Diagnostic::new(Severity::Error, "Invalid Token").with_label(Label::new_primary(
-                    Span::new(ByteIndex(*location as u32), ByteIndex(*location as u32)),
-                ));
-            let mut buffer = Buffer::no_color();
-            emit(&mut buffer, &code, &error).unwrap();
-            std::str::from_utf8(buffer.as_slice()).unwrap().to_string()
+            Diagnostic::new(DiagnosticCode::ParserError, "Invalid Token")
+                .with_primary(span(*location, *location), "")
         }
         ParseError::UnrecognizedToken {
             token: (l, tok, r),
             expected,
-        } => {
-            let error = Diagnostic::new(Severity::Error, format!("Unrecognized Token: {}", tok))
-                .with_label(
-                    Label::new_primary(Span::new(ByteIndex(*l as u32), ByteIndex(*r as u32)))
-                        .with_message(format!(
-                            "Expected: {}",
-                            expected
-                                .iter()
-                                .fold(String::new(), |acc, token| format!("{} {},", acc, token))
-                        )),
-                );
-            let mut buffer = Buffer::no_color();
-            emit(&mut buffer, &code, &error).unwrap();
-            std::str::from_utf8(buffer.as_slice()).unwrap().to_string()
+        } => Diagnostic::new(
+            DiagnosticCode::ParserError,
+            format!("Unrecognized Token: {}", tok),
+        )
+        .with_primary(span(*l, *r), format!("Expected: {}", expected.join(", "))),
+        ParseError::ExtraToken { token: (l, tok, r) } => {
+            Diagnostic::new(DiagnosticCode::ParserError, format!("Extra Token: {}", tok))
+                .with_primary(span(*l, *r), "")
         }
-        _ => format!("{}", e),
+        _ => Diagnostic::new(DiagnosticCode::ParserError, format!("{}", e)),
     };
-    println!("{}", msg);
-    bail!("ParserError: {}", e)
+    Err(diagnostic.into())
 }
 
 #[cfg(test)]
@@ -0,0 +1,173 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Source maps relating compiled bytecode back to the Move IR it was compiled from.
+//!
+//! The compiler records the span of every IR command (and of `if`/`while` conditions) at the
+//! offset of the first instruction emitted for it. An instruction belongs to the closest recorded
+//! offset at or before it.
+
+use crate::parser::ast::Loc;
+use libra_types::{
+    identifier::{IdentStr, Identifier},
+    vm_error::{CodeLocation, VMStatus},
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use vm::{
+    errors::err_code_location,
+    file_format::{CodeOffset, FunctionDefinitionIndex, LocalIndex},
+    printers::SourceLocator,
+};
+
+/// Source information for a single compiled function.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FunctionSourceMap {
+    /// The span of the IR that starts at each recorded bytecode offset.
+    code_map: BTreeMap<CodeOffset, Loc>,
+    /// The name and declaration span of each local, indexed by `LocalIndex`.
+    locals: Vec<(String, Loc)>,
+}
+
+impl FunctionSourceMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records that the instructions starting at `offset` were compiled from `loc`.
+    pub fn add_code_mapping(&mut self, offset: CodeOffset, loc: Loc) {
+        self.code_map.insert(offset, loc);
+    }
+
+    /// Records the name of the next local.
+    pub fn add_local_mapping(&mut self, name: String, loc: Loc) {
+        self.locals.push((name, loc));
+    }
+
+    /// Returns the span of the IR the instruction at `offset` was compiled from.
+    pub fn get_code_location(&self, offset: CodeOffset) -> Option<Loc> {
+        self.code_map
+            .range(..=offset)
+            .next_back()
+            .map(|(_, loc)| *loc)
+    }
+
+    /// Returns the source name of a local.
+    pub fn get_local_name(&self, idx: LocalIndex) -> Option<&str> {
+        self.locals.get(idx as usize).map(|(name, _)| name.as_str())
+    }
+
+    /// Returns the span a local was declared at.
+    pub fn get_local_location(&self, idx: LocalIndex) -> Option<Loc> {
+        self.locals.get(idx as usize).map(|(_, loc)| *loc)
+    }
+}
+
+/// Source information for a compiled module, or for a compiled script where `main` is the only
+/// function definition.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModuleSourceMap {
+    /// The name and source map of each function, indexed by `FunctionDefinitionIndex`.
+    functions: Vec<(Identifier, FunctionSourceMap)>,
+}
+
+impl ModuleSourceMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the source map of the next function definition.
+    pub fn add_function(&mut self, name: Identifier, function: FunctionSourceMap) {
+        self.functions.push((name, function));
+    }
+
+    pub fn get_function(&self, idx: FunctionDefinitionIndex) -> Option<&FunctionSourceMap> {
+        self.functions
+            .get(idx.0 as usize)
+            .map(|(_, function)| function)
+    }
+
+    pub fn get_function_name(&self, idx: FunctionDefinitionIndex) -> Option<&IdentStr> {
+        self.functions
+            .get(idx.0 as usize)
+            .map(|(name, _)| name.as_ident_str())
+    }
+
+    pub fn get_function_by_name(&self, name: &IdentStr) -> Option<&FunctionSourceMap> {
+        self.functions
+            .iter()
+            .find(|(function_name, _)| function_name.as_ident_str() == name)
+            .map(|(_, function)| function)
+    }
+
+    /// Resolves a location recorded in a VM status, e.g. where a transaction stopped. The caller
+    /// is responsible for checking that `location.module`, if set, is the module this source map
+    /// was produced for.
+    pub fn resolve_location(&self, location: &CodeLocation) -> Option<Loc> {
+        let function = match location.function_definition_index {
+            Some(idx) => self.get_function(FunctionDefinitionIndex::new(idx))?,
+            None => self.get_function_by_name(location.function.as_ref()?)?,
+        };
+        function.get_code_location(location.code_offset)
+    }
+
+    /// Resolves a bytecode verifier error that points at an instruction of a function.
+    pub fn resolve_verification_error(&self, status: &VMStatus) -> Option<Loc> {
+        let (idx, offset) = err_code_location(status)?;
+        self.get_function(idx)?.get_code_location(offset)
+    }
+}
+
+/// The source maps produced when compiling a `Program`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProgramSourceMap {
+    pub modules: Vec<ModuleSourceMap>,
+    pub script: ModuleSourceMap,
+}
+
+/// Returns the 1-based line and column at which `loc` starts in `source`.
+pub fn line_and_column(source: &str, loc: Loc) -> (usize, usize) {
+    let offset = std::cmp::min(loc.start().0 as usize, source.len());
+    let prefix = &source.as_bytes()[..offset];
+    let line = prefix.iter().filter(|c| **c == b'\n').count() + 1;
+    let line_start = prefix
+        .iter()
+        .rposition(|c| *c == b'\n')
+        .map_or(0, |idx| idx + 1);
+    (line, offset - line_start + 1)
+}
+
+/// A `SourceLocator` that renders the locations of a `ModuleSourceMap` as `line:column` of the
+/// IR source it was compiled from.
+pub struct SourceMapLocator<'a> {
+    source_map: &'a ModuleSourceMap,
+    source: &'a str,
+}
+
+impl<'a> SourceMapLocator<'a> {
+    pub fn new(source_map: &'a ModuleSourceMap, source: &'a str) -> Self {
+        Self { source_map, source }
+    }
+}
+
+impl<'a> SourceLocator for SourceMapLocator<'a> {
+    fn code_location(
+        &self,
+        function: FunctionDefinitionIndex,
+        offset: CodeOffset,
+    ) -> Option<String> {
+        let loc = self
+            .source_map
+            .get_function(function)?
+            .get_code_location(offset)?;
+        let (line, column) = line_and_column(self.source, loc);
+        Some(format!("{}:{}", line, column))
+    }
+
+    fn local_name(&self, function: FunctionDefinitionIndex, local: LocalIndex) -> Option<String> {
+        self.source_map
+            .get_function(function)?
+            .get_local_name(local)
+            .map(str::to_string)
+    }
+}
@@ -165,8 +165,12 @@ pub enum Kind {
 pub enum Type {
     /// `address`
     Address,
+    /// `u8`
+    U8,
     /// `u64`
     U64,
+    /// `u128`
+    U128,
     /// `bool`
     Bool,
     /// `bytearray`
@@ -221,6 +225,8 @@ pub struct StructDefinition {
     pub type_formals: Vec<(TypeVar_, Kind)>,
     /// the fields each instance has
     pub fields: StructDefinitionFields,
+    /// the invariants that hold for every instance of the struct
+    pub invariants: Vec<SpecExp_>,
 }
 
 /// The fields of a Move struct definition
@@ -288,10 +294,97 @@ pub struct Function {
     /// This list of acquires grants the borrow checker the ability to statically verify the safety
     /// of references into global storage
     pub acquires: Vec<StructName>,
+    /// List of specifications for the Move prover (experimental)
+    pub specifications: Vec<Condition_>,
     /// The code for the procedure
     pub body: FunctionBody,
 }
 
+//**************************************************************************************************
+// Specifications
+//**************************************************************************************************
+
+/// A location in storage that a specification expression can refer to
+#[derive(PartialEq, Debug, Clone)]
+pub enum StorageLocation {
+    /// A formal of the procedure, or a field of the struct in an invariant
+    Formal(Identifier),
+    /// A resource of type `type_` stored in global storage at `address`
+    /// `global<t>(address)`
+    GlobalResource {
+        /// The type of the resource
+        type_: QualifiedStructIdent,
+        /// Type actuals of the resource
+        type_actuals: Vec<Type>,
+        /// The address the resource is stored under
+        address: Box<StorageLocation>,
+    },
+    /// An access path rooted at `base` with nonempty offsets in `fields`
+    /// `base.f_1.f_2...f_j`
+    AccessPath {
+        /// The location the path starts from
+        base: Box<StorageLocation>,
+        /// The fields selected in order
+        fields: Vec<Field>,
+    },
+    /// Account address constant
+    Address(AccountAddress),
+    /// The sender address of the current transaction
+    /// `txn_sender`
+    TxnSenderAddress,
+    /// The i-th return value of the procedure
+    /// `RET(i)`
+    Ret(u8),
+}
+
+/// An expression in a specification
+#[derive(PartialEq, Debug, Clone)]
+pub enum SpecExp {
+    /// A Move constant
+    Constant(CopyableVal),
+    /// The value stored at a storage location
+    StorageLocation(StorageLocation),
+    /// `global_exists<t>(address)`
+    GlobalExists {
+        /// The type of the resource
+        type_: QualifiedStructIdent,
+        /// Type actuals of the resource
+        type_actuals: Vec<Type>,
+        /// The address being checked
+        address: StorageLocation,
+    },
+    /// The value behind a reference-typed storage location
+    /// `*l`
+    Dereference(StorageLocation),
+    /// `!e`
+    Not(Box<SpecExp>),
+    /// `e_1 op e_2`
+    Binop(Box<SpecExp>, BinOp, Box<SpecExp>),
+    /// The value of the expression in the pre-state of the procedure
+    /// `old(e)`
+    Old(Box<SpecExp>),
+}
+
+/// The type for a `SpecExp` and its location
+pub type SpecExp_ = Spanned<SpecExp>;
+
+/// A pre- or postcondition attached to a procedure
+#[derive(PartialEq, Debug, Clone)]
+pub enum Condition {
+    /// Precondition the caller must establish
+    /// `requires e`
+    Requires(SpecExp),
+    /// Postcondition that holds whenever the procedure returns normally
+    /// `ensures e`
+    Ensures(SpecExp),
+    /// Condition under which the procedure aborts
+    /// `aborts_if e`
+    AbortsIf(SpecExp),
+}
+
+/// The type for a `Condition` and its location
+pub type Condition_ = Spanned<Condition>;
+
 //**************************************************************************************************
 // Statements
 //**************************************************************************************************
@@ -329,6 +422,13 @@ pub enum Builtin {
 
     /// Convert a mutable reference into an immutable one
     Freeze,
+
+    /// Cast an integer into u8, aborting if the value does not fit
+    ToU8,
+    /// Cast an integer into u64, aborting if the value does not fit
+    ToU64,
+    /// Cast an integer into u128
+    ToU128,
 }
 
 /// Enum for different function calls
@@ -438,8 +538,12 @@ pub struct Block {
 pub enum CopyableVal {
     /// An address in the global storage
     Address(AccountAddress),
+    /// An unsigned 8-bit integer
+    U8(u8),
     /// An unsigned 64-bit integer
     U64(u64),
+    /// An unsigned 128-bit integer
+    U128(u128),
     /// true or false
     Bool(bool),
     /// `b"<bytes>"`
@@ -689,11 +793,21 @@ impl Type {
         Type::Address
     }
 
+    /// Creates a new u8 type
+    pub fn u8() -> Type {
+        Type::U8
+    }
+
     /// Creates a new u64 type
     pub fn u64() -> Type {
         Type::U64
     }
 
+    /// Creates a new u128 type
+    pub fn u128() -> Type {
+        Type::U128
+    }
+
     /// Creates a new bool type
     pub fn bool() -> Type {
         Type::Bool
@@ -758,8 +872,8 @@ impl StructName {
 
 impl StructDefinition {
     /// Creates a new StructDefinition from the resource kind (true if resource), the string
-    /// representation of the name, and the user specified fields, a map from their names to their
-    /// types
+    /// representation of the name, the user specified fields, a map from their names to their
+    /// types, and the invariants over those fields
     /// Does not verify the correctness of any internal properties, e.g. doesn't check that the
     /// fields do not have reference types
     pub fn move_declared<L, T>(
@@ -767,12 +881,14 @@ impl StructDefinition {
         name: impl Into<Box<str>>,
         type_formals: Vec<(TypeVar_, Kind)>,
         fields: Fields<Type>,
+        invariants: Vec<SpecExp_>,
     ) -> Result<Self, ParseError<L, T, failure::Error>> {
         Ok(StructDefinition {
             is_nominal_resource,
             name: StructName::parse(name)?,
             type_formals,
             fields: StructDefinitionFields::Move { fields },
+            invariants,
         })
     }
 
@@ -789,6 +905,7 @@ impl StructDefinition {
             name: StructName::parse(name)?,
             type_formals,
             fields: StructDefinitionFields::Native,
+            invariants: vec![],
         })
     }
 }
@@ -839,6 +956,7 @@ impl Function {
         return_type: Vec<Type>,
         type_formals: Vec<(TypeVar_, Kind)>,
         acquires: Vec<StructName>,
+        specifications: Vec<Condition_>,
         body: FunctionBody,
     ) -> Self {
         let signature = FunctionSignature::new(formals, return_type, type_formals);
@@ -846,6 +964,7 @@ impl Function {
             visibility,
             signature,
             acquires,
+            specifications,
             body,
         }
     }
@@ -1315,7 +1434,9 @@ fn format_type_formals(formals: &[(TypeVar_, Kind)]) -> String {
 impl fmt::Display for Type {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
+            Type::U8 => write!(f, "u8"),
             Type::U64 => write!(f, "u64"),
+            Type::U128 => write!(f, "u128"),
             Type::Bool => write!(f, "bool"),
             Type::Address => write!(f, "address"),
             Type::ByteArray => write!(f, "bytearray"),
@@ -1361,6 +1482,9 @@ impl fmt::Display for Builtin {
                 write!(f, "move_to_sender<{}{}>", t, format_type_actuals(tys))
             }
             Builtin::Freeze => write!(f, "freeze"),
+            Builtin::ToU8 => write!(f, "to_u8"),
+            Builtin::ToU64 => write!(f, "to_u64"),
+            Builtin::ToU128 => write!(f, "to_u128"),
         }
     }
 }
@@ -1487,7 +1611,9 @@ impl fmt::Display for Block {
 impl fmt::Display for CopyableVal {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
+            CopyableVal::U8(v) => write!(f, "{}u8", v),
             CopyableVal::U64(v) => write!(f, "{}", v),
+            CopyableVal::U128(v) => write!(f, "{}u128", v),
             CopyableVal::Bool(v) => write!(f, "{}", v),
             CopyableVal::ByteArray(v) => write!(f, "{}", v),
             CopyableVal::Address(v) => write!(f, "0x{}", hex::encode(&v)),
@@ -1583,3 +1709,66 @@ impl fmt::Display for Exp {
         }
     }
 }
+
+impl fmt::Display for StorageLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StorageLocation::Formal(v) => write!(f, "{}", v),
+            StorageLocation::GlobalResource {
+                type_,
+                type_actuals,
+                address,
+            } => write!(
+                f,
+                "global<{}{}>({})",
+                type_,
+                format_type_actuals(type_actuals),
+                address
+            ),
+            StorageLocation::AccessPath { base, fields } => {
+                write!(f, "{}", base)?;
+                for field in fields {
+                    write!(f, ".{}", field)?;
+                }
+                Ok(())
+            }
+            StorageLocation::Address(address) => write!(f, "0x{}", hex::encode(&address)),
+            StorageLocation::TxnSenderAddress => write!(f, "txn_sender"),
+            StorageLocation::Ret(i) => write!(f, "RET({})", i),
+        }
+    }
+}
+
+impl fmt::Display for SpecExp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpecExp::Constant(v) => write!(f, "{}", v),
+            SpecExp::StorageLocation(l) => write!(f, "{}", l),
+            SpecExp::GlobalExists {
+                type_,
+                type_actuals,
+                address,
+            } => write!(
+                f,
+                "global_exists<{}{}>({})",
+                type_,
+                format_type_actuals(type_actuals),
+                address
+            ),
+            SpecExp::Dereference(l) => write!(f, "*{}", l),
+            SpecExp::Not(e) => write!(f, "!{}", e),
+            SpecExp::Binop(e1, o, e2) => write!(f, "({} {} {})", e1, o, e2),
+            SpecExp::Old(e) => write!(f, "old({})", e),
+        }
+    }
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Condition::Requires(e) => write!(f, "requires {}", e),
+            Condition::Ensures(e) => write!(f, "ensures {}", e),
+            Condition::AbortsIf(e) => write!(f, "aborts_if {}", e),
+        }
+    }
+}
@@ -177,17 +177,17 @@
 //! ## Modules
 //! ```text
 //! sdecl ∈ StructDecl ::=
-//!   | resource n { f_1: t_1, ..., f_j: t_j } // declaration of a resource struct
-//!   | struct n { f_1: t_1, ..., f_j: t_j }   // declaration of a non-resource (value) struct
-//!                                            // s.t. any 't_i' is not of resource kind
+//!   | resource n { f_1: t_1, ..., f_j: t_j } inv* // declaration of a resource struct
+//!   | struct n { f_1: t_1, ..., f_j: t_j } inv*   // declaration of a non-resource (value) struct
+//!                                                 // s.t. any 't_i' is not of resource kind
 //!
 //! body ∈ ProcedureBody ::=
 //!  | let x_1; ... let x_j; s // The locals declared in this procedure, and the code for that procedure
 //!
 //! pdecl ∈ ProcedureDecl ::=
-//!   | (public?) p(x_1: 𝛕_1, ..., x_j: 𝛕_j): 𝛕-list { body } // declaration of a defined procedure
-//!                                                          // the procedure may be public, or internal to the module
-//!   | native (public?) p(x_1: 𝛕_1, ..., x_j: 𝛕_j): 𝛕-list; // declaration of a native procedure
+//!   | (public?) p(x_1: 𝛕_1, ..., x_j: 𝛕_j): 𝛕-list spec* { body } // declaration of a defined procedure
+//!                                                                // the procedure may be public, or internal to the module
+//!   | native (public?) p(x_1: 𝛕_1, ..., x_j: 𝛕_j): 𝛕-list spec*; // declaration of a native procedure
 //!                                                         // the implementation is provided by the VM
 //!                                                         // the procedure may be public, or internal to the module
 //!
@@ -195,6 +195,30 @@
 //!   | module m { idecl_1 ... idecl_i sdecl_1 ... sdecl_j pdecl_1 ... pdecl_k }
 //! ```
 //!
+//! ## Specifications
+//! Specifications are only consumed by the Move prover; the compiler ignores them.
+//! ```text
+//! l ∈ StorageLocation ::=
+//!   | x                  // formal of the procedure, or field of the struct in an invariant
+//!   | l.f_1. ... .f_j    // access path
+//!   | global<t>(l)       // resource 't' stored at address 'l' (or an address literal)
+//!   | txn_sender         // sender of the current transaction
+//!   | RET | RET(i)       // i-th return value of the procedure
+//!
+//! se ∈ SpecExp ::=
+//!   | v | l | *l | !se | se_1 op se_2 | (se)
+//!   | global_exists<t>(l)  // 'true' if resource 't' is stored at address 'l'
+//!   | old(se)              // value of 'se' in the pre-state of the procedure
+//!
+//! spec ∈ Condition ::=
+//!   | requires se  // precondition
+//!   | ensures se   // postcondition if the procedure returns
+//!   | aborts_if se // the procedure aborts iff 'se' holds
+//!
+//! inv ∈ Invariant ::=
+//!   | invariant se // holds for every packed value of the struct
+//! ```
+//!
 //! ## Transaction Scripts
 //! ```text
 //! TransactionScript ::=
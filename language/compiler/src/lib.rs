@@ -1,6 +1,7 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod package;
 pub mod util;
 
 #[cfg(test)]
@@ -9,8 +10,9 @@ mod unit_tests;
 use bytecode_verifier::VerifiedModule;
 use failure::prelude::*;
 use ir_to_bytecode::{
-    compiler::{compile_module, compile_program},
+    compiler::{compile_module, compile_program_with_source_map},
     parser::parse_program,
+    source_map::ProgramSourceMap,
 };
 use libra_types::{
     account_address::AccountAddress,
@@ -55,6 +57,15 @@ impl Compiler {
         mut self,
         code: &str,
     ) -> Result<(CompiledProgram, Vec<VerifiedModule>)> {
+        let (compiled_program, _, deps) = self.compile_impl(code)?;
+        Ok((compiled_program, deps))
+    }
+
+    /// Compiles into a `CompiledProgram` and also returns its source map and the dependencies.
+    pub fn into_compiled_program_with_source_map(
+        mut self,
+        code: &str,
+    ) -> Result<(CompiledProgram, ProgramSourceMap, Vec<VerifiedModule>)> {
         self.compile_impl(code)
     }
 
@@ -92,11 +103,15 @@ impl Compiler {
         Ok(Script::new(self.into_script_blob(code)?, args))
     }
 
-    fn compile_impl(&mut self, code: &str) -> Result<(CompiledProgram, Vec<VerifiedModule>)> {
+    fn compile_impl(
+        &mut self,
+        code: &str,
+    ) -> Result<(CompiledProgram, ProgramSourceMap, Vec<VerifiedModule>)> {
         let parsed_program = parse_program(code)?;
         let deps = self.deps();
-        let compiled_program = compile_program(self.address, parsed_program, &deps)?;
-        Ok((compiled_program, deps))
+        let (compiled_program, source_map) =
+            compile_program_with_source_map(self.address, parsed_program, &deps)?;
+        Ok((compiled_program, source_map, deps))
     }
 
     fn compile_mod(&mut self, code: &str) -> Result<(CompiledModule, Vec<VerifiedModule>)> {
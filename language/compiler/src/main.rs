@@ -2,11 +2,17 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use bytecode_verifier::{
-    verifier::{verify_module_dependencies, VerifiedProgram},
+    verifier::{verify_module_dependencies, verify_script_dependencies, VerifiedScript},
     VerifiedModule,
 };
-use compiler::{util, Compiler};
-use ir_to_bytecode::parser::{parse_module, parse_script};
+use compiler::{package::build_package, Compiler};
+use failure::Error;
+use ir_to_bytecode::{
+    compiler::compile_module_with_source_map,
+    diagnostics::{verification_diagnostics, Diagnostic},
+    parser::{parse_module, parse_script},
+    source_map::{ModuleSourceMap, ProgramSourceMap},
+};
 use libra_types::{
     access_path::AccessPath,
     account_address::AccountAddress,
@@ -19,14 +25,18 @@ use std::{
     fs,
     io::Write,
     path::{Path, PathBuf},
+    slice,
 };
 use stdlib::stdlib_modules;
 use structopt::StructOpt;
-use vm::file_format::CompiledModule;
+use vm::file_format::{CompiledModule, CompiledProgram};
 
 #[derive(Debug, StructOpt)]
 #[structopt(name = "IR Compiler", about = "Move IR to bytecode compiler.")]
 struct Args {
+    /// Treat input file as a package manifest and build the package and its dependencies
+    #[structopt(short = "p", long = "package")]
+    pub package: bool,
     /// Treat input file as a module (default is to treat file as a program)
     #[structopt(short = "m", long = "module")]
     pub module_input: bool,
@@ -48,26 +58,97 @@ struct Args {
     /// Path to the list of modules that we want to link with
     #[structopt(long = "deps")]
     pub deps_path: Option<String>,
+    /// Also write the source map of the compiled code, as JSON, next to the output file
+    #[structopt(long = "source-map")]
+    pub output_source_map: bool,
+}
+
+/// The Move IR source being compiled, used to render diagnostics.
+struct SourceFile {
+    name: String,
+    contents: String,
 }
 
-fn print_errors_and_exit(verification_errors: &[VMStatus]) -> ! {
-    println!("Verification failed. Errors below:");
-    for e in verification_errors {
-        println!("{:?}", e);
+impl SourceFile {
+    fn read(path: &Path) -> Self {
+        SourceFile {
+            name: path.to_string_lossy().into_owned(),
+            contents: fs::read_to_string(path).expect("Unable to read file"),
+        }
+    }
+
+    fn print_diagnostics_and_exit(&self, diagnostics: &[Diagnostic]) -> ! {
+        for diagnostic in diagnostics {
+            println!("{}", diagnostic.render(&self.name, &self.contents));
+        }
+        std::process::exit(1);
+    }
+
+    fn print_error_and_exit(&self, error: &Error) -> ! {
+        match error.downcast_ref::<Diagnostic>() {
+            Some(diagnostic) => self.print_diagnostics_and_exit(slice::from_ref(diagnostic)),
+            None => {
+                println!("error: {}", error);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    fn print_verification_errors_and_exit(
+        &self,
+        verification_errors: &[VMStatus],
+        source_map: &ModuleSourceMap,
+    ) -> ! {
+        println!("Verification failed. Errors below:");
+        self.print_diagnostics_and_exit(&verification_diagnostics(verification_errors, source_map))
     }
-    std::process::exit(1);
 }
 
-fn do_verify_module(module: CompiledModule, dependencies: &[VerifiedModule]) -> VerifiedModule {
-    let verified_module =
-        VerifiedModule::new(module).unwrap_or_else(|(_, errors)| print_errors_and_exit(&errors));
+fn do_verify_module(
+    module: CompiledModule,
+    dependencies: &[VerifiedModule],
+    source_map: &ModuleSourceMap,
+    source: &SourceFile,
+) -> VerifiedModule {
+    let verified_module = VerifiedModule::new(module).unwrap_or_else(|(_, errors)| {
+        source.print_verification_errors_and_exit(&errors, source_map)
+    });
     let errors = verify_module_dependencies(&verified_module, dependencies);
     if !errors.is_empty() {
-        print_errors_and_exit(&errors);
+        source.print_verification_errors_and_exit(&errors, source_map);
     }
     verified_module
 }
 
+// Verifies the modules and the script of a program one at a time so that errors can be reported
+// against the source map of the unit they occur in.
+fn do_verify_program(
+    program: CompiledProgram,
+    dependencies: &[VerifiedModule],
+    source_map: &ProgramSourceMap,
+    source: &SourceFile,
+) -> CompiledProgram {
+    let mut deps = dependencies.to_vec();
+    let mut modules = vec![];
+    for (module, module_source_map) in program.modules.into_iter().zip(&source_map.modules) {
+        let verified_module = do_verify_module(module, &deps, module_source_map, source);
+        modules.push(verified_module.as_inner().clone());
+        deps.push(verified_module);
+    }
+
+    let script = VerifiedScript::new(program.script).unwrap_or_else(|(_, errors)| {
+        source.print_verification_errors_and_exit(&errors, &source_map.script)
+    });
+    let errors = verify_script_dependencies(&script, &deps);
+    if !errors.is_empty() {
+        source.print_verification_errors_and_exit(&errors, &source_map.script);
+    }
+    CompiledProgram {
+        modules,
+        script: script.into_inner(),
+    }
+}
+
 fn write_output(path: &PathBuf, buf: &[u8]) {
     let mut f = fs::File::create(path)
         .unwrap_or_else(|err| panic!("Unable to open output file {:?}: {}", path, err));
@@ -75,6 +156,29 @@ fn write_output(path: &PathBuf, buf: &[u8]) {
         .unwrap_or_else(|err| panic!("Unable to write to output file {:?}: {}", path, err));
 }
 
+// Loads the modules to link against: the modules listed in `deps_path`, or the standard library.
+fn load_dependencies(deps_path: Option<&String>, no_stdlib: bool) -> Vec<VerifiedModule> {
+    if let Some(path) = deps_path {
+        let deps = fs::read_to_string(path).expect("Unable to read dependency file");
+        let deps_list: Vec<Vec<u8>> =
+            serde_json::from_str(deps.as_str()).expect("Unable to parse dependency file");
+        deps_list
+            .into_iter()
+            .map(|module_bytes| {
+                VerifiedModule::new(
+                    CompiledModule::deserialize(module_bytes.as_slice())
+                        .expect("Downloaded module blob can't be deserialized"),
+                )
+                .expect("Downloaded module blob failed verifier")
+            })
+            .collect()
+    } else if no_stdlib {
+        vec![]
+    } else {
+        stdlib_modules().to_vec()
+    }
+}
+
 fn main() {
     let args = Args::from_args();
 
@@ -83,8 +187,20 @@ fn main() {
         .map(|a| AccountAddress::try_from(a).unwrap())
         .unwrap_or_else(AccountAddress::default);
     let source_path = Path::new(&args.source_path);
+    if args.package {
+        let deps = load_dependencies(args.deps_path.as_ref(), args.no_stdlib);
+        let bundle = build_package(source_path, &deps).unwrap_or_else(|err| {
+            println!("error: {}", err);
+            std::process::exit(1);
+        });
+        for entry in bundle.modules {
+            println!("{}", entry.path.display());
+        }
+        return;
+    }
     let mvir_extension = "mvir";
     let mv_extension = "mv";
+    let source_map_extension = "mvsm";
     let extension = source_path
         .extension()
         .expect("Missing file extension for input source file");
@@ -96,13 +212,15 @@ fn main() {
         std::process::exit(1);
     }
 
+    let source = SourceFile::read(source_path);
     if args.list_dependencies {
-        let source = fs::read_to_string(args.source_path.clone()).expect("Unable to read file");
         let dependency_list: Vec<AccessPath> = if args.module_input {
-            let module = parse_module(&source).expect("Unable to parse module");
+            let module = parse_module(&source.contents)
+                .unwrap_or_else(|err| source.print_error_and_exit(&err));
             module.get_external_deps()
         } else {
-            let script = parse_script(&source).expect("Unable to parse module");
+            let script = parse_script(&source.contents)
+                .unwrap_or_else(|err| source.print_error_and_exit(&err));
             script.get_external_deps()
         }
         .into_iter()
@@ -115,44 +233,21 @@ fn main() {
         return;
     }
 
-    let deps = {
-        if let Some(path) = args.deps_path {
-            let deps = fs::read_to_string(path).expect("Unable to read dependency file");
-            let deps_list: Vec<Vec<u8>> =
-                serde_json::from_str(deps.as_str()).expect("Unable to parse dependency file");
-            deps_list
-                .into_iter()
-                .map(|module_bytes| {
-                    VerifiedModule::new(
-                        CompiledModule::deserialize(module_bytes.as_slice())
-                            .expect("Downloaded module blob can't be deserialized"),
-                    )
-                    .expect("Downloaded module blob failed verifier")
-                })
-                .collect()
-        } else if args.no_stdlib {
-            vec![]
-        } else {
-            stdlib_modules().to_vec()
-        }
-    };
+    let deps = load_dependencies(args.deps_path.as_ref(), args.no_stdlib);
 
     if !args.module_input {
-        let source = fs::read_to_string(args.source_path.clone()).expect("Unable to read file");
         let compiler = Compiler {
             address,
             skip_stdlib_deps: args.no_stdlib,
             extra_deps: deps,
             ..Compiler::default()
         };
-        let (compiled_program, dependencies) = compiler
-            .into_compiled_program_and_deps(&source)
-            .expect("Failed to compile program");
+        let (compiled_program, source_map, dependencies) = compiler
+            .into_compiled_program_with_source_map(&source.contents)
+            .unwrap_or_else(|err| source.print_error_and_exit(&err));
 
         let compiled_program = if !args.no_verify {
-            let verified_program = VerifiedProgram::new(compiled_program, &dependencies)
-                .expect("Failed to verify program");
-            verified_program.into_inner()
+            do_verify_program(compiled_program, &dependencies, &source_map, &source)
         } else {
             compiled_program
         };
@@ -165,10 +260,20 @@ fn main() {
         let payload = Script::new(script, vec![]);
         let payload_bytes = serde_json::to_vec(&payload).expect("Unable to serialize program");
         write_output(&source_path.with_extension(mv_extension), &payload_bytes);
+        if args.output_source_map {
+            let source_map_bytes =
+                serde_json::to_vec(&source_map).expect("Unable to serialize source map");
+            write_output(
+                &source_path.with_extension(source_map_extension),
+                &source_map_bytes,
+            );
+        }
     } else {
-        let compiled_module = util::do_compile_module(&args.source_path, address, &deps);
+        let (compiled_module, source_map) = parse_module(&source.contents)
+            .and_then(|parsed_module| compile_module_with_source_map(address, parsed_module, &deps))
+            .unwrap_or_else(|err| source.print_error_and_exit(&err));
         let compiled_module = if !args.no_verify {
-            let verified_module = do_verify_module(compiled_module, &deps);
+            let verified_module = do_verify_module(compiled_module, &deps, &source_map, &source);
             verified_module.into_inner()
         } else {
             compiled_module
@@ -180,5 +285,13 @@ fn main() {
         let payload = Module::new(module);
         let payload_bytes = serde_json::to_vec(&payload).expect("Unable to serialize program");
         write_output(&source_path.with_extension(mv_extension), &payload_bytes);
+        if args.output_source_map {
+            let source_map_bytes =
+                serde_json::to_vec(&source_map).expect("Unable to serialize source map");
+            write_output(
+                &source_path.with_extension(source_map_extension),
+                &source_map_bytes,
+            );
+        }
     }
 }
@@ -0,0 +1,419 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Building packages of interdependent Move IR modules.
+//!
+//! A package is a directory with a `Move.toml` manifest:
+//!
+//! ```toml
+//! [package]
+//! name = "payments"
+//! address = "0x7257c2417e4d1038e1817c8f283ace2e"
+//! source_dirs = ["modules"]
+//!
+//! [dependencies]
+//! core = { path = "../core" }
+//! ```
+//!
+//! Building a package compiles the modules of the package and of all packages it depends on in
+//! dependency order, verifies them and writes them to the `build` directory next to the manifest
+//! together with a bundle listing the modules in the order they have to be published. Verified
+//! output is reused across builds as long as a module's source and dependencies are unchanged.
+
+use bytecode_verifier::{verifier::verify_module_dependencies, VerifiedModule};
+use crypto::HashValue;
+use failure::prelude::*;
+use ir_to_bytecode::{
+    compiler::compile_module_with_source_map,
+    diagnostics::{verification_diagnostics, Diagnostic},
+    parser::{
+        ast::{ModuleDefinition, ModuleIdent},
+        parse_module,
+    },
+    source_map::ModuleSourceMap,
+};
+use libra_types::{
+    account_address::AccountAddress, language_storage::ModuleId, transaction::Module,
+    vm_error::VMStatus,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    iter::once,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+use vm::{access::ModuleAccess, file_format::CompiledModule};
+
+pub const MANIFEST_FILE_NAME: &str = "Move.toml";
+pub const BUILD_DIR_NAME: &str = "build";
+pub const BUNDLE_FILE_NAME: &str = "bundle.json";
+
+const SOURCE_EXTENSION: &str = "mvir";
+const OUTPUT_EXTENSION: &str = "mv";
+const CACHE_KEY_EXTENSION: &str = "hash";
+
+/// The contents of a `Move.toml` manifest.
+#[derive(Clone, Debug, Deserialize)]
+pub struct PackageManifest {
+    pub package: PackageInfo,
+    #[serde(default)]
+    pub dependencies: BTreeMap<String, PackageDependency>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct PackageInfo {
+    pub name: String,
+    /// The hex encoded address the modules of the package are published under.
+    pub address: String,
+    /// The directories holding the `.mvir` module sources, relative to the manifest.
+    #[serde(default = "default_source_dirs")]
+    pub source_dirs: Vec<PathBuf>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct PackageDependency {
+    /// The directory of the dependency package, relative to the manifest.
+    pub path: PathBuf,
+}
+
+fn default_source_dirs() -> Vec<PathBuf> {
+    vec![PathBuf::from("modules")]
+}
+
+impl PackageManifest {
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .map_err(|err| format_err!("Unable to read {}: {}", path.display(), err))?;
+        toml::from_str(&contents)
+            .map_err(|err| format_err!("Invalid manifest {}: {}", path.display(), err))
+    }
+
+    /// The address of the package. Short addresses are padded with leading zeros.
+    pub fn address(&self) -> Result<AccountAddress> {
+        let address = self.package.address.trim_start_matches("0x");
+        ensure!(
+            !address.is_empty(),
+            "Package {} has an empty address",
+            self.package.name
+        );
+        AccountAddress::from_str(&format!("{:0>64}", address))
+    }
+}
+
+/// A compiled module of a bundle.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct BundleEntry {
+    /// The hex encoded address the module has to be published under.
+    pub address: String,
+    pub name: String,
+    /// The compiled module, in the format published by `dev publish`.
+    pub path: PathBuf,
+}
+
+/// The compiled modules of a package and its dependencies, in publishing order.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Bundle {
+    pub modules: Vec<BundleEntry>,
+}
+
+impl Bundle {
+    pub fn load(path: &Path) -> Result<Self> {
+        Ok(serde_json::from_slice(&fs::read(path)?)?)
+    }
+}
+
+struct Package {
+    manifest: PackageManifest,
+    address: AccountAddress,
+    dir: PathBuf,
+}
+
+struct ModuleSource {
+    path: PathBuf,
+    source: String,
+    address: AccountAddress,
+    definition: ModuleDefinition,
+    deps: BTreeSet<ModuleId>,
+}
+
+/// Builds the package whose manifest is at `manifest_path`, linking against the already
+/// published modules in `deps`. Returns the bundle, which is also written to
+/// `build/bundle.json`.
+pub fn build_package(manifest_path: &Path, deps: &[VerifiedModule]) -> Result<Bundle> {
+    let packages = load_packages(manifest_path)?;
+    let sources = collect_sources(&packages)?;
+    let order = build_order(&sources, deps)?;
+    let build_dir = packages[0].dir.join(BUILD_DIR_NAME);
+
+    let mut keys = BTreeMap::new();
+    for dep in deps {
+        let mut bytes = vec![];
+        dep.serialize(&mut bytes)?;
+        keys.insert(dep.self_id(), HashValue::from_sha3_256(&bytes));
+    }
+
+    let mut linked = deps.to_vec();
+    let mut bundle = Bundle::default();
+    for id in order {
+        let source = &sources[&id];
+        let key = cache_key(source, &keys);
+        let output_dir = build_dir.join(format!("{:x}", id.address()));
+        let output_path = output_dir
+            .join(id.name().as_str())
+            .with_extension(OUTPUT_EXTENSION);
+        let key_path = output_path.with_extension(CACHE_KEY_EXTENSION);
+
+        let verified_module = match load_cached(&output_path, &key_path, key) {
+            Some(verified_module) => verified_module,
+            None => {
+                let verified_module = compile_and_verify(source, &linked)?;
+                let mut code = vec![];
+                verified_module.serialize(&mut code)?;
+                fs::create_dir_all(&output_dir)?;
+                fs::write(&output_path, serde_json::to_vec(&Module::new(code))?)?;
+                fs::write(&key_path, format!("{:x}", key))?;
+                verified_module
+            }
+        };
+        linked.push(verified_module);
+        keys.insert(id.clone(), key);
+        bundle.modules.push(BundleEntry {
+            address: format!("{:x}", id.address()),
+            name: id.name().to_string(),
+            path: output_path,
+        });
+    }
+
+    fs::write(
+        build_dir.join(BUNDLE_FILE_NAME),
+        serde_json::to_vec_pretty(&bundle)?,
+    )?;
+    Ok(bundle)
+}
+
+// Loads the root package and, transitively, the packages it depends on. The root package is
+// always first.
+fn load_packages(manifest_path: &Path) -> Result<Vec<Package>> {
+    let mut packages = vec![];
+    let mut stack = vec![];
+    let manifest_path = manifest_path
+        .canonicalize()
+        .map_err(|err| format_err!("Unable to find {}: {}", manifest_path.display(), err))?;
+    let root_dir = manifest_path
+        .parent()
+        .ok_or_else(|| format_err!("Invalid manifest path {}", manifest_path.display()))?;
+    load_package(root_dir, &mut stack, &mut packages)?;
+    Ok(packages)
+}
+
+fn load_package(
+    dir: &Path,
+    stack: &mut Vec<(PathBuf, String)>,
+    packages: &mut Vec<Package>,
+) -> Result<()> {
+    let dir = dir
+        .canonicalize()
+        .map_err(|err| format_err!("Unable to find package {}: {}", dir.display(), err))?;
+    // The packages on the stack have already been added to `packages`, so this has to be checked
+    // before skipping the packages that were loaded before.
+    if let Some(start) = stack.iter().position(|(stack_dir, _)| *stack_dir == dir) {
+        let cycle = stack[start..]
+            .iter()
+            .chain(once(&stack[start]))
+            .map(|(_, name)| name.as_str())
+            .collect::<Vec<_>>();
+        bail!("Dependency cycle between packages: {}", cycle.join(" -> "));
+    }
+    if packages.iter().any(|package| package.dir == dir) {
+        return Ok(());
+    }
+    let manifest = PackageManifest::load(&dir.join(MANIFEST_FILE_NAME))?;
+    let name = manifest.package.name.clone();
+
+    let address = manifest.address()?;
+    let dependencies = manifest.dependencies.clone();
+    packages.push(Package {
+        manifest,
+        address,
+        dir: dir.clone(),
+    });
+    stack.push((dir.clone(), name));
+    for dependency in dependencies.values() {
+        load_package(&dir.join(&dependency.path), stack, packages)?;
+    }
+    stack.pop();
+    Ok(())
+}
+
+fn collect_sources(packages: &[Package]) -> Result<BTreeMap<ModuleId, ModuleSource>> {
+    let mut sources = BTreeMap::new();
+    for package in packages {
+        for source_dir in &package.manifest.package.source_dirs {
+            let source_dir = package.dir.join(source_dir);
+            let mut paths = fs::read_dir(&source_dir)
+                .map_err(|err| format_err!("Unable to read {}: {}", source_dir.display(), err))?
+                .map(|entry| Ok(entry?.path()))
+                .collect::<Result<Vec<_>>>()?;
+            paths.sort();
+            for path in paths {
+                if path.extension().map_or(true, |ext| ext != SOURCE_EXTENSION) {
+                    continue;
+                }
+                let source = read_source(path, package.address)?;
+                let id = ModuleId::new(
+                    package.address,
+                    source.definition.name.as_inner().to_owned(),
+                );
+                if let Some(other) = sources.get(&id) {
+                    bail!(
+                        "Module {} is defined in both {} and {}",
+                        display_id(&id),
+                        other.path.display(),
+                        source.path.display()
+                    );
+                }
+                sources.insert(id, source);
+            }
+        }
+    }
+    Ok(sources)
+}
+
+fn read_source(path: PathBuf, address: AccountAddress) -> Result<ModuleSource> {
+    let source = fs::read_to_string(&path)
+        .map_err(|err| format_err!("Unable to read {}: {}", path.display(), err))?;
+    let definition = parse_module(&source).map_err(|err| render_error(err, &path, &source))?;
+    let deps = definition
+        .imports
+        .iter()
+        .map(|import| match &import.ident {
+            ModuleIdent::Transaction(name) => ModuleId::new(address, name.as_inner().to_owned()),
+            ModuleIdent::Qualified(id) => ModuleId::new(id.address, id.name.as_inner().to_owned()),
+        })
+        .collect();
+    Ok(ModuleSource {
+        path,
+        source,
+        address,
+        definition,
+        deps,
+    })
+}
+
+// Orders the modules so that every module comes after the modules it imports.
+fn build_order(
+    sources: &BTreeMap<ModuleId, ModuleSource>,
+    deps: &[VerifiedModule],
+) -> Result<Vec<ModuleId>> {
+    let external = deps
+        .iter()
+        .map(|dep| dep.self_id())
+        .collect::<BTreeSet<_>>();
+    for (id, source) in sources {
+        for dep in &source.deps {
+            ensure!(
+                sources.contains_key(dep) || external.contains(dep),
+                "Module {} imports {}, which is not part of the package or its dependencies",
+                display_id(id),
+                display_id(dep)
+            );
+        }
+    }
+
+    let mut order = vec![];
+    let mut stack = vec![];
+    for id in sources.keys() {
+        visit(id, sources, &mut stack, &mut order)?;
+    }
+    Ok(order)
+}
+
+fn visit(
+    id: &ModuleId,
+    sources: &BTreeMap<ModuleId, ModuleSource>,
+    stack: &mut Vec<ModuleId>,
+    order: &mut Vec<ModuleId>,
+) -> Result<()> {
+    if order.contains(id) {
+        return Ok(());
+    }
+    if let Some(pos) = stack.iter().position(|other| other == id) {
+        let cycle = stack[pos..]
+            .iter()
+            .chain(once(id))
+            .map(display_id)
+            .collect::<Vec<_>>();
+        bail!("Dependency cycle between modules: {}", cycle.join(" -> "));
+    }
+    stack.push(id.clone());
+    for dep in &sources[id].deps {
+        if sources.contains_key(dep) {
+            visit(dep, sources, stack, order)?;
+        }
+    }
+    stack.pop();
+    order.push(id.clone());
+    Ok(())
+}
+
+// A module has to be rebuilt whenever its source or anything it links against changes.
+fn cache_key(source: &ModuleSource, keys: &BTreeMap<ModuleId, HashValue>) -> HashValue {
+    let mut bytes = source.address.to_vec();
+    bytes.extend_from_slice(source.source.as_bytes());
+    for dep in &source.deps {
+        bytes.extend_from_slice(&keys[dep].to_vec());
+    }
+    HashValue::from_sha3_256(&bytes)
+}
+
+fn load_cached(output_path: &Path, key_path: &Path, key: HashValue) -> Option<VerifiedModule> {
+    if fs::read_to_string(key_path).ok()? != format!("{:x}", key) {
+        return None;
+    }
+    let module: Module = serde_json::from_slice(&fs::read(output_path).ok()?).ok()?;
+    let compiled_module = CompiledModule::deserialize(module.code()).ok()?;
+    VerifiedModule::new(compiled_module).ok()
+}
+
+fn compile_and_verify(source: &ModuleSource, linked: &[VerifiedModule]) -> Result<VerifiedModule> {
+    let (compiled_module, source_map) =
+        compile_module_with_source_map(source.address, source.definition.clone(), linked)
+            .map_err(|err| render_error(err, &source.path, &source.source))?;
+    let verified_module = VerifiedModule::new(compiled_module)
+        .map_err(|(_, errors)| render_verification_errors(&errors, &source_map, source))?;
+    let errors = verify_module_dependencies(&verified_module, linked);
+    if !errors.is_empty() {
+        return Err(render_verification_errors(&errors, &source_map, source));
+    }
+    Ok(verified_module)
+}
+
+fn render_error(error: Error, path: &Path, source: &str) -> Error {
+    match error.downcast_ref::<Diagnostic>() {
+        Some(diagnostic) => format_err!("{}", diagnostic.render(&path.to_string_lossy(), source)),
+        None => format_err!("{}: {}", path.display(), error),
+    }
+}
+
+fn render_verification_errors(
+    errors: &[VMStatus],
+    source_map: &ModuleSourceMap,
+    source: &ModuleSource,
+) -> Error {
+    let file_name = source.path.to_string_lossy();
+    let rendered = verification_diagnostics(errors, source_map)
+        .iter()
+        .map(|diagnostic| diagnostic.render(&file_name, &source.source))
+        .collect::<Vec<_>>();
+    format_err!(
+        "Verification of {} failed:\n{}",
+        source.path.display(),
+        rendered.join("\n")
+    )
+}
+
+fn display_id(id: &ModuleId) -> String {
+    format!("{}.{}", id.address(), id.name())
+}
@@ -0,0 +1,112 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use bytecode_verifier::VerifiedModule;
+use failure::prelude::*;
+use ir_to_bytecode::{
+    compiler::compile_module_with_source_map,
+    diagnostics::{verification_diagnostics, Diagnostic, DiagnosticCode},
+    parser::parse_module,
+    source_map::{line_and_column, ModuleSourceMap},
+};
+use libra_types::{account_address::AccountAddress, vm_error::StatusCode};
+use vm::file_format::CompiledModule;
+
+fn compile(code: &str) -> Result<(CompiledModule, ModuleSourceMap)> {
+    let deps: Vec<CompiledModule> = vec![];
+    compile_module_with_source_map(AccountAddress::default(), parse_module(code)?, &deps)
+}
+
+fn diagnostic_of(error: Error) -> Diagnostic {
+    error
+        .downcast_ref::<Diagnostic>()
+        .expect("error should be a diagnostic")
+        .clone()
+}
+
+fn primary_line(diagnostic: &Diagnostic, code: &str) -> usize {
+    let (loc, _) = diagnostic
+        .primary
+        .as_ref()
+        .expect("diagnostic should have a span");
+    line_and_column(code, *loc).0
+}
+
+#[test]
+fn parse_error_has_span() {
+    let code = "
+module M {
+    f() {
+        return 0 +;
+    }
+}";
+    let diagnostic = diagnostic_of(parse_module(code).unwrap_err());
+    assert_eq!(diagnostic.code, DiagnosticCode::ParserError);
+    assert!(diagnostic.message.contains("Unrecognized Token"));
+    assert_eq!(primary_line(&diagnostic, code), 4);
+}
+
+#[test]
+fn unbound_name_points_at_expression() {
+    let code = "
+module M {
+    f(): u64 {
+        let x: u64;
+        x = 1;
+        return move(y);
+    }
+}";
+    let diagnostic = diagnostic_of(compile(code).unwrap_err());
+    assert_eq!(diagnostic.code, DiagnosticCode::CompilerError);
+    assert!(diagnostic.message.contains("variable y undefined"));
+    let (loc, _) = diagnostic.primary.as_ref().unwrap();
+    assert_eq!(line_and_column(code, *loc), (6, 16));
+}
+
+#[test]
+fn unbound_function_points_at_call() {
+    let code = "
+module M {
+    f() {
+        Self.g();
+        return;
+    }
+}";
+    let diagnostic = diagnostic_of(compile(code).unwrap_err());
+    assert_eq!(diagnostic.code, DiagnosticCode::CompilerError);
+    assert!(diagnostic.message.contains("Unbound function"));
+    assert_eq!(primary_line(&diagnostic, code), 4);
+}
+
+#[test]
+fn verification_error_is_located_and_rendered() {
+    let code = "
+module M {
+    f() {
+        let x: u64;
+        x = 1;
+        _ = move(x);
+        _ = move(x);
+        return;
+    }
+}";
+    let (module, source_map) = compile(code).unwrap();
+    let errors = match VerifiedModule::new(module) {
+        Ok(_) => panic!("module should fail verification"),
+        Err((_, errors)) => errors,
+    };
+    let diagnostics = verification_diagnostics(&errors, &source_map);
+    assert_eq!(
+        diagnostics[0].code,
+        DiagnosticCode::VerificationError(StatusCode::MOVELOC_UNAVAILABLE_ERROR)
+    );
+    assert_eq!(primary_line(&diagnostics[0], code), 7);
+    assert!(diagnostics[0]
+        .notes
+        .contains(&"in function `f`".to_string()));
+
+    let rendered = diagnostics[0].render("m.mvir", code);
+    assert!(rendered.contains("MOVELOC_UNAVAILABLE_ERROR"));
+    assert!(rendered.contains("_ = move(x);"));
+    assert!(rendered.contains("= note: in function `f`"));
+}
@@ -39,6 +39,37 @@ fn compile_script_with_functions() {
     assert!(compiled_module_res.is_ok());
 }
 
+#[test]
+fn compile_module_with_specifications() {
+    let code = String::from(
+        "
+        module Foobar {
+            resource FooCoin { value: u64, limit: u64 } invariant value <= limit
+
+            public value(this: &Self.FooCoin): u64
+                ensures RET(0) == this.value
+            {
+                return *&move(this).value;
+            }
+
+            public deposit(this: &mut Self.FooCoin, amount: u64)
+                requires amount > 0
+                aborts_if old(this.value) + amount > this.limit
+                ensures this.value == old(this.value) + amount
+            {
+                let value_ref: &mut u64;
+                value_ref = &mut move(this).value;
+                *copy(value_ref) = *copy(value_ref) + move(amount);
+                _ = move(value_ref);
+                return;
+            }
+        }
+        ",
+    );
+    let compiled_module_res = compile_module_string(&code);
+    assert!(compiled_module_res.is_ok());
+}
+
 fn generate_function(name: &str, num_formals: usize, num_locals: usize) -> String {
     let mut code = format!("public {}(", name);
 
@@ -0,0 +1,207 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::package::{build_package, Bundle, BUILD_DIR_NAME, BUNDLE_FILE_NAME, MANIFEST_FILE_NAME};
+use std::{fs, path::Path};
+use tools::tempdir::TempPath;
+
+const CORE_ADDRESS: &str = "0000000000000000000000000000000000000000000000000000000000000001";
+const APP_ADDRESS: &str = "0000000000000000000000000000000000000000000000000000000000000002";
+
+fn write_package(dir: &Path, manifest: &str, modules: &[(&str, &str)]) {
+    fs::create_dir_all(dir.join("modules")).unwrap();
+    fs::write(dir.join(MANIFEST_FILE_NAME), manifest).unwrap();
+    for (name, code) in modules {
+        fs::write(dir.join("modules").join(format!("{}.mvir", name)), code).unwrap();
+    }
+}
+
+fn write_core(root: &Path) {
+    write_package(
+        &root.join("core"),
+        "[package]\nname = \"core\"\naddress = \"0x1\"\n",
+        &[(
+            "Counter",
+            "module Counter {
+                public zero(): u64 {
+                    return 0;
+                }
+            }",
+        )],
+    );
+}
+
+// Module names are chosen so that alphabetical order is not a valid build order.
+fn write_app(root: &Path) {
+    write_package(
+        &root.join("app"),
+        "[package]\nname = \"app\"\naddress = \"0x2\"\n\n\
+         [dependencies]\ncore = { path = \"../core\" }\n",
+        &[
+            (
+                "A",
+                "module A {
+                    import Transaction.B;
+                    public a(): u64 {
+                        return B.b();
+                    }
+                }",
+            ),
+            (
+                "B",
+                "module B {
+                    import 0x1.Counter;
+                    public b(): u64 {
+                        return Counter.zero();
+                    }
+                }",
+            ),
+        ],
+    );
+}
+
+fn module_order(bundle: &Bundle) -> Vec<(&str, &str)> {
+    bundle
+        .modules
+        .iter()
+        .map(|entry| (entry.address.as_str(), entry.name.as_str()))
+        .collect()
+}
+
+#[test]
+fn builds_packages_in_dependency_order() {
+    let root = TempPath::new();
+    root.create_as_dir().unwrap();
+    write_core(root.path());
+    write_app(root.path());
+
+    let manifest_path = root.path().join("app").join(MANIFEST_FILE_NAME);
+    let bundle = build_package(&manifest_path, &[]).unwrap();
+    assert_eq!(
+        module_order(&bundle),
+        vec![
+            (CORE_ADDRESS, "Counter"),
+            (APP_ADDRESS, "B"),
+            (APP_ADDRESS, "A")
+        ]
+    );
+    for entry in &bundle.modules {
+        assert!(entry.path.is_file());
+    }
+
+    let bundle_path = root
+        .path()
+        .join("app")
+        .join(BUILD_DIR_NAME)
+        .join(BUNDLE_FILE_NAME);
+    assert_eq!(Bundle::load(&bundle_path).unwrap(), bundle);
+}
+
+#[test]
+fn reuses_cached_modules() {
+    let root = TempPath::new();
+    root.create_as_dir().unwrap();
+    write_core(root.path());
+    write_app(root.path());
+
+    let manifest_path = root.path().join("app").join(MANIFEST_FILE_NAME);
+    let bundle = build_package(&manifest_path, &[]).unwrap();
+    let modified = |bundle: &Bundle| {
+        bundle
+            .modules
+            .iter()
+            .map(|entry| fs::metadata(&entry.path).unwrap().modified().unwrap())
+            .collect::<Vec<_>>()
+    };
+    let before = modified(&bundle);
+
+    let rebuilt = build_package(&manifest_path, &[]).unwrap();
+    assert_eq!(rebuilt, bundle);
+    assert_eq!(modified(&rebuilt), before);
+
+    // A corrupted output is rebuilt even though its cache key is current.
+    fs::write(&bundle.modules[0].path, "garbage").unwrap();
+    let rebuilt = build_package(&manifest_path, &[]).unwrap();
+    assert_eq!(rebuilt, bundle);
+    assert_ne!(
+        fs::read(&bundle.modules[0].path).unwrap(),
+        b"garbage".to_vec()
+    );
+}
+
+#[test]
+fn rejects_module_cycles() {
+    let root = TempPath::new();
+    root.create_as_dir().unwrap();
+    write_package(
+        root.path(),
+        "[package]\nname = \"cycle\"\naddress = \"0x2\"\n",
+        &[
+            (
+                "A",
+                "module A {
+                    import Transaction.B;
+                    public a() {
+                        return;
+                    }
+                }",
+            ),
+            (
+                "B",
+                "module B {
+                    import Transaction.A;
+                    public b() {
+                        return;
+                    }
+                }",
+            ),
+        ],
+    );
+
+    let err = build_package(&root.path().join(MANIFEST_FILE_NAME), &[]).unwrap_err();
+    let message = err.to_string();
+    assert!(message.starts_with("Dependency cycle between modules"));
+    assert!(message.contains(".A -> 0x"));
+    assert!(message.contains(".B -> 0x"));
+}
+
+#[test]
+fn rejects_package_cycles() {
+    let root = TempPath::new();
+    root.create_as_dir().unwrap();
+    write_package(
+        &root.path().join("a"),
+        "[package]\nname = \"a\"\naddress = \"0x1\"\n\n\
+         [dependencies]\nb = { path = \"../b\" }\n",
+        &[],
+    );
+    write_package(
+        &root.path().join("b"),
+        "[package]\nname = \"b\"\naddress = \"0x2\"\n\n\
+         [dependencies]\na = { path = \"../a\" }\n",
+        &[],
+    );
+
+    let err = build_package(&root.path().join("a").join(MANIFEST_FILE_NAME), &[]).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Dependency cycle between packages: a -> b -> a"
+    );
+}
+
+#[test]
+fn rejects_unresolved_imports() {
+    let root = TempPath::new();
+    root.create_as_dir().unwrap();
+    // The app package imports Counter without declaring a dependency on the core package.
+    write_core(root.path());
+    write_app(root.path());
+    fs::write(
+        root.path().join("app").join(MANIFEST_FILE_NAME),
+        "[package]\nname = \"app\"\naddress = \"0x2\"\n",
+    )
+    .unwrap();
+
+    let err = build_package(&root.path().join("app").join(MANIFEST_FILE_NAME), &[]).unwrap_err();
+    assert!(err.to_string().contains("which is not part of the package"));
+}
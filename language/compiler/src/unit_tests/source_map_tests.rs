@@ -0,0 +1,218 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use bytecode_verifier::VerifiedModule;
+use ir_to_bytecode::{
+    compiler::{compile_module_with_source_map, compile_program_with_source_map},
+    parser::{parse_module, parse_program},
+    source_map::{line_and_column, ModuleSourceMap, SourceMapLocator},
+};
+use libra_types::{
+    account_address::AccountAddress,
+    identifier::Identifier,
+    language_storage::ModuleId,
+    vm_error::{CodeLocation, StatusCode},
+};
+use vm::{
+    file_format::{Bytecode, CodeOffset, CompiledModule, FunctionDefinitionIndex},
+    printers::AnnotatedModule,
+};
+
+fn compile_module_with_map(code: &str) -> (CompiledModule, ModuleSourceMap) {
+    let module = parse_module(code).unwrap();
+    let deps: Vec<CompiledModule> = vec![];
+    compile_module_with_source_map(AccountAddress::default(), module, &deps).unwrap()
+}
+
+fn line_of(source_map: &ModuleSourceMap, code: &str, function: u16, offset: CodeOffset) -> usize {
+    let loc = source_map
+        .get_function(FunctionDefinitionIndex::new(function))
+        .unwrap()
+        .get_code_location(offset)
+        .unwrap();
+    line_and_column(code, loc).0
+}
+
+#[test]
+fn maps_offsets_to_commands() {
+    let code = "
+module M {
+    public f(x: u64): u64 {
+        let y: u64;
+        y = move(x) + 1;
+        return move(y);
+    }
+}";
+    let (module, source_map) = compile_module_with_map(code);
+    let function_code = &module.as_inner().function_defs[0].code.code;
+    assert_eq!(function_code.len(), 6);
+
+    // MoveLoc(x), LdConst(1), Add, StLoc(y) come from line 5; MoveLoc(y), Ret from line 6.
+    for offset in 0..4 {
+        assert_eq!(line_of(&source_map, code, 0, offset), 5);
+    }
+    for offset in 4..6 {
+        assert_eq!(line_of(&source_map, code, 0, offset), 6);
+    }
+
+    let function = source_map
+        .get_function(FunctionDefinitionIndex::new(0))
+        .unwrap();
+    assert_eq!(function.get_local_name(0), Some("x"));
+    assert_eq!(function.get_local_name(1), Some("y"));
+    assert_eq!(function.get_local_name(2), None);
+    assert_eq!(
+        line_and_column(code, function.get_local_location(1).unwrap()).0,
+        4
+    );
+}
+
+#[test]
+fn maps_loop_back_edge_to_condition() {
+    let code = "
+module M {
+    f() {
+        let i: u64;
+        i = 0;
+        while (copy(i) < 10) {
+            i = move(i) + 1;
+        }
+        return;
+    }
+}";
+    let (module, source_map) = compile_module_with_map(code);
+    let function_code = &module.as_inner().function_defs[0].code.code;
+    let back_edge = function_code
+        .iter()
+        .position(|bytecode| *bytecode == Bytecode::Branch(2))
+        .unwrap();
+    assert_eq!(line_of(&source_map, code, 0, 2), 6);
+    assert_eq!(line_of(&source_map, code, 0, back_edge as CodeOffset), 6);
+    assert_eq!(
+        line_of(&source_map, code, 0, back_edge as CodeOffset - 1),
+        7
+    );
+}
+
+#[test]
+fn resolves_verification_error() {
+    let code = "
+module M {
+    f() {
+        let x: u64;
+        x = 1;
+        _ = move(x);
+        _ = move(x);
+        return;
+    }
+}";
+    let (module, source_map) = compile_module_with_map(code);
+    let errors = match VerifiedModule::new(module) {
+        Ok(_) => panic!("module should fail verification"),
+        Err((_, errors)) => errors,
+    };
+    assert_eq!(
+        errors[0].major_status,
+        StatusCode::MOVELOC_UNAVAILABLE_ERROR
+    );
+    let loc = source_map.resolve_verification_error(&errors[0]).unwrap();
+    assert_eq!(line_and_column(code, loc), (7, 9));
+}
+
+#[test]
+fn resolves_location() {
+    let code = "
+module M {
+    f() {
+        return;
+    }
+    g(x: u64) {
+        assert(copy(x) > 0, 42);
+        return;
+    }
+}";
+    let (_, source_map) = compile_module_with_map(code);
+    let location = CodeLocation {
+        module: Some(ModuleId::new(
+            AccountAddress::default(),
+            Identifier::new("M").unwrap(),
+        )),
+        function: Some(Identifier::new("g").unwrap()),
+        function_definition_index: None,
+        code_offset: 0,
+        call_stack_depth: 1,
+    };
+    let loc = source_map.resolve_location(&location).unwrap();
+    assert_eq!(line_and_column(code, loc).0, 7);
+
+    // The verifier records the index of the function definition instead of its name.
+    let by_index = CodeLocation {
+        function: None,
+        function_definition_index: Some(1),
+        ..location.clone()
+    };
+    assert_eq!(source_map.resolve_location(&by_index), Some(loc));
+
+    let missing = CodeLocation {
+        function: Some(Identifier::new("h").unwrap()),
+        ..location
+    };
+    assert!(source_map.resolve_location(&missing).is_none());
+}
+
+#[test]
+fn annotates_disassembly() {
+    let code = "
+module M {
+    public f(x: u64): u64 {
+        return move(x);
+    }
+}";
+    let (module, source_map) = compile_module_with_map(code);
+    let locator = SourceMapLocator::new(&source_map, code);
+    let disassembly = AnnotatedModule::new(&module, &locator).to_string();
+    assert!(disassembly.contains("local(0): x"));
+    assert!(disassembly.contains("MoveLoc(0) // 4:9"));
+    assert!(disassembly.contains("Ret // 4:9"));
+}
+
+#[test]
+fn program_source_map_covers_modules_and_script() {
+    let code = "
+modules:
+module M {
+    public f() {
+        return;
+    }
+}
+script:
+main() {
+    let a: u64;
+    a = 1;
+    return;
+}";
+    let program = parse_program(code).unwrap();
+    let deps: Vec<CompiledModule> = vec![];
+    let (_, source_map) =
+        compile_program_with_source_map(AccountAddress::default(), program, &deps).unwrap();
+    assert_eq!(source_map.modules.len(), 1);
+    assert_eq!(line_of(&source_map.modules[0], code, 0, 0), 5);
+    assert_eq!(line_of(&source_map.script, code, 0, 0), 11);
+    assert_eq!(line_of(&source_map.script, code, 0, 2), 12);
+}
+
+#[test]
+fn source_map_json_roundtrip() {
+    let code = "
+module M {
+    f(x: u64): u64 {
+        let y: u64;
+        y = move(x) + 1;
+        return move(y);
+    }
+}";
+    let (_, source_map) = compile_module_with_map(code);
+    let json = serde_json::to_string(&source_map).unwrap();
+    let decoded: ModuleSourceMap = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded, source_map);
+}
@@ -17,6 +17,15 @@ fn compile_libra_coin() {
     let _compiled_module = compile_module_string(&code).unwrap();
 }
 
+#[test]
+fn compile_currency() {
+    let vector_code = include_str!("../../../stdlib/modules/vector.mvir");
+    let code = include_str!("../../../stdlib/modules/currency.mvir");
+
+    let vector_module = compile_module_string(vector_code).unwrap();
+    let _compiled_module = compile_module_string_with_deps(code, vec![vector_module]).unwrap();
+}
+
 #[test]
 fn compile_account_module() {
     let address_util_code = include_str!("../../../stdlib/modules/address_util.mvir");
@@ -26,6 +35,9 @@ fn compile_account_module() {
 
     let hash_code = include_str!("../../../stdlib/modules/hash.mvir");
     let coin_code = include_str!("../../../stdlib/modules/libra_coin.mvir");
+    let vector_code = include_str!("../../../stdlib/modules/vector.mvir");
+    let currency_code = include_str!("../../../stdlib/modules/currency.mvir");
+    let transaction_fee_code = include_str!("../../../stdlib/modules/transaction_fee.mvir");
     let account_code = include_str!("../../../stdlib/modules/libra_account.mvir");
 
     let address_util_module = compile_module_string(address_util_code).unwrap();
@@ -44,6 +56,11 @@ fn compile_account_module() {
     )
     .unwrap();
     let coin_module = compile_module_string(coin_code).unwrap();
+    let vector_module = compile_module_string(vector_code).unwrap();
+    let currency_module =
+        compile_module_string_with_deps(currency_code, vec![vector_module.clone()]).unwrap();
+    let transaction_fee_module =
+        compile_module_string_with_deps(transaction_fee_code, vec![coin_module.clone()]).unwrap();
 
     let _compiled_module = compile_module_string_with_deps(
         account_code,
@@ -54,6 +71,9 @@ fn compile_account_module() {
             bytearray_util_module,
             event_module,
             coin_module,
+            vector_module,
+            currency_module,
+            transaction_fee_module,
         ],
     )
     .unwrap();
@@ -65,6 +85,18 @@ fn compile_create_account_script() {
     let _compiled_script = compile_script_string_with_stdlib(code).unwrap();
 }
 
+#[test]
+fn compile_freeze_account_script() {
+    let code = include_str!("../../../stdlib/transaction_scripts/freeze_account.mvir");
+    let _compiled_script = compile_script_string_with_stdlib(code).unwrap();
+}
+
+#[test]
+fn compile_unfreeze_account_script() {
+    let code = include_str!("../../../stdlib/transaction_scripts/unfreeze_account.mvir");
+    let _compiled_script = compile_script_string_with_stdlib(code).unwrap();
+}
+
 #[test]
 fn compile_mint_script() {
     let code = include_str!("../../../stdlib/transaction_scripts/mint.mvir");
@@ -77,8 +109,45 @@ fn compile_rotate_authentication_key_script() {
     let _compiled_script = compile_script_string_with_stdlib(code).unwrap();
 }
 
+#[test]
+fn compile_enable_nonces_script() {
+    let code = include_str!("../../../stdlib/transaction_scripts/enable_nonces.mvir");
+    let _compiled_script = compile_script_string_with_stdlib(code).unwrap();
+}
+
 #[test]
 fn compile_peer_to_peer_transfer_script() {
     let code = include_str!("../../../stdlib/transaction_scripts/peer_to_peer_transfer.mvir");
     let _compiled_script = compile_script_string_with_stdlib(code).unwrap();
 }
+
+#[test]
+fn compile_register_currency_script() {
+    let code = include_str!("../../../stdlib/transaction_scripts/register_currency.mvir");
+    let _compiled_script = compile_script_string_with_stdlib(code).unwrap();
+}
+
+#[test]
+fn compile_accept_currency_script() {
+    let code = include_str!("../../../stdlib/transaction_scripts/accept_currency.mvir");
+    let _compiled_script = compile_script_string_with_stdlib(code).unwrap();
+}
+
+#[test]
+fn compile_mint_currency_script() {
+    let code = include_str!("../../../stdlib/transaction_scripts/mint_currency.mvir");
+    let _compiled_script = compile_script_string_with_stdlib(code).unwrap();
+}
+
+#[test]
+fn compile_peer_to_peer_currency_transfer_script() {
+    let code =
+        include_str!("../../../stdlib/transaction_scripts/peer_to_peer_currency_transfer.mvir");
+    let _compiled_script = compile_script_string_with_stdlib(code).unwrap();
+}
+
+#[test]
+fn compile_burn_currency_script() {
+    let code = include_str!("../../../stdlib/transaction_scripts/burn_currency.mvir");
+    let _compiled_script = compile_script_string_with_stdlib(code).unwrap();
+}
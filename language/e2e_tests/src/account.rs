@@ -259,12 +259,23 @@ pub struct AccountData {
     delegated_withdrawal_capability: bool,
     sent_events: EventHandle,
     received_events: EventHandle,
+    account_creation_events: EventHandle,
+    key_rotation_events: EventHandle,
+    key_rotation_capability_events: EventHandle,
+    withdrawal_capability_events: EventHandle,
 }
 
 fn new_event_handle(count: u64) -> EventHandle {
     EventHandle::random_handle(count)
 }
 
+fn event_handle_value(handle: &EventHandle) -> Value {
+    Value::struct_(Struct::new(vec![
+        Value::u64(handle.count()),
+        Value::byte_array(ByteArray::new(handle.key().to_vec())),
+    ]))
+}
+
 impl AccountData {
     /// Creates a new `AccountData` with a new account.
     ///
@@ -296,6 +307,10 @@ impl AccountData {
             delegated_withdrawal_capability,
             sent_events: new_event_handle(sent_events_count),
             received_events: new_event_handle(received_events_count),
+            account_creation_events: new_event_handle(0),
+            key_rotation_events: new_event_handle(0),
+            key_rotation_capability_events: new_event_handle(0),
+            withdrawal_capability_events: new_event_handle(0),
         }
     }
 
@@ -309,21 +324,21 @@ impl AccountData {
         // TODO: publish some concept of Account
         let coin = Value::struct_(Struct::new(vec![Value::u64(self.balance)]));
         Value::struct_(Struct::new(vec![
+            event_handle_value(&self.account_creation_events),
             Value::byte_array(ByteArray::new(
                 AccountAddress::from_public_key(&self.account.pubkey).to_vec(),
             )),
             coin,
             Value::bool(self.delegated_key_rotation_capability),
             Value::bool(self.delegated_withdrawal_capability),
-            Value::struct_(Struct::new(vec![
-                Value::u64(self.received_events.count()),
-                Value::byte_array(ByteArray::new(self.received_events.key().to_vec())),
-            ])),
-            Value::struct_(Struct::new(vec![
-                Value::u64(self.sent_events.count()),
-                Value::byte_array(ByteArray::new(self.sent_events.key().to_vec())),
-            ])),
+            // is_frozen
+            Value::bool(false),
+            event_handle_value(&self.key_rotation_capability_events),
+            event_handle_value(&self.key_rotation_events),
+            event_handle_value(&self.received_events),
+            event_handle_value(&self.sent_events),
             Value::u64(self.sequence_number),
+            event_handle_value(&self.withdrawal_capability_events),
         ]))
     }
 
@@ -382,4 +397,9 @@ impl AccountData {
     pub fn received_events_count(&self) -> u64 {
         self.received_events.count()
     }
+
+    /// Returns the unique key for this key rotation events stream.
+    pub fn key_rotation_events_key(&self) -> &[u8] {
+        self.key_rotation_events.key().as_bytes()
+    }
 }
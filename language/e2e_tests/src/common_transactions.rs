@@ -4,12 +4,15 @@
 //! Support for encoding transactions for common situations.
 
 use crate::{account::Account, compile::compile_script, gas_costs};
+use crypto::{traits::SigningKey, HashValue};
 use lazy_static::lazy_static;
 use libra_types::{
     account_address::AccountAddress,
+    block_metadata::BlockMetadata,
     byte_array::ByteArray,
     transaction::{SignedTransaction, TransactionArgument},
 };
+use std::collections::BTreeMap;
 use stdlib::transaction_scripts;
 
 lazy_static! {
@@ -105,6 +108,25 @@ pub fn mint_txn(
     )
 }
 
+/// Returns the metadata of a block proposed by `proposer` whose parent was voted for by `voters`.
+pub fn block_metadata(
+    timestamp_usec: u64,
+    proposer: &Account,
+    voters: &[&Account],
+) -> BlockMetadata {
+    let mut votes = BTreeMap::new();
+    for voter in voters {
+        let signature = voter.privkey.sign_message(&HashValue::zero());
+        votes.insert(*voter.address(), signature);
+    }
+    BlockMetadata::new(
+        HashValue::random(),
+        timestamp_usec,
+        votes,
+        *proposer.address(),
+    )
+}
+
 fn create_account() -> Vec<u8> {
     compile_script(transaction_scripts::create_account())
 }
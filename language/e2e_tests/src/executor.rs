@@ -13,7 +13,7 @@ use libra_types::{
     access_path::AccessPath,
     account_config::AccountResource,
     language_storage::ModuleId,
-    transaction::{SignedTransaction, TransactionOutput},
+    transaction::{SignedTransaction, Transaction, TransactionOutput},
     vm_error::VMStatus,
     write_set::WriteSet,
 };
@@ -133,6 +133,19 @@ impl FakeExecutor {
     /// Typical tests will call this method and check that the output matches what was expected.
     /// However, this doesn't apply the results of successful transactions to the data store.
     pub fn execute_block(&self, txn_block: Vec<SignedTransaction>) -> Vec<TransactionOutput> {
+        self.execute_transaction_block(
+            txn_block
+                .into_iter()
+                .map(Transaction::UserTransaction)
+                .collect(),
+        )
+    }
+
+    /// Executes the given block of transactions, which may include block metadata transactions.
+    ///
+    /// Like [`execute_block`](FakeExecutor::execute_block), this doesn't apply the results of
+    /// successful transactions to the data store.
+    pub fn execute_transaction_block(&self, txn_block: Vec<Transaction>) -> Vec<TransactionOutput> {
         MoveVM::execute_block(txn_block, &self.config.vm_config, &self.data_store)
     }
 
@@ -155,3 +168,15 @@ impl FakeExecutor {
         vm.validate_transaction(txn, &self.data_store)
     }
 }
+
+/// Flushes the coverage collected by the VM when the executor goes away, so that it is aggregated
+/// across all the tests run with `MOVE_COVERAGE_FILE` set.
+#[cfg(feature = "coverage")]
+impl Drop for FakeExecutor {
+    fn drop(&mut self) {
+        if let Some(path) = std::env::var_os(vm_runtime::coverage::COVERAGE_FILE_ENV_VAR) {
+            vm_runtime::coverage::flush_coverage(std::path::Path::new(&path))
+                .expect("Failed to flush coverage");
+        }
+    }
+}
@@ -11,12 +11,18 @@
 
 mod account_universe;
 mod arithmetic;
+mod block_metadata;
 mod create_account;
+mod currency;
+mod freeze_account;
 mod function_call;
+mod gas_schedule;
 mod genesis;
 mod mint;
 mod module_publishing;
+mod nonce_transactions;
 mod pack_unpack;
 mod peer_to_peer;
 mod rotate_key;
+mod transaction_fees;
 mod verify_txn;
@@ -0,0 +1,139 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    account::{Account, AccountData},
+    common_transactions::{block_metadata, peer_to_peer_txn},
+    compile::compile_script,
+    executor::{test_all_genesis, FakeExecutor},
+    gas_costs::TXN_RESERVED,
+    transaction_status_eq,
+};
+use libra_types::{
+    transaction::{Transaction, TransactionArgument, TransactionOutput, TransactionStatus},
+    vm_error::{StatusCode, VMStatus},
+};
+
+/// Returns a transaction checking the on-chain height and timestamp of the current block.
+fn check_block_txn(sender: &AccountData, height: u64, timestamp_usec: u64) -> Transaction {
+    let program = compile_script(
+        "
+        import 0x0.Block;
+
+        main(height: u64, timestamp: u64) {
+            assert(Block.get_current_height() == move(height), 77);
+            assert(Block.get_current_timestamp() == move(timestamp), 78);
+            return;
+        }
+        ",
+    );
+    Transaction::UserTransaction(sender.account().create_signed_txn_with_args(
+        program,
+        vec![
+            TransactionArgument::U64(height),
+            TransactionArgument::U64(timestamp_usec),
+        ],
+        sender.sequence_number(),
+        TXN_RESERVED,
+        1,
+    ))
+}
+
+fn assert_executed(output: &TransactionOutput) {
+    assert!(transaction_status_eq(
+        output.status(),
+        &TransactionStatus::Keep(VMStatus::new(StatusCode::EXECUTED))
+    ));
+}
+
+#[test]
+fn block_metadata_updates_block_resource() {
+    test_all_genesis(|mut executor| {
+        let sender = AccountData::new(1_000_000, 10);
+        let proposer = AccountData::new(1_000_000, 10);
+        executor.add_account_data(&sender);
+        executor.add_account_data(&proposer);
+
+        let output = executor.execute_transaction_block(vec![
+            Transaction::BlockMetadata(block_metadata(1_000, proposer.account(), &[])),
+            check_block_txn(&sender, 1, 1_000),
+        ]);
+        assert_eq!(output.len(), 2);
+        assert_executed(&output[0]);
+        // The user transaction sees the block resource updated by the block metadata transaction.
+        assert_executed(&output[1]);
+        // Block metadata transactions are not charged for.
+        assert_eq!(output[0].gas_used(), 0);
+    });
+}
+
+#[test]
+fn block_metadata_rejects_time_going_backwards() {
+    test_all_genesis(|mut executor| {
+        let sender = AccountData::new(1_000_000, 10);
+        let proposer = AccountData::new(1_000_000, 10);
+        executor.add_account_data(&sender);
+        executor.add_account_data(&proposer);
+
+        let output = executor.execute_transaction_block(vec![Transaction::BlockMetadata(
+            block_metadata(1_000, proposer.account(), &[]),
+        )]);
+        assert_executed(&output[0]);
+        executor.apply_write_set(output[0].write_set());
+
+        // The block resource is left unchanged and the transactions of the block still run.
+        let output = executor.execute_transaction_block(vec![
+            Transaction::BlockMetadata(block_metadata(999, proposer.account(), &[])),
+            check_block_txn(&sender, 1, 1_000),
+        ]);
+        assert!(transaction_status_eq(
+            output[0].status(),
+            &TransactionStatus::Discard(VMStatus::new(StatusCode::ABORTED).with_sub_status(98))
+        ));
+        assert_executed(&output[1]);
+    });
+}
+
+#[test]
+fn block_metadata_distributes_fees() {
+    let mut executor = FakeExecutor::from_genesis_file();
+    let sender = AccountData::new(1_000_000, 10);
+    let receiver = AccountData::new(1_000_000, 10);
+    let proposer = AccountData::new(1_000_000, 10);
+    let voter1 = AccountData::new(1_000_000, 10);
+    let voter2 = AccountData::new(1_000_000, 10);
+    for account in &[&sender, &receiver, &proposer, &voter1, &voter2] {
+        executor.add_account_data(account);
+    }
+
+    let output = executor.execute_transaction(peer_to_peer_txn(
+        sender.account(),
+        receiver.account(),
+        10,
+        1_000,
+    ));
+    assert_executed(&output);
+    executor.apply_write_set(output.write_set());
+    let fees = output.gas_used();
+
+    // The fees collected in a block are distributed when the next block starts.
+    let voters = [voter1.account(), voter2.account()];
+    let metadata = block_metadata(1_000, proposer.account(), &voters);
+    let output = executor.execute_transaction_block(vec![Transaction::BlockMetadata(metadata)]);
+    assert_executed(&output[0]);
+    executor.apply_write_set(output[0].write_set());
+
+    let voter_share = fees / 2 / 2;
+    let balance = |account: &Account| {
+        executor
+            .read_account_resource(account)
+            .expect("account must exist")
+            .balance()
+    };
+    assert_eq!(balance(voter1.account()), 1_000_000 + voter_share);
+    assert_eq!(balance(voter2.account()), 1_000_000 + voter_share);
+    assert_eq!(
+        balance(proposer.account()),
+        1_000_000 + fees - 2 * voter_share
+    );
+}
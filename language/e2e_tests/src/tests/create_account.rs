@@ -7,6 +7,7 @@ use crate::{
     executor::test_all_genesis,
 };
 use libra_types::{
+    account_config::CreateAccountEvent,
     transaction::{SignedTransaction, TransactionStatus},
     vm_error::{StatusCode, VMStatus},
 };
@@ -45,6 +46,17 @@ fn create_account() {
         assert_eq!(initial_amount, updated_receiver.balance(),);
         assert_eq!(sender_balance, updated_sender.balance(),);
         assert_eq!(11, updated_sender.sequence_number());
+
+        // check that the creation of the account was recorded on the new account
+        assert_eq!(1, updated_receiver.account_creation_events().count());
+        let creation_event = txn_output
+            .events()
+            .iter()
+            .find(|event| event.key() == updated_receiver.account_creation_events().key())
+            .expect("account creation event must be emitted");
+        let creation_event = CreateAccountEvent::try_from(creation_event.event_data())
+            .expect("event data must parse");
+        assert_eq!(sender.address(), &creation_event.creator());
     });
 }
 
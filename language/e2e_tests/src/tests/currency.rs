@@ -0,0 +1,312 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    account::{Account, AccountData},
+    compile::compile_script,
+    executor::FakeExecutor,
+    gas_costs::TXN_RESERVED,
+    transaction_status_eq,
+};
+use canonical_serialization::SimpleDeserializer;
+use libra_types::{
+    access_path::AccessPath,
+    account_config::{currency_balances_resource_path, CurrencyBalancesResource},
+    byte_array::ByteArray,
+    transaction::{
+        Script, SignedTransaction, TransactionArgument, TransactionOutput, TransactionPayload,
+        TransactionStatus,
+    },
+    vm_error::{StatusCode, VMStatus},
+};
+use transaction_builder::{
+    encode_accept_currency_script, encode_burn_currency_script, encode_currency_transfer_script,
+    encode_mint_currency_script, encode_register_currency_script,
+};
+
+/// Pays `amount` of `currency_code` to `payee` through the sender's withdrawal capability.
+const PAY_CURRENCY_WITH_CAPABILITY: &str = "
+import 0x0.LibraAccount;
+
+main(payee: address, currency_code: bytearray, amount: u64) {
+    let cap: LibraAccount.WithdrawalCapability;
+
+    cap = LibraAccount.extract_sender_withdrawal_capability();
+    LibraAccount.deposit_currency(
+        move(payee),
+        LibraAccount.withdraw_currency_with_capability(&cap, move(currency_code), move(amount))
+    );
+    LibraAccount.restore_withdrawal_capability(move(cap));
+    return;
+}
+";
+
+fn script_txn(sender: &Account, script: Script, seq_num: u64) -> SignedTransaction {
+    sender.create_signed_txn(TransactionPayload::Script(script), seq_num, TXN_RESERVED, 1)
+}
+
+fn assert_executed(output: &TransactionOutput) {
+    assert!(transaction_status_eq(
+        output.status(),
+        &TransactionStatus::Keep(VMStatus::new(StatusCode::EXECUTED))
+    ));
+}
+
+fn assert_aborted(output: &TransactionOutput, code: u64) {
+    assert!(transaction_status_eq(
+        output.status(),
+        &TransactionStatus::Keep(VMStatus::new(StatusCode::ABORTED).with_sub_status(code))
+    ));
+}
+
+fn execute_and_apply(executor: &mut FakeExecutor, txn: SignedTransaction) -> TransactionOutput {
+    let output = executor.execute_transaction(txn);
+    assert_executed(&output);
+    executor.apply_write_set(output.write_set());
+    output
+}
+
+/// Returns the balance of `account` in `currency_code`, or None if it doesn't accept the currency.
+fn currency_balance(
+    executor: &FakeExecutor,
+    account: &Account,
+    currency_code: &str,
+) -> Option<u64> {
+    let path = AccessPath::new(*account.address(), currency_balances_resource_path());
+    executor.read_from_access_path(&path).and_then(|blob| {
+        let balances: CurrencyBalancesResource =
+            SimpleDeserializer::deserialize(&blob).expect("currency balances must deserialize");
+        balances.balance(currency_code.as_bytes())
+    })
+}
+
+#[test]
+fn currency_mint_transfer_and_burn() {
+    // Currencies can only be registered by the association, whose private key is only known for
+    // the genesis from file.
+    let mut executor = FakeExecutor::from_genesis_file();
+    let association = Account::new_association();
+    let sender = AccountData::new(1_000_000, 10);
+    let receiver = AccountData::new(1_000_000, 10);
+    executor.add_account_data(&sender);
+    executor.add_account_data(&receiver);
+    let association_seq_num = executor
+        .read_account_resource(&association)
+        .expect("association must exist")
+        .sequence_number();
+
+    execute_and_apply(
+        &mut executor,
+        script_txn(
+            &association,
+            encode_register_currency_script("USD", association.address()),
+            association_seq_num,
+        ),
+    );
+    execute_and_apply(
+        &mut executor,
+        script_txn(sender.account(), encode_accept_currency_script("USD"), 10),
+    );
+    assert_eq!(
+        currency_balance(&executor, sender.account(), "USD"),
+        Some(0)
+    );
+
+    let output = execute_and_apply(
+        &mut executor,
+        script_txn(
+            &association,
+            encode_mint_currency_script(sender.address(), "USD", 1_000),
+            association_seq_num + 1,
+        ),
+    );
+    // The minter doesn't accept the currency, so only the received event is emitted.
+    assert_eq!(output.events().len(), 1);
+    assert_eq!(
+        currency_balance(&executor, sender.account(), "USD"),
+        Some(1_000)
+    );
+
+    // Coins can't be sent to an account that doesn't accept the currency.
+    let output = executor.execute_transaction(script_txn(
+        sender.account(),
+        encode_currency_transfer_script(receiver.address(), "USD", 400),
+        11,
+    ));
+    assert_aborted(&output, 15);
+
+    execute_and_apply(
+        &mut executor,
+        script_txn(receiver.account(), encode_accept_currency_script("USD"), 10),
+    );
+    let output = execute_and_apply(
+        &mut executor,
+        script_txn(
+            sender.account(),
+            encode_currency_transfer_script(receiver.address(), "USD", 400),
+            11,
+        ),
+    );
+    assert_eq!(output.events().len(), 2);
+    assert_eq!(
+        currency_balance(&executor, sender.account(), "USD"),
+        Some(600)
+    );
+    assert_eq!(
+        currency_balance(&executor, receiver.account(), "USD"),
+        Some(400)
+    );
+
+    // The minter burns the coins it holds.
+    execute_and_apply(
+        &mut executor,
+        script_txn(
+            &association,
+            encode_accept_currency_script("USD"),
+            association_seq_num + 2,
+        ),
+    );
+    execute_and_apply(
+        &mut executor,
+        script_txn(
+            receiver.account(),
+            encode_currency_transfer_script(association.address(), "USD", 400),
+            12,
+        ),
+    );
+    execute_and_apply(
+        &mut executor,
+        script_txn(
+            &association,
+            encode_burn_currency_script("USD", 400),
+            association_seq_num + 3,
+        ),
+    );
+    assert_eq!(currency_balance(&executor, &association, "USD"), Some(0));
+}
+
+#[test]
+fn only_minter_can_mint() {
+    let mut executor = FakeExecutor::from_genesis_file();
+    let association = Account::new_association();
+    let minter = AccountData::new(1_000_000, 10);
+    let other = AccountData::new(1_000_000, 10);
+    executor.add_account_data(&minter);
+    executor.add_account_data(&other);
+    let association_seq_num = executor
+        .read_account_resource(&association)
+        .expect("association must exist")
+        .sequence_number();
+
+    // Only the association can register currencies.
+    let output = executor.execute_transaction(script_txn(
+        minter.account(),
+        encode_register_currency_script("USD", minter.address()),
+        10,
+    ));
+    assert_aborted(&output, 1);
+
+    execute_and_apply(
+        &mut executor,
+        script_txn(
+            &association,
+            encode_register_currency_script("USD", minter.address()),
+            association_seq_num,
+        ),
+    );
+    execute_and_apply(
+        &mut executor,
+        script_txn(other.account(), encode_accept_currency_script("USD"), 10),
+    );
+
+    let output = executor.execute_transaction(script_txn(
+        other.account(),
+        encode_mint_currency_script(other.address(), "USD", 1_000),
+        11,
+    ));
+    assert_aborted(&output, 3);
+
+    // Unknown currencies can't be minted.
+    let output = executor.execute_transaction(script_txn(
+        minter.account(),
+        encode_mint_currency_script(other.address(), "EUR", 1_000),
+        10,
+    ));
+    assert_aborted(&output, 4);
+
+    execute_and_apply(
+        &mut executor,
+        script_txn(
+            minter.account(),
+            encode_mint_currency_script(other.address(), "USD", 1_000),
+            10,
+        ),
+    );
+    assert_eq!(
+        currency_balance(&executor, other.account(), "USD"),
+        Some(1_000)
+    );
+}
+
+#[test]
+fn currency_withdraw_with_capability() {
+    let mut executor = FakeExecutor::from_genesis_file();
+    let association = Account::new_association();
+    let sender = AccountData::new(1_000_000, 10);
+    let receiver = AccountData::new(1_000_000, 10);
+    executor.add_account_data(&sender);
+    executor.add_account_data(&receiver);
+    let association_seq_num = executor
+        .read_account_resource(&association)
+        .expect("association must exist")
+        .sequence_number();
+
+    execute_and_apply(
+        &mut executor,
+        script_txn(
+            &association,
+            encode_register_currency_script("USD", association.address()),
+            association_seq_num,
+        ),
+    );
+    execute_and_apply(
+        &mut executor,
+        script_txn(sender.account(), encode_accept_currency_script("USD"), 10),
+    );
+    execute_and_apply(
+        &mut executor,
+        script_txn(receiver.account(), encode_accept_currency_script("USD"), 10),
+    );
+    execute_and_apply(
+        &mut executor,
+        script_txn(
+            &association,
+            encode_mint_currency_script(sender.address(), "USD", 1_000),
+            association_seq_num + 1,
+        ),
+    );
+
+    let args = vec![
+        TransactionArgument::Address(*receiver.address()),
+        TransactionArgument::ByteArray(ByteArray::new(b"USD".to_vec())),
+        TransactionArgument::U64(300),
+    ];
+    execute_and_apply(
+        &mut executor,
+        sender.account().create_signed_txn_with_args(
+            compile_script(PAY_CURRENCY_WITH_CAPABILITY),
+            args,
+            11,
+            TXN_RESERVED,
+            1,
+        ),
+    );
+    assert_eq!(
+        currency_balance(&executor, sender.account(), "USD"),
+        Some(700)
+    );
+    assert_eq!(
+        currency_balance(&executor, receiver.account(), "USD"),
+        Some(300)
+    );
+}
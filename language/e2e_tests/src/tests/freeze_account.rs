@@ -0,0 +1,116 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    account::{Account, AccountData},
+    assert_prologue_parity, assert_status_eq,
+    common_transactions::peer_to_peer_txn,
+    executor::FakeExecutor,
+    gas_costs::TXN_RESERVED,
+    transaction_status_eq,
+};
+use libra_types::{
+    transaction::{
+        Script, SignedTransaction, TransactionOutput, TransactionPayload, TransactionStatus,
+    },
+    vm_error::{StatusCode, VMStatus},
+};
+use transaction_builder::{encode_freeze_account_script, encode_unfreeze_account_script};
+
+fn script_txn(sender: &Account, script: Script, seq_num: u64) -> SignedTransaction {
+    sender.create_signed_txn(TransactionPayload::Script(script), seq_num, TXN_RESERVED, 1)
+}
+
+fn assert_executed(output: &TransactionOutput) {
+    assert!(transaction_status_eq(
+        output.status(),
+        &TransactionStatus::Keep(VMStatus::new(StatusCode::EXECUTED))
+    ));
+}
+
+fn assert_aborted(output: &TransactionOutput, code: u64) {
+    assert!(transaction_status_eq(
+        output.status(),
+        &TransactionStatus::Keep(VMStatus::new(StatusCode::ABORTED).with_sub_status(code))
+    ));
+}
+
+#[test]
+fn frozen_account_cannot_send_or_receive() {
+    // Accounts can only be frozen by the association, whose private key is only known for the
+    // genesis from file.
+    let mut executor = FakeExecutor::from_genesis_file();
+    let association = Account::new_association();
+    let frozen = AccountData::new(1_000_000, 10);
+    let other = AccountData::new(1_000_000, 10);
+    executor.add_account_data(&frozen);
+    executor.add_account_data(&other);
+    let association_seq_num = executor
+        .read_account_resource(&association)
+        .expect("association must exist")
+        .sequence_number();
+
+    let output = executor.execute_transaction(script_txn(
+        &association,
+        encode_freeze_account_script(frozen.address()),
+        association_seq_num,
+    ));
+    assert_executed(&output);
+    assert_eq!(output.events().len(), 1);
+    executor.apply_write_set(output.write_set());
+    assert!(executor
+        .read_account_resource(frozen.account())
+        .expect("frozen account must exist")
+        .is_frozen());
+
+    // The frozen account can't send transactions.
+    let txn = peer_to_peer_txn(frozen.account(), other.account(), 10, 1_000);
+    assert_prologue_parity!(
+        executor.verify_transaction(txn.clone()),
+        executor.execute_transaction(txn).status(),
+        VMStatus::new(StatusCode::SENDING_ACCOUNT_FROZEN)
+    );
+
+    // Coins can't be deposited into the frozen account.
+    let output = executor.execute_transaction(peer_to_peer_txn(
+        other.account(),
+        frozen.account(),
+        10,
+        1_000,
+    ));
+    assert_aborted(&output, 13);
+
+    // Once unfrozen, the account can receive coins again.
+    let output = executor.execute_transaction(script_txn(
+        &association,
+        encode_unfreeze_account_script(frozen.address()),
+        association_seq_num + 1,
+    ));
+    assert_executed(&output);
+    assert_eq!(output.events().len(), 1);
+    executor.apply_write_set(output.write_set());
+
+    let output = executor.execute_transaction(peer_to_peer_txn(
+        other.account(),
+        frozen.account(),
+        10,
+        1_000,
+    ));
+    assert_executed(&output);
+}
+
+#[test]
+fn only_association_can_freeze() {
+    let mut executor = FakeExecutor::from_genesis_file();
+    let sender = AccountData::new(1_000_000, 10);
+    let target = AccountData::new(1_000_000, 10);
+    executor.add_account_data(&sender);
+    executor.add_account_data(&target);
+
+    let output = executor.execute_transaction(script_txn(
+        sender.account(),
+        encode_freeze_account_script(target.address()),
+        10,
+    ));
+    assert_aborted(&output, 12);
+}
@@ -0,0 +1,90 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    account::{Account, AccountData},
+    common_transactions::peer_to_peer_txn,
+    executor::FakeExecutor,
+    gas_costs::TXN_RESERVED,
+    transaction_status_eq,
+};
+use libra_types::{
+    byte_array::ByteArray,
+    transaction::{
+        Script, SignedTransaction, TransactionArgument, TransactionOutput, TransactionPayload,
+        TransactionStatus,
+    },
+    vm_error::{StatusCode, VMStatus},
+};
+use transaction_builder::encode_update_gas_schedule_script;
+use vm::gas_schedule::{GasAlgebra, GasUnits, DEFAULT_GAS_SCHEDULE};
+
+fn script_txn(sender: &Account, script: Script, seq_num: u64) -> SignedTransaction {
+    sender.create_signed_txn(TransactionPayload::Script(script), seq_num, TXN_RESERVED, 1)
+}
+
+fn assert_status(output: &TransactionOutput, status: VMStatus) {
+    assert!(transaction_status_eq(
+        output.status(),
+        &TransactionStatus::Keep(status)
+    ));
+}
+
+#[test]
+fn update_gas_schedule() {
+    // The gas schedule can only be updated by the association, whose private key is only known
+    // for the genesis from file.
+    let mut executor = FakeExecutor::from_genesis_file();
+    let association = Account::new_association();
+    let sender = AccountData::new(1_000_000, 10);
+    let receiver = AccountData::new(1_000_000, 10);
+    executor.add_account_data(&sender);
+    executor.add_account_data(&receiver);
+    let association_seq_num = executor
+        .read_account_resource(&association)
+        .expect("association must exist")
+        .sequence_number();
+
+    let transfer = peer_to_peer_txn(sender.account(), receiver.account(), 10, 1_000);
+    let default_gas_used = executor.execute_transaction(transfer.clone()).gas_used();
+
+    let mut schedule = DEFAULT_GAS_SCHEDULE.clone();
+    for cost in schedule.compute_table.values_mut() {
+        *cost = GasUnits::new(cost.get() * 2);
+    }
+    let output = executor.execute_transaction(script_txn(
+        &association,
+        encode_update_gas_schedule_script(&schedule),
+        association_seq_num,
+    ));
+    assert_status(&output, VMStatus::new(StatusCode::EXECUTED));
+    executor.apply_write_set(output.write_set());
+
+    // The new table is charged from the next block on.
+    assert!(executor.execute_transaction(transfer).gas_used() > default_gas_used);
+}
+
+#[test]
+fn reject_undecodable_gas_schedule() {
+    let executor = FakeExecutor::from_genesis_file();
+    let association = Account::new_association();
+    let association_seq_num = executor
+        .read_account_resource(&association)
+        .expect("association must exist")
+        .sequence_number();
+
+    // A table the VM cannot decode is rejected rather than published.
+    let code = encode_update_gas_schedule_script(&DEFAULT_GAS_SCHEDULE)
+        .code()
+        .to_vec();
+    let script = Script::new(
+        code,
+        vec![TransactionArgument::ByteArray(ByteArray::new(vec![0xff]))],
+    );
+    let output =
+        executor.execute_transaction(script_txn(&association, script, association_seq_num));
+    assert_status(
+        &output,
+        VMStatus::new(StatusCode::ABORTED).with_sub_status(2),
+    );
+}
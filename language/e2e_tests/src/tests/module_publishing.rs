@@ -5,11 +5,14 @@ use crate::{
     account::AccountData, assert_prologue_parity, assert_status_eq,
     compile::compile_module_with_address, executor::FakeExecutor, transaction_status_eq,
 };
+use bytecode_verifier::VerifiedModule;
+use compiler::Compiler;
 use config::config::VMPublishingOption;
 use libra_types::{
-    transaction::TransactionStatus,
+    transaction::{Module, Script, TransactionPayload, TransactionStatus},
     vm_error::{StatusCode, StatusType, VMStatus},
 };
+use vm::file_format::CompiledModule;
 
 // A module with an address different from the sender's address should be rejected
 #[test]
@@ -59,9 +62,9 @@ fn bad_module_address() {
     assert!(status.major_status == StatusCode::MODULE_ADDRESS_DOES_NOT_MATCH_SENDER);
 }
 
-// Publishing a module named M under the same address twice should be rejected
+// Publishing an unchanged module named M under the same address again is accepted
 #[test]
-fn duplicate_module() {
+fn republish_unchanged_module() {
     let mut executor = FakeExecutor::from_genesis_with_options(VMPublishingOption::Open);
 
     let sequence_number = 2;
@@ -95,18 +98,175 @@ fn duplicate_module() {
 
     let output1 = executor.execute_transaction(txn1);
     executor.apply_write_set(output1.write_set());
-    // first tx should succeed
     assert!(transaction_status_eq(
         &output1.status(),
         &TransactionStatus::Keep(VMStatus::new(StatusCode::EXECUTED)),
     ));
 
-    // second one should fail because it tries to re-publish a module named M
     let output2 = executor.execute_transaction(txn2);
     assert!(transaction_status_eq(
         &output2.status(),
-        &TransactionStatus::Keep(VMStatus::new(StatusCode::DUPLICATE_MODULE_NAME)),
+        &TransactionStatus::Keep(VMStatus::new(StatusCode::EXECUTED)),
+    ));
+}
+
+// A new version of a module that removes a public function should be rejected
+#[test]
+fn incompatible_module_update() {
+    let mut executor = FakeExecutor::from_genesis_with_options(VMPublishingOption::Open);
+
+    let account = AccountData::new(1_000_000, 10);
+    executor.add_account_data(&account);
+
+    let old_module = compile_module_with_address(
+        account.address(),
+        "
+        module M {
+            public f(): u64 {
+                return 1;
+            }
+        }
+        ",
+    );
+    let new_module = compile_module_with_address(
+        account.address(),
+        "
+        module M {
+            public g(): u64 {
+                return 2;
+            }
+        }
+        ",
+    );
+
+    let txn1 =
+        account
+            .account()
+            .create_signed_txn_impl(*account.address(), old_module, 10, 100_000, 1);
+    let output1 = executor.execute_transaction(txn1);
+    executor.apply_write_set(output1.write_set());
+    assert!(transaction_status_eq(
+        &output1.status(),
+        &TransactionStatus::Keep(VMStatus::new(StatusCode::EXECUTED)),
+    ));
+
+    let txn2 =
+        account
+            .account()
+            .create_signed_txn_impl(*account.address(), new_module, 11, 100_000, 1);
+    let output2 = executor.execute_transaction(txn2);
+    assert!(transaction_status_eq(
+        &output2.status(),
+        &TransactionStatus::Keep(VMStatus::new(StatusCode::INCOMPATIBLE_MODULE_UPDATE)),
+    ));
+}
+
+// A compatible new version of a module replaces the old one, including for the transactions
+// following it in the same block
+#[test]
+fn compatible_module_update() {
+    let mut executor = FakeExecutor::from_genesis_with_options(VMPublishingOption::Open);
+
+    let account = AccountData::new(1_000_000, 10);
+    executor.add_account_data(&account);
+
+    let compile_module = |code: &str| {
+        Compiler {
+            address: *account.address(),
+            ..Compiler::default()
+        }
+        .into_compiled_module(code)
+        .expect("Failed to compile")
+    };
+    let old_module = compile_module(
+        "
+        module M {
+            public f(): u64 {
+                return 1;
+            }
+        }
+        ",
+    );
+    let new_module = compile_module(
+        "
+        module M {
+            public f(): u64 {
+                return 2;
+            }
+
+            public g(): u64 {
+                return 3;
+            }
+        }
+        ",
+    );
+    let module_payload = |module: &CompiledModule| {
+        let mut blob = vec![];
+        module
+            .serialize(&mut blob)
+            .expect("Failed to serialize module");
+        TransactionPayload::Module(Module::new(blob))
+    };
+    let check_f = |expected: u64| {
+        let code = format!(
+            "
+            import Transaction.M;
+
+            main() {{
+                let x: u64;
+                x = M.f();
+                assert(move(x) == {}, 42);
+                return;
+            }}
+            ",
+            expected
+        );
+        let compiler = Compiler {
+            address: *account.address(),
+            extra_deps: vec![VerifiedModule::bypass_verifier_DANGEROUS_FOR_TESTING_ONLY(
+                old_module.clone(),
+            )],
+            ..Compiler::default()
+        };
+        let script = compiler.into_script_blob(&code).expect("Failed to compile");
+        TransactionPayload::Script(Script::new(script, vec![]))
+    };
+
+    let txn = account.account().create_signed_txn_impl(
+        *account.address(),
+        module_payload(&old_module),
+        10,
+        100_000,
+        1,
+    );
+    let output = executor.execute_transaction(txn);
+    executor.apply_write_set(output.write_set());
+    assert!(transaction_status_eq(
+        &output.status(),
+        &TransactionStatus::Keep(VMStatus::new(StatusCode::EXECUTED)),
     ));
+
+    // The first transaction loads the old version into the cache of the block.
+    let payloads = vec![check_f(1), module_payload(&new_module), check_f(2)];
+    let txns = payloads
+        .into_iter()
+        .zip(11..)
+        .map(|(payload, sequence_number)| {
+            account.account().create_signed_txn_impl(
+                *account.address(),
+                payload,
+                sequence_number,
+                100_000,
+                1,
+            )
+        })
+        .collect();
+    for output in executor.execute_block(txns) {
+        assert!(transaction_status_eq(
+            &output.status(),
+            &TransactionStatus::Keep(VMStatus::new(StatusCode::EXECUTED)),
+        ));
+    }
 }
 
 #[test]
@@ -0,0 +1,136 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    account::{Account, AccountData},
+    assert_prologue_parity, assert_status_eq,
+    executor::{test_all_genesis, FakeExecutor},
+    gas_costs::TXN_RESERVED,
+    transaction_status_eq,
+};
+use libra_types::{
+    transaction::{
+        sequence_number_from_nonce, SignedTransaction, TransactionPayload, TransactionStatus,
+    },
+    vm_error::{StatusCode, VMStatus},
+};
+use transaction_builder::{encode_enable_nonces_script, encode_transfer_script};
+
+fn enable_nonces_txn(sender: &Account, seq_num: u64) -> SignedTransaction {
+    sender.create_signed_txn(
+        TransactionPayload::Script(encode_enable_nonces_script()),
+        seq_num,
+        TXN_RESERVED,
+        1,
+    )
+}
+
+fn transfer_with_nonce_txn(
+    sender: &Account,
+    receiver: &Account,
+    nonce: u64,
+    amount: u64,
+) -> SignedTransaction {
+    sender.create_signed_txn(
+        TransactionPayload::Script(encode_transfer_script(receiver.address(), amount)),
+        sequence_number_from_nonce(nonce),
+        TXN_RESERVED,
+        1,
+    )
+}
+
+fn execute_and_apply(executor: &mut FakeExecutor, txn: SignedTransaction) {
+    let output = executor.execute_transaction(txn);
+    assert!(transaction_status_eq(
+        output.status(),
+        &TransactionStatus::Keep(VMStatus::new(StatusCode::EXECUTED))
+    ));
+    executor.apply_write_set(output.write_set());
+}
+
+#[test]
+fn nonce_transactions_require_enabling() {
+    test_all_genesis(|mut executor| {
+        let sender = AccountData::new(1_000_000, 10);
+        let receiver = AccountData::new(1_000_000, 10);
+        executor.add_account_data(&sender);
+        executor.add_account_data(&receiver);
+
+        let txn = transfer_with_nonce_txn(sender.account(), receiver.account(), 0, 1_000);
+        assert_prologue_parity!(
+            executor.verify_transaction(txn.clone()),
+            executor.execute_transaction(txn).status(),
+            VMStatus::new(StatusCode::NONCES_NOT_ENABLED)
+        );
+    });
+}
+
+#[test]
+fn nonce_transactions_run_in_any_order() {
+    test_all_genesis(|mut executor| {
+        let sender = AccountData::new(1_000_000, 10);
+        let receiver = AccountData::new(1_000_000, 10);
+        executor.add_account_data(&sender);
+        executor.add_account_data(&receiver);
+        execute_and_apply(&mut executor, enable_nonces_txn(sender.account(), 10));
+
+        execute_and_apply(
+            &mut executor,
+            transfer_with_nonce_txn(sender.account(), receiver.account(), 7, 1_000),
+        );
+        execute_and_apply(
+            &mut executor,
+            transfer_with_nonce_txn(sender.account(), receiver.account(), 3, 2_000),
+        );
+
+        // Nonce transactions don't use up sequence numbers.
+        let sender_resource = executor
+            .read_account_resource(sender.account())
+            .expect("sender must exist");
+        assert_eq!(sender_resource.sequence_number(), 11);
+        let receiver_resource = executor
+            .read_account_resource(receiver.account())
+            .expect("receiver must exist");
+        assert_eq!(receiver_resource.balance(), 1_000_000 + 3_000);
+
+        // A nonce can't be used twice.
+        let txn = transfer_with_nonce_txn(sender.account(), receiver.account(), 7, 1_000);
+        assert_prologue_parity!(
+            executor.verify_transaction(txn.clone()),
+            executor.execute_transaction(txn).status(),
+            VMStatus::new(StatusCode::NONCE_ALREADY_USED)
+        );
+    });
+}
+
+#[test]
+fn old_nonces_are_dropped() {
+    test_all_genesis(|mut executor| {
+        let sender = AccountData::new(100_000_000, 10);
+        let receiver = AccountData::new(1_000_000, 10);
+        executor.add_account_data(&sender);
+        executor.add_account_data(&receiver);
+        execute_and_apply(&mut executor, enable_nonces_txn(sender.account(), 10));
+
+        // Only the last 128 used nonces are stored: once more are used, the smallest ones can't be
+        // used anymore, even if they are below other unused nonces.
+        for nonce in 1..=129 {
+            execute_and_apply(
+                &mut executor,
+                transfer_with_nonce_txn(sender.account(), receiver.account(), nonce * 2, 1),
+            );
+        }
+        for nonce in &[0, 1, 2] {
+            let txn = transfer_with_nonce_txn(sender.account(), receiver.account(), *nonce, 1);
+            assert_prologue_parity!(
+                executor.verify_transaction(txn.clone()),
+                executor.execute_transaction(txn).status(),
+                VMStatus::new(StatusCode::NONCE_ALREADY_USED)
+            );
+        }
+        execute_and_apply(
+            &mut executor,
+            transfer_with_nonce_txn(sender.account(), receiver.account(), 3, 1),
+        );
+    });
+}
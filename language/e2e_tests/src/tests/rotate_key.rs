@@ -3,15 +3,26 @@
 
 use crate::{
     account::{Account, AccountData},
-    common_transactions::{create_account_txn, rotate_key_txn},
+    common_transactions::{create_account_txn, rotate_key_txn, CREATE_ACCOUNT},
     executor::test_all_genesis,
+    gas_costs,
+};
+use crypto::{
+    ed25519::compat,
+    hash::CryptoHash,
+    multi_ed25519::{MultiEd25519PublicKey, MultiEd25519Signature},
+    traits::SigningKey,
 };
-use crypto::ed25519::compat;
 use libra_types::{
     account_address::AccountAddress,
-    transaction::TransactionStatus,
+    account_config::KeyRotationEvent,
+    transaction::{
+        RawTransaction, Script, SignedTransaction, TransactionArgument, TransactionStatus,
+    },
     vm_error::{StatusCode, VMStatus},
 };
+use rand::{rngs::StdRng, SeedableRng};
+use std::time::Duration;
 
 #[test]
 fn rotate_key() {
@@ -45,6 +56,19 @@ fn rotate_key() {
         assert_eq!(balance, updated_sender.balance());
         assert_eq!(11, updated_sender.sequence_number());
 
+        // Check that the rotation was recorded.
+        assert_eq!(1, updated_sender.key_rotation_events().count());
+        assert_eq!(1, output.events().len());
+        let event = &output.events()[0];
+        assert_eq!(sender.key_rotation_events_key(), event.key().as_bytes());
+        let rotation_event =
+            KeyRotationEvent::try_from(event.event_data()).expect("event data must parse");
+        assert_eq!(
+            new_key_hash.as_ref(),
+            rotation_event.new_authentication_key().as_bytes(),
+        );
+        assert_eq!(sender.address(), &rotation_event.rotated_by());
+
         // Check that transactions cannot be sent with the old key any more.
         let new_account = Account::new();
         let old_key_txn = create_account_txn(sender.account(), &new_account, 11, 100_000);
@@ -64,3 +88,83 @@ fn rotate_key() {
         );
     });
 }
+
+#[test]
+fn rotate_key_to_multisig() {
+    test_all_genesis(|mut executor| {
+        let sender = AccountData::new(1_000_000, 10);
+        executor.add_account_data(&sender);
+
+        // A 2-of-3 key.
+        let mut rng = StdRng::from_seed([9u8; 32]);
+        let keypairs: Vec<_> = (0..3).map(|_| compat::generate_keypair(&mut rng)).collect();
+        let public_key = MultiEd25519PublicKey::new(
+            keypairs
+                .iter()
+                .map(|(_, public_key)| public_key.clone())
+                .collect(),
+            2,
+        )
+        .unwrap();
+        let txn = rotate_key_txn(
+            sender.account(),
+            AccountAddress::from_public_key(&public_key),
+            10,
+        );
+        let output = &executor.execute_block(vec![txn])[0];
+        assert_eq!(
+            output.status(),
+            &TransactionStatus::Keep(VMStatus::new(StatusCode::EXECUTED)),
+        );
+        executor.apply_write_set(output.write_set());
+
+        let new_account = Account::new();
+        let multisig_txn = |signers: &[u8], public_key: &MultiEd25519PublicKey| {
+            let raw_txn = RawTransaction::new_script(
+                *sender.address(),
+                11,
+                Script::new(
+                    CREATE_ACCOUNT.clone(),
+                    vec![
+                        TransactionArgument::Address(*new_account.address()),
+                        TransactionArgument::U64(100_000),
+                    ],
+                ),
+                gas_costs::TXN_RESERVED,
+                1,
+                Duration::from_secs(u64::max_value()),
+            );
+            let signatures = signers
+                .iter()
+                .map(|i| (keypairs[*i as usize].0.sign_message(&raw_txn.hash()), *i))
+                .collect();
+            SignedTransaction::new_multisig(
+                raw_txn,
+                public_key.clone(),
+                MultiEd25519Signature::new(signatures).unwrap(),
+            )
+        };
+
+        // A single officer cannot sign for the account.
+        let output = &executor.execute_block(vec![multisig_txn(&[1], &public_key)])[0];
+        assert_eq!(
+            output.status(),
+            &TransactionStatus::Discard(VMStatus::new(StatusCode::INVALID_SIGNATURE)),
+        );
+
+        // The same officers cannot sign with a weaker key.
+        let weaker_key = MultiEd25519PublicKey::new(public_key.public_keys().to_vec(), 1).unwrap();
+        let output = &executor.execute_block(vec![multisig_txn(&[1], &weaker_key)])[0];
+        assert_eq!(
+            output.status(),
+            &TransactionStatus::Discard(VMStatus::new(StatusCode::INVALID_AUTH_KEY)),
+        );
+
+        // Any two officers can.
+        let output = &executor.execute_block(vec![multisig_txn(&[2, 0], &public_key)])[0];
+        assert_eq!(
+            output.status(),
+            &TransactionStatus::Keep(VMStatus::new(StatusCode::EXECUTED)),
+        );
+    });
+}
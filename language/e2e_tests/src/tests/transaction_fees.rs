@@ -0,0 +1,143 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    account::{Account, AccountData},
+    common_transactions::{block_metadata, peer_to_peer_txn},
+    compile::compile_script,
+    executor::FakeExecutor,
+    gas_costs::TXN_RESERVED,
+    transaction_status_eq,
+};
+use libra_types::{
+    transaction::{SignedTransaction, Transaction, TransactionStatus},
+    vm_error::{StatusCode, VMStatus},
+};
+
+/// Tries to start a new block from a transaction script instead of a block metadata transaction.
+const START_BLOCK: &str = "
+import 0x0.Block;
+import 0x0.Vector;
+
+main() {
+    Block.prologue(
+        Block.get_current_height() + 1,
+        Block.get_current_timestamp(),
+        get_txn_sender(),
+        Vector.empty<address>()
+    );
+    return;
+}
+";
+
+#[test]
+fn fees_skip_addresses_without_accounts() {
+    let mut executor = FakeExecutor::from_genesis_file();
+
+    let sender = AccountData::new(1_000_000, 10);
+    let receiver = AccountData::new(1_000_000, 10);
+    let proposer = AccountData::new(1_000_000, 10);
+    let voter = AccountData::new(1_000_000, 10);
+    for account in &[&sender, &receiver, &proposer, &voter] {
+        executor.add_account_data(account);
+    }
+    // Neither of these addresses holds an account.
+    let missing_voter = Account::new();
+    let missing_proposer = Account::new();
+
+    let balance = |executor: &FakeExecutor, account: &Account| {
+        executor
+            .read_account_resource(account)
+            .expect("account must exist")
+            .balance()
+    };
+    // Runs `txn` and returns the fees it adds to the pool.
+    let execute = |executor: &mut FakeExecutor, txn: SignedTransaction| {
+        let output = executor.execute_transaction(txn);
+        assert_eq!(
+            output.status(),
+            &TransactionStatus::Keep(VMStatus::new(StatusCode::EXECUTED))
+        );
+        executor.apply_write_set(output.write_set());
+        output.gas_used()
+    };
+    let start_block = |executor: &mut FakeExecutor, proposer: &Account, voters: &[&Account]| {
+        let metadata = block_metadata(0, proposer, voters);
+        let output = executor.execute_transaction_block(vec![Transaction::BlockMetadata(metadata)]);
+        assert_eq!(
+            output[0].status(),
+            &TransactionStatus::Keep(VMStatus::new(StatusCode::EXECUTED))
+        );
+        executor.apply_write_set(output[0].write_set());
+    };
+
+    // The share of a voter without an account goes to the proposer.
+    let fees = execute(
+        &mut executor,
+        peer_to_peer_txn(sender.account(), receiver.account(), 10, 1_000),
+    );
+    start_block(
+        &mut executor,
+        proposer.account(),
+        &[voter.account(), &missing_voter],
+    );
+    let voter_share = fees / 2 / 2;
+    let mut voter_balance = 1_000_000 + voter_share;
+    let proposer_balance = 1_000_000 + fees - voter_share;
+    assert_eq!(balance(&executor, voter.account()), voter_balance);
+    assert_eq!(balance(&executor, proposer.account()), proposer_balance);
+
+    // If the proposer has no account, the voters are still paid and the rest of the fees is kept
+    // for the next block.
+    let mut pool = execute(
+        &mut executor,
+        peer_to_peer_txn(sender.account(), receiver.account(), 11, 1_000),
+    );
+    start_block(&mut executor, &missing_proposer, &[voter.account()]);
+    let voter_share = pool / 2;
+    pool -= voter_share;
+    voter_balance += voter_share;
+    assert_eq!(balance(&executor, voter.account()), voter_balance);
+    assert_eq!(balance(&executor, proposer.account()), proposer_balance);
+
+    // The kept fees all go to the next proposer when none of the voters has an account.
+    start_block(&mut executor, proposer.account(), &[&missing_voter]);
+    assert_eq!(balance(&executor, voter.account()), voter_balance);
+    assert_eq!(
+        balance(&executor, proposer.account()),
+        proposer_balance + pool
+    );
+}
+
+#[test]
+fn transactions_cannot_run_block_prologue() {
+    // The association can't start a block or take the fees from a transaction script either.
+    let mut executor = FakeExecutor::from_genesis_file();
+    let association = Account::new_association();
+    let sender = AccountData::new(1_000_000, 10);
+    executor.add_account_data(&sender);
+
+    let txns = vec![
+        association.create_signed_txn_with_args(
+            compile_script(START_BLOCK),
+            vec![],
+            1,
+            TXN_RESERVED,
+            1,
+        ),
+        sender.account().create_signed_txn_with_args(
+            compile_script(START_BLOCK),
+            vec![],
+            10,
+            TXN_RESERVED,
+            1,
+        ),
+    ];
+    for txn in txns {
+        let output = executor.execute_transaction(txn);
+        assert!(transaction_status_eq(
+            output.status(),
+            &TransactionStatus::Discard(VMStatus::new(StatusCode::VISIBILITY_MISMATCH))
+        ));
+    }
+}
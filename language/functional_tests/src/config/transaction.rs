@@ -3,7 +3,7 @@
 
 use crate::{config::global::Config as GlobalConfig, errors::*, evaluator::Stage};
 use libra_types::transaction::{parse_as_transaction_argument, TransactionArgument};
-use std::{collections::BTreeSet, str::FromStr};
+use std::{collections::BTreeSet, fmt, str::FromStr};
 
 /// A partially parsed transaction argument.
 #[derive(Debug)]
@@ -26,12 +26,107 @@ impl FromStr for Argument {
     }
 }
 
+/// The kind of payment event emitted to an account.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum EventKind {
+    Sent,
+    Received,
+}
+
+impl FromStr for EventKind {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "sent" => Ok(EventKind::Sent),
+            "received" => Ok(EventKind::Received),
+            _ => Err(ErrorKind::Other(format!("unrecognized event kind '{}'", s)).into()),
+        }
+    }
+}
+
+/// A property of the ledger that must hold once the transaction has been executed successfully.
+#[derive(Debug, Clone)]
+pub enum Expectation {
+    /// The exact number of gas units charged for the transaction.
+    GasUsed(u64),
+    /// The balance of the given account.
+    Balance { account: String, value: u64 },
+    /// The sequence number of the given account.
+    SequenceNumber { account: String, value: u64 },
+    /// A payment event emitted to the sent or received event stream of the given account.
+    Event {
+        account: String,
+        kind: EventKind,
+        amount: u64,
+        counterparty: String,
+    },
+}
+
+impl Expectation {
+    /// Returns the names of the accounts this expectation refers to.
+    fn accounts(&self) -> Vec<&str> {
+        match self {
+            Expectation::GasUsed(_) => vec![],
+            Expectation::Balance { account, .. } | Expectation::SequenceNumber { account, .. } => {
+                vec![account.as_str()]
+            }
+            Expectation::Event {
+                account,
+                counterparty,
+                ..
+            } => vec![account.as_str(), counterparty.as_str()],
+        }
+    }
+}
+
+impl fmt::Display for Expectation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expectation::GasUsed(value) => write!(f, "gas used == {}", value),
+            Expectation::Balance { account, value } => {
+                write!(f, "balance of '{}' == {}", account, value)
+            }
+            Expectation::SequenceNumber { account, value } => {
+                write!(f, "sequence number of '{}' == {}", account, value)
+            }
+            Expectation::Event {
+                account,
+                kind,
+                amount,
+                counterparty,
+            } => write!(
+                f,
+                "'{}' has a {:?} event of {} with '{}'",
+                account, kind, amount, counterparty
+            ),
+        }
+    }
+}
+
 /// A raw entry extracted from the input. Used to build a transaction config table.
 #[derive(Debug)]
 pub enum Entry {
     DisableStages(Vec<Stage>),
     Sender(String),
     Arguments(Vec<Argument>),
+    BlockHeight(u64),
+    BlockTime(u64),
+    Expectation(Expectation),
+}
+
+/// Parses a comma separated list of exactly `n` parameters.
+fn split_params<'a>(s: &'a str, n: usize, entry: &str) -> Result<Vec<&'a str>> {
+    let v: Vec<_> = s.split(',').map(|s| s.trim()).collect();
+    if v.len() != n || v.iter().any(|s| s.is_empty()) {
+        return Err(ErrorKind::Other(format!("config '{}' takes {} parameters", entry, n)).into());
+    }
+    Ok(v)
+}
+
+fn parse_u64(s: &str) -> Result<u64> {
+    s.parse::<u64>()
+        .map_err(|_| ErrorKind::Other(format!("failed to parse '{}' as u64", s)).into())
 }
 
 impl FromStr for Entry {
@@ -70,6 +165,40 @@ impl FromStr for Entry {
                 .collect();
             return Ok(Entry::DisableStages(res?));
         }
+        if s.starts_with("block-height:") {
+            return Ok(Entry::BlockHeight(parse_u64(&s[13..])?));
+        }
+        if s.starts_with("block-time:") {
+            return Ok(Entry::BlockTime(parse_u64(&s[11..])?));
+        }
+        if s.starts_with("expect-gas-used:") {
+            return Ok(Entry::Expectation(Expectation::GasUsed(parse_u64(
+                &s[16..],
+            )?)));
+        }
+        if s.starts_with("expect-balance:") {
+            let v = split_params(&s[15..], 2, "expect-balance")?;
+            return Ok(Entry::Expectation(Expectation::Balance {
+                account: v[0].to_ascii_lowercase(),
+                value: parse_u64(v[1])?,
+            }));
+        }
+        if s.starts_with("expect-sequence-number:") {
+            let v = split_params(&s[23..], 2, "expect-sequence-number")?;
+            return Ok(Entry::Expectation(Expectation::SequenceNumber {
+                account: v[0].to_ascii_lowercase(),
+                value: parse_u64(v[1])?,
+            }));
+        }
+        if s.starts_with("expect-event:") {
+            let v = split_params(&s[13..], 4, "expect-event")?;
+            return Ok(Entry::Expectation(Expectation::Event {
+                account: v[0].to_ascii_lowercase(),
+                kind: v[1].parse::<EventKind>()?,
+                amount: parse_u64(v[2])?,
+                counterparty: v[3].to_ascii_lowercase(),
+            }));
+        }
         Err(ErrorKind::Other(format!(
             "failed to parse '{}' as transaction config entry",
             s
@@ -104,6 +233,13 @@ pub struct Config {
     pub disabled_stages: BTreeSet<Stage>,
     pub sender: String,
     pub args: Vec<TransactionArgument>,
+    /// The height of the block the transaction should be executed in. If set, the ledger is
+    /// advanced to that height before the transaction runs.
+    pub block_height: Option<u64>,
+    /// The timestamp, in microseconds, of the block the transaction should be executed in.
+    /// Setting it without a block height starts a new block right after the current one.
+    pub block_time: Option<u64>,
+    pub expectations: Vec<Expectation>,
 }
 
 impl Config {
@@ -112,6 +248,9 @@ impl Config {
         let mut disabled_stages = BTreeSet::new();
         let mut sender = None;
         let mut args = None;
+        let mut block_height = None;
+        let mut block_time = None;
+        let mut expectations = vec![];
 
         for entry in entries {
             match entry {
@@ -170,6 +309,28 @@ impl Config {
                         }
                     }
                 }
+                Entry::BlockHeight(height) => match block_height {
+                    None => block_height = Some(*height),
+                    _ => {
+                        return Err(ErrorKind::Other("block height already set".to_string()).into())
+                    }
+                },
+                Entry::BlockTime(time) => match block_time {
+                    None => block_time = Some(*time),
+                    _ => return Err(ErrorKind::Other("block time already set".to_string()).into()),
+                },
+                Entry::Expectation(expectation) => {
+                    for name in expectation.accounts() {
+                        if config.get_account_for_name(name).is_none() {
+                            return Err(ErrorKind::Other(format!(
+                                "account '{}' does not exist",
+                                name
+                            ))
+                            .into());
+                        }
+                    }
+                    expectations.push(expectation.clone());
+                }
             }
         }
 
@@ -177,9 +338,18 @@ impl Config {
             disabled_stages,
             sender: sender.unwrap_or_else(|| "default".to_string()),
             args: args.unwrap_or_else(|| vec![]),
+            block_height,
+            block_time,
+            expectations,
         })
     }
 
+    /// Returns true if a new block needs to be started before the transaction runs.
+    #[inline]
+    pub fn starts_new_block(&self) -> bool {
+        self.block_height.is_some() || self.block_time.is_some()
+    }
+
     #[inline]
     pub fn is_stage_disabled(&self, stage: Stage) -> bool {
         self.disabled_stages.contains(&stage)
@@ -15,6 +15,8 @@ pub enum ErrorKind {
     DiscardedTransaction(TransactionOutput),
     #[fail(display = "the checker has failed to match the directives against the output")]
     CheckerFailure,
+    #[fail(display = "expectation failed: {}", _0)]
+    ExpectationFailure(String),
     #[fail(display = "verification error {:?}", _0)]
     VerificationFailure(Vec<VMStatus>),
     #[fail(display = "other error: {}", _0)]
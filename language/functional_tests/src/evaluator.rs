@@ -2,8 +2,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    config::{global::Config as GlobalConfig, transaction::Config as TransactionConfig},
+    config::{
+        global::Config as GlobalConfig,
+        transaction::{Config as TransactionConfig, EventKind, Expectation},
+    },
     errors::*,
+    genesis_accounts::ASSOCIATION_NAME,
 };
 use bytecode_verifier::verifier::{
     verify_module_dependencies, verify_script_dependencies, VerifiedModule, VerifiedScript,
@@ -14,11 +18,18 @@ use ir_to_bytecode::{
     parser::parse_script_or_module,
 };
 use ir_to_bytecode_syntax::ast::ScriptOrModule;
-use language_e2e_tests::{account::Account, executor::FakeExecutor};
+use language_e2e_tests::{
+    account::Account, common_transactions::block_metadata, executor::FakeExecutor,
+};
 use libra_types::{
+    access_path::AccessPath,
+    account_config::{
+        association_address, block_resource_path, AccountEvent, AccountResource, BlockResource,
+    },
     transaction::{
         Module as TransactionModule, RawTransaction, Script as TransactionScript,
-        SignedTransaction, TransactionArgument, TransactionOutput, TransactionStatus,
+        SignedTransaction, Transaction as LibraTransaction, TransactionArgument, TransactionOutput,
+        TransactionStatus,
     },
     vm_error::StatusCode,
 };
@@ -265,6 +276,141 @@ fn run_transaction(
     }
 }
 
+/// Starts new blocks proposed by the association until the ledger reaches the requested height,
+/// stamping each of them with the requested time.
+///
+/// If no height is given, a single block is started. If no time is given, the new blocks keep the
+/// timestamp of the current one. The association proposes the new blocks, so it receives the
+/// transaction fees collected so far.
+fn advance_block(
+    config: &GlobalConfig,
+    exec: &mut FakeExecutor,
+    height: Option<u64>,
+    time: Option<u64>,
+) -> Result<()> {
+    let block = read_block_resource(exec)?;
+    let target_height = height.unwrap_or_else(|| block.height() + 1);
+    let timestamp = time.unwrap_or_else(|| block.timestamp());
+    if target_height <= block.height() {
+        return Err(ErrorKind::Other(format!(
+            "cannot start block {}, the ledger is already at height {}",
+            target_height,
+            block.height()
+        ))
+        .into());
+    }
+
+    let association = config.get_account_for_name(ASSOCIATION_NAME).unwrap();
+    for _ in block.height()..target_height {
+        let metadata = block_metadata(timestamp, association, &[]);
+        let mut outputs =
+            exec.execute_transaction_block(vec![LibraTransaction::BlockMetadata(metadata)]);
+        let output = outputs
+            .pop()
+            .expect("a block metadata transaction should have an output");
+        match output.status() {
+            TransactionStatus::Keep(status) if status.major_status == StatusCode::EXECUTED => {
+                exec.apply_write_set(output.write_set())
+            }
+            status => {
+                return Err(ErrorKind::Other(format!(
+                    "failed to start a new block (height: {:?}, time: {:?}): {:?}",
+                    height, time, status
+                ))
+                .into())
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Reads the height and timestamp of the current block.
+fn read_block_resource(exec: &FakeExecutor) -> Result<BlockResource> {
+    let path = AccessPath::new(association_address(), block_resource_path());
+    match exec.read_from_access_path(&path) {
+        Some(bytes) => BlockResource::try_from(&bytes),
+        None => Err(ErrorKind::Other("failed to read the block resource".to_string()).into()),
+    }
+}
+
+/// Reads the account resource of the account with the given name.
+fn read_account_resource(
+    config: &GlobalConfig,
+    exec: &FakeExecutor,
+    name: &str,
+) -> Result<AccountResource> {
+    let account = config.get_account_for_name(name).unwrap();
+    exec.read_account_resource(account).ok_or_else(|| {
+        ErrorKind::Other(format!("failed to read the account resource of '{}'", name)).into()
+    })
+}
+
+/// Checks the expectations of a transaction against its output and the resulting ledger state.
+fn check_expectations(
+    config: &GlobalConfig,
+    exec: &FakeExecutor,
+    expectations: &[Expectation],
+    output: &TransactionOutput,
+) -> Result<()> {
+    for expectation in expectations {
+        let actual = match expectation {
+            Expectation::GasUsed(value) => {
+                if output.gas_used() == *value {
+                    continue;
+                }
+                format!("{} gas units were used", output.gas_used())
+            }
+            Expectation::Balance { account, value } => {
+                let balance = read_account_resource(config, exec, account)?.balance();
+                if balance == *value {
+                    continue;
+                }
+                format!("the balance is {}", balance)
+            }
+            Expectation::SequenceNumber { account, value } => {
+                let sequence_number =
+                    read_account_resource(config, exec, account)?.sequence_number();
+                if sequence_number == *value {
+                    continue;
+                }
+                format!("the sequence number is {}", sequence_number)
+            }
+            Expectation::Event {
+                account,
+                kind,
+                amount,
+                counterparty,
+            } => {
+                let resource = read_account_resource(config, exec, account)?;
+                let key = match kind {
+                    EventKind::Sent => resource.sent_events().key(),
+                    EventKind::Received => resource.received_events().key(),
+                };
+                let counterparty = *config.get_account_for_name(counterparty).unwrap().address();
+                let found = output.events().iter().any(|event| {
+                    event.key() == key
+                        && match AccountEvent::try_from(event.event_data()) {
+                            Ok(payload) => {
+                                payload.amount() == *amount && payload.account() == counterparty
+                            }
+                            Err(_) => false,
+                        }
+                });
+                if found {
+                    continue;
+                }
+                format!("the emitted events are {:?}", output.events())
+            }
+        };
+        return Err(ErrorKind::ExpectationFailure(format!(
+            "expected {}, but {}",
+            expectation, actual
+        ))
+        .into());
+    }
+    Ok(())
+}
+
 /// Serializes the script then deserializes it.
 fn serialize_and_deserialize_script(script: &CompiledScript) -> Result<()> {
     let mut script_blob = vec![];
@@ -328,6 +474,16 @@ fn eval_transaction(
     // insert a barrier in the output
     log.append(EvaluationOutput::Transaction(idx));
 
+    // move the ledger to the block the transaction should be executed in
+    if transaction.config.starts_new_block() {
+        advance_block(
+            config,
+            exec,
+            transaction.config.block_height,
+            transaction.config.block_time,
+        )?;
+    }
+
     // stage 1: parse the script/module
     if transaction.config.is_stage_disabled(Stage::Parser) {
         return Ok(Status::Success);
@@ -378,6 +534,7 @@ fn eval_transaction(
                 transaction.config.args.clone(),
             )?;
             let txn_output = unwrap_or_abort!(run_transaction(exec, script_transaction), log);
+            check_expectations(config, exec, &transaction.config.expectations, &txn_output)?;
             log.append(EvaluationOutput::Output(Box::new(
                 OutputType::TransactionOutput(txn_output),
             )));
@@ -422,6 +579,7 @@ fn eval_transaction(
             log.append(EvaluationOutput::Stage(Stage::Runtime));
             let module_transaction = make_module_transaction(&exec, account, compiled_module)?;
             let txn_output = unwrap_or_abort!(run_transaction(exec, module_transaction), log);
+            check_expectations(config, exec, &transaction.config.expectations, &txn_output)?;
             log.append(EvaluationOutput::Output(Box::new(
                 OutputType::TransactionOutput(txn_output),
             )));
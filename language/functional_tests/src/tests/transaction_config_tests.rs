@@ -61,6 +61,50 @@ fn parse_args() {
     }
 }
 
+#[test]
+fn parse_block_entries() {
+    for s in &[
+        "//! block-height: 3",
+        "//! block-time: 1000000",
+        "//!block-time:0",
+    ] {
+        s.parse::<Entry>().unwrap();
+    }
+
+    for s in &[
+        "//! block-height:",
+        "//! block-height: -1",
+        "//! block-time: 1s",
+        "//! block-time: 1, 2",
+    ] {
+        s.parse::<Entry>().unwrap_err();
+    }
+}
+
+#[test]
+fn parse_expectations() {
+    for s in &[
+        "//! expect-gas-used: 42",
+        "//! expect-balance: bob, 100",
+        "//! expect-sequence-number: Alice, 1",
+        "//! expect-event: alice, sent, 5, bob",
+        "//! expect-event: bob, received, 5, alice",
+    ] {
+        s.parse::<Entry>().unwrap();
+    }
+
+    for s in &[
+        "//! expect-gas-used:",
+        "//! expect-balance: bob",
+        "//! expect-balance: bob, 1, 2",
+        "//! expect-sequence-number: , 1",
+        "//! expect-event: alice, paid, 5, bob",
+        "//! expect-event: alice, sent, 5",
+    ] {
+        s.parse::<Entry>().unwrap_err();
+    }
+}
+
 #[test]
 fn parse_new_transaction() {
     assert!(is_new_transaction("//! new-transaction"));
@@ -111,3 +155,42 @@ fn build_transaction_config_3() {
         //! args: {{bob}}
     ").unwrap_err();
 }
+
+#[rustfmt::skip]
+#[test]
+fn build_transaction_config_4() {
+    let global = parse_and_build_global_config(r"
+        //! account: bob
+    ").unwrap();
+
+    let config = parse_and_build_config(&global, r"
+        //! block-height: 2
+        //! block-time: 100
+        //! expect-gas-used: 10
+        //! expect-balance: bob, 100
+        //! expect-event: bob, received, 5, association
+    ").unwrap();
+    assert_eq!(config.block_height, Some(2));
+    assert_eq!(config.block_time, Some(100));
+    assert!(config.starts_new_block());
+    assert_eq!(config.expectations.len(), 3);
+}
+
+#[rustfmt::skip]
+#[test]
+fn build_transaction_config_5() {
+    let global = parse_and_build_global_config("").unwrap();
+
+    parse_and_build_config(&global, r"
+        //! block-time: 1
+        //! block-time: 2
+    ").unwrap_err();
+
+    parse_and_build_config(&global, r"
+        //! expect-balance: bob, 100
+    ").unwrap_err();
+
+    parse_and_build_config(&global, r"
+        //! expect-event: default, sent, 5, bob
+    ").unwrap_err();
+}
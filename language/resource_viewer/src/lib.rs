@@ -0,0 +1,420 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This crate decodes the resources stored in account states into trees of named and typed
+//! fields.
+//!
+//! Resources are stored as their canonical serialization, which carries neither the names nor the
+//! types of their fields, and under a hash of their `StructTag`. `MoveValueAnnotator` recovers
+//! both from the `CompiledModule`s declaring the types, which are themselves stored in the
+//! account states of the addresses that published them.
+
+use canonical_serialization::{CanonicalDeserializer, SimpleDeserializer};
+use failure::prelude::*;
+use libra_types::{
+    access_path::{AccessPath, Accesses},
+    account_address::AccountAddress,
+    account_config::core_code_address,
+    account_state_blob::AccountStateBlob,
+    byte_array::ByteArray,
+    identifier::{IdentStr, Identifier},
+    language_storage::{ModuleId, StructTag},
+};
+use serde_json::{Map, Value as JsonValue};
+use std::{collections::BTreeMap, convert::TryFrom, fmt};
+use vm::{
+    access::ModuleAccess,
+    file_format::{CompiledModule, SignatureToken},
+    views::StructDefinitionView,
+};
+
+/// A Move type with all its type parameters instantiated.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MoveType {
+    Bool,
+    U8,
+    U64,
+    U128,
+    String,
+    ByteArray,
+    Address,
+    Struct(StructType),
+}
+
+/// A struct type with all its type parameters instantiated.
+///
+/// Unlike `StructTag`, the type actuals can be of any type, e.g. in `Vector.T<u64>`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StructType {
+    pub module: ModuleId,
+    pub name: Identifier,
+    pub type_actuals: Vec<MoveType>,
+}
+
+impl StructType {
+    // The native vector type, which is serialized as its length followed by its elements.
+    fn is_vector(&self) -> bool {
+        *self.module.address() == core_code_address()
+            && self.module.name().as_str() == "Vector"
+            && self.name.as_str() == "T"
+    }
+}
+
+impl From<&StructTag> for StructType {
+    fn from(tag: &StructTag) -> Self {
+        Self {
+            module: ModuleId::new(tag.address, tag.module.clone()),
+            name: tag.name.clone(),
+            type_actuals: tag
+                .type_params
+                .iter()
+                .map(|param| MoveType::Struct(param.into()))
+                .collect(),
+        }
+    }
+}
+
+impl fmt::Display for MoveType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MoveType::Bool => write!(f, "bool"),
+            MoveType::U8 => write!(f, "u8"),
+            MoveType::U64 => write!(f, "u64"),
+            MoveType::U128 => write!(f, "u128"),
+            MoveType::String => write!(f, "string"),
+            MoveType::ByteArray => write!(f, "bytearray"),
+            MoveType::Address => write!(f, "address"),
+            MoveType::Struct(struct_type) => write!(f, "{}", struct_type),
+        }
+    }
+}
+
+impl fmt::Display for StructType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}.{}.{}",
+            self.module.address(),
+            self.module.name(),
+            self.name
+        )?;
+        if !self.type_actuals.is_empty() {
+            let actuals: Vec<_> = self.type_actuals.iter().map(|ty| ty.to_string()).collect();
+            write!(f, "<{}>", actuals.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// A decoded Move value.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AnnotatedMoveValue {
+    Bool(bool),
+    U8(u8),
+    U64(u64),
+    U128(u128),
+    String(String),
+    ByteArray(ByteArray),
+    Address(AccountAddress),
+    Vector(Vec<AnnotatedMoveValue>),
+    Struct(AnnotatedMoveStruct),
+}
+
+/// A decoded struct value, with its fields in declaration order.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AnnotatedMoveStruct {
+    pub type_: StructType,
+    pub fields: Vec<(Identifier, AnnotatedMoveValue)>,
+}
+
+impl AnnotatedMoveValue {
+    /// Renders the value as JSON. Byte arrays and addresses are rendered as hex strings, and
+    /// `u128`s as decimal strings since JSON numbers can't represent them exactly.
+    pub fn to_json(&self) -> JsonValue {
+        match self {
+            AnnotatedMoveValue::Bool(b) => JsonValue::Bool(*b),
+            AnnotatedMoveValue::U8(n) => JsonValue::from(*n),
+            AnnotatedMoveValue::U64(n) => JsonValue::from(*n),
+            AnnotatedMoveValue::U128(n) => JsonValue::String(n.to_string()),
+            AnnotatedMoveValue::String(s) => JsonValue::String(s.clone()),
+            AnnotatedMoveValue::ByteArray(bytes) => {
+                JsonValue::String(hex::encode(bytes.as_bytes()))
+            }
+            AnnotatedMoveValue::Address(address) => JsonValue::String(address.to_string()),
+            AnnotatedMoveValue::Vector(elements) => {
+                JsonValue::Array(elements.iter().map(AnnotatedMoveValue::to_json).collect())
+            }
+            AnnotatedMoveValue::Struct(s) => s.to_json(),
+        }
+    }
+}
+
+impl AnnotatedMoveStruct {
+    /// Returns the value of the field `name`, if the struct has one.
+    pub fn field(&self, name: &IdentStr) -> Option<&AnnotatedMoveValue> {
+        self.fields
+            .iter()
+            .find(|(field_name, _)| field_name.as_ident_str() == name)
+            .map(|(_, value)| value)
+    }
+
+    /// Renders the struct as a JSON object mapping its field names to their values.
+    pub fn to_json(&self) -> JsonValue {
+        JsonValue::Object(
+            self.fields
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_json()))
+                .collect(),
+        )
+    }
+}
+
+/// The decoded content of an account state.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct AnnotatedAccountState {
+    /// The resources whose types are declared by known modules.
+    pub resources: Vec<AnnotatedMoveStruct>,
+    /// The modules published under the account.
+    pub modules: Vec<ModuleId>,
+    /// The paths and values of the other entries: resources of unknown modules, resources of
+    /// generic types, whose paths can't be computed without knowing their type actuals, and
+    /// resources that can't be decoded with the layout declared by their module.
+    pub undecoded: Vec<(Vec<u8>, Vec<u8>)>,
+}
+
+impl AnnotatedAccountState {
+    /// Returns the resource of type `struct_type`, if the account holds one.
+    pub fn resource(&self, struct_type: &StructType) -> Option<&AnnotatedMoveStruct> {
+        self.resources
+            .iter()
+            .find(|resource| resource.type_ == *struct_type)
+    }
+
+    /// Renders the account state as a JSON object with the resources keyed by their types, the
+    /// ids of the modules, and the undecoded entries as hex strings keyed by their hex paths.
+    pub fn to_json(&self) -> JsonValue {
+        let resources: Map<_, _> = self
+            .resources
+            .iter()
+            .map(|resource| (resource.type_.to_string(), resource.to_json()))
+            .collect();
+        let modules = self
+            .modules
+            .iter()
+            .map(|id| JsonValue::String(format!("{}.{}", id.address(), id.name())))
+            .collect();
+        let undecoded: Map<_, _> = self
+            .undecoded
+            .iter()
+            .map(|(path, value)| (hex::encode(path), JsonValue::String(hex::encode(value))))
+            .collect();
+
+        let mut json = Map::new();
+        json.insert("resources".to_string(), JsonValue::Object(resources));
+        json.insert("modules".to_string(), JsonValue::Array(modules));
+        json.insert("undecoded".to_string(), JsonValue::Object(undecoded));
+        JsonValue::Object(json)
+    }
+}
+
+/// Decodes resources using the modules declaring their types and the types of their fields.
+#[derive(Debug, Default)]
+pub struct MoveValueAnnotator {
+    modules: BTreeMap<ModuleId, CompiledModule>,
+}
+
+impl MoveValueAnnotator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a module to the ones used to decode resources, replacing any module with the same id.
+    pub fn add_module(&mut self, module: CompiledModule) {
+        self.modules.insert(module.self_id(), module);
+    }
+
+    /// Adds the modules published in `account_state`.
+    pub fn add_modules_from_account_state(
+        &mut self,
+        account_state: &AccountStateBlob,
+    ) -> Result<()> {
+        for (path, value) in BTreeMap::<Vec<u8>, Vec<u8>>::try_from(account_state)? {
+            if let Some(module) = published_module(&path, &value) {
+                self.add_module(module);
+            }
+        }
+        Ok(())
+    }
+
+    /// Decodes `blob` as a resource of type `tag`.
+    pub fn view_resource(&self, tag: &StructTag, blob: &[u8]) -> Result<AnnotatedMoveStruct> {
+        let mut deserializer = SimpleDeserializer::new(blob);
+        let resource = self.view_struct(&mut deserializer, &StructType::from(tag))?;
+        ensure!(
+            deserializer.is_empty(),
+            "{} bytes left after decoding the resource",
+            deserializer.len() as u64 - deserializer.position()
+        );
+        Ok(resource)
+    }
+
+    /// Decodes the resources of `account_state` whose types are declared by known modules.
+    ///
+    /// Resources are stored under a hash of their type, so only the non generic resource types of
+    /// the known modules are looked for. A resource that fails to decode, e.g. because it was
+    /// published by an older version of its module, is kept in the undecoded entries.
+    pub fn view_account_state(
+        &self,
+        account_state: &AccountStateBlob,
+    ) -> Result<AnnotatedAccountState> {
+        let mut entries = BTreeMap::<Vec<u8>, Vec<u8>>::try_from(account_state)?;
+        let mut annotated = AnnotatedAccountState::default();
+
+        for module in self.modules.values() {
+            for def in module.struct_defs() {
+                let def = StructDefinitionView::new(module, def);
+                if !def.is_nominal_resource() || !def.type_formals().is_empty() {
+                    continue;
+                }
+                let tag = StructTag {
+                    address: *module.address(),
+                    module: module.name().to_owned(),
+                    name: def.name().to_owned(),
+                    type_params: vec![],
+                };
+                let path = AccessPath::resource_access_vec(&tag, &Accesses::empty());
+                let resource = entries
+                    .get(&path)
+                    .and_then(|blob| self.view_resource(&tag, blob).ok());
+                if let Some(resource) = resource {
+                    entries.remove(&path);
+                    annotated.resources.push(resource);
+                }
+            }
+        }
+        for (path, value) in entries {
+            match published_module(&path, &value) {
+                Some(module) => annotated.modules.push(module.self_id()),
+                None => annotated.undecoded.push((path, value)),
+            }
+        }
+        Ok(annotated)
+    }
+
+    fn view_struct(
+        &self,
+        deserializer: &mut SimpleDeserializer,
+        struct_type: &StructType,
+    ) -> Result<AnnotatedMoveStruct> {
+        let module = self.modules.get(&struct_type.module).ok_or_else(|| {
+            format_err!(
+                "Module {}.{} is unknown",
+                struct_type.module.address(),
+                struct_type.module.name()
+            )
+        })?;
+        let def = module
+            .struct_defs()
+            .iter()
+            .map(|def| StructDefinitionView::new(module, def))
+            .find(|def| def.name() == struct_type.name.as_ident_str())
+            .ok_or_else(|| format_err!("Struct {} is not declared", struct_type))?;
+        let fields = def
+            .fields()
+            .ok_or_else(|| format_err!("Native struct {} can't be decoded", struct_type))?;
+
+        let mut annotated_fields = vec![];
+        for field in fields {
+            let field_type =
+                resolve_type(module, field.signature_token(), &struct_type.type_actuals)?;
+            annotated_fields.push((
+                field.name().to_owned(),
+                self.view_value(deserializer, &field_type)?,
+            ));
+        }
+        Ok(AnnotatedMoveStruct {
+            type_: struct_type.clone(),
+            fields: annotated_fields,
+        })
+    }
+
+    fn view_value(
+        &self,
+        deserializer: &mut SimpleDeserializer,
+        ty: &MoveType,
+    ) -> Result<AnnotatedMoveValue> {
+        Ok(match ty {
+            MoveType::Bool => AnnotatedMoveValue::Bool(deserializer.decode_bool()?),
+            MoveType::U8 => AnnotatedMoveValue::U8(deserializer.decode_u8()?),
+            MoveType::U64 => AnnotatedMoveValue::U64(deserializer.decode_u64()?),
+            MoveType::U128 => AnnotatedMoveValue::U128(deserializer.decode_u128()?),
+            MoveType::String => {
+                AnnotatedMoveValue::String(String::from_utf8(deserializer.decode_bytes()?)?)
+            }
+            MoveType::ByteArray => {
+                AnnotatedMoveValue::ByteArray(ByteArray::new(deserializer.decode_bytes()?))
+            }
+            MoveType::Address => {
+                AnnotatedMoveValue::Address(AccountAddress::try_from(deserializer.decode_bytes()?)?)
+            }
+            MoveType::Struct(struct_type) if struct_type.is_vector() => {
+                ensure!(
+                    struct_type.type_actuals.len() == 1,
+                    "{} must have a single type actual",
+                    struct_type
+                );
+                let len = deserializer.decode_u32()?;
+                let mut elements = vec![];
+                for _ in 0..len {
+                    elements.push(self.view_value(deserializer, &struct_type.type_actuals[0])?);
+                }
+                AnnotatedMoveValue::Vector(elements)
+            }
+            MoveType::Struct(struct_type) => {
+                AnnotatedMoveValue::Struct(self.view_struct(deserializer, struct_type)?)
+            }
+        })
+    }
+}
+
+// Returns the module stored in an account state entry, if the entry is a published module.
+fn published_module(path: &[u8], value: &[u8]) -> Option<CompiledModule> {
+    CompiledModule::deserialize(value)
+        .ok()
+        .filter(|module| AccessPath::code_access_path(&module.self_id()).path == path)
+}
+
+// Instantiates `token`, which appears in `module`, with `type_actuals`.
+fn resolve_type(
+    module: &CompiledModule,
+    token: &SignatureToken,
+    type_actuals: &[MoveType],
+) -> Result<MoveType> {
+    Ok(match token {
+        SignatureToken::Bool => MoveType::Bool,
+        SignatureToken::U8 => MoveType::U8,
+        SignatureToken::U64 => MoveType::U64,
+        SignatureToken::U128 => MoveType::U128,
+        SignatureToken::String => MoveType::String,
+        SignatureToken::ByteArray => MoveType::ByteArray,
+        SignatureToken::Address => MoveType::Address,
+        SignatureToken::Struct(idx, actuals) => {
+            let handle = module.struct_handle_at(*idx);
+            MoveType::Struct(StructType {
+                module: module.module_id_for_handle(module.module_handle_at(handle.module)),
+                name: module.identifier_at(handle.name).to_owned(),
+                type_actuals: actuals
+                    .iter()
+                    .map(|actual| resolve_type(module, actual, type_actuals))
+                    .collect::<Result<_>>()?,
+            })
+        }
+        SignatureToken::TypeParameter(idx) => type_actuals
+            .get(*idx as usize)
+            .cloned()
+            .ok_or_else(|| format_err!("Type parameter {} is not instantiated", idx))?,
+        SignatureToken::Reference(_) | SignatureToken::MutableReference(_) => {
+            bail!("References can't be stored in resources")
+        }
+    })
+}
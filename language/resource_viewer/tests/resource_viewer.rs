@@ -0,0 +1,217 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use canonical_serialization::{CanonicalSerializer, SimpleSerializer};
+use ir_to_bytecode::{compiler::compile_module, parser::parse_module};
+use libra_types::{
+    access_path::{AccessPath, Accesses},
+    account_address::AccountAddress,
+    account_config::{account_struct_tag, AccountResource},
+    account_state_blob::AccountStateBlob,
+    byte_array::ByteArray,
+    event::{EventHandle, EventKey},
+    identifier::Identifier,
+    language_storage::StructTag,
+};
+use resource_viewer::{AnnotatedMoveValue, MoveValueAnnotator, StructType};
+use serde_json::json;
+use std::{collections::BTreeMap, convert::TryFrom};
+use stdlib::stdlib_modules;
+use vm::{access::ModuleAccess, file_format::CompiledModule};
+
+const MODULE: &str = "
+module Registry {
+    import 0x0.Vector;
+
+    struct Point { x: u64, y: u64 }
+    resource T {
+        active: bool,
+        data: bytearray,
+        origin: Self.Point,
+        owner: address,
+        values: Vector.T<u64>,
+    }
+}";
+
+fn ident(name: &str) -> Identifier {
+    Identifier::new(name).unwrap()
+}
+
+fn compile(address: AccountAddress) -> CompiledModule {
+    compile_module(address, parse_module(MODULE).unwrap(), stdlib_modules()).unwrap()
+}
+
+fn stdlib_annotator() -> MoveValueAnnotator {
+    let mut annotator = MoveValueAnnotator::new();
+    for module in stdlib_modules() {
+        annotator.add_module(module.as_inner().clone());
+    }
+    annotator
+}
+
+fn registry_tag(address: AccountAddress) -> StructTag {
+    StructTag {
+        address,
+        module: ident("Registry"),
+        name: ident("T"),
+        type_params: vec![],
+    }
+}
+
+fn registry_blob(owner: AccountAddress) -> Vec<u8> {
+    let mut serializer = SimpleSerializer::<Vec<u8>>::new();
+    serializer
+        .encode_bool(true)
+        .unwrap()
+        .encode_bytes(&[0xca, 0xfe])
+        .unwrap()
+        .encode_u64(1)
+        .unwrap()
+        .encode_u64(2)
+        .unwrap()
+        .encode_bytes(owner.as_ref())
+        .unwrap()
+        .encode_u32(3)
+        .unwrap();
+    for value in &[10, 20, 30] {
+        serializer.encode_u64(*value).unwrap();
+    }
+    serializer.get_output()
+}
+
+#[test]
+fn view_custom_resource() {
+    let address = AccountAddress::random();
+    let mut annotator = stdlib_annotator();
+    annotator.add_module(compile(address));
+
+    let resource = annotator
+        .view_resource(&registry_tag(address), &registry_blob(address))
+        .unwrap();
+    assert_eq!(
+        resource.field(&ident("values")),
+        Some(&AnnotatedMoveValue::Vector(vec![
+            AnnotatedMoveValue::U64(10),
+            AnnotatedMoveValue::U64(20),
+            AnnotatedMoveValue::U64(30),
+        ]))
+    );
+    assert_eq!(
+        resource.to_json(),
+        json!({
+            "active": true,
+            "data": "cafe",
+            "origin": { "x": 1, "y": 2 },
+            "owner": address.to_string(),
+            "values": [10, 20, 30],
+        })
+    );
+}
+
+#[test]
+fn view_resource_rejects_bad_blobs() {
+    let address = AccountAddress::random();
+    let mut annotator = stdlib_annotator();
+
+    // The module declaring the resource is unknown.
+    let blob = registry_blob(address);
+    assert!(annotator
+        .view_resource(&registry_tag(address), &blob)
+        .is_err());
+
+    annotator.add_module(compile(address));
+    // Truncated and overlong blobs.
+    assert!(annotator
+        .view_resource(&registry_tag(address), &blob[..blob.len() - 1])
+        .is_err());
+    let mut overlong = blob;
+    overlong.push(0);
+    assert!(annotator
+        .view_resource(&registry_tag(address), &overlong)
+        .is_err());
+}
+
+#[test]
+fn view_account_resource() {
+    let account_resource = AccountResource::new(
+        1000,
+        3,
+        ByteArray::new(vec![0x66; 32]),
+        false,
+        true,
+        false,
+        EventHandle::new(EventKey::new([0x22; 32]), 1),
+        EventHandle::new(EventKey::new([0x23; 32]), 2),
+        EventHandle::new(EventKey::new([0x24; 32]), 1),
+        EventHandle::new(EventKey::new([0x25; 32]), 0),
+        EventHandle::new(EventKey::new([0x26; 32]), 0),
+        EventHandle::new(EventKey::new([0x27; 32]), 1),
+    );
+    let blob = SimpleSerializer::<Vec<u8>>::serialize(&account_resource).unwrap();
+
+    let resource = stdlib_annotator()
+        .view_resource(&account_struct_tag(), &blob)
+        .unwrap();
+    let json = resource.to_json();
+    assert_eq!(json["balance"], json!({ "value": 1000 }));
+    assert_eq!(json["sequence_number"], json!(3));
+    assert_eq!(json["delegated_withdrawal_capability"], json!(true));
+    assert_eq!(
+        json["sent_events"],
+        json!({ "counter": 1, "guid": hex::encode([0x22; 32]) })
+    );
+}
+
+#[test]
+fn view_account_state() {
+    let address = AccountAddress::random();
+    let module = compile(address);
+    let mut module_bytes = vec![];
+    module.serialize(&mut module_bytes).unwrap();
+
+    let mut state = BTreeMap::new();
+    state.insert(
+        AccessPath::resource_access_vec(&registry_tag(address), &Accesses::empty()),
+        registry_blob(address),
+    );
+    state.insert(
+        AccessPath::code_access_path(&module.self_id()).path,
+        module_bytes,
+    );
+    state.insert(vec![0xff], vec![0x01, 0x02]);
+    let blob = AccountStateBlob::try_from(&state).unwrap();
+
+    let mut annotator = stdlib_annotator();
+    annotator.add_modules_from_account_state(&blob).unwrap();
+    let annotated = annotator.view_account_state(&blob).unwrap();
+
+    let registry_type = StructType::from(&registry_tag(address));
+    assert_eq!(annotated.resources.len(), 1);
+    assert!(annotated.resource(&registry_type).is_some());
+    assert_eq!(annotated.modules, vec![module.self_id()]);
+    assert_eq!(annotated.undecoded, vec![(vec![0xff], vec![0x01, 0x02])]);
+
+    let json = annotated.to_json();
+    assert_eq!(
+        json["resources"][registry_type.to_string()]["origin"],
+        json!({ "x": 1, "y": 2 })
+    );
+    assert_eq!(json["undecoded"]["ff"], json!("0102"));
+}
+
+#[test]
+fn view_account_state_with_undecodable_resource() {
+    let address = AccountAddress::random();
+    let path = AccessPath::resource_access_vec(&registry_tag(address), &Accesses::empty());
+    // Truncated after the `active` field.
+    let mut state = BTreeMap::new();
+    state.insert(path.clone(), vec![0x01]);
+    let blob = AccountStateBlob::try_from(&state).unwrap();
+
+    let mut annotator = stdlib_annotator();
+    annotator.add_module(compile(address));
+    let annotated = annotator.view_account_state(&blob).unwrap();
+
+    assert!(annotated.resources.is_empty());
+    assert_eq!(annotated.undecoded, vec![(path, vec![0x01])]);
+}
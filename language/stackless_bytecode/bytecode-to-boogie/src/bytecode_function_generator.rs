@@ -1,8 +1,19 @@
 //! This module generates the Boogie version of bytecode instructions in the format of Boogie
-//! procedures.
+//! procedures, and the Boogie contracts for the specifications attached to functions and structs.
 use crate::translator::*;
 use bytecode_verifier::VerifiedModule;
-use vm::access::ModuleAccess;
+use ir_to_bytecode::parser::ast::{
+    BinOp, Condition, CopyableVal, ModuleDefinition, ModuleName, QualifiedStructIdent, SpecExp,
+    StorageLocation,
+};
+use libra_types::access_path::Field;
+use num::{BigInt, Num};
+use vm::{
+    access::ModuleAccess,
+    file_format::SignatureToken,
+    views::{FunctionHandleView, StructDefinitionView, StructHandleView, ViewInternals},
+};
+
 impl BoogieTranslator {
     pub fn emit_stratified_functions(&self) -> String {
         let mut res = String::new();
@@ -62,12 +73,14 @@ impl BoogieTranslator {
     pub fn emit_struct_specific_functions(
         &self,
         module: &VerifiedModule,
+        module_def: &ModuleDefinition,
         def_idx: usize,
     ) -> String {
         let mut res = String::from("\n");
         let field_info = get_field_info_from_def_index(module, def_idx);
         let struct_handle_index = module.struct_defs()[def_idx].struct_handle;
         let struct_name = struct_name_from_handle_index(module, struct_handle_index);
+        let invariants = self.translate_struct_invariants(module, module_def, def_idx);
         let mut args_str = String::new();
         let mut typechecking_str = String::new();
         let mut fields_str = String::new();
@@ -88,7 +101,12 @@ impl BoogieTranslator {
             struct_name, args_str
         ));
         res.push_str(&typechecking_str);
-        res.push_str(&format!("    v := Map(DefaultMap{});\n}}\n\n", fields_str));
+        res.push_str(&format!("    v := Map(DefaultMap{});\n", fields_str));
+        // every packed value has to satisfy the struct invariants
+        for invariant in &invariants {
+            res.push_str(&format!("    assert {};\n", invariant));
+        }
+        res.push_str("}\n\n");
 
         // unpack
         res.push_str(&format!(
@@ -96,6 +114,9 @@ impl BoogieTranslator {
             struct_name, args_str
         ));
         res.push_str("    assert is#Map(v);\n");
+        for invariant in &invariants {
+            res.push_str(&format!("    assume {};\n", invariant));
+        }
         for (i, (field_name, _)) in field_info.iter().enumerate() {
             res.push_str(&format!(
                 "    v{} := m#Map(v)[Field({}_{})];\n",
@@ -141,4 +162,375 @@ impl BoogieTranslator {
         res.push_str("    res := Boolean(!b#Boolean(res_val));\n}\n\n");
         res
     }
+    /// Translates the invariants declared for the struct at `def_idx` into Boogie conditions
+    /// over the struct value `v`.
+    fn translate_struct_invariants(
+        &self,
+        module: &VerifiedModule,
+        module_def: &ModuleDefinition,
+        def_idx: usize,
+    ) -> Vec<String> {
+        let struct_handle = module.struct_handle_at(module.struct_defs()[def_idx].struct_handle);
+        let name = StructHandleView::new(module, struct_handle).name();
+        let struct_def = match module_def
+            .structs
+            .iter()
+            .find(|s| s.name.as_inner() == name)
+        {
+            Some(struct_def) => struct_def,
+            None => return vec![],
+        };
+        let translator = SpecTranslator {
+            parent: self,
+            module,
+            module_def,
+            target: SpecTarget::Struct(def_idx, "v".to_string()),
+        };
+        struct_def
+            .invariants
+            .iter()
+            .map(|invariant| translator.translate_condition(&invariant.value, false))
+            .collect()
+    }
+}
+
+impl<'a> ModuleTranslator<'a> {
+    /// Generates the Boogie contract of the function at `idx` from its `requires`, `ensures`
+    /// and `aborts_if` conditions. Every clause is emitted on its own line, so the result can be
+    /// placed between the procedure signature and its body.
+    pub fn generate_function_spec(&self, idx: usize) -> String {
+        let mut res = String::new();
+        let function_def = &self.module.function_defs()[idx];
+        let function_handle = self.module.function_handle_at(function_def.function);
+        let name = FunctionHandleView::new(self.module, function_handle).name();
+        let function = match self
+            .module_def
+            .functions
+            .iter()
+            .find(|(function_name, _)| function_name.as_inner() == name)
+        {
+            Some((_, function)) => function,
+            None => return res,
+        };
+        let translator = SpecTranslator {
+            parent: self.parent,
+            module: self.module,
+            module_def: self.module_def,
+            target: SpecTarget::Function(idx),
+        };
+        let mut aborts_if = vec![];
+        for condition in &function.specifications {
+            match &condition.value {
+                Condition::Requires(exp) => res.push_str(&format!(
+                    "\nrequires {};",
+                    translator.translate_condition(exp, true)
+                )),
+                Condition::Ensures(exp) => res.push_str(&format!(
+                    "\nensures !abort_flag ==> {};",
+                    translator.translate_condition(exp, false)
+                )),
+                Condition::AbortsIf(exp) => {
+                    aborts_if.push(translator.translate_condition(exp, true));
+                }
+            }
+        }
+        // the function aborts exactly when one of the conditions held in the pre-state
+        if !aborts_if.is_empty() {
+            res.push_str(&format!(
+                "\nensures old({}) <==> abort_flag;",
+                aborts_if.join(" || ")
+            ));
+        }
+        res
+    }
+}
+
+/// What the storage locations in a specification are resolved against.
+enum SpecTarget {
+    /// The formals and return values of the function at the given definition index.
+    Function(usize),
+    /// The fields of the struct at the given definition index, read from the named value.
+    Struct(usize, String),
+}
+
+/// Translates specification expressions into Boogie expressions of type `Value`.
+struct SpecTranslator<'a> {
+    parent: &'a BoogieTranslator,
+    module: &'a VerifiedModule,
+    module_def: &'a ModuleDefinition,
+    target: SpecTarget,
+}
+
+impl<'a> SpecTranslator<'a> {
+    /// Translates a boolean specification expression into a Boogie `bool`. `in_old` is set when
+    /// the expression is evaluated in the pre-state of a function.
+    fn translate_condition(&self, exp: &SpecExp, in_old: bool) -> String {
+        format!("b#Boolean({})", self.translate_exp(exp, in_old))
+    }
+
+    fn translate_exp(&self, exp: &SpecExp, in_old: bool) -> String {
+        match exp {
+            SpecExp::Constant(val) => match val {
+                CopyableVal::Address(addr) => format!("Address({})", format_address(addr)),
+                CopyableVal::U8(i) => format!("Integer({})", i),
+                CopyableVal::U64(i) => format!("Integer({})", i),
+                CopyableVal::U128(i) => format!("Integer({})", i),
+                CopyableVal::Bool(b) => format!("Boolean({})", b),
+                CopyableVal::ByteArray(_) | CopyableVal::String(_) => {
+                    panic!("unsupported constant {} in specification", val)
+                }
+            },
+            // references are always dereferenced when a location is read
+            SpecExp::StorageLocation(location) | SpecExp::Dereference(location) => {
+                self.translate_location(location, in_old).0
+            }
+            SpecExp::GlobalExists { type_, address, .. } => {
+                let (struct_name, _, _) = self.resolve_struct(type_);
+                format!(
+                    "Boolean(domain#ResourceStore(rs_{})[a#Address({})])",
+                    struct_name,
+                    self.translate_address(address, in_old)
+                )
+            }
+            SpecExp::Not(exp) => {
+                format!("Boolean(!b#Boolean({}))", self.translate_exp(exp, in_old))
+            }
+            SpecExp::Binop(exp1, op, exp2) => {
+                let exp1 = self.translate_exp(exp1, in_old);
+                let exp2 = self.translate_exp(exp2, in_old);
+                let (cons, operand, boogie_op) = match op {
+                    BinOp::Add => ("Integer", "i#Integer", "+"),
+                    BinOp::Sub => ("Integer", "i#Integer", "-"),
+                    BinOp::Mul => ("Integer", "i#Integer", "*"),
+                    BinOp::Div => ("Integer", "i#Integer", "div"),
+                    BinOp::Mod => ("Integer", "i#Integer", "mod"),
+                    BinOp::Lt => ("Boolean", "i#Integer", "<"),
+                    BinOp::Gt => ("Boolean", "i#Integer", ">"),
+                    BinOp::Le => ("Boolean", "i#Integer", "<="),
+                    BinOp::Ge => ("Boolean", "i#Integer", ">="),
+                    BinOp::And => ("Boolean", "b#Boolean", "&&"),
+                    BinOp::Or => ("Boolean", "b#Boolean", "||"),
+                    BinOp::Eq => return format!("Boolean({} == {})", exp1, exp2),
+                    BinOp::Neq => return format!("Boolean({} != {})", exp1, exp2),
+                    BinOp::BitOr | BinOp::BitAnd | BinOp::Xor => {
+                        panic!("bit operation {} not supported in specification", op)
+                    }
+                };
+                format!(
+                    "{}({}({}) {} {}({}))",
+                    cons, operand, exp1, boogie_op, operand, exp2
+                )
+            }
+            SpecExp::Old(exp) => format!("old({})", self.translate_exp(exp, true)),
+        }
+    }
+
+    fn translate_address(&self, location: &StorageLocation, in_old: bool) -> String {
+        match location {
+            StorageLocation::Address(addr) => format_address(addr),
+            _ => format!("a#Address({})", self.translate_location(location, in_old).0),
+        }
+    }
+
+    /// Returns the Boogie value stored at `location`, together with its type and the module the
+    /// type is expressed in.
+    fn translate_location(
+        &self,
+        location: &StorageLocation,
+        in_old: bool,
+    ) -> (String, &'a VerifiedModule, SignatureToken) {
+        let (value, module, sig) = match location {
+            StorageLocation::Formal(name) => match &self.target {
+                SpecTarget::Function(idx) => {
+                    let function_def = &self.module.function_defs()[*idx];
+                    let function_handle = self.module.function_handle_at(function_def.function);
+                    let function_name =
+                        FunctionHandleView::new(self.module, function_handle).name();
+                    let (_, function) = self
+                        .module_def
+                        .functions
+                        .iter()
+                        .find(|(n, _)| n.as_inner() == function_name)
+                        .expect("specified function not found");
+                    let arg_idx = function
+                        .signature
+                        .formals
+                        .iter()
+                        .position(|(var, _)| var.value.name() == name.as_ident_str())
+                        .unwrap_or_else(|| panic!("{} is not a formal of {}", name, function_name));
+                    let sig = self
+                        .module
+                        .function_signature_at(function_handle.signature)
+                        .arg_types[arg_idx]
+                        .clone();
+                    // mutable references are returned by the procedure, so in the post-state
+                    // they are read from the returned local instead of the argument
+                    let value = if sig.is_mutable_reference() && !in_old {
+                        format!("t{}", arg_idx)
+                    } else {
+                        format!("arg{}", arg_idx)
+                    };
+                    (value, self.module, sig)
+                }
+                SpecTarget::Struct(def_idx, value) => {
+                    let struct_handle = self.module.struct_defs()[*def_idx].struct_handle;
+                    self.select_field(
+                        value.clone(),
+                        self.module,
+                        &SignatureToken::Struct(struct_handle, vec![]),
+                        &Field::new(name.clone()),
+                    )
+                }
+            },
+            StorageLocation::Ret(i) => match &self.target {
+                SpecTarget::Function(idx) => {
+                    let function_def = &self.module.function_defs()[*idx];
+                    let function_handle = self.module.function_handle_at(function_def.function);
+                    let sig = self
+                        .module
+                        .function_signature_at(function_handle.signature)
+                        .return_types
+                        .get(*i as usize)
+                        .unwrap_or_else(|| panic!("no return value {} in specification", i))
+                        .clone();
+                    (format!("ret{}", i), self.module, sig)
+                }
+                SpecTarget::Struct(_, _) => panic!("RET used in struct invariant"),
+            },
+            StorageLocation::GlobalResource { type_, address, .. } => {
+                let (struct_name, def_module, def_idx) = self.resolve_struct(type_);
+                let struct_handle = def_module.struct_defs()[def_idx].struct_handle;
+                (
+                    format!(
+                        "contents#ResourceStore(rs_{})[{}]",
+                        struct_name,
+                        self.translate_address(address, in_old)
+                    ),
+                    def_module,
+                    SignatureToken::Struct(struct_handle, vec![]),
+                )
+            }
+            StorageLocation::AccessPath { base, fields } => {
+                let (mut value, mut module, mut sig) = self.translate_location(base, in_old);
+                for field in fields {
+                    let (field_value, field_module, field_sig) =
+                        self.select_field(value, module, &sig, field);
+                    value = field_value;
+                    module = field_module;
+                    sig = field_sig;
+                }
+                return (value, module, sig);
+            }
+            StorageLocation::Address(addr) => (
+                format!("Address({})", format_address(addr)),
+                self.module,
+                SignatureToken::Address,
+            ),
+            StorageLocation::TxnSenderAddress => (
+                "Address(sender#Transaction_cons(txn))".to_string(),
+                self.module,
+                SignatureToken::Address,
+            ),
+        };
+        match sig {
+            SignatureToken::Reference(inner) | SignatureToken::MutableReference(inner) => {
+                (format!("v#Reference({})", value), module, *inner)
+            }
+            _ => (value, module, sig),
+        }
+    }
+
+    /// Selects `field` from the struct `value` of type `sig`, which is expressed in `module`.
+    fn select_field(
+        &self,
+        value: String,
+        module: &'a VerifiedModule,
+        sig: &SignatureToken,
+        field: &Field,
+    ) -> (String, &'a VerifiedModule, SignatureToken) {
+        let struct_handle_index = match sig {
+            SignatureToken::Struct(idx, _) => *idx,
+            _ => panic!(
+                "field {} selected from a non-struct value in specification",
+                field
+            ),
+        };
+        let struct_handle = module.struct_handle_at(struct_handle_index);
+        let module_name = module.identifier_at(
+            StructHandleView::new(module, struct_handle)
+                .module_handle()
+                .name,
+        );
+        let def_module = &self.parent.modules[*self
+            .parent
+            .module_name_to_idx
+            .get(module_name)
+            .unwrap_or_else(|| panic!("no module named {}", module_name))];
+        let struct_name = struct_name_from_handle_index(module, struct_handle_index);
+        let def_idx = *self
+            .parent
+            .struct_defs
+            .get(&struct_name)
+            .expect("can't find struct def");
+        let struct_definition_view =
+            StructDefinitionView::new(def_module, &def_module.struct_defs()[def_idx]);
+        let field_definition_view = struct_definition_view
+            .fields()
+            .unwrap_or_else(|| {
+                panic!(
+                    "field {} selected from native struct {}",
+                    field, struct_name
+                )
+            })
+            .find(|f| f.name() == field.name())
+            .unwrap_or_else(|| panic!("struct {} has no field {}", struct_name, field));
+        (
+            format!(
+                "m#Map({})[Field({}_{})]",
+                value,
+                struct_name,
+                field_definition_view.name()
+            ),
+            def_module,
+            field_definition_view
+                .type_signature()
+                .token()
+                .as_inner()
+                .clone(),
+        )
+    }
+
+    /// Resolves the module alias of `type_` and returns the Boogie name of the struct together
+    /// with the module that defines it and its definition index there.
+    fn resolve_struct(&self, type_: &QualifiedStructIdent) -> (String, &'a VerifiedModule, usize) {
+        let alias = type_.module().as_inner();
+        let module_name = if alias == ModuleName::self_name() {
+            self.module_def.name.as_inner()
+        } else {
+            self.module_def
+                .imports
+                .iter()
+                .find(|import| import.alias.as_inner() == alias)
+                .unwrap_or_else(|| panic!("no module imported as {}", alias))
+                .ident
+                .name()
+                .as_inner()
+        };
+        let struct_name = format!("{}_{}", module_name, type_.name());
+        let def_module = &self.parent.modules[*self
+            .parent
+            .module_name_to_idx
+            .get(module_name)
+            .unwrap_or_else(|| panic!("no module named {}", module_name))];
+        let def_idx = *self
+            .parent
+            .struct_defs
+            .get(&struct_name)
+            .expect("can't find struct def");
+        (struct_name, def_module, def_idx)
+    }
+}
+
+fn format_address(addr: &libra_types::account_address::AccountAddress) -> BigInt {
+    BigInt::from_str_radix(&addr.to_string(), 16).unwrap()
 }
@@ -1,6 +1,9 @@
 use bytecode_to_boogie::translator::BoogieTranslator;
 use bytecode_verifier::VerifiedModule;
-use ir_to_bytecode::{compiler::compile_module, parser::parse_module};
+use ir_to_bytecode::{
+    compiler::compile_module,
+    parser::{ast::ModuleDefinition, parse_module},
+};
 use libra_types::account_address::AccountAddress;
 use std::{
     env,
@@ -10,8 +13,12 @@ use std::{
 use stdlib::stdlib_modules;
 
 // mod translator;
-fn compile_files(file_names: Vec<String>) -> Vec<VerifiedModule> {
+fn compile_files(file_names: Vec<String>) -> (Vec<VerifiedModule>, Vec<ModuleDefinition>) {
     let mut verified_modules = stdlib_modules().to_vec();
+    let mut module_defs: Vec<ModuleDefinition> = stdlib::stdlib::module_defs()
+        .iter()
+        .map(|def| (*def).clone())
+        .collect();
     let files_len = file_names.len();
     //    let dep_files = &file_names[0..files_len - 1];
     let dep_files = &file_names[0..files_len];
@@ -21,6 +28,7 @@ fn compile_files(file_names: Vec<String>) -> Vec<VerifiedModule> {
     for file_name in dep_files {
         let code = fs::read_to_string(file_name).unwrap();
         let module = parse_module(&code).unwrap();
+        module_defs.push(module.clone());
         let compiled_module =
             compile_module(address, module, &verified_modules).expect("module failed to compile");
         let verified_module_res = VerifiedModule::new(compiled_module);
@@ -54,15 +62,15 @@ fn compile_files(file_names: Vec<String>) -> Vec<VerifiedModule> {
     //     }
     // }
 
-    verified_modules
+    (verified_modules, module_defs)
 }
 
 fn main() {
     let args: Vec<String> = env::args().collect();
     let file_names = &args[1..];
     // read files and compile into compiled modules
-    let modules = compile_files(file_names.to_vec());
-    let mut ts = BoogieTranslator::new(&modules);
+    let (modules, module_defs) = compile_files(file_names.to_vec());
+    let mut ts = BoogieTranslator::new(&modules, &module_defs);
     let mut res = String::new();
 
     // handwritten boogie code
@@ -1,17 +1,19 @@
 //! This module translates the bytecode of a module to Boogie code.
 
 use bytecode_verifier::VerifiedModule;
+use ir_to_bytecode::parser::ast::ModuleDefinition;
 use libra_types::identifier::Identifier;
 use num::{BigInt, Num};
 use stackless_bytecode_generator::{
     stackless_bytecode::StacklessBytecode::{self, *},
     stackless_bytecode_generator::{StacklessFunction, StacklessModuleGenerator},
+    stackless_control_flow_graph::StacklessControlFlowGraph,
 };
 use std::collections::{BTreeMap, BTreeSet};
 use vm::{
     access::ModuleAccess,
     file_format::{
-        FieldDefinitionIndex, FunctionHandleIndex, ModuleHandleIndex, SignatureToken,
+        CodeOffset, FieldDefinitionIndex, FunctionHandleIndex, ModuleHandleIndex, SignatureToken,
         StructDefinitionIndex, StructHandleIndex,
     },
     internals::ModuleIndex,
@@ -23,19 +25,25 @@ use vm::{
 
 pub struct BoogieTranslator {
     pub modules: Vec<VerifiedModule>,
+    pub module_defs: Vec<ModuleDefinition>,
     pub struct_defs: BTreeMap<String, usize>,
     pub max_struct_depth: usize,
     pub module_name_to_idx: BTreeMap<Identifier, usize>,
 }
 
 pub struct ModuleTranslator<'a> {
+    pub parent: &'a BoogieTranslator,
     pub module: &'a VerifiedModule,
+    pub module_def: &'a ModuleDefinition,
     pub stackless_bytecode: Vec<StacklessFunction>,
     pub all_type_strs: BTreeSet<String>,
 }
 
 impl BoogieTranslator {
-    pub fn new(modules: &[VerifiedModule]) -> Self {
+    /// Creates a translator for `modules`. `module_defs` are the IR definitions the modules were
+    /// compiled from, in the same order, and supply the specifications.
+    pub fn new(modules: &[VerifiedModule], module_defs: &[ModuleDefinition]) -> Self {
+        assert_eq!(modules.len(), module_defs.len());
         let mut struct_defs: BTreeMap<String, usize> = BTreeMap::new();
         let mut module_name_to_idx: BTreeMap<Identifier, usize> = BTreeMap::new();
         for (module_idx, module) in modules.iter().enumerate() {
@@ -55,6 +63,7 @@ impl BoogieTranslator {
         }
         Self {
             modules: modules.to_vec(),
+            module_defs: module_defs.to_vec(),
             struct_defs,
             max_struct_depth: 0,
             module_name_to_idx,
@@ -72,8 +81,8 @@ impl BoogieTranslator {
         // generate IsPrefix and UpdateValue to the max depth
         res.push_str(&self.emit_stratified_functions());
 
-        for module in self.modules.iter() {
-            let mut mt = ModuleTranslator::new(&module);
+        for (module, module_def) in self.modules.iter().zip(self.module_defs.iter()) {
+            let mut mt = ModuleTranslator::new(self, &module, module_def);
             res.push_str(&mt.translate());
         }
         res
@@ -103,7 +112,7 @@ impl BoogieTranslator {
 
     pub fn emit_struct_code(&mut self) -> String {
         let mut res = String::new();
-        for module in self.modules.iter() {
+        for (module, module_def) in self.modules.iter().zip(self.module_defs.iter()) {
             for (def_idx, struct_def) in module.struct_defs().iter().enumerate() {
                 let struct_name = struct_name_from_handle_index(module, struct_def.struct_handle);
                 res.push_str(&format!("const unique {}: TypeName;\n", struct_name));
@@ -119,7 +128,7 @@ impl BoogieTranslator {
                         struct_name, field_name
                     ));
                 }
-                res.push_str(&self.emit_struct_specific_functions(module, def_idx));
+                res.push_str(&self.emit_struct_specific_functions(module, module_def, def_idx));
                 let struct_handle_index = struct_def.struct_handle;
                 // calculate the max depth of a struct
                 self.max_struct_depth = std::cmp::max(
@@ -170,7 +179,11 @@ impl BoogieTranslator {
 }
 
 impl<'a> ModuleTranslator<'a> {
-    pub fn new(module: &'a VerifiedModule) -> Self {
+    pub fn new(
+        parent: &'a BoogieTranslator,
+        module: &'a VerifiedModule,
+        module_def: &'a ModuleDefinition,
+    ) -> Self {
         let stackless_bytecode = StacklessModuleGenerator::new(module.as_inner()).generate_module();
         let mut all_type_strs = BTreeSet::new();
         for struct_def in module.struct_defs().iter() {
@@ -178,7 +191,9 @@ impl<'a> ModuleTranslator<'a> {
             all_type_strs.insert(struct_name);
         }
         Self {
+            parent,
             module,
+            module_def,
             stackless_bytecode,
             all_type_strs,
         }
@@ -190,7 +205,9 @@ impl<'a> ModuleTranslator<'a> {
         for (idx, function_def) in self.module.function_defs().iter().enumerate() {
             if function_def.is_native() {
                 res.push_str(&self.generate_function_sig(idx, false, &None));
-                res.push_str(";\n");
+                res.push(';');
+                res.push_str(&self.generate_function_spec(idx));
+                res.push('\n');
                 continue;
             }
             res.push_str(&self.translate_function(idx));
@@ -200,9 +217,11 @@ impl<'a> ModuleTranslator<'a> {
 
     pub fn translate_function(&self, idx: usize) -> String {
         let mut res = String::new();
-        // generate function signature
-        res.push_str(&self.generate_function_sig(idx, false, &None)); // no inline
-                                                                      // generate function body
+        // generate function signature, no inline
+        res.push_str(&self.generate_function_sig(idx, false, &None));
+        // generate function contract
+        res.push_str(&self.generate_function_spec(idx));
+        // generate function body
         res.push_str(&self.generate_function_body(idx, false, &None));
         res
     }
@@ -411,7 +430,14 @@ impl<'a> ModuleTranslator<'a> {
             }
             LdTrue(idx) => vec![format!("call t{} := LdTrue();", idx)],
             LdFalse(idx) => vec![format!("call t{} := LdFalse();", idx)],
+            LdU8(idx, num) => vec![format!("call t{} := LdConst({});", idx, num)],
             LdConst(idx, num) => vec![format!("call t{} := LdConst({});", idx, num)],
+            LdU128(idx, num) => vec![format!("call t{} := LdConst({});", idx, num)],
+            CastU8(dest, operand) => vec![format!("call t{} := CastU8(t{});", dest, operand)],
+            CastU64(dest, operand) => vec![format!("call t{} := CastU64(t{});", dest, operand)],
+            CastU128(dest, operand) => {
+                vec![format!("call t{} := CastU128(t{});", dest, operand)]
+            }
             LdAddr(idx, addr_idx) => {
                 let addr = self.module.address_pool()[(*addr_idx).into_index()];
                 let addr_int = BigInt::from_str_radix(&addr.to_string(), 16).unwrap();
@@ -591,22 +617,14 @@ impl<'a> ModuleTranslator<'a> {
         res.push_str("\n    // bytecode translation starts here\n");
 
         // identify all the branching targets so we can insert labels in front of them
-        let mut branching_targets: BTreeSet<usize> = BTreeSet::new();
-        for bytecode in code.code.iter() {
-            match bytecode {
-                Branch(target) | BrTrue(target, _) | BrFalse(target, _) => {
-                    branching_targets.insert(*target as usize);
-                }
-                _ => {}
-            }
-        }
+        let branching_targets = StacklessControlFlowGraph::branch_targets(&code.code);
 
         for (offset, bytecode) in code.code.iter().enumerate() {
             // uncomment to print out bytecode for debugging purpose
             // println!("{:?}", bytecode);
 
             // insert labels for branching targets
-            if branching_targets.contains(&offset) {
+            if branching_targets.contains(&(offset as CodeOffset)) {
                 res.push_str(&format!("Label_{}:\n", offset));
             }
             res.push_str(&self.translate_bytecode(bytecode, idx, arg_names));
@@ -793,7 +811,7 @@ pub fn struct_name_from_handle_index(module: &VerifiedModule, idx: StructHandleI
 pub fn format_type(module: &VerifiedModule, sig: &SignatureToken) -> String {
     match sig {
         SignatureToken::Bool => "bool".into(),
-        SignatureToken::U64 => "int".into(),
+        SignatureToken::U8 | SignatureToken::U64 | SignatureToken::U128 => "int".into(),
         SignatureToken::String => "string".into(),
         SignatureToken::ByteArray => "bytearray".into(),
         SignatureToken::Address => "address".into(),
@@ -808,7 +826,7 @@ pub fn format_type(module: &VerifiedModule, sig: &SignatureToken) -> String {
 pub fn format_value_cons(sig: &SignatureToken) -> String {
     match sig {
         SignatureToken::Bool => "Boolean",
-        SignatureToken::U64 => "Integer",
+        SignatureToken::U8 | SignatureToken::U64 | SignatureToken::U128 => "Integer",
         SignatureToken::String => "Str",
         SignatureToken::ByteArray => "ByteArray",
         SignatureToken::Address => "Address",
@@ -1,13 +1,20 @@
 use bytecode_to_boogie::translator::BoogieTranslator;
 use bytecode_verifier::VerifiedModule;
-use ir_to_bytecode::{compiler::compile_module, parser::parse_module};
+use ir_to_bytecode::{
+    compiler::compile_module,
+    parser::{ast::ModuleDefinition, parse_module},
+};
 use libra_types::account_address::AccountAddress;
 use std::fs;
 use stdlib::stdlib_modules;
 
 // mod translator;
-fn compile_files(file_names: Vec<String>) -> Vec<VerifiedModule> {
+fn compile_files(file_names: Vec<String>) -> (Vec<VerifiedModule>, Vec<ModuleDefinition>) {
     let mut verified_modules = stdlib_modules().to_vec();
+    let mut module_defs: Vec<ModuleDefinition> = stdlib::stdlib::module_defs()
+        .iter()
+        .map(|def| (*def).clone())
+        .collect();
     let files_len = file_names.len();
     let dep_files = &file_names[0..files_len];
 
@@ -16,6 +23,7 @@ fn compile_files(file_names: Vec<String>) -> Vec<VerifiedModule> {
     for file_name in dep_files {
         let code = fs::read_to_string(file_name).unwrap();
         let module = parse_module(&code).unwrap();
+        module_defs.push(module.clone());
         let compiled_module =
             compile_module(address, module, &verified_modules).expect("module failed to compile");
         let verified_module_res = VerifiedModule::new(compiled_module);
@@ -29,7 +37,7 @@ fn compile_files(file_names: Vec<String>) -> Vec<VerifiedModule> {
             }
         }
     }
-    verified_modules
+    (verified_modules, module_defs)
 }
 
 #[test]
@@ -37,9 +45,9 @@ fn test3() {
     let mut file_names = vec![];
     let name = "test_mvir/test3.mvir".to_string();
     file_names.push(name);
-    let modules = compile_files(file_names.to_vec());
+    let (modules, module_defs) = compile_files(file_names.to_vec());
 
-    let mut ts = BoogieTranslator::new(&modules);
+    let mut ts = BoogieTranslator::new(&modules, &module_defs);
     let mut res = String::new();
 
     // handwritten boogie code
@@ -56,9 +64,9 @@ fn test_arithmetic() {
     let mut file_names = vec![];
     let name = "test_mvir/test-arithmetic.mvir".to_string();
     file_names.push(name);
-    let modules = compile_files(file_names.to_vec());
+    let (modules, module_defs) = compile_files(file_names.to_vec());
 
-    let mut ts = BoogieTranslator::new(&modules);
+    let mut ts = BoogieTranslator::new(&modules, &module_defs);
     let mut res = String::new();
 
     // handwritten boogie code
@@ -72,9 +80,9 @@ fn test_control_flow() {
     let mut file_names = vec![];
     let name = "test_mvir/test-control-flow.mvir".to_string();
     file_names.push(name);
-    let modules = compile_files(file_names.to_vec());
+    let (modules, module_defs) = compile_files(file_names.to_vec());
 
-    let mut ts = BoogieTranslator::new(&modules);
+    let mut ts = BoogieTranslator::new(&modules, &module_defs);
     let mut res = String::new();
 
     // handwritten boogie code
@@ -88,9 +96,9 @@ fn test_func_call() {
     let mut file_names = vec![];
     let name = "test_mvir/test-func-call.mvir".to_string();
     file_names.push(name);
-    let modules = compile_files(file_names.to_vec());
+    let (modules, module_defs) = compile_files(file_names.to_vec());
 
-    let mut ts = BoogieTranslator::new(&modules);
+    let mut ts = BoogieTranslator::new(&modules, &module_defs);
     let mut res = String::new();
 
     // handwritten boogie code
@@ -104,9 +112,9 @@ fn test_reference() {
     let mut file_names = vec![];
     let name = "test_mvir/test-reference.mvir".to_string();
     file_names.push(name);
-    let modules = compile_files(file_names.to_vec());
+    let (modules, module_defs) = compile_files(file_names.to_vec());
 
-    let mut ts = BoogieTranslator::new(&modules);
+    let mut ts = BoogieTranslator::new(&modules, &module_defs);
     let mut res = String::new();
 
     // handwritten boogie code
@@ -120,9 +128,9 @@ fn test_special_instr() {
     let mut file_names = vec![];
     let name = "test_mvir/test-special-instr.mvir".to_string();
     file_names.push(name);
-    let modules = compile_files(file_names.to_vec());
+    let (modules, module_defs) = compile_files(file_names.to_vec());
 
-    let mut ts = BoogieTranslator::new(&modules);
+    let mut ts = BoogieTranslator::new(&modules, &module_defs);
     let mut res = String::new();
 
     // handwritten boogie code
@@ -136,9 +144,9 @@ fn test_struct() {
     let mut file_names = vec![];
     let name = "test_mvir/test-struct.mvir".to_string();
     file_names.push(name);
-    let modules = compile_files(file_names.to_vec());
+    let (modules, module_defs) = compile_files(file_names.to_vec());
 
-    let mut ts = BoogieTranslator::new(&modules);
+    let mut ts = BoogieTranslator::new(&modules, &module_defs);
     let mut res = String::new();
 
     // handwritten boogie code
@@ -146,3 +154,57 @@ fn test_struct() {
     res.push_str(&written_code);
     res.push_str(&ts.translate());
 }
+
+#[test]
+fn test_specs() {
+    let mut file_names = vec![];
+    let name = "test_mvir/test-specs.mvir".to_string();
+    file_names.push(name);
+    let (modules, module_defs) = compile_files(file_names.to_vec());
+
+    let mut ts = BoogieTranslator::new(&modules, &module_defs);
+    let res = ts.translate();
+
+    // struct invariants are asserted when packing and assumed when unpacking
+    let invariant = concat!(
+        "b#Boolean(Boolean(",
+        "i#Integer(m#Map(m#Map(v)[Field(TestSpecs_Vault_coin)])[Field(LibraCoin_T_value)]) <= ",
+        "i#Integer(m#Map(v)[Field(TestSpecs_Vault_limit)])))",
+    );
+    assert!(res.contains(&format!(
+        "{}\n    assert {};\n}}",
+        "    v := Map(DefaultMap[Field(TestSpecs_Vault_coin) := v0][Field(TestSpecs_Vault_limit) := v1]);",
+        invariant
+    )));
+    assert!(res.contains(&format!(
+        "    assert is#Map(v);\n    assume {};\n",
+        invariant
+    )));
+
+    // function conditions become the contract of the procedure
+    let take_spec = concat!(
+        "returns (addr_exists': [Address]bool, t0: Reference, ret0: Value)\n",
+        "requires b#Boolean(Boolean(i#Integer(arg1) > i#Integer(Integer(0))));\n",
+        "ensures !abort_flag ==> b#Boolean(Boolean(Integer(",
+        "i#Integer(m#Map(m#Map(v#Reference(t0))[Field(TestSpecs_Vault_coin)])[Field(LibraCoin_T_value)]) + ",
+        "i#Integer(m#Map(ret0)[Field(LibraCoin_T_value)])) == ",
+        "old(m#Map(m#Map(v#Reference(arg0))[Field(TestSpecs_Vault_coin)])[Field(LibraCoin_T_value)])));\n",
+        "ensures old(b#Boolean(Boolean(",
+        "i#Integer(m#Map(m#Map(v#Reference(arg0))[Field(TestSpecs_Vault_coin)])[Field(LibraCoin_T_value)]) < ",
+        "i#Integer(arg1)))) <==> abort_flag;\n{",
+    );
+    assert!(res.contains(take_spec));
+    assert!(res.contains(concat!(
+        "ensures !abort_flag ==> b#Boolean(Boolean(",
+        "ret0 == Boolean(domain#ResourceStore(rs_TestSpecs_Vault)[a#Address(arg0)])));",
+    )));
+
+    // the specifications in the stdlib are translated as well
+    assert!(res.contains(concat!(
+        "returns (addr_exists': [Address]bool, t0: Reference)\n",
+        "ensures !abort_flag ==> b#Boolean(Boolean(",
+        "m#Map(v#Reference(t0))[Field(LibraCoin_T_value)] == Integer(",
+        "i#Integer(old(m#Map(v#Reference(arg0))[Field(LibraCoin_T_value)])) + ",
+        "i#Integer(m#Map(arg1)[Field(LibraCoin_T_value)]))));\n{",
+    )));
+}
@@ -0,0 +1,133 @@
+//! This module computes, for each reference held in a temporary before each instruction, the
+//! memory it may point into: a local of the function, a global resource, or the memory reached
+//! through a reference argument.
+//!
+//! A reference returned by a call may point into the memory of any reference passed to the call,
+//! following the borrow rules of the bytecode verifier.
+use crate::{
+    dataflow_analysis::{AbstractDomain, DataflowAnalysis, JoinResult, TransferFunctions},
+    stackless_bytecode::{StacklessBytecode, TempIndex},
+    stackless_bytecode_generator::StacklessFunction,
+    stackless_control_flow_graph::StacklessControlFlowGraph,
+};
+use std::collections::{BTreeMap, BTreeSet};
+use vm::file_format::{CodeOffset, SignatureToken, StructDefinitionIndex};
+
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum BorrowRoot {
+    /// A local of the function, borrowed with `BorrowLoc`.
+    Local(TempIndex),
+    /// A global resource, borrowed with `BorrowGlobal`.
+    Global(StructDefinitionIndex),
+    /// The memory the reference argument in this temporary points to.
+    Argument(TempIndex),
+}
+
+/// Temporaries that do not hold a reference are absent.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct BorrowState {
+    pub borrows: BTreeMap<TempIndex, BTreeSet<BorrowRoot>>,
+}
+
+impl BorrowState {
+    /// Returns the memory the reference in `temp` may point into.
+    pub fn roots(&self, temp: TempIndex) -> BTreeSet<BorrowRoot> {
+        self.borrows.get(&temp).cloned().unwrap_or_default()
+    }
+
+    // Makes `dest` point where `src` points, or removes it if `src` is not a reference.
+    fn copy(&mut self, dest: TempIndex, src: TempIndex) {
+        match self.borrows.get(&src).cloned() {
+            Some(roots) => {
+                self.borrows.insert(dest, roots);
+            }
+            None => {
+                self.borrows.remove(&dest);
+            }
+        }
+    }
+}
+
+impl AbstractDomain for BorrowState {
+    fn join(&mut self, other: &Self) -> JoinResult {
+        let mut result = JoinResult::Unchanged;
+        for (temp, other_roots) in &other.borrows {
+            let roots = self.borrows.entry(*temp).or_insert_with(BTreeSet::new);
+            let count = roots.len();
+            roots.extend(other_roots.iter().cloned());
+            if roots.len() != count {
+                result = JoinResult::Changed;
+            }
+        }
+        result
+    }
+}
+
+pub struct BorrowAnalysis<'a> {
+    local_types: &'a [SignatureToken],
+}
+
+impl<'a> BorrowAnalysis<'a> {
+    /// Returns the borrows held before each reachable instruction of `function`, whose first
+    /// `arg_count` temporaries are the arguments of the function.
+    pub fn analyze(
+        function: &'a StacklessFunction,
+        arg_count: usize,
+    ) -> BTreeMap<CodeOffset, BorrowState> {
+        let code = &function.code;
+        let cfg = StacklessControlFlowGraph::new_forward(code);
+        let mut initial_state = BorrowState::default();
+        for (temp, arg_type) in function.local_types.iter().enumerate().take(arg_count) {
+            if arg_type.is_reference() {
+                initial_state
+                    .borrows
+                    .insert(temp, vec![BorrowRoot::Argument(temp)].into_iter().collect());
+            }
+        }
+        let analysis = BorrowAnalysis {
+            local_types: &function.local_types,
+        };
+        let state_map = analysis.analyze_function(initial_state, code, &cfg);
+        analysis.state_per_instruction(&state_map, code, &cfg)
+    }
+}
+
+impl<'a> TransferFunctions for BorrowAnalysis<'a> {
+    type State = BorrowState;
+
+    fn execute(&self, state: &mut BorrowState, instr: &StacklessBytecode, _offset: CodeOffset) {
+        use StacklessBytecode::*;
+
+        match instr {
+            BorrowLoc(dest, local) => {
+                let roots = vec![BorrowRoot::Local(*local as TempIndex)];
+                state.borrows.insert(*dest, roots.into_iter().collect());
+            }
+            BorrowGlobal(dest, _, idx) => {
+                let roots = vec![BorrowRoot::Global(*idx)];
+                state.borrows.insert(*dest, roots.into_iter().collect());
+            }
+            BorrowField(dest, src, _) | FreezeRef(dest, src) => state.copy(*dest, *src),
+            MoveLoc(dest, local) | CopyLoc(dest, local) => state.copy(*dest, *local as TempIndex),
+            StLoc(local, src) => state.copy(*local as TempIndex, *src),
+            Call(dests, _, args) => {
+                let roots: BTreeSet<BorrowRoot> =
+                    args.iter().flat_map(|arg| state.roots(*arg)).collect();
+                for dest in dests {
+                    if self.local_types[*dest].is_reference() {
+                        state.borrows.insert(*dest, roots.clone());
+                    } else {
+                        state.borrows.remove(dest);
+                    }
+                }
+            }
+            _ => {
+                for dest in instr.defined_temps() {
+                    state.borrows.remove(&dest);
+                }
+            }
+        }
+    }
+}
+
+impl<'a> DataflowAnalysis for BorrowAnalysis<'a> {}
@@ -0,0 +1,284 @@
+//! This module computes the temporaries that hold a known boolean or integer constant before each
+//! instruction.
+//!
+//! Operations that abort, such as an overflowing addition or a division by zero, produce a
+//! non-constant value. Locals that are borrowed anywhere in the function are never considered
+//! constant, since they may be modified through a reference.
+use crate::{
+    dataflow_analysis::{AbstractDomain, DataflowAnalysis, JoinResult, TransferFunctions},
+    stackless_bytecode::{StacklessBytecode, TempIndex},
+    stackless_control_flow_graph::StacklessControlFlowGraph,
+};
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, BTreeSet},
+};
+use vm::file_format::CodeOffset;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Constant {
+    Bool(bool),
+    U8(u8),
+    U64(u64),
+    U128(u128),
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ConstantValue {
+    Constant(Constant),
+    NonConstant,
+}
+
+/// Temporaries that have not been assigned on any path to this state are absent.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ConstantState {
+    pub values: BTreeMap<TempIndex, ConstantValue>,
+}
+
+impl ConstantState {
+    /// Returns the constant `temp` holds in this state, if it holds one on every path.
+    pub fn constant(&self, temp: TempIndex) -> Option<Constant> {
+        match self.values.get(&temp) {
+            Some(ConstantValue::Constant(constant)) => Some(*constant),
+            _ => None,
+        }
+    }
+}
+
+impl AbstractDomain for ConstantState {
+    fn join(&mut self, other: &Self) -> JoinResult {
+        let mut result = JoinResult::Unchanged;
+        for (temp, other_value) in &other.values {
+            match self.values.get(temp) {
+                None => {
+                    self.values.insert(*temp, *other_value);
+                    result = JoinResult::Changed;
+                }
+                Some(value) if value != other_value && *value != ConstantValue::NonConstant => {
+                    self.values.insert(*temp, ConstantValue::NonConstant);
+                    result = JoinResult::Changed;
+                }
+                Some(_) => (),
+            }
+        }
+        result
+    }
+}
+
+pub struct ConstantPropagation {
+    borrowed_locals: BTreeSet<TempIndex>,
+}
+
+impl ConstantPropagation {
+    /// Returns the constants known before each reachable instruction of `code`, whose first
+    /// `arg_count` temporaries are the arguments of the function.
+    pub fn analyze(
+        code: &[StacklessBytecode],
+        arg_count: usize,
+    ) -> BTreeMap<CodeOffset, ConstantState> {
+        let cfg = StacklessControlFlowGraph::new_forward(code);
+        let mut initial_state = ConstantState::default();
+        for temp in 0..arg_count {
+            initial_state
+                .values
+                .insert(temp, ConstantValue::NonConstant);
+        }
+        let borrowed_locals = code
+            .iter()
+            .filter_map(|instr| match instr {
+                StacklessBytecode::BorrowLoc(_, local) => Some(*local as TempIndex),
+                _ => None,
+            })
+            .collect();
+        let analysis = ConstantPropagation { borrowed_locals };
+        let state_map = analysis.analyze_function(initial_state, code, &cfg);
+        analysis.state_per_instruction(&state_map, code, &cfg)
+    }
+
+    fn evaluate(&self, state: &ConstantState, instr: &StacklessBytecode) -> Option<Constant> {
+        use StacklessBytecode::*;
+
+        match instr {
+            LdTrue(_) => Some(Constant::Bool(true)),
+            LdFalse(_) => Some(Constant::Bool(false)),
+            LdU8(_, value) => Some(Constant::U8(*value)),
+            LdConst(_, value) => Some(Constant::U64(*value)),
+            LdU128(_, value) => Some(Constant::U128(*value)),
+            MoveLoc(_, local) | CopyLoc(_, local) => {
+                if self.borrowed_locals.contains(&(*local as TempIndex)) {
+                    None
+                } else {
+                    state.constant(*local as TempIndex)
+                }
+            }
+            StLoc(local, temp) => {
+                if self.borrowed_locals.contains(&(*local as TempIndex)) {
+                    None
+                } else {
+                    state.constant(*temp)
+                }
+            }
+            CastU8(_, temp) => cast(state.constant(*temp)?, |value| {
+                if value <= u128::from(std::u8::MAX) {
+                    Some(Constant::U8(value as u8))
+                } else {
+                    None
+                }
+            }),
+            CastU64(_, temp) => cast(state.constant(*temp)?, |value| {
+                if value <= u128::from(std::u64::MAX) {
+                    Some(Constant::U64(value as u64))
+                } else {
+                    None
+                }
+            }),
+            CastU128(_, temp) => cast(state.constant(*temp)?, |value| Some(Constant::U128(value))),
+            Not(_, temp) => match state.constant(*temp)? {
+                Constant::Bool(value) => Some(Constant::Bool(!value)),
+                _ => None,
+            },
+            Add(_, t1, t2) => arithmetic(
+                state.constant(*t1)?,
+                state.constant(*t2)?,
+                u8::checked_add,
+                u64::checked_add,
+                u128::checked_add,
+            ),
+            Sub(_, t1, t2) => arithmetic(
+                state.constant(*t1)?,
+                state.constant(*t2)?,
+                u8::checked_sub,
+                u64::checked_sub,
+                u128::checked_sub,
+            ),
+            Mul(_, t1, t2) => arithmetic(
+                state.constant(*t1)?,
+                state.constant(*t2)?,
+                u8::checked_mul,
+                u64::checked_mul,
+                u128::checked_mul,
+            ),
+            Div(_, t1, t2) => arithmetic(
+                state.constant(*t1)?,
+                state.constant(*t2)?,
+                u8::checked_div,
+                u64::checked_div,
+                u128::checked_div,
+            ),
+            Mod(_, t1, t2) => arithmetic(
+                state.constant(*t1)?,
+                state.constant(*t2)?,
+                u8::checked_rem,
+                u64::checked_rem,
+                u128::checked_rem,
+            ),
+            BitOr(_, t1, t2) => arithmetic(
+                state.constant(*t1)?,
+                state.constant(*t2)?,
+                |a, b| Some(a | b),
+                |a, b| Some(a | b),
+                |a, b| Some(a | b),
+            ),
+            BitAnd(_, t1, t2) => arithmetic(
+                state.constant(*t1)?,
+                state.constant(*t2)?,
+                |a, b| Some(a & b),
+                |a, b| Some(a & b),
+                |a, b| Some(a & b),
+            ),
+            Xor(_, t1, t2) => arithmetic(
+                state.constant(*t1)?,
+                state.constant(*t2)?,
+                |a, b| Some(a ^ b),
+                |a, b| Some(a ^ b),
+                |a, b| Some(a ^ b),
+            ),
+            Lt(_, t1, t2) => compare(state.constant(*t1)?, state.constant(*t2)?, |ord| {
+                ord == Ordering::Less
+            }),
+            Gt(_, t1, t2) => compare(state.constant(*t1)?, state.constant(*t2)?, |ord| {
+                ord == Ordering::Greater
+            }),
+            Le(_, t1, t2) => compare(state.constant(*t1)?, state.constant(*t2)?, |ord| {
+                ord != Ordering::Greater
+            }),
+            Ge(_, t1, t2) => compare(state.constant(*t1)?, state.constant(*t2)?, |ord| {
+                ord != Ordering::Less
+            }),
+            Or(_, t1, t2) => match (state.constant(*t1)?, state.constant(*t2)?) {
+                (Constant::Bool(a), Constant::Bool(b)) => Some(Constant::Bool(a || b)),
+                _ => None,
+            },
+            And(_, t1, t2) => match (state.constant(*t1)?, state.constant(*t2)?) {
+                (Constant::Bool(a), Constant::Bool(b)) => Some(Constant::Bool(a && b)),
+                _ => None,
+            },
+            Eq(_, t1, t2) => equal(state.constant(*t1)?, state.constant(*t2)?).map(Constant::Bool),
+            Neq(_, t1, t2) => {
+                equal(state.constant(*t1)?, state.constant(*t2)?).map(|eq| Constant::Bool(!eq))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl TransferFunctions for ConstantPropagation {
+    type State = ConstantState;
+
+    fn execute(&self, state: &mut ConstantState, instr: &StacklessBytecode, _offset: CodeOffset) {
+        let value = match self.evaluate(state, instr) {
+            Some(constant) => ConstantValue::Constant(constant),
+            None => ConstantValue::NonConstant,
+        };
+        for temp in instr.defined_temps() {
+            state.values.insert(temp, value);
+        }
+    }
+}
+
+impl DataflowAnalysis for ConstantPropagation {}
+
+fn arithmetic(
+    lhs: Constant,
+    rhs: Constant,
+    op_u8: fn(u8, u8) -> Option<u8>,
+    op_u64: fn(u64, u64) -> Option<u64>,
+    op_u128: fn(u128, u128) -> Option<u128>,
+) -> Option<Constant> {
+    match (lhs, rhs) {
+        (Constant::U8(a), Constant::U8(b)) => op_u8(a, b).map(Constant::U8),
+        (Constant::U64(a), Constant::U64(b)) => op_u64(a, b).map(Constant::U64),
+        (Constant::U128(a), Constant::U128(b)) => op_u128(a, b).map(Constant::U128),
+        _ => None,
+    }
+}
+
+fn compare(lhs: Constant, rhs: Constant, pred: fn(Ordering) -> bool) -> Option<Constant> {
+    let ord = match (lhs, rhs) {
+        (Constant::U8(a), Constant::U8(b)) => a.cmp(&b),
+        (Constant::U64(a), Constant::U64(b)) => a.cmp(&b),
+        (Constant::U128(a), Constant::U128(b)) => a.cmp(&b),
+        _ => return None,
+    };
+    Some(Constant::Bool(pred(ord)))
+}
+
+fn equal(lhs: Constant, rhs: Constant) -> Option<bool> {
+    match (lhs, rhs) {
+        (Constant::Bool(a), Constant::Bool(b)) => Some(a == b),
+        (Constant::U8(a), Constant::U8(b)) => Some(a == b),
+        (Constant::U64(a), Constant::U64(b)) => Some(a == b),
+        (Constant::U128(a), Constant::U128(b)) => Some(a == b),
+        _ => None,
+    }
+}
+
+// Applies `convert` to the value of an integer constant.
+fn cast(constant: Constant, convert: impl Fn(u128) -> Option<Constant>) -> Option<Constant> {
+    match constant {
+        Constant::U8(value) => convert(u128::from(value)),
+        Constant::U64(value) => convert(u128::from(value)),
+        Constant::U128(value) => convert(value),
+        Constant::Bool(_) => None,
+    }
+}
@@ -0,0 +1,127 @@
+//! This module defines a generic dataflow analysis over stackless bytecode.
+//!
+//! An analysis provides an abstract domain and transfer functions for single instructions. The
+//! framework runs them over a `StacklessControlFlowGraph` until the state at the start of every
+//! reachable block reaches a fixed point, in the direction of the graph. The abstract domains are
+//! expected to have finite height, as in the bytecode verifier.
+use crate::{
+    stackless_bytecode::StacklessBytecode,
+    stackless_control_flow_graph::{BlockId, StacklessControlFlowGraph},
+};
+use std::collections::BTreeMap;
+use vm::file_format::CodeOffset;
+
+/// Trait for finite-height abstract domains.
+pub trait AbstractDomain: Clone + Eq {
+    fn join(&mut self, other: &Self) -> JoinResult;
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum JoinResult {
+    Unchanged,
+    Changed,
+}
+
+/// The states at the start and at the end of a block, in the direction of the analysis.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BlockState<State> {
+    pub pre: State,
+    pub post: State,
+}
+
+/// A map from the reachable blocks to their states after a fixed point is reached.
+pub type StateMap<State> = BTreeMap<BlockId, BlockState<State>>;
+
+pub trait TransferFunctions {
+    type State: AbstractDomain;
+
+    /// Updates `state` with the effect of `instr`, found at `offset`. For a backward analysis
+    /// `state` holds before the call what is true after `instr` and holds after the call what is
+    /// true before it.
+    fn execute(&self, state: &mut Self::State, instr: &StacklessBytecode, offset: CodeOffset);
+}
+
+pub trait DataflowAnalysis: TransferFunctions {
+    /// Runs the analysis over `code` starting from `initial_state` in every entry block of `cfg`.
+    fn analyze_function(
+        &self,
+        initial_state: Self::State,
+        code: &[StacklessBytecode],
+        cfg: &StacklessControlFlowGraph,
+    ) -> StateMap<Self::State> {
+        let mut state_map: StateMap<Self::State> = StateMap::new();
+        let mut work_list = vec![];
+        for entry_block_id in cfg.entry_blocks() {
+            state_map.insert(
+                *entry_block_id,
+                BlockState {
+                    pre: initial_state.clone(),
+                    post: initial_state.clone(),
+                },
+            );
+            work_list.push(*entry_block_id);
+        }
+
+        while let Some(block_id) = work_list.pop() {
+            let pre = state_map[&block_id].pre.clone();
+            let post = self.execute_block(block_id, pre, code, cfg);
+
+            // propagate the state at the end of this block to its successors
+            for next_block_id in cfg.successors(block_id) {
+                match state_map.get_mut(next_block_id) {
+                    Some(next_block_state) => {
+                        if next_block_state.pre.join(&post) == JoinResult::Changed {
+                            work_list.push(*next_block_id);
+                        }
+                    }
+                    None => {
+                        state_map.insert(
+                            *next_block_id,
+                            BlockState {
+                                pre: post.clone(),
+                                post: post.clone(),
+                            },
+                        );
+                        work_list.push(*next_block_id);
+                    }
+                }
+            }
+            state_map.get_mut(&block_id).unwrap().post = post;
+        }
+
+        state_map
+    }
+
+    fn execute_block(
+        &self,
+        block_id: BlockId,
+        mut state: Self::State,
+        code: &[StacklessBytecode],
+        cfg: &StacklessControlFlowGraph,
+    ) -> Self::State {
+        for offset in cfg.instr_indexes(block_id) {
+            self.execute(&mut state, &code[offset as usize], offset);
+        }
+        state
+    }
+
+    /// Returns the state in front of every instruction of the reachable blocks, in the direction
+    /// of the analysis: the state before the instruction for a forward analysis and the state
+    /// after it for a backward one.
+    fn state_per_instruction(
+        &self,
+        state_map: &StateMap<Self::State>,
+        code: &[StacklessBytecode],
+        cfg: &StacklessControlFlowGraph,
+    ) -> BTreeMap<CodeOffset, Self::State> {
+        let mut result = BTreeMap::new();
+        for (block_id, block_state) in state_map {
+            let mut state = block_state.pre.clone();
+            for offset in cfg.instr_indexes(*block_id) {
+                result.insert(offset, state.clone());
+                self.execute(&mut state, &code[offset as usize], offset);
+            }
+        }
+        result
+    }
+}
@@ -1,2 +1,8 @@
+pub mod borrow_analysis;
+pub mod constant_propagation;
+pub mod dataflow_analysis;
+pub mod livevar_analysis;
+pub mod reaching_definition_analysis;
 pub mod stackless_bytecode;
 pub mod stackless_bytecode_generator;
+pub mod stackless_control_flow_graph;
@@ -0,0 +1,55 @@
+//! This module computes the temporaries that are live after each instruction, that is, the
+//! temporaries whose current value may still be read.
+use crate::{
+    dataflow_analysis::{AbstractDomain, DataflowAnalysis, JoinResult, TransferFunctions},
+    stackless_bytecode::{StacklessBytecode, TempIndex},
+    stackless_control_flow_graph::StacklessControlFlowGraph,
+};
+use std::collections::{BTreeMap, BTreeSet};
+use vm::file_format::CodeOffset;
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct LiveVarState {
+    pub live_vars: BTreeSet<TempIndex>,
+}
+
+impl AbstractDomain for LiveVarState {
+    fn join(&mut self, other: &Self) -> JoinResult {
+        let count = self.live_vars.len();
+        self.live_vars.extend(other.live_vars.iter().cloned());
+        if self.live_vars.len() == count {
+            JoinResult::Unchanged
+        } else {
+            JoinResult::Changed
+        }
+    }
+}
+
+pub struct LiveVarAnalysis;
+
+impl LiveVarAnalysis {
+    /// Returns the temporaries live after each instruction of `code` that can reach an exit.
+    pub fn analyze(code: &[StacklessBytecode]) -> BTreeMap<CodeOffset, BTreeSet<TempIndex>> {
+        let cfg = StacklessControlFlowGraph::new_backward(code);
+        let analysis = LiveVarAnalysis;
+        let state_map = analysis.analyze_function(LiveVarState::default(), code, &cfg);
+        analysis
+            .state_per_instruction(&state_map, code, &cfg)
+            .into_iter()
+            .map(|(offset, state)| (offset, state.live_vars))
+            .collect()
+    }
+}
+
+impl TransferFunctions for LiveVarAnalysis {
+    type State = LiveVarState;
+
+    fn execute(&self, state: &mut LiveVarState, instr: &StacklessBytecode, _offset: CodeOffset) {
+        for temp in instr.defined_temps() {
+            state.live_vars.remove(&temp);
+        }
+        state.live_vars.extend(instr.used_temps());
+    }
+}
+
+impl DataflowAnalysis for LiveVarAnalysis {}
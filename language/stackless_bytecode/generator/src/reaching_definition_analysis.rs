@@ -0,0 +1,82 @@
+//! This module computes the definitions of each temporary that may reach each instruction.
+//!
+//! Assignments through references are not definitions of the local they point to; use the borrow
+//! analysis to find which locals a `WriteRef` or a call may modify.
+use crate::{
+    dataflow_analysis::{AbstractDomain, DataflowAnalysis, JoinResult, TransferFunctions},
+    stackless_bytecode::{StacklessBytecode, TempIndex},
+    stackless_control_flow_graph::StacklessControlFlowGraph,
+};
+use std::collections::{BTreeMap, BTreeSet};
+use vm::file_format::CodeOffset;
+
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Definition {
+    /// The value passed by the caller for an argument.
+    Argument,
+    /// The assignment done by the instruction at this offset.
+    At(CodeOffset),
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ReachingDefState {
+    pub defs: BTreeMap<TempIndex, BTreeSet<Definition>>,
+}
+
+impl ReachingDefState {
+    /// Returns the definitions of `temp` that may reach this state.
+    pub fn definitions(&self, temp: TempIndex) -> BTreeSet<Definition> {
+        self.defs.get(&temp).cloned().unwrap_or_default()
+    }
+}
+
+impl AbstractDomain for ReachingDefState {
+    fn join(&mut self, other: &Self) -> JoinResult {
+        let mut result = JoinResult::Unchanged;
+        for (temp, other_defs) in &other.defs {
+            let defs = self.defs.entry(*temp).or_insert_with(BTreeSet::new);
+            let count = defs.len();
+            defs.extend(other_defs.iter().cloned());
+            if defs.len() != count {
+                result = JoinResult::Changed;
+            }
+        }
+        result
+    }
+}
+
+pub struct ReachingDefinitionAnalysis;
+
+impl ReachingDefinitionAnalysis {
+    /// Returns the definitions reaching each reachable instruction of `code`, whose first
+    /// `arg_count` temporaries are the arguments of the function.
+    pub fn analyze(
+        code: &[StacklessBytecode],
+        arg_count: usize,
+    ) -> BTreeMap<CodeOffset, ReachingDefState> {
+        let cfg = StacklessControlFlowGraph::new_forward(code);
+        let mut initial_state = ReachingDefState::default();
+        for temp in 0..arg_count {
+            initial_state
+                .defs
+                .insert(temp, vec![Definition::Argument].into_iter().collect());
+        }
+        let analysis = ReachingDefinitionAnalysis;
+        let state_map = analysis.analyze_function(initial_state, code, &cfg);
+        analysis.state_per_instruction(&state_map, code, &cfg)
+    }
+}
+
+impl TransferFunctions for ReachingDefinitionAnalysis {
+    type State = ReachingDefState;
+
+    fn execute(&self, state: &mut ReachingDefState, instr: &StacklessBytecode, offset: CodeOffset) {
+        for temp in instr.defined_temps() {
+            state
+                .defs
+                .insert(temp, vec![Definition::At(offset)].into_iter().collect());
+        }
+    }
+}
+
+impl DataflowAnalysis for ReachingDefinitionAnalysis {}
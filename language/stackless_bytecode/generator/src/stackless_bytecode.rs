@@ -3,7 +3,7 @@ use vm::file_format::{
     LocalIndex, StructDefinitionIndex, UserStringIndex,
 };
 
-type TempIndex = usize;
+pub type TempIndex = usize;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum StacklessBytecode {
@@ -39,11 +39,17 @@ pub enum StacklessBytecode {
 
     LdTrue(TempIndex),
     LdFalse(TempIndex),
+    LdU8(TempIndex, u8),
     LdConst(TempIndex, u64),
+    LdU128(TempIndex, u128),
     LdAddr(TempIndex, AddressPoolIndex),
     LdByteArray(TempIndex, ByteArrayPoolIndex),
     LdStr(TempIndex, UserStringIndex),
 
+    CastU8(TempIndex, TempIndex), // t1 = (u8)t2
+    CastU64(TempIndex, TempIndex),
+    CastU128(TempIndex, TempIndex),
+
     Not(TempIndex, TempIndex),            // t1 = !t2
     Add(TempIndex, TempIndex, TempIndex), // t1 = t2 binop t3
     Sub(TempIndex, TempIndex, TempIndex),
@@ -69,3 +75,136 @@ pub enum StacklessBytecode {
     Abort(TempIndex), // abort t
     NoOp,
 }
+
+impl StacklessBytecode {
+    pub fn is_unconditional_branch(&self) -> bool {
+        match self {
+            StacklessBytecode::Ret(_)
+            | StacklessBytecode::Abort(_)
+            | StacklessBytecode::Branch(_) => true,
+            _ => false,
+        }
+    }
+
+    pub fn is_conditional_branch(&self) -> bool {
+        match self {
+            StacklessBytecode::BrTrue(_, _) | StacklessBytecode::BrFalse(_, _) => true,
+            _ => false,
+        }
+    }
+
+    pub fn is_branch(&self) -> bool {
+        self.is_conditional_branch() || self.is_unconditional_branch()
+    }
+
+    /// Returns the offset this instruction may jump to, if any.
+    pub fn branch_dest(&self) -> Option<CodeOffset> {
+        match self {
+            StacklessBytecode::Branch(offset)
+            | StacklessBytecode::BrTrue(offset, _)
+            | StacklessBytecode::BrFalse(offset, _) => Some(*offset),
+            _ => None,
+        }
+    }
+
+    /// Returns the temporaries assigned by this instruction. Locals are the first temporaries,
+    /// so `StLoc` assigns the temporary of its local.
+    pub fn defined_temps(&self) -> Vec<TempIndex> {
+        use StacklessBytecode::*;
+
+        match self {
+            MoveLoc(t, _) | CopyLoc(t, _) | BorrowLoc(t, _) => vec![*t],
+            StLoc(l, _) => vec![*l as TempIndex],
+            ReadRef(t, _) | FreezeRef(t, _) => vec![*t],
+            Call(ts, _, _) | Unpack(ts, _, _) => ts.clone(),
+            Pack(t, _, _) | BorrowField(t, _, _) => vec![*t],
+            MoveFrom(t, _, _) | BorrowGlobal(t, _, _) | Exists(t, _, _) => vec![*t],
+            GetGasRemaining(t)
+            | GetTxnSequenceNumber(t)
+            | GetTxnPublicKey(t)
+            | GetTxnSenderAddress(t)
+            | GetTxnMaxGasUnits(t)
+            | GetTxnGasUnitPrice(t) => vec![*t],
+            LdTrue(t) | LdFalse(t) => vec![*t],
+            LdU8(t, _) | LdConst(t, _) | LdU128(t, _) => vec![*t],
+            LdAddr(t, _) | LdByteArray(t, _) | LdStr(t, _) => vec![*t],
+            CastU8(t, _) | CastU64(t, _) | CastU128(t, _) | Not(t, _) => vec![*t],
+            Add(t, _, _)
+            | Sub(t, _, _)
+            | Mul(t, _, _)
+            | Div(t, _, _)
+            | Mod(t, _, _)
+            | BitOr(t, _, _)
+            | BitAnd(t, _, _)
+            | Xor(t, _, _)
+            | Lt(t, _, _)
+            | Gt(t, _, _)
+            | Le(t, _, _)
+            | Ge(t, _, _)
+            | Or(t, _, _)
+            | And(t, _, _)
+            | Eq(t, _, _)
+            | Neq(t, _, _) => vec![*t],
+            WriteRef(_, _)
+            | Ret(_)
+            | MoveToSender(_, _)
+            | CreateAccount(_)
+            | Branch(_)
+            | BrTrue(_, _)
+            | BrFalse(_, _)
+            | Abort(_)
+            | NoOp => vec![],
+        }
+    }
+
+    /// Returns the temporaries read by this instruction. `BorrowLoc` reads the temporary of the
+    /// local it borrows, and `WriteRef` reads the reference it writes through.
+    pub fn used_temps(&self) -> Vec<TempIndex> {
+        use StacklessBytecode::*;
+
+        match self {
+            MoveLoc(_, l) | CopyLoc(_, l) | BorrowLoc(_, l) => vec![*l as TempIndex],
+            StLoc(_, t) => vec![*t],
+            ReadRef(_, t) | FreezeRef(_, t) => vec![*t],
+            WriteRef(t1, t2) => vec![*t1, *t2],
+            Call(_, _, ts) | Ret(ts) | Pack(_, _, ts) => ts.clone(),
+            Unpack(_, _, t) | BorrowField(_, t, _) | MoveToSender(t, _) => vec![*t],
+            MoveFrom(_, t, _) | BorrowGlobal(_, t, _) | Exists(_, t, _) => vec![*t],
+            CreateAccount(t) => vec![*t],
+            CastU8(_, t) | CastU64(_, t) | CastU128(_, t) | Not(_, t) => vec![*t],
+            Add(_, t1, t2)
+            | Sub(_, t1, t2)
+            | Mul(_, t1, t2)
+            | Div(_, t1, t2)
+            | Mod(_, t1, t2)
+            | BitOr(_, t1, t2)
+            | BitAnd(_, t1, t2)
+            | Xor(_, t1, t2)
+            | Lt(_, t1, t2)
+            | Gt(_, t1, t2)
+            | Le(_, t1, t2)
+            | Ge(_, t1, t2)
+            | Or(_, t1, t2)
+            | And(_, t1, t2)
+            | Eq(_, t1, t2)
+            | Neq(_, t1, t2) => vec![*t1, *t2],
+            BrTrue(_, t) | BrFalse(_, t) | Abort(t) => vec![*t],
+            GetGasRemaining(_)
+            | GetTxnSequenceNumber(_)
+            | GetTxnPublicKey(_)
+            | GetTxnSenderAddress(_)
+            | GetTxnMaxGasUnits(_)
+            | GetTxnGasUnitPrice(_)
+            | LdTrue(_)
+            | LdFalse(_)
+            | LdU8(_, _)
+            | LdConst(_, _)
+            | LdU128(_, _)
+            | LdAddr(_, _)
+            | LdByteArray(_, _)
+            | LdStr(_, _)
+            | Branch(_)
+            | NoOp => vec![],
+        }
+    }
+}
@@ -0,0 +1,159 @@
+//! This module defines the control-flow graph of a function in stackless bytecode.
+//!
+//! Stackless bytecode keeps the offsets of the stack bytecode it was generated from, so blocks are
+//! identified by the offset of their first instruction, as in the bytecode verifier. A backward
+//! graph has the same blocks with the edges reversed; its entry blocks are the blocks that leave
+//! the function, and the instructions of each block are visited from last to first. Blocks that
+//! cannot reach an exit, such as the body of an infinite loop, are not visited backward.
+use crate::stackless_bytecode::StacklessBytecode;
+use std::collections::{BTreeMap, BTreeSet};
+use vm::file_format::CodeOffset;
+
+pub type BlockId = CodeOffset;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct Block {
+    lower: CodeOffset,
+    upper: CodeOffset,
+    successors: Vec<BlockId>,
+}
+
+#[derive(Clone, Debug)]
+pub struct StacklessControlFlowGraph {
+    entry_block_ids: Vec<BlockId>,
+    blocks: BTreeMap<BlockId, Block>,
+    backward: bool,
+}
+
+impl StacklessControlFlowGraph {
+    pub fn new_forward(code: &[StacklessBytecode]) -> Self {
+        Self {
+            entry_block_ids: if code.is_empty() { vec![] } else { vec![0] },
+            blocks: Self::collect_blocks(code),
+            backward: false,
+        }
+    }
+
+    pub fn new_backward(code: &[StacklessBytecode]) -> Self {
+        let forward_blocks = Self::collect_blocks(code);
+        let mut blocks: BTreeMap<BlockId, Block> = forward_blocks
+            .iter()
+            .map(|(id, block)| {
+                let reversed = Block {
+                    successors: vec![],
+                    ..block.clone()
+                };
+                (*id, reversed)
+            })
+            .collect();
+        for (id, block) in &forward_blocks {
+            for successor in &block.successors {
+                let predecessor_list = &mut blocks.get_mut(successor).unwrap().successors;
+                if !predecessor_list.contains(id) {
+                    predecessor_list.push(*id);
+                }
+            }
+        }
+        let entry_block_ids = forward_blocks
+            .iter()
+            .filter(|(_, block)| block.successors.is_empty())
+            .map(|(id, _)| *id)
+            .collect();
+        Self {
+            entry_block_ids,
+            blocks,
+            backward: true,
+        }
+    }
+
+    /// Returns the offsets that start a block: the first instruction, every branch target and every
+    /// instruction following a branch.
+    pub fn block_starts(code: &[StacklessBytecode]) -> BTreeSet<CodeOffset> {
+        let mut starts = BTreeSet::new();
+        if code.is_empty() {
+            return starts;
+        }
+        starts.insert(0);
+        for (offset, instr) in code.iter().enumerate() {
+            if let Some(dest) = instr.branch_dest() {
+                starts.insert(dest);
+            }
+            if instr.is_branch() && offset + 1 < code.len() {
+                starts.insert((offset + 1) as CodeOffset);
+            }
+        }
+        starts
+    }
+
+    /// Returns the offsets some branch instruction jumps to.
+    pub fn branch_targets(code: &[StacklessBytecode]) -> BTreeSet<CodeOffset> {
+        code.iter()
+            .filter_map(StacklessBytecode::branch_dest)
+            .collect()
+    }
+
+    fn collect_blocks(code: &[StacklessBytecode]) -> BTreeMap<BlockId, Block> {
+        let starts: Vec<CodeOffset> = Self::block_starts(code).into_iter().collect();
+        let mut blocks = BTreeMap::new();
+        for (idx, lower) in starts.iter().enumerate() {
+            let upper = match starts.get(idx + 1) {
+                Some(next) => next - 1,
+                None => (code.len() - 1) as CodeOffset,
+            };
+            let last = &code[upper as usize];
+            let mut successors = vec![];
+            if let Some(dest) = last.branch_dest() {
+                successors.push(dest);
+            }
+            if !last.is_unconditional_branch() && (upper as usize) + 1 < code.len() {
+                let fall_through = upper + 1;
+                if !successors.contains(&fall_through) {
+                    successors.push(fall_through);
+                }
+            }
+            blocks.insert(
+                *lower,
+                Block {
+                    lower: *lower,
+                    upper,
+                    successors,
+                },
+            );
+        }
+        blocks
+    }
+
+    /// Returns the blocks the analysis starts from.
+    pub fn entry_blocks(&self) -> &[BlockId] {
+        &self.entry_block_ids
+    }
+
+    /// Returns the ids of all blocks in offset order.
+    pub fn blocks(&self) -> Vec<BlockId> {
+        self.blocks.keys().cloned().collect()
+    }
+
+    /// Returns the successors of `block_id` in the direction of the graph.
+    pub fn successors(&self, block_id: BlockId) -> &[BlockId] {
+        &self.blocks[&block_id].successors
+    }
+
+    /// Returns the offsets of the instructions in `block_id` in the direction of the graph.
+    pub fn instr_indexes(&self, block_id: BlockId) -> Box<dyn Iterator<Item = CodeOffset>> {
+        let block = &self.blocks[&block_id];
+        let range = block.lower..=block.upper;
+        if self.backward {
+            Box::new(range.rev())
+        } else {
+            Box::new(range)
+        }
+    }
+
+    pub fn is_backward(&self) -> bool {
+        self.backward
+    }
+
+    pub fn num_blocks(&self) -> usize {
+        self.blocks.len()
+    }
+}
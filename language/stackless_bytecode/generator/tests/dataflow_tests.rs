@@ -0,0 +1,212 @@
+use ir_to_bytecode::{compiler::compile_module, parser::parse_module};
+use libra_types::account_address::AccountAddress;
+use stackless_bytecode_generator::{
+    borrow_analysis::{BorrowAnalysis, BorrowRoot},
+    constant_propagation::{Constant, ConstantPropagation, ConstantValue},
+    livevar_analysis::LiveVarAnalysis,
+    reaching_definition_analysis::{Definition, ReachingDefinitionAnalysis},
+    stackless_bytecode::StacklessBytecode::{self, *},
+    stackless_bytecode_generator::{StacklessFunction, StacklessModuleGenerator},
+    stackless_control_flow_graph::StacklessControlFlowGraph,
+};
+use std::{collections::BTreeSet, fs};
+use stdlib::stdlib_modules;
+use vm::{access::ModuleAccess, file_format::CodeOffset};
+
+// Compiles a module of the Boogie translator tests and returns the stackless bytecode of one of
+// its functions, with the number of its arguments.
+fn generate_function(file_name: &str, function_name: &str) -> (StacklessFunction, usize) {
+    let path = format!("../bytecode-to-boogie/test_mvir/{}", file_name);
+    let code = fs::read_to_string(path).unwrap();
+    let module = parse_module(&code).unwrap();
+    let module = compile_module(AccountAddress::default(), module, stdlib_modules()).unwrap();
+    let idx = module
+        .function_defs()
+        .iter()
+        .position(|def| {
+            let handle = module.function_handle_at(def.function);
+            module.identifier_at(handle.name).as_str() == function_name
+        })
+        .unwrap();
+    let handle = module.function_handle_at(module.function_defs()[idx].function);
+    let arg_count = module
+        .function_signature_at(handle.signature)
+        .arg_types
+        .len();
+    let mut functions = StacklessModuleGenerator::new(&module).generate_module();
+    (functions.remove(idx), arg_count)
+}
+
+fn offsets(
+    code: &[StacklessBytecode],
+    pred: impl Fn(&StacklessBytecode) -> bool,
+) -> Vec<CodeOffset> {
+    code.iter()
+        .enumerate()
+        .filter(|(_, instr)| pred(instr))
+        .map(|(offset, _)| offset as CodeOffset)
+        .collect()
+}
+
+// Returns the offsets of the instructions of `code` matching a pattern.
+macro_rules! offsets_of {
+    ($code:expr, $pattern:pat) => {
+        offsets(
+            $code,
+            |instr| if let $pattern = instr { true } else { false },
+        )
+    };
+}
+
+fn first_offset(code: &[StacklessBytecode], pred: impl Fn(&StacklessBytecode) -> bool) -> usize {
+    offsets(code, pred)[0] as usize
+}
+
+#[test]
+fn control_flow_graph_of_branch() {
+    let (function, _) = generate_function("test-control-flow.mvir", "branch_once");
+    let code = &function.code;
+    let branch = first_offset(code, |instr| instr.is_conditional_branch());
+    let target = code[branch].branch_dest().unwrap();
+    let fall_through = (branch + 1) as CodeOffset;
+
+    let starts = StacklessControlFlowGraph::block_starts(code);
+    assert_eq!(starts, vec![0, fall_through, target].into_iter().collect());
+
+    let cfg = StacklessControlFlowGraph::new_forward(code);
+    assert_eq!(cfg.entry_blocks(), &[0]);
+    assert_eq!(cfg.successors(0), &[target, fall_through]);
+    assert!(cfg.successors(fall_through).is_empty());
+    assert!(cfg.successors(target).is_empty());
+
+    let backward_cfg = StacklessControlFlowGraph::new_backward(code);
+    assert_eq!(backward_cfg.entry_blocks(), &[fall_through, target]);
+    assert_eq!(backward_cfg.successors(fall_through), &[0]);
+    assert_eq!(backward_cfg.successors(target), &[0]);
+    assert!(backward_cfg.successors(0).is_empty());
+    let instrs: Vec<_> = backward_cfg.instr_indexes(0).collect();
+    assert_eq!(instrs, (0..=branch as CodeOffset).rev().collect::<Vec<_>>());
+}
+
+#[test]
+fn live_vars_of_branch() {
+    let (function, _) = generate_function("test-control-flow.mvir", "branch_once");
+    let code = &function.code;
+    let live_vars = LiveVarAnalysis::analyze(code);
+    assert_eq!(live_vars.len(), code.len());
+
+    // The condition is only live until the branch reads it.
+    let branch = first_offset(code, |instr| instr.is_conditional_branch());
+    let cond = code[branch].used_temps();
+    assert_eq!(
+        live_vars[&(branch as CodeOffset - 1)],
+        cond.into_iter().collect()
+    );
+    assert!(live_vars[&(branch as CodeOffset)].is_empty());
+
+    // The returned values are live right before each return.
+    for ret in offsets_of!(code, Ret(_)) {
+        let returned: BTreeSet<_> = code[ret as usize].used_temps().into_iter().collect();
+        assert_eq!(live_vars[&(ret - 1)], returned);
+        assert!(live_vars[&ret].is_empty());
+    }
+}
+
+#[test]
+fn reaching_definitions_across_join() {
+    let (function, arg_count) = generate_function("test3.mvir", "test3");
+    let code = &function.code;
+    let defs = ReachingDefinitionAnalysis::analyze(code, arg_count);
+
+    // `flag` is an argument and is never assigned.
+    let write = offsets_of!(code, WriteRef(_, _))[0] as usize;
+    assert_eq!(
+        defs[&(write as CodeOffset)].definitions(0),
+        vec![Definition::Argument].into_iter().collect()
+    );
+
+    // `f_or_g_ref` is assigned in both branches of the first `if`.
+    let stores = offsets_of!(code, StLoc(3, _));
+    assert_eq!(stores.len(), 2);
+    assert_eq!(
+        defs[&(write as CodeOffset)].definitions(3),
+        stores.into_iter().map(Definition::At).collect()
+    );
+}
+
+#[test]
+fn constant_folding() {
+    let (function, arg_count) = generate_function("test-arithmetic.mvir", "arithmetic_ops");
+    let code = &function.code;
+    let constants = ConstantPropagation::analyze(code, arg_count);
+
+    let ret = offsets_of!(code, Ret(_))[0] as usize;
+    let state = &constants[&(ret as CodeOffset)];
+    // c = (6 + 4 - 1) * 2 / 3 % 4
+    assert_eq!(state.constant(2), Some(Constant::U64(2)));
+    assert_eq!(state.constant(0), None);
+
+    // The assertion on `c` always holds.
+    let eq = offsets_of!(code, Eq(_, _, _))[0] as usize;
+    let result = code[eq].defined_temps()[0];
+    assert_eq!(
+        constants[&(eq as CodeOffset + 1)].constant(result),
+        Some(Constant::Bool(true))
+    );
+}
+
+#[test]
+fn constant_division_by_zero_is_not_folded() {
+    let (function, arg_count) = generate_function("test-arithmetic.mvir", "div_by_zero");
+    let code = &function.code;
+    let constants = ConstantPropagation::analyze(code, arg_count);
+
+    let ret = offsets_of!(code, Ret(_))[0] as usize;
+    let state = &constants[&(ret as CodeOffset)];
+    assert_eq!(state.constant(0), Some(Constant::U64(0)));
+    assert_eq!(state.values.get(&1), Some(&ConstantValue::NonConstant));
+}
+
+#[test]
+fn borrowed_locals_are_not_constant() {
+    let (function, arg_count) = generate_function("test-reference.mvir", "mut_ref");
+    let code = &function.code;
+    let constants = ConstantPropagation::analyze(code, arg_count);
+
+    // `b = 20` is later modified through `b_ref`.
+    let store = offsets_of!(code, StLoc(0, _))[0] as usize;
+    assert_eq!(constants[&(store as CodeOffset + 1)].constant(0), None);
+}
+
+#[test]
+fn references_to_locals() {
+    let (function, arg_count) = generate_function("test-reference.mvir", "mut_ref");
+    let code = &function.code;
+    let borrows = BorrowAnalysis::analyze(&function, arg_count);
+
+    let call = offsets_of!(code, Call(_, _, _))[0] as usize;
+    let arg = code[call].used_temps()[0];
+    assert_eq!(
+        borrows[&(call as CodeOffset)].roots(arg),
+        vec![BorrowRoot::Local(0)].into_iter().collect()
+    );
+}
+
+#[test]
+fn references_to_fields_across_join() {
+    let (function, arg_count) = generate_function("test3.mvir", "test3");
+    let code = &function.code;
+    let borrows = BorrowAnalysis::analyze(&function, arg_count);
+
+    // `f_or_g_ref` points into `x` on both branches.
+    let write = offsets_of!(code, WriteRef(_, _))[0] as usize;
+    let reference = code[write].used_temps()[0];
+    let state = &borrows[&(write as CodeOffset)];
+    assert_eq!(
+        state.roots(reference),
+        vec![BorrowRoot::Local(1)].into_iter().collect()
+    );
+    assert_eq!(state.roots(3), state.roots(reference));
+    // `flag` is not a reference.
+    assert!(state.roots(0).is_empty());
+}
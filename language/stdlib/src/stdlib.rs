@@ -18,6 +18,8 @@ lazy_static! {
         make_module_definition!("../modules/block.mvir");
     static ref COIN_MODULE: ModuleDefinition =
         make_module_definition!("../modules/libra_coin.mvir");
+    static ref CURRENCY_MODULE: ModuleDefinition =
+        make_module_definition!("../modules/currency.mvir");
     static ref NATIVE_HASH_MODULE: ModuleDefinition =
         make_module_definition!("../modules/hash.mvir");
     static ref SIGNATURE_MODULE: ModuleDefinition =
@@ -33,20 +35,29 @@ lazy_static! {
     static ref BYTEARRAY_UTIL_MODULE: ModuleDefinition =
         make_module_definition!("../modules/bytearray_util.mvir");
     static ref EVENT_MODULE: ModuleDefinition = make_module_definition!("../modules/event.mvir");
+    static ref GAS_SCHEDULE_MODULE: ModuleDefinition =
+        make_module_definition!("../modules/gas_schedule.mvir");
+    static ref TRANSACTION_FEE_MODULE: ModuleDefinition =
+        make_module_definition!("../modules/transaction_fee.mvir");
     static ref MODULE_DEFS: Vec<&'static ModuleDefinition> = {
         // Note: a module can depend on earlier modules in the list, but not vice versa. Don't try
         // to rearrange without considering this!
         vec![
             &*ADDRESS_UTIL_MODULE,
-            &*BLOCK_MODULE,
             &*BYTEARRAY_UTIL_MODULE,
             &*COIN_MODULE,
+            &*GAS_SCHEDULE_MODULE,
             &*NATIVE_HASH_MODULE,
             &*SIGNATURE_MODULE,
             &*U64_UTIL_MODULE,
             &*VECTOR_MODULE,
             &*EVENT_MODULE, // depends on AddressUtil, BytearrayUtil, Hash, U64Util
-            &*ACCOUNT_MODULE, // depends on LibraCoin, Event, AddressUtil, BytearrayUtil, U64Util
+            &*CURRENCY_MODULE, // depends on Vector
+            &*TRANSACTION_FEE_MODULE, // depends on LibraCoin
+            // depends on LibraCoin, Currency, Event, AddressUtil, BytearrayUtil, U64Util,
+            // TransactionFee, Vector
+            &*ACCOUNT_MODULE,
+            &*BLOCK_MODULE, // depends on LibraAccount, LibraCoin, TransactionFee, Vector
             &*VALIDATOR_SET_MODULE, // depends on LibraAccount
         ]
     };
@@ -60,6 +71,10 @@ pub fn coin_module() -> ModuleDefinition {
     COIN_MODULE.clone()
 }
 
+pub fn gas_schedule_module() -> ModuleDefinition {
+    GAS_SCHEDULE_MODULE.clone()
+}
+
 pub fn native_hash_module() -> ModuleDefinition {
     NATIVE_HASH_MODULE.clone()
 }
@@ -24,6 +24,51 @@ pub fn mint() -> &'static str {
     include_str!("../transaction_scripts/mint.mvir")
 }
 
+/// Returns the source code for the update-gas-schedule transaction script.
+pub fn update_gas_schedule() -> &'static str {
+    include_str!("../transaction_scripts/update_gas_schedule.mvir")
+}
+
+/// Returns the source code for the enable-nonces transaction script.
+pub fn enable_nonces() -> &'static str {
+    include_str!("../transaction_scripts/enable_nonces.mvir")
+}
+
+/// Returns the source code for the freeze-account transaction script.
+pub fn freeze_account() -> &'static str {
+    include_str!("../transaction_scripts/freeze_account.mvir")
+}
+
+/// Returns the source code for the unfreeze-account transaction script.
+pub fn unfreeze_account() -> &'static str {
+    include_str!("../transaction_scripts/unfreeze_account.mvir")
+}
+
+/// Returns the source code for the register-currency transaction script.
+pub fn register_currency() -> &'static str {
+    include_str!("../transaction_scripts/register_currency.mvir")
+}
+
+/// Returns the source code for the accept-currency transaction script.
+pub fn accept_currency() -> &'static str {
+    include_str!("../transaction_scripts/accept_currency.mvir")
+}
+
+/// Returns the source code for the mint-currency transaction script.
+pub fn mint_currency() -> &'static str {
+    include_str!("../transaction_scripts/mint_currency.mvir")
+}
+
+/// Returns the source code for the peer-to-peer currency transfer transaction script.
+pub fn peer_to_peer_currency_transfer() -> &'static str {
+    include_str!("../transaction_scripts/peer_to_peer_currency_transfer.mvir")
+}
+
+/// Returns the source code for the burn-currency transaction script.
+pub fn burn_currency() -> &'static str {
+    include_str!("../transaction_scripts/burn_currency.mvir")
+}
+
 lazy_static! {
     pub static ref PEER_TO_PEER_TRANSFER_TXN_BODY: Program =
         { parse_program(peer_to_peer()).unwrap() };
@@ -41,3 +86,42 @@ lazy_static! {
 lazy_static! {
     pub static ref MINT_TXN_BODY: Program = parse_program(mint()).unwrap();
 }
+
+lazy_static! {
+    pub static ref UPDATE_GAS_SCHEDULE_TXN_BODY: Program =
+        { parse_program(update_gas_schedule()).unwrap() };
+}
+
+lazy_static! {
+    pub static ref ENABLE_NONCES_TXN_BODY: Program = parse_program(enable_nonces()).unwrap();
+}
+
+lazy_static! {
+    pub static ref FREEZE_ACCOUNT_TXN_BODY: Program = parse_program(freeze_account()).unwrap();
+}
+
+lazy_static! {
+    pub static ref UNFREEZE_ACCOUNT_TXN_BODY: Program = parse_program(unfreeze_account()).unwrap();
+}
+
+lazy_static! {
+    pub static ref REGISTER_CURRENCY_TXN_BODY: Program =
+        { parse_program(register_currency()).unwrap() };
+}
+
+lazy_static! {
+    pub static ref ACCEPT_CURRENCY_TXN_BODY: Program = parse_program(accept_currency()).unwrap();
+}
+
+lazy_static! {
+    pub static ref MINT_CURRENCY_TXN_BODY: Program = parse_program(mint_currency()).unwrap();
+}
+
+lazy_static! {
+    pub static ref PEER_TO_PEER_CURRENCY_TRANSFER_TXN_BODY: Program =
+        { parse_program(peer_to_peer_currency_transfer()).unwrap() };
+}
+
+lazy_static! {
+    pub static ref BURN_CURRENCY_TXN_BODY: Program = parse_program(burn_currency()).unwrap();
+}
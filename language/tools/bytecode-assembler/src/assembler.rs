@@ -0,0 +1,501 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Parses the assembly syntax printed by the disassembler back into a `CompiledModuleMut`.
+//!
+//! The assembler performs no checks beyond the syntax: indices are taken as written, so modules
+//! that fail bounds checking or verification can be produced on purpose. Serializing the result
+//! of assembling a disassembled module yields the original bytes.
+
+use crate::lexer::{tokenize, Spanned, Token};
+use failure::prelude::*;
+use libra_types::{
+    account_address::{AccountAddress, ADDRESS_LENGTH},
+    byte_array::ByteArray,
+    identifier::Identifier,
+};
+use std::{convert::TryFrom, fmt::Display};
+use vm::{
+    file_format::{
+        AddressPoolIndex, ByteArrayPoolIndex, Bytecode, CodeUnit, CompiledModuleMut,
+        FieldDefinition, FieldDefinitionIndex, FunctionDefinition, FunctionHandle,
+        FunctionHandleIndex, FunctionSignature, FunctionSignatureIndex, IdentifierIndex, Kind,
+        LocalsSignature, LocalsSignatureIndex, ModuleHandle, ModuleHandleIndex, SignatureToken,
+        StructDefinition, StructDefinitionIndex, StructFieldInformation, StructHandle,
+        StructHandleIndex, TypeSignature, TypeSignatureIndex, UserStringIndex,
+    },
+    vm_string::VMString,
+};
+
+/// Parses the assembly of a module.
+pub fn assemble_module(source: &str) -> Result<CompiledModuleMut> {
+    let mut parser = Parser {
+        tokens: tokenize(source)?,
+        pos: 0,
+    };
+    let module = parser.module()?;
+    if let Some((token, line)) = parser.tokens.get(parser.pos) {
+        bail!(
+            "line {}: unexpected {} after the end of the module",
+            line,
+            token
+        );
+    }
+    Ok(module)
+}
+
+struct Parser {
+    tokens: Vec<Spanned>,
+    pos: usize,
+}
+
+impl Parser {
+    fn module(&mut self) -> Result<CompiledModuleMut> {
+        self.keyword("module")?;
+        self.punct('{')?;
+        let module = CompiledModuleMut {
+            module_handles: self.table("module_handles", |p| {
+                Ok(ModuleHandle {
+                    address: AddressPoolIndex::new(p.field("address")?),
+                    name: IdentifierIndex::new(p.field("name")?),
+                })
+            })?,
+            struct_handles: self.table("struct_handles", |p| {
+                Ok(StructHandle {
+                    module: ModuleHandleIndex::new(p.field("module")?),
+                    name: IdentifierIndex::new(p.field("name")?),
+                    is_nominal_resource: {
+                        p.keyword("resource")?;
+                        p.boolean()?
+                    },
+                    type_formals: {
+                        p.keyword("type_formals")?;
+                        p.kinds()?
+                    },
+                })
+            })?,
+            function_handles: self.table("function_handles", |p| {
+                Ok(FunctionHandle {
+                    module: ModuleHandleIndex::new(p.field("module")?),
+                    name: IdentifierIndex::new(p.field("name")?),
+                    signature: FunctionSignatureIndex::new(p.field("signature")?),
+                })
+            })?,
+            type_signatures: self.table("type_signatures", |p| Ok(TypeSignature(p.type_()?)))?,
+            function_signatures: self.table("function_signatures", |p| {
+                p.keyword("type_formals")?;
+                let type_formals = p.kinds()?;
+                p.keyword("args")?;
+                let arg_types = p.types()?;
+                p.keyword("returns")?;
+                let return_types = p.types()?;
+                Ok(FunctionSignature {
+                    return_types,
+                    arg_types,
+                    type_formals,
+                })
+            })?,
+            locals_signatures: self
+                .table("locals_signatures", |p| Ok(LocalsSignature(p.types()?)))?,
+            identifiers: self.table("identifiers", |p| Identifier::new(p.string()?))?,
+            user_strings: self.table("user_strings", |p| Ok(VMString::new(p.string()?)))?,
+            byte_array_pool: self.table("byte_arrays", |p| Ok(ByteArray::new(p.bytes()?)))?,
+            address_pool: self.table("addresses", |p| {
+                let line = p.line();
+                let bytes = p.bytes()?;
+                ensure!(
+                    bytes.len() == ADDRESS_LENGTH,
+                    "line {}: addresses must be {} bytes long",
+                    line,
+                    ADDRESS_LENGTH
+                );
+                AccountAddress::try_from(bytes)
+            })?,
+            struct_defs: self.table("struct_defs", |p| {
+                let struct_handle = StructHandleIndex::new(p.field("handle")?);
+                let field_information = if p.eat_keyword("native") {
+                    StructFieldInformation::Native
+                } else {
+                    let fields = FieldDefinitionIndex::new(p.field("fields")?);
+                    let field_count = p.field("count")?;
+                    StructFieldInformation::Declared {
+                        field_count,
+                        fields,
+                    }
+                };
+                Ok(StructDefinition {
+                    struct_handle,
+                    field_information,
+                })
+            })?,
+            field_defs: self.table("field_defs", |p| {
+                Ok(FieldDefinition {
+                    struct_: StructHandleIndex::new(p.field("struct")?),
+                    name: IdentifierIndex::new(p.field("name")?),
+                    signature: TypeSignatureIndex::new(p.field("type")?),
+                })
+            })?,
+            function_defs: self.table("function_defs", Self::function_def)?,
+        };
+        self.punct('}')?;
+        Ok(module)
+    }
+
+    fn function_def(&mut self) -> Result<FunctionDefinition> {
+        let function = FunctionHandleIndex::new(self.field("function")?);
+        let flags = self.field("flags")?;
+        self.keyword("acquires")?;
+        let acquires_global_resources =
+            self.list('[', ']', |p| Ok(StructDefinitionIndex::new(p.number()?)))?;
+        let locals = LocalsSignatureIndex::new(self.field("locals")?);
+        let max_stack_size = self.field("max_stack")?;
+        self.punct('{')?;
+        let mut code = vec![];
+        while !self.eat_punct('}') {
+            let line = self.line();
+            let offset: usize = self.number()?;
+            ensure!(
+                offset == code.len(),
+                "line {}: expected instruction at offset {}, found offset {}",
+                line,
+                code.len(),
+                offset
+            );
+            self.punct(':')?;
+            code.push(self.bytecode()?);
+        }
+        Ok(FunctionDefinition {
+            function,
+            flags,
+            acquires_global_resources,
+            code: CodeUnit {
+                max_stack_size,
+                locals,
+                code,
+            },
+        })
+    }
+
+    fn bytecode(&mut self) -> Result<Bytecode> {
+        use Bytecode::*;
+
+        let line = self.line();
+        let name = self.name()?;
+        Ok(match name.as_str() {
+            "Pop" => Pop,
+            "Ret" => Ret,
+            "BrTrue" => BrTrue(self.number()?),
+            "BrFalse" => BrFalse(self.number()?),
+            "Branch" => Branch(self.number()?),
+            "LdU8" => LdU8(self.number()?),
+            "LdConst" => LdConst(self.number()?),
+            "LdU128" => LdU128(self.number()?),
+            "CastU8" => CastU8,
+            "CastU64" => CastU64,
+            "CastU128" => CastU128,
+            "LdStr" => LdStr(UserStringIndex::new(self.number()?)),
+            "LdByteArray" => LdByteArray(ByteArrayPoolIndex::new(self.number()?)),
+            "LdAddr" => LdAddr(AddressPoolIndex::new(self.number()?)),
+            "LdTrue" => LdTrue,
+            "LdFalse" => LdFalse,
+            "CopyLoc" => CopyLoc(self.number()?),
+            "MoveLoc" => MoveLoc(self.number()?),
+            "StLoc" => StLoc(self.number()?),
+            "Call" => Call(
+                FunctionHandleIndex::new(self.number()?),
+                LocalsSignatureIndex::new(self.number()?),
+            ),
+            "Pack" => {
+                let (idx, types) = self.struct_operands()?;
+                Pack(idx, types)
+            }
+            "Unpack" => {
+                let (idx, types) = self.struct_operands()?;
+                Unpack(idx, types)
+            }
+            "ReadRef" => ReadRef,
+            "WriteRef" => WriteRef,
+            "FreezeRef" => FreezeRef,
+            "MutBorrowLoc" => MutBorrowLoc(self.number()?),
+            "ImmBorrowLoc" => ImmBorrowLoc(self.number()?),
+            "MutBorrowField" => MutBorrowField(FieldDefinitionIndex::new(self.number()?)),
+            "ImmBorrowField" => ImmBorrowField(FieldDefinitionIndex::new(self.number()?)),
+            "MutBorrowGlobal" => {
+                let (idx, types) = self.struct_operands()?;
+                MutBorrowGlobal(idx, types)
+            }
+            "ImmBorrowGlobal" => {
+                let (idx, types) = self.struct_operands()?;
+                ImmBorrowGlobal(idx, types)
+            }
+            "Add" => Add,
+            "Sub" => Sub,
+            "Mul" => Mul,
+            "Mod" => Mod,
+            "Div" => Div,
+            "BitOr" => BitOr,
+            "BitAnd" => BitAnd,
+            "Xor" => Xor,
+            "Or" => Or,
+            "And" => And,
+            "Not" => Not,
+            "Eq" => Eq,
+            "Neq" => Neq,
+            "Lt" => Lt,
+            "Gt" => Gt,
+            "Le" => Le,
+            "Ge" => Ge,
+            "Abort" => Abort,
+            "GetTxnGasUnitPrice" => GetTxnGasUnitPrice,
+            "GetTxnMaxGasUnits" => GetTxnMaxGasUnits,
+            "GetGasRemaining" => GetGasRemaining,
+            "GetTxnSenderAddress" => GetTxnSenderAddress,
+            "Exists" => {
+                let (idx, types) = self.struct_operands()?;
+                Exists(idx, types)
+            }
+            "MoveFrom" => {
+                let (idx, types) = self.struct_operands()?;
+                MoveFrom(idx, types)
+            }
+            "MoveToSender" => {
+                let (idx, types) = self.struct_operands()?;
+                MoveToSender(idx, types)
+            }
+            "CreateAccount" => CreateAccount,
+            "GetTxnSequenceNumber" => GetTxnSequenceNumber,
+            "GetTxnPublicKey" => GetTxnPublicKey,
+            _ => bail!("line {}: unknown instruction `{}`", line, name),
+        })
+    }
+
+    fn struct_operands(&mut self) -> Result<(StructDefinitionIndex, LocalsSignatureIndex)> {
+        Ok((
+            StructDefinitionIndex::new(self.number()?),
+            LocalsSignatureIndex::new(self.number()?),
+        ))
+    }
+
+    fn type_(&mut self) -> Result<SignatureToken> {
+        if self.eat_punct('&') {
+            return Ok(if self.eat_keyword("mut") {
+                SignatureToken::MutableReference(Box::new(self.type_()?))
+            } else {
+                SignatureToken::Reference(Box::new(self.type_()?))
+            });
+        }
+        let line = self.line();
+        let name = self.name()?;
+        Ok(match name.as_str() {
+            "bool" => SignatureToken::Bool,
+            "u8" => SignatureToken::U8,
+            "u64" => SignatureToken::U64,
+            "u128" => SignatureToken::U128,
+            "string" => SignatureToken::String,
+            "bytearray" => SignatureToken::ByteArray,
+            "address" => SignatureToken::Address,
+            "struct" => {
+                let idx = StructHandleIndex::new(self.number()?);
+                let actuals = if self.peek_punct('<') {
+                    self.list('<', '>', Self::type_)?
+                } else {
+                    vec![]
+                };
+                SignatureToken::Struct(idx, actuals)
+            }
+            _ => match type_parameter(&name) {
+                Some(idx) => SignatureToken::TypeParameter(
+                    idx.parse()
+                        .map_err(|_| format_err!("line {}: invalid type parameter", line))?,
+                ),
+                None => bail!("line {}: unknown type `{}`", line, name),
+            },
+        })
+    }
+
+    fn types(&mut self) -> Result<Vec<SignatureToken>> {
+        self.list('[', ']', Self::type_)
+    }
+
+    fn kinds(&mut self) -> Result<Vec<Kind>> {
+        self.list('[', ']', |p| {
+            let line = p.line();
+            match p.name()?.as_str() {
+                "all" => Ok(Kind::All),
+                "resource" => Ok(Kind::Resource),
+                "unrestricted" => Ok(Kind::Unrestricted),
+                other => bail!("line {}: unknown kind `{}`", line, other),
+            }
+        })
+    }
+
+    // Parses `name { #0 entry #1 entry ... }`.
+    fn table<T>(
+        &mut self,
+        name: &str,
+        mut entry: impl FnMut(&mut Self) -> Result<T>,
+    ) -> Result<Vec<T>> {
+        self.keyword(name)?;
+        self.punct('{')?;
+        let mut entries = vec![];
+        while !self.eat_punct('}') {
+            let line = self.line();
+            match self.next()? {
+                Token::Entry(idx) if idx == entries.len() => entries.push(entry(self)?),
+                token => bail!(
+                    "line {}: expected entry #{} of {}, found {}",
+                    line,
+                    entries.len(),
+                    name,
+                    token
+                ),
+            }
+        }
+        Ok(entries)
+    }
+
+    // Parses a possibly empty, comma separated list between `open` and `close`.
+    fn list<T>(
+        &mut self,
+        open: char,
+        close: char,
+        mut item: impl FnMut(&mut Self) -> Result<T>,
+    ) -> Result<Vec<T>> {
+        self.punct(open)?;
+        let mut items = vec![];
+        if self.eat_punct(close) {
+            return Ok(items);
+        }
+        loop {
+            items.push(item(self)?);
+            if self.eat_punct(close) {
+                return Ok(items);
+            }
+            self.punct(',')?;
+        }
+    }
+
+    // Parses `keyword number`.
+    fn field<T: TryFrom<u128>>(&mut self, keyword: &str) -> Result<T> {
+        self.keyword(keyword)?;
+        self.number()
+    }
+
+    fn number<T: TryFrom<u128>>(&mut self) -> Result<T> {
+        let line = self.line();
+        match self.next()? {
+            Token::Number(value) => T::try_from(value)
+                .map_err(|_| format_err!("line {}: {} is out of range", line, value)),
+            token => unexpected(line, "a number", token),
+        }
+    }
+
+    fn boolean(&mut self) -> Result<bool> {
+        let line = self.line();
+        match self.name()?.as_str() {
+            "true" => Ok(true),
+            "false" => Ok(false),
+            other => bail!(
+                "line {}: expected `true` or `false`, found `{}`",
+                line,
+                other
+            ),
+        }
+    }
+
+    fn string(&mut self) -> Result<String> {
+        let line = self.line();
+        match self.next()? {
+            Token::String(string) => Ok(string),
+            token => unexpected(line, "a string", token),
+        }
+    }
+
+    fn bytes(&mut self) -> Result<Vec<u8>> {
+        let line = self.line();
+        match self.next()? {
+            Token::Bytes(bytes) => Ok(bytes),
+            token => unexpected(line, "a hex literal", token),
+        }
+    }
+
+    fn name(&mut self) -> Result<String> {
+        let line = self.line();
+        match self.next()? {
+            Token::Name(name) => Ok(name),
+            token => unexpected(line, "a name", token),
+        }
+    }
+
+    fn keyword(&mut self, keyword: &str) -> Result<()> {
+        let line = self.line();
+        match self.next()? {
+            Token::Name(ref name) if name == keyword => Ok(()),
+            token => unexpected(line, format!("`{}`", keyword), token),
+        }
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        match self.tokens.get(self.pos) {
+            Some((Token::Name(name), _)) if name == keyword => {
+                self.pos += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn punct(&mut self, punct: char) -> Result<()> {
+        let line = self.line();
+        match self.next()? {
+            Token::Punct(c) if c == punct => Ok(()),
+            token => unexpected(line, format!("`{}`", punct), token),
+        }
+    }
+
+    fn peek_punct(&self, punct: char) -> bool {
+        match self.tokens.get(self.pos) {
+            Some((Token::Punct(c), _)) => *c == punct,
+            _ => false,
+        }
+    }
+
+    fn eat_punct(&mut self, punct: char) -> bool {
+        let found = self.peek_punct(punct);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn next(&mut self) -> Result<Token> {
+        match self.tokens.get(self.pos) {
+            Some((token, _)) => {
+                self.pos += 1;
+                Ok(token.clone())
+            }
+            None => bail!("unexpected end of input"),
+        }
+    }
+
+    // The line of the next token, or of the last one at the end of the input.
+    fn line(&self) -> usize {
+        self.tokens
+            .get(self.pos)
+            .or_else(|| self.tokens.last())
+            .map_or(1, |(_, line)| *line)
+    }
+}
+
+// Type parameters are written as `T` followed by their index.
+fn type_parameter(name: &str) -> Option<&str> {
+    if name.starts_with('T') && name.len() > 1 {
+        Some(&name[1..])
+    } else {
+        None
+    }
+}
+
+fn unexpected<T>(line: usize, expected: impl Display, found: Token) -> Result<T> {
+    bail!("line {}: expected {}, found {}", line, expected, found)
+}
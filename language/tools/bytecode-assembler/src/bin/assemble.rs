@@ -0,0 +1,45 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use bytecode_assembler::assembler::assemble_module;
+use failure::prelude::*;
+use libra_types::transaction::Module;
+use std::{fs, path::PathBuf};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+#[structopt(
+    name = "assemble",
+    about = "Assembles a Move module from the bytecode assembly syntax."
+)]
+struct Args {
+    /// Assembly source to assemble
+    #[structopt(parse(from_os_str))]
+    pub source_path: PathBuf,
+    /// Output file. With the `mv` extension, the module is written in the format of the IR
+    /// compiler, ready to be published with `dev publish`; otherwise the raw bytes are written.
+    /// Defaults to the source path with the `mv` extension
+    #[structopt(short = "o", long = "output", parse(from_os_str))]
+    pub output_path: Option<PathBuf>,
+}
+
+fn main() -> Result<()> {
+    let args = Args::from_args();
+    let source = fs::read_to_string(&args.source_path)?;
+    let module = assemble_module(&source)?;
+
+    // The module is serialized as written, without bounds checks, so that malformed modules can
+    // be fed to the verifier.
+    let mut bytes = vec![];
+    module.serialize(&mut bytes)?;
+
+    let output_path = args
+        .output_path
+        .unwrap_or_else(|| args.source_path.with_extension("mv"));
+    if output_path.extension().map_or(false, |ext| ext == "mv") {
+        fs::write(&output_path, serde_json::to_vec(&Module::new(bytes))?)?;
+    } else {
+        fs::write(&output_path, bytes)?;
+    }
+    Ok(())
+}
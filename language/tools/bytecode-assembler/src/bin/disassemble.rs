@@ -0,0 +1,99 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use bytecode_assembler::disassembler::disassemble_module;
+use failure::prelude::*;
+use grpcio::EnvBuilder;
+use libra_types::{
+    access_path::AccessPath, account_address::AccountAddress, identifier::Identifier,
+    language_storage::ModuleId, transaction::Module,
+};
+use std::{
+    collections::BTreeMap,
+    convert::TryFrom,
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+};
+use storage_client::{StorageRead, StorageReadServiceClient};
+use structopt::StructOpt;
+use vm::file_format::CompiledModuleMut;
+
+#[derive(Debug, StructOpt)]
+#[structopt(
+    name = "disassemble",
+    about = "Prints a compiled Move module in the bytecode assembly syntax."
+)]
+struct Args {
+    /// Serialized module to disassemble. Files with the `mv` extension are read as the output of
+    /// the IR compiler, anything else as the raw module bytes
+    #[structopt(parse(from_os_str), required_unless = "module")]
+    pub module_path: Option<PathBuf>,
+    /// Name of a module to read from storage instead of a file
+    #[structopt(long = "module", requires = "address", conflicts_with = "module_path")]
+    pub module: Option<String>,
+    /// Account the module is published under
+    #[structopt(short = "a", long = "address")]
+    pub address: Option<String>,
+    /// Host of the storage service to read the module from
+    #[structopt(long = "storage-host", default_value = "localhost")]
+    pub storage_host: String,
+    /// Port of the storage service to read the module from
+    #[structopt(long = "storage-port", default_value = "6184")]
+    pub storage_port: u16,
+    /// Write the assembly to this file instead of stdout
+    #[structopt(short = "o", long = "output", parse(from_os_str))]
+    pub output_path: Option<PathBuf>,
+}
+
+fn read_file(path: &Path) -> Result<Vec<u8>> {
+    let bytes = fs::read(path)?;
+    if path.extension().map_or(false, |ext| ext == "mv") {
+        let module: Module = serde_json::from_slice(&bytes)?;
+        Ok(module.code().to_vec())
+    } else {
+        Ok(bytes)
+    }
+}
+
+// Reads the latest version of a module from the code section of its account.
+fn read_from_storage(args: &Args, address: &str, name: &str) -> Result<Vec<u8>> {
+    let address = AccountAddress::from_str(&format!("{:0>64}", address.trim_start_matches("0x")))?;
+    let module_id = ModuleId::new(address, Identifier::new(name)?);
+    let client = StorageReadServiceClient::new(
+        Arc::new(EnvBuilder::new().name_prefix("grpc-disasm-sto-").build()),
+        &args.storage_host,
+        args.storage_port,
+    );
+    let version = client
+        .get_startup_info()?
+        .ok_or_else(|| format_err!("Storage is empty"))?
+        .latest_version;
+    let (blob, _) = client.get_account_state_with_proof_by_version(address, version)?;
+    let blob = blob.ok_or_else(|| format_err!("Account {} does not exist", address))?;
+    let state = BTreeMap::<Vec<u8>, Vec<u8>>::try_from(&blob)?;
+    state
+        .get(&AccessPath::code_access_path(&module_id).path)
+        .cloned()
+        .ok_or_else(|| format_err!("Module {} is not published under {}", name, address))
+}
+
+fn main() -> Result<()> {
+    let args = Args::from_args();
+    let bytes = match (&args.module_path, &args.module, &args.address) {
+        (Some(path), _, _) => read_file(path)?,
+        (None, Some(name), Some(address)) => read_from_storage(&args, address, name)?,
+        _ => bail!("Either a module file or a module name and address are required"),
+    };
+
+    // Bounds are not checked so that malformed modules can be inspected too.
+    let module = CompiledModuleMut::deserialize_no_check_bounds(&bytes)
+        .map_err(|status| format_err!("Unable to deserialize module: {:?}", status))?;
+    let assembly = disassemble_module(&module);
+    match &args.output_path {
+        Some(path) => fs::write(path, assembly)?,
+        None => print!("{}", assembly),
+    }
+    Ok(())
+}
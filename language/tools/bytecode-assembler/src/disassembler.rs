@@ -0,0 +1,308 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Prints a `CompiledModuleMut` in the assembly syntax accepted by the assembler.
+//!
+//! The output lists every table of the module in file format order. Each entry is prefixed with
+//! its index, and references between tables are printed as the raw index they hold, so that the
+//! output describes exactly the bytes of the module, including modules that fail bounds checking.
+//! Comments (`// ...`) naming the functions and structs being defined are added for readability
+//! and ignored by the assembler.
+
+use libra_types::identifier::IdentStr;
+use std::fmt::{self, Write};
+use vm::file_format::{
+    Bytecode, CompiledModuleMut, FunctionDefinition, FunctionHandleIndex, IdentifierIndex, Kind,
+    SignatureToken, StructFieldInformation, StructHandleIndex,
+};
+
+const INDENT: &str = "    ";
+
+/// Returns the assembly for `module`.
+pub fn disassemble_module(module: &CompiledModuleMut) -> String {
+    let mut out = String::new();
+    write_module(&mut out, module).expect("writing to a String cannot fail");
+    out
+}
+
+fn write_module(out: &mut String, module: &CompiledModuleMut) -> fmt::Result {
+    writeln!(out, "module {{")?;
+
+    write_table(
+        out,
+        "module_handles",
+        &module.module_handles,
+        |out, handle| write!(out, "address {} name {}", handle.address.0, handle.name.0),
+    )?;
+    write_table(
+        out,
+        "struct_handles",
+        &module.struct_handles,
+        |out, handle| {
+            write!(
+                out,
+                "module {} name {} resource {} type_formals ",
+                handle.module.0, handle.name.0, handle.is_nominal_resource
+            )?;
+            write_kinds(out, &handle.type_formals)
+        },
+    )?;
+    write_table(
+        out,
+        "function_handles",
+        &module.function_handles,
+        |out, handle| {
+            write!(
+                out,
+                "module {} name {} signature {}",
+                handle.module.0, handle.name.0, handle.signature.0
+            )
+        },
+    )?;
+    write_table(
+        out,
+        "type_signatures",
+        &module.type_signatures,
+        |out, signature| write_type(out, &signature.0),
+    )?;
+    write_table(
+        out,
+        "function_signatures",
+        &module.function_signatures,
+        |out, signature| {
+            write!(out, "type_formals ")?;
+            write_kinds(out, &signature.type_formals)?;
+            write!(out, " args ")?;
+            write_types(out, &signature.arg_types)?;
+            write!(out, " returns ")?;
+            write_types(out, &signature.return_types)
+        },
+    )?;
+    write_table(
+        out,
+        "locals_signatures",
+        &module.locals_signatures,
+        |out, signature| write_types(out, &signature.0),
+    )?;
+    write_table(
+        out,
+        "identifiers",
+        &module.identifiers,
+        |out, identifier| write!(out, "{:?}", identifier.as_str()),
+    )?;
+    write_table(out, "user_strings", &module.user_strings, |out, string| {
+        write!(out, "{:?}", string.as_str())
+    })?;
+    write_table(out, "byte_arrays", &module.byte_array_pool, |out, bytes| {
+        write!(out, "0x{}", hex::encode(bytes.as_bytes()))
+    })?;
+    write_table(out, "addresses", &module.address_pool, |out, address| {
+        write!(out, "0x{:x}", address)
+    })?;
+    write_table(out, "struct_defs", &module.struct_defs, |out, def| {
+        write!(out, "handle {} ", def.struct_handle.0)?;
+        match &def.field_information {
+            StructFieldInformation::Native => write!(out, "native")?,
+            StructFieldInformation::Declared {
+                field_count,
+                fields,
+            } => write!(out, "fields {} count {}", fields.0, field_count)?,
+        }
+        write_struct_name(out, module, def.struct_handle)
+    })?;
+    write_table(out, "field_defs", &module.field_defs, |out, def| {
+        write!(
+            out,
+            "struct {} name {} type {}",
+            def.struct_.0, def.name.0, def.signature.0
+        )
+    })?;
+    write_table(out, "function_defs", &module.function_defs, |out, def| {
+        write_function_def(out, module, def)
+    })?;
+
+    writeln!(out, "}}")
+}
+
+fn write_table<T>(
+    out: &mut String,
+    name: &str,
+    entries: &[T],
+    mut write_entry: impl FnMut(&mut String, &T) -> fmt::Result,
+) -> fmt::Result {
+    if entries.is_empty() {
+        return writeln!(out, "{}{} {{}}", INDENT, name);
+    }
+    writeln!(out, "{}{} {{", INDENT, name)?;
+    for (idx, entry) in entries.iter().enumerate() {
+        write!(out, "{}{}#{} ", INDENT, INDENT, idx)?;
+        write_entry(out, entry)?;
+        writeln!(out)?;
+    }
+    writeln!(out, "{}}}", INDENT)
+}
+
+fn write_function_def(
+    out: &mut String,
+    module: &CompiledModuleMut,
+    def: &FunctionDefinition,
+) -> fmt::Result {
+    write!(
+        out,
+        "function {} flags {} acquires [",
+        def.function.0, def.flags
+    )?;
+    for (idx, struct_def) in def.acquires_global_resources.iter().enumerate() {
+        if idx > 0 {
+            write!(out, ", ")?;
+        }
+        write!(out, "{}", struct_def.0)?;
+    }
+    write!(
+        out,
+        "] locals {} max_stack {} {{",
+        def.code.locals.0, def.code.max_stack_size
+    )?;
+    write_function_name(out, module, def.function)?;
+    writeln!(out)?;
+    for (offset, bytecode) in def.code.code.iter().enumerate() {
+        write!(out, "{}{}{}{}: ", INDENT, INDENT, INDENT, offset)?;
+        write_bytecode(out, bytecode)?;
+        writeln!(out)?;
+    }
+    write!(out, "{}{}}}", INDENT, INDENT)
+}
+
+fn write_bytecode(out: &mut String, bytecode: &Bytecode) -> fmt::Result {
+    use Bytecode::*;
+
+    match bytecode {
+        BrTrue(offset) => write!(out, "BrTrue {}", offset),
+        BrFalse(offset) => write!(out, "BrFalse {}", offset),
+        Branch(offset) => write!(out, "Branch {}", offset),
+        LdU8(value) => write!(out, "LdU8 {}", value),
+        LdConst(value) => write!(out, "LdConst {}", value),
+        LdU128(value) => write!(out, "LdU128 {}", value),
+        LdStr(idx) => write!(out, "LdStr {}", idx.0),
+        LdByteArray(idx) => write!(out, "LdByteArray {}", idx.0),
+        LdAddr(idx) => write!(out, "LdAddr {}", idx.0),
+        CopyLoc(idx) => write!(out, "CopyLoc {}", idx),
+        MoveLoc(idx) => write!(out, "MoveLoc {}", idx),
+        StLoc(idx) => write!(out, "StLoc {}", idx),
+        MutBorrowLoc(idx) => write!(out, "MutBorrowLoc {}", idx),
+        ImmBorrowLoc(idx) => write!(out, "ImmBorrowLoc {}", idx),
+        MutBorrowField(idx) => write!(out, "MutBorrowField {}", idx.0),
+        ImmBorrowField(idx) => write!(out, "ImmBorrowField {}", idx.0),
+        Call(idx, types) => write!(out, "Call {} {}", idx.0, types.0),
+        Pack(idx, types) => write!(out, "Pack {} {}", idx.0, types.0),
+        Unpack(idx, types) => write!(out, "Unpack {} {}", idx.0, types.0),
+        MutBorrowGlobal(idx, types) => write!(out, "MutBorrowGlobal {} {}", idx.0, types.0),
+        ImmBorrowGlobal(idx, types) => write!(out, "ImmBorrowGlobal {} {}", idx.0, types.0),
+        Exists(idx, types) => write!(out, "Exists {} {}", idx.0, types.0),
+        MoveFrom(idx, types) => write!(out, "MoveFrom {} {}", idx.0, types.0),
+        MoveToSender(idx, types) => write!(out, "MoveToSender {} {}", idx.0, types.0),
+        // The remaining instructions have no operands and are printed by name.
+        _ => write!(out, "{:?}", bytecode),
+    }
+}
+
+fn write_type(out: &mut String, token: &SignatureToken) -> fmt::Result {
+    match token {
+        SignatureToken::Bool => write!(out, "bool"),
+        SignatureToken::U8 => write!(out, "u8"),
+        SignatureToken::U64 => write!(out, "u64"),
+        SignatureToken::U128 => write!(out, "u128"),
+        SignatureToken::String => write!(out, "string"),
+        SignatureToken::ByteArray => write!(out, "bytearray"),
+        SignatureToken::Address => write!(out, "address"),
+        SignatureToken::Struct(idx, actuals) => {
+            write!(out, "struct {}", idx.0)?;
+            if !actuals.is_empty() {
+                write!(out, "<")?;
+                write_type_list(out, actuals)?;
+                write!(out, ">")?;
+            }
+            Ok(())
+        }
+        SignatureToken::Reference(inner) => {
+            write!(out, "&")?;
+            write_type(out, inner)
+        }
+        SignatureToken::MutableReference(inner) => {
+            write!(out, "&mut ")?;
+            write_type(out, inner)
+        }
+        SignatureToken::TypeParameter(idx) => write!(out, "T{}", idx),
+    }
+}
+
+fn write_types(out: &mut String, tokens: &[SignatureToken]) -> fmt::Result {
+    write!(out, "[")?;
+    write_type_list(out, tokens)?;
+    write!(out, "]")
+}
+
+fn write_type_list(out: &mut String, tokens: &[SignatureToken]) -> fmt::Result {
+    for (idx, token) in tokens.iter().enumerate() {
+        if idx > 0 {
+            write!(out, ", ")?;
+        }
+        write_type(out, token)?;
+    }
+    Ok(())
+}
+
+fn write_kinds(out: &mut String, kinds: &[Kind]) -> fmt::Result {
+    write!(out, "[")?;
+    for (idx, kind) in kinds.iter().enumerate() {
+        if idx > 0 {
+            write!(out, ", ")?;
+        }
+        match kind {
+            Kind::All => write!(out, "all")?,
+            Kind::Resource => write!(out, "resource")?,
+            Kind::Unrestricted => write!(out, "unrestricted")?,
+        }
+    }
+    write!(out, "]")
+}
+
+// Names are looked up defensively since the module may not pass bounds checking. Names that
+// would end the comment they are printed in are skipped.
+fn identifier(module: &CompiledModuleMut, idx: IdentifierIndex) -> Option<&IdentStr> {
+    module
+        .identifiers
+        .get(idx.0 as usize)
+        .map(|identifier| identifier.as_ident_str())
+        .filter(|name| !name.as_str().contains('\n'))
+}
+
+fn write_struct_name(
+    out: &mut String,
+    module: &CompiledModuleMut,
+    idx: StructHandleIndex,
+) -> fmt::Result {
+    let name = module
+        .struct_handles
+        .get(idx.0 as usize)
+        .and_then(|handle| identifier(module, handle.name));
+    match name {
+        Some(name) => write!(out, " // {}", name),
+        None => Ok(()),
+    }
+}
+
+fn write_function_name(
+    out: &mut String,
+    module: &CompiledModuleMut,
+    idx: FunctionHandleIndex,
+) -> fmt::Result {
+    let name = module
+        .function_handles
+        .get(idx.0 as usize)
+        .and_then(|handle| identifier(module, handle.name));
+    match name {
+        Some(name) => write!(out, " // {}", name),
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,158 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use failure::prelude::*;
+use std::{fmt, iter::Peekable, str::CharIndices};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) enum Token {
+    /// A keyword or instruction name.
+    Name(String),
+    /// A decimal integer.
+    Number(u128),
+    /// `0x` followed by an even number of hex digits.
+    Bytes(Vec<u8>),
+    /// A double quoted string with Rust escapes.
+    String(String),
+    /// `#` followed by a decimal integer, the index of a table entry.
+    Entry(usize),
+    /// One of `{ } [ ] < > , : &`.
+    Punct(char),
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Token::Name(name) => write!(f, "`{}`", name),
+            Token::Number(value) => write!(f, "`{}`", value),
+            Token::Bytes(bytes) => write!(f, "`0x{}`", hex::encode(bytes)),
+            Token::String(string) => write!(f, "{:?}", string),
+            Token::Entry(idx) => write!(f, "`#{}`", idx),
+            Token::Punct(c) => write!(f, "`{}`", c),
+        }
+    }
+}
+
+/// A token and the 1-based line it starts on.
+pub(crate) type Spanned = (Token, usize);
+
+pub(crate) fn tokenize(source: &str) -> Result<Vec<Spanned>> {
+    let mut tokens = vec![];
+    let mut chars = source.char_indices().peekable();
+    let mut line = 1;
+    while let Some((start, c)) = chars.next() {
+        let token = match c {
+            '\n' => {
+                line += 1;
+                continue;
+            }
+            c if c.is_whitespace() => continue,
+            '/' if chars.peek().map(|(_, c)| *c) == Some('/') => {
+                while chars.peek().map_or(false, |(_, c)| *c != '\n') {
+                    chars.next();
+                }
+                continue;
+            }
+            '{' | '}' | '[' | ']' | '<' | '>' | ',' | ':' | '&' => Token::Punct(c),
+            '#' => {
+                let digits = take_while(source, start + 1, &mut chars, |c| c.is_ascii_digit());
+                let idx = digits
+                    .parse()
+                    .map_err(|_| format_err!("line {}: expected an index after `#`", line))?;
+                Token::Entry(idx)
+            }
+            '"' => Token::String(string_literal(&mut chars, line)?),
+            '0' if chars.peek().map(|(_, c)| *c) == Some('x') => {
+                chars.next();
+                let digits = take_while(source, start + 2, &mut chars, |c| c.is_ascii_hexdigit());
+                let bytes = hex::decode(digits)
+                    .map_err(|err| format_err!("line {}: invalid hex literal: {}", line, err))?;
+                Token::Bytes(bytes)
+            }
+            c if c.is_ascii_digit() => {
+                let digits = take_while(source, start, &mut chars, |c| c.is_ascii_digit());
+                let value = digits
+                    .parse()
+                    .map_err(|_| format_err!("line {}: integer {} is too large", line, digits))?;
+                Token::Number(value)
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let name = take_while(source, start, &mut chars, |c| {
+                    c.is_ascii_alphanumeric() || c == '_'
+                });
+                Token::Name(name.to_string())
+            }
+            c => bail!("line {}: unexpected character {:?}", line, c),
+        };
+        tokens.push((token, line));
+    }
+    Ok(tokens)
+}
+
+// Consumes the characters following the one at `start` while they match `pred` and returns the
+// slice of `source` from `start` to the last character consumed.
+fn take_while<'a>(
+    source: &'a str,
+    start: usize,
+    chars: &mut Peekable<CharIndices>,
+    pred: impl Fn(char) -> bool,
+) -> &'a str {
+    let mut end = source.len();
+    while let Some((idx, c)) = chars.peek() {
+        if !pred(*c) {
+            end = *idx;
+            break;
+        }
+        chars.next();
+    }
+    &source[start..end]
+}
+
+// Parses the rest of a string literal, undoing the escapes `{:?}` produces for a `str`.
+fn string_literal(chars: &mut Peekable<CharIndices>, line: usize) -> Result<String> {
+    let mut string = String::new();
+    loop {
+        let c = match chars.next() {
+            Some((_, c)) => c,
+            None => bail!("line {}: unterminated string literal", line),
+        };
+        match c {
+            '"' => return Ok(string),
+            '\\' => {
+                let escaped = match chars.next().map(|(_, c)| c) {
+                    Some('n') => '\n',
+                    Some('r') => '\r',
+                    Some('t') => '\t',
+                    Some('0') => '\0',
+                    Some('\\') => '\\',
+                    Some('"') => '"',
+                    Some('\'') => '\'',
+                    Some('u') => unicode_escape(chars, line)?,
+                    other => bail!("line {}: invalid escape {:?}", line, other),
+                };
+                string.push(escaped);
+            }
+            c => string.push(c),
+        }
+    }
+}
+
+fn unicode_escape(chars: &mut Peekable<CharIndices>, line: usize) -> Result<char> {
+    ensure!(
+        chars.next().map(|(_, c)| c) == Some('{'),
+        "line {}: expected `{{` in unicode escape",
+        line
+    );
+    let mut digits = String::new();
+    loop {
+        match chars.next().map(|(_, c)| c) {
+            Some('}') => break,
+            Some(c) if c.is_ascii_hexdigit() => digits.push(c),
+            _ => bail!("line {}: invalid unicode escape", line),
+        }
+    }
+    u32::from_str_radix(&digits, 16)
+        .ok()
+        .and_then(std::char::from_u32)
+        .ok_or_else(|| format_err!("line {}: invalid unicode escape", line))
+}
@@ -0,0 +1,13 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A textual assembly format for compiled Move modules.
+//!
+//! Unlike the IR, the assembly describes the tables of a `CompiledModule` directly, so any
+//! module, including one the IR compiler could not produce or one that fails verification, can
+//! be disassembled, edited by hand and assembled back. Assembling the disassembly of a module and
+//! serializing it reproduces the original bytes.
+
+pub mod assembler;
+pub mod disassembler;
+mod lexer;
@@ -0,0 +1,85 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use bytecode_assembler::{assembler::assemble_module, disassembler::disassemble_module};
+use proptest::prelude::*;
+use stdlib::stdlib_modules;
+use vm::file_format::{Bytecode, CompiledModule, CompiledModuleMut};
+
+fn assert_round_trip(module: &CompiledModuleMut) {
+    let mut serialized = vec![];
+    module.serialize(&mut serialized).unwrap();
+
+    let assembly = disassemble_module(module);
+    let assembled = assemble_module(&assembly)
+        .unwrap_or_else(|err| panic!("failed to assemble:\n{}\n{}", assembly, err));
+    let mut reserialized = vec![];
+    assembled.serialize(&mut reserialized).unwrap();
+    assert_eq!(&assembled, module);
+    assert_eq!(reserialized, serialized);
+}
+
+#[test]
+fn stdlib_round_trip() {
+    for module in stdlib_modules() {
+        assert_round_trip(module.as_inner().as_inner());
+    }
+}
+
+#[test]
+fn hand_patched_module() {
+    // Hand-patch the first instruction of the first function that has code.
+    let (assembly, first) = stdlib_modules()
+        .iter()
+        .map(|module| disassemble_module(module.as_inner().as_inner()))
+        .find_map(|assembly| {
+            let first = assembly.find("            0: ")?;
+            Some((assembly, first))
+        })
+        .unwrap();
+    let end = first + assembly[first..].find('\n').unwrap();
+    let patched = format!(
+        "{}            0: LdU8 255 // patched{}",
+        &assembly[..first],
+        &assembly[end..]
+    );
+    let assembled = assemble_module(&patched).unwrap();
+    let function = assembled
+        .function_defs
+        .iter()
+        .find(|def| !def.code.code.is_empty())
+        .unwrap();
+    assert_eq!(function.code.code[0], Bytecode::LdU8(255));
+}
+
+#[test]
+fn reports_lines_of_errors() {
+    let assembly = "module {\n    module_handles {\n        #1 address 0 name 0\n    }\n}";
+    let err = assemble_module(assembly).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "line 3: expected entry #0 of module_handles, found `#1`"
+    );
+
+    let assembly = "module {\n    module_handles {}\n    struct_handles {\n";
+    let err = assemble_module(assembly).unwrap_err();
+    assert_eq!(err.to_string(), "unexpected end of input");
+}
+
+proptest! {
+    #[test]
+    fn valid_module_round_trip(module in CompiledModule::valid_strategy(20)) {
+        assert_round_trip(module.as_inner());
+    }
+}
+
+proptest! {
+    // Generating arbitrary compiled modules is slow, see the serializer tests.
+    #![proptest_config(ProptestConfig::with_cases(16))]
+
+    /// Modules that fail bounds checking and arbitrary strings survive the round trip.
+    #[test]
+    fn garbage_module_round_trip(module in any_with::<CompiledModuleMut>(16)) {
+        assert_round_trip(&module);
+    }
+}
@@ -0,0 +1,55 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use bytecode_verifier::CompatibilityChecker;
+use failure::prelude::*;
+use libra_types::transaction::Module;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process,
+};
+use structopt::StructOpt;
+use vm::file_format::CompiledModule;
+
+#[derive(Debug, StructOpt)]
+#[structopt(
+    name = "compatibility-checker",
+    about = "Reports the changes in a new version of a Move module that break the published one."
+)]
+struct Args {
+    /// The published version of the module
+    #[structopt(parse(from_os_str))]
+    pub old_module_path: PathBuf,
+    /// The version of the module to be published. Files with the `mv` extension are read as the
+    /// output of the IR compiler, anything else as the raw module bytes
+    #[structopt(parse(from_os_str))]
+    pub new_module_path: PathBuf,
+}
+
+fn read_module(path: &Path) -> Result<CompiledModule> {
+    let mut bytes = fs::read(path)?;
+    if path.extension().map_or(false, |ext| ext == "mv") {
+        let module: Module = serde_json::from_slice(&bytes)?;
+        bytes = module.code().to_vec();
+    }
+    CompiledModule::deserialize(&bytes)
+        .map_err(|status| format_err!("Unable to deserialize {}: {:?}", path.display(), status))
+}
+
+fn main() -> Result<()> {
+    let args = Args::from_args();
+    let old_module = read_module(&args.old_module_path)?;
+    let new_module = read_module(&args.new_module_path)?;
+
+    let errors = CompatibilityChecker::new(&old_module, &new_module).check();
+    if errors.is_empty() {
+        println!("The new version is compatible");
+        return Ok(());
+    }
+    println!("Found {} breaking changes:", errors.len());
+    for error in errors {
+        println!("  {}", error);
+    }
+    process::exit(1);
+}
@@ -0,0 +1,173 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use bytecode_verifier::{CompatibilityChecker, CompatibilityError};
+use ir_to_bytecode::{compiler::compile_module, parser::parse_module};
+use libra_types::{account_address::AccountAddress, identifier::Identifier};
+use vm::file_format::CompiledModule;
+
+const BASE: &str = "
+module M {
+    resource T { value: u64 }
+    struct S { flag: bool }
+
+    public get(addr: address): u64 acquires T {
+        let t: &Self.T;
+        t = borrow_global<T>(move(addr));
+        return *&move(t).value;
+    }
+    public make(flag: bool): Self.S {
+        return S { flag: move(flag) };
+    }
+    helper(): u64 {
+        return 0;
+    }
+}";
+
+fn compile(code: &str) -> CompiledModule {
+    let deps: Vec<CompiledModule> = vec![];
+    compile_module(
+        AccountAddress::default(),
+        parse_module(code).unwrap(),
+        &deps,
+    )
+    .unwrap()
+}
+
+fn check(new: &str) -> Vec<CompatibilityError> {
+    let (old, new) = (compile(BASE), compile(new));
+    CompatibilityChecker::new(&old, &new).check()
+}
+
+fn ident(name: &str) -> Identifier {
+    Identifier::new(name).unwrap()
+}
+
+#[test]
+fn identical_module_is_compatible() {
+    assert_eq!(check(BASE), vec![]);
+}
+
+#[test]
+fn additions_and_private_changes_are_compatible() {
+    let new = "
+module M {
+    struct U { count: u64 }
+    struct S { flag: bool }
+    resource T { value: u64 }
+
+    helper(x: u64): u64 {
+        return move(x);
+    }
+    public make(flag: bool): Self.S {
+        return S { flag: move(flag) };
+    }
+    public get(addr: address): u64 acquires T {
+        return Self.helper(0);
+    }
+    public extra(): u64 acquires T {
+        return 1;
+    }
+}";
+    // `get` no longer borrows `T` but still declares that it acquires it.
+    assert_eq!(check(new), vec![]);
+}
+
+#[test]
+fn removed_and_changed_functions_break() {
+    let new = "
+module M {
+    resource T { value: u64 }
+    struct S { flag: bool }
+
+    public get(addr: address, extra: u64): u64 acquires T {
+        return move(extra);
+    }
+    make(flag: bool): Self.S {
+        return S { flag: move(flag) };
+    }
+}";
+    assert_eq!(
+        check(new),
+        vec![
+            CompatibilityError::PublicFunctionRemoved(ident("make")),
+            CompatibilityError::PublicFunctionSignatureChanged(ident("get")),
+        ]
+    );
+}
+
+#[test]
+fn removed_acquires_break() {
+    let new = "
+module M {
+    resource T { value: u64 }
+    struct S { flag: bool }
+
+    public get(addr: address): u64 {
+        return 0;
+    }
+    public make(flag: bool): Self.S {
+        return S { flag: move(flag) };
+    }
+}";
+    assert_eq!(
+        check(new),
+        vec![CompatibilityError::AcquiresRemoved {
+            function: ident("get"),
+            resource: ident("T"),
+        }]
+    );
+}
+
+#[test]
+fn changed_structs_break() {
+    let new = "
+module M {
+    resource T { value: u64, extra: bool }
+    resource S { flag: bool }
+
+    public get(addr: address): u64 acquires T {
+        return 0;
+    }
+    public make(flag: bool): u64 {
+        return 0;
+    }
+}";
+    assert_eq!(
+        check(new),
+        vec![
+            CompatibilityError::PublicFunctionSignatureChanged(ident("make")),
+            CompatibilityError::StructKindChanged(ident("S")),
+            CompatibilityError::StructLayoutChanged(ident("T")),
+        ]
+    );
+}
+
+#[test]
+fn removed_struct_breaks() {
+    let new = "
+module M {
+    resource T { value: u64 }
+
+    public get(addr: address): u64 acquires T {
+        return 0;
+    }
+    public make(flag: bool): bool {
+        return move(flag);
+    }
+}";
+    let errors = check(new);
+    assert!(errors.contains(&CompatibilityError::StructRemoved(ident("S"))));
+}
+
+#[test]
+fn renamed_module_is_rejected() {
+    let new = BASE.replace("module M", "module N");
+    match check(&new).as_slice() {
+        [CompatibilityError::ModuleIdMismatch { old, new }] => {
+            assert_eq!(old.name().as_str(), "M");
+            assert_eq!(new.name().as_str(), "N");
+        }
+        errors => panic!("unexpected errors {:?}", errors),
+    }
+}
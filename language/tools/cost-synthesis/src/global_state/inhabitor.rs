@@ -145,7 +145,9 @@ where
     pub fn inhabit(&mut self, sig_token: &SignatureToken) -> Value {
         match sig_token {
             SignatureToken::Bool => Value::bool(self.next_bool()),
+            SignatureToken::U8 => Value::u8(self.gen.gen()),
             SignatureToken::U64 => Value::u64(self.next_int()),
+            SignatureToken::U128 => Value::u128(self.gen.gen()),
             SignatureToken::String => Value::string(self.next_vm_string()),
             SignatureToken::Address => Value::address(self.next_addr()),
             SignatureToken::Reference(sig) | SignatureToken::MutableReference(sig) => {
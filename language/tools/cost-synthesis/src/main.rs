@@ -140,7 +140,12 @@ fn stack_instructions(options: &Opt) {
         Abort,
         LdFalse,
         LdTrue,
+        LdU8(0),
         LdConst(0),
+        LdU128(0),
+        CastU8,
+        CastU64,
+        CastU128,
         LdStr(UserStringIndex::new(0)),
         LdByteArray(ByteArrayPoolIndex::new(0)),
         LdAddr(AddressPoolIndex::new(0)),
@@ -383,10 +383,12 @@ where
                 let index = self.next_bounded_index(frame_len as TableIndex);
                 (Branch(index as CodeOffset), 1)
             }
+            LdU8(_) => (LdU8(self.gen.gen()), 1),
             LdConst(_) => {
                 let i = self.next_int(&[]);
                 (LdConst(i), 1)
             }
+            LdU128(_) => (LdU128(self.gen.gen()), 1),
             LdStr(_) => {
                 let string_idx = self.next_user_string_idx();
                 let string_size = self.root_module.user_string_at(string_idx).len();
@@ -496,7 +498,9 @@ where
     fn resolve_to_value(&mut self, sig_token: &SignatureToken, stk: &[Value]) -> Value {
         match sig_token {
             SignatureToken::Bool => Value::bool(self.next_bool()),
+            SignatureToken::U8 => Value::u8(self.gen.gen()),
             SignatureToken::U64 => Value::u64(self.next_int(stk)),
+            SignatureToken::U128 => Value::u128(self.gen.gen()),
             SignatureToken::String => Value::string(self.next_vm_string(false)),
             SignatureToken::Address => Value::address(self.next_addr(false)),
             SignatureToken::Reference(sig) | SignatureToken::MutableReference(sig) => {
@@ -0,0 +1,59 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use failure::prelude::*;
+use libra_types::transaction::Module;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use stdlib::stdlib_modules;
+use structopt::StructOpt;
+use vm::file_format::CompiledModule;
+use vm_runtime::coverage::{CoverageMap, ModuleCoverageReport};
+
+#[derive(Debug, StructOpt)]
+#[structopt(
+    name = "coverage-report",
+    about = "Reports the functions and basic blocks of Move modules left uncovered by a test run."
+)]
+struct Args {
+    /// The coverage file written by a test run with `MOVE_COVERAGE_FILE` set
+    #[structopt(parse(from_os_str))]
+    pub coverage_path: PathBuf,
+    /// The modules to report on, the standard library if none are given. Files with the `mv`
+    /// extension are read as the output of the IR compiler, anything else as the raw module bytes
+    #[structopt(parse(from_os_str))]
+    pub module_paths: Vec<PathBuf>,
+}
+
+fn read_module(path: &Path) -> Result<CompiledModule> {
+    let mut bytes = fs::read(path)?;
+    if path.extension().map_or(false, |ext| ext == "mv") {
+        let module: Module = serde_json::from_slice(&bytes)?;
+        bytes = module.code().to_vec();
+    }
+    CompiledModule::deserialize(&bytes)
+        .map_err(|status| format_err!("Unable to deserialize {}: {:?}", path.display(), status))
+}
+
+fn main() -> Result<()> {
+    let args = Args::from_args();
+    let coverage = CoverageMap::read_from_file(&args.coverage_path)?;
+    let modules = if args.module_paths.is_empty() {
+        stdlib_modules()
+            .iter()
+            .map(|module| module.as_inner().clone())
+            .collect()
+    } else {
+        args.module_paths
+            .iter()
+            .map(|path| read_module(path))
+            .collect::<Result<Vec<_>>>()?
+    };
+
+    for module in &modules {
+        println!("{}", ModuleCoverageReport::new(module, &coverage));
+    }
+    Ok(())
+}
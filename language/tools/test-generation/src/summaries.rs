@@ -42,12 +42,54 @@ pub fn instruction_summary(instruction: Bytecode) -> Summary {
             ],
             effects: vec![state_stack_pop!()],
         },
+        Bytecode::LdU8(_) => Summary {
+            preconditions: vec![],
+            effects: vec![state_stack_push!(AbstractValue::new_primitive(
+                SignatureToken::U8
+            ))],
+        },
         Bytecode::LdConst(_) => Summary {
             preconditions: vec![],
             effects: vec![state_stack_push!(AbstractValue::new_primitive(
                 SignatureToken::U64
             ))],
         },
+        Bytecode::LdU128(_) => Summary {
+            preconditions: vec![],
+            effects: vec![state_stack_push!(AbstractValue::new_primitive(
+                SignatureToken::U128
+            ))],
+        },
+        Bytecode::CastU8 => Summary {
+            preconditions: vec![state_stack_has!(
+                0,
+                Some(AbstractValue::new_primitive(SignatureToken::U8))
+            )],
+            effects: vec![
+                state_stack_pop!(),
+                state_stack_push!(AbstractValue::new_primitive(SignatureToken::U8)),
+            ],
+        },
+        Bytecode::CastU64 => Summary {
+            preconditions: vec![state_stack_has!(
+                0,
+                Some(AbstractValue::new_primitive(SignatureToken::U64))
+            )],
+            effects: vec![
+                state_stack_pop!(),
+                state_stack_push!(AbstractValue::new_primitive(SignatureToken::U64)),
+            ],
+        },
+        Bytecode::CastU128 => Summary {
+            preconditions: vec![state_stack_has!(
+                0,
+                Some(AbstractValue::new_primitive(SignatureToken::U64))
+            )],
+            effects: vec![
+                state_stack_pop!(),
+                state_stack_push!(AbstractValue::new_primitive(SignatureToken::U128)),
+            ],
+        },
         Bytecode::LdStr(_) => Summary {
             preconditions: vec![],
             effects: vec![state_stack_push!(AbstractValue::new_primitive(
@@ -13,12 +13,16 @@ use std::{collections::HashSet, iter::FromIterator};
 use stdlib::{
     stdlib_modules,
     transaction_scripts::{
-        CREATE_ACCOUNT_TXN_BODY, MINT_TXN_BODY, PEER_TO_PEER_TRANSFER_TXN_BODY,
-        ROTATE_AUTHENTICATION_KEY_TXN_BODY,
+        ACCEPT_CURRENCY_TXN_BODY, BURN_CURRENCY_TXN_BODY, CREATE_ACCOUNT_TXN_BODY,
+        ENABLE_NONCES_TXN_BODY, FREEZE_ACCOUNT_TXN_BODY, MINT_CURRENCY_TXN_BODY, MINT_TXN_BODY,
+        PEER_TO_PEER_CURRENCY_TRANSFER_TXN_BODY, PEER_TO_PEER_TRANSFER_TXN_BODY,
+        REGISTER_CURRENCY_TXN_BODY, ROTATE_AUTHENTICATION_KEY_TXN_BODY, UNFREEZE_ACCOUNT_TXN_BODY,
+        UPDATE_GAS_SCHEDULE_TXN_BODY,
     },
 };
 #[cfg(any(test, feature = "testing"))]
 use vm::file_format::Bytecode;
+use vm::gas_schedule::CostTable;
 
 lazy_static! {
     static ref PEER_TO_PEER_TXN: Vec<u8> = { compile_script(&PEER_TO_PEER_TRANSFER_TXN_BODY) };
@@ -26,6 +30,16 @@ lazy_static! {
     static ref ROTATE_AUTHENTICATION_KEY_TXN: Vec<u8> =
         { compile_script(&ROTATE_AUTHENTICATION_KEY_TXN_BODY) };
     static ref MINT_TXN: Vec<u8> = { compile_script(&MINT_TXN_BODY) };
+    static ref UPDATE_GAS_SCHEDULE_TXN: Vec<u8> = { compile_script(&UPDATE_GAS_SCHEDULE_TXN_BODY) };
+    static ref ENABLE_NONCES_TXN: Vec<u8> = { compile_script(&ENABLE_NONCES_TXN_BODY) };
+    static ref FREEZE_ACCOUNT_TXN: Vec<u8> = { compile_script(&FREEZE_ACCOUNT_TXN_BODY) };
+    static ref UNFREEZE_ACCOUNT_TXN: Vec<u8> = { compile_script(&UNFREEZE_ACCOUNT_TXN_BODY) };
+    static ref REGISTER_CURRENCY_TXN: Vec<u8> = { compile_script(&REGISTER_CURRENCY_TXN_BODY) };
+    static ref ACCEPT_CURRENCY_TXN: Vec<u8> = { compile_script(&ACCEPT_CURRENCY_TXN_BODY) };
+    static ref MINT_CURRENCY_TXN: Vec<u8> = { compile_script(&MINT_CURRENCY_TXN_BODY) };
+    static ref PEER_TO_PEER_CURRENCY_TXN: Vec<u8> =
+        { compile_script(&PEER_TO_PEER_CURRENCY_TRANSFER_TXN_BODY) };
+    static ref BURN_CURRENCY_TXN: Vec<u8> = { compile_script(&BURN_CURRENCY_TXN_BODY) };
 }
 
 fn compile_script(body: &ast::Program) -> Vec<u8> {
@@ -126,6 +140,115 @@ pub fn encode_mint_script(sender: &AccountAddress, amount: u64) -> Script {
     )
 }
 
+/// Encode a program that replaces the on-chain instruction cost table with `gas_schedule`. Only
+/// the association account is allowed to send this transaction.
+pub fn encode_update_gas_schedule_script(gas_schedule: &CostTable) -> Script {
+    let schedule_bytes = ByteArray::new(
+        gas_schedule
+            .to_bytes()
+            .expect("Serializing a cost table should not fail"),
+    );
+    Script::new(
+        UPDATE_GAS_SCHEDULE_TXN.clone(),
+        vec![TransactionArgument::ByteArray(schedule_bytes)],
+    )
+}
+
+/// Encode a program that lets the sender's account accept nonce transactions, which don't have to
+/// use the next sequence number of the account.
+pub fn encode_enable_nonces_script() -> Script {
+    Script::new(ENABLE_NONCES_TXN.clone(), vec![])
+}
+
+/// Encode a program that freezes the account at `account`: it can't send transactions anymore, and
+/// coins can't be deposited into or withdrawn from it. Only the association account is allowed
+/// to send this transaction.
+pub fn encode_freeze_account_script(account: &AccountAddress) -> Script {
+    Script::new(
+        FREEZE_ACCOUNT_TXN.clone(),
+        vec![TransactionArgument::Address(*account)],
+    )
+}
+
+/// Encode a program that unfreezes the account at `account`. Only the association account is
+/// allowed to send this transaction.
+pub fn encode_unfreeze_account_script(account: &AccountAddress) -> Script {
+    Script::new(
+        UNFREEZE_ACCOUNT_TXN.clone(),
+        vec![TransactionArgument::Address(*account)],
+    )
+}
+
+/// Encode a program that registers the currency `currency_code`, whose coins can only be minted
+/// and burnt by `minter`. Only the association account is allowed to send this transaction.
+pub fn encode_register_currency_script(currency_code: &str, minter: &AccountAddress) -> Script {
+    Script::new(
+        REGISTER_CURRENCY_TXN.clone(),
+        vec![
+            currency_code_argument(currency_code),
+            TransactionArgument::Address(*minter),
+        ],
+    )
+}
+
+/// Encode a program that lets the sender's account accept coins of `currency_code`.
+pub fn encode_accept_currency_script(currency_code: &str) -> Script {
+    Script::new(
+        ACCEPT_CURRENCY_TXN.clone(),
+        vec![currency_code_argument(currency_code)],
+    )
+}
+
+/// Encode a program minting `amount` of `currency_code` into the account at `payee`, which must
+/// accept the currency. Only the minter of the currency is allowed to send this transaction.
+pub fn encode_mint_currency_script(
+    payee: &AccountAddress,
+    currency_code: &str,
+    amount: u64,
+) -> Script {
+    Script::new(
+        MINT_CURRENCY_TXN.clone(),
+        vec![
+            TransactionArgument::Address(*payee),
+            currency_code_argument(currency_code),
+            TransactionArgument::U64(amount),
+        ],
+    )
+}
+
+/// Encode a program transferring `amount` of `currency_code` from the sender to `recipient`, which
+/// must accept the currency.
+pub fn encode_currency_transfer_script(
+    recipient: &AccountAddress,
+    currency_code: &str,
+    amount: u64,
+) -> Script {
+    Script::new(
+        PEER_TO_PEER_CURRENCY_TXN.clone(),
+        vec![
+            TransactionArgument::Address(*recipient),
+            currency_code_argument(currency_code),
+            TransactionArgument::U64(amount),
+        ],
+    )
+}
+
+/// Encode a program burning `amount` of `currency_code` from the sender's account. Only the
+/// minter of the currency is allowed to send this transaction.
+pub fn encode_burn_currency_script(currency_code: &str, amount: u64) -> Script {
+    Script::new(
+        BURN_CURRENCY_TXN.clone(),
+        vec![
+            currency_code_argument(currency_code),
+            TransactionArgument::U64(amount),
+        ],
+    )
+}
+
+fn currency_code_argument(currency_code: &str) -> TransactionArgument {
+    TransactionArgument::ByteArray(ByteArray::new(currency_code.as_bytes().to_vec()))
+}
+
 /// Returns a user friendly mnemonic for the transaction type if the transaction is
 /// for a known, white listed, transaction.
 pub fn get_transaction_name(code: &[u8]) -> String {
@@ -137,6 +260,24 @@ pub fn get_transaction_name(code: &[u8]) -> String {
         return "mint_transaction".to_string();
     } else if code == &ROTATE_AUTHENTICATION_KEY_TXN[..] {
         return "rotate_authentication_key_transaction".to_string();
+    } else if code == &UPDATE_GAS_SCHEDULE_TXN[..] {
+        return "update_gas_schedule_transaction".to_string();
+    } else if code == &ENABLE_NONCES_TXN[..] {
+        return "enable_nonces_transaction".to_string();
+    } else if code == &FREEZE_ACCOUNT_TXN[..] {
+        return "freeze_account_transaction".to_string();
+    } else if code == &UNFREEZE_ACCOUNT_TXN[..] {
+        return "unfreeze_account_transaction".to_string();
+    } else if code == &REGISTER_CURRENCY_TXN[..] {
+        return "register_currency_transaction".to_string();
+    } else if code == &ACCEPT_CURRENCY_TXN[..] {
+        return "accept_currency_transaction".to_string();
+    } else if code == &MINT_CURRENCY_TXN[..] {
+        return "mint_currency_transaction".to_string();
+    } else if code == &PEER_TO_PEER_CURRENCY_TXN[..] {
+        return "peer_to_peer_currency_transaction".to_string();
+    } else if code == &BURN_CURRENCY_TXN[..] {
+        return "burn_currency_transaction".to_string();
     }
     "<unknown transaction>".to_string()
 }
@@ -147,6 +288,15 @@ pub fn allowing_script_hashes() -> Vec<[u8; SCRIPT_HASH_LENGTH]> {
         PEER_TO_PEER_TXN.clone(),
         ROTATE_AUTHENTICATION_KEY_TXN.clone(),
         CREATE_ACCOUNT_TXN.clone(),
+        UPDATE_GAS_SCHEDULE_TXN.clone(),
+        ENABLE_NONCES_TXN.clone(),
+        FREEZE_ACCOUNT_TXN.clone(),
+        UNFREEZE_ACCOUNT_TXN.clone(),
+        REGISTER_CURRENCY_TXN.clone(),
+        ACCEPT_CURRENCY_TXN.clone(),
+        MINT_CURRENCY_TXN.clone(),
+        PEER_TO_PEER_CURRENCY_TXN.clone(),
+        BURN_CURRENCY_TXN.clone(),
     ]
     .into_iter()
     .map(|s| *HashValue::from_sha3_256(&s).as_ref())
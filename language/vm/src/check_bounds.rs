@@ -518,11 +518,12 @@ impl BoundsCheck<(&CompiledModuleMut, &FunctionSignature)> for CodeUnit {
 
                     // List out the other options explicitly so there's a compile error if a new
                     // bytecode gets added.
-                    FreezeRef | Pop | Ret | LdConst(_) | LdTrue | LdFalse | ReadRef | WriteRef
-                    | Add | Sub | Mul | Mod | Div | BitOr | BitAnd | Xor | Or | And | Not | Eq
-                    | Neq | Lt | Gt | Le | Ge | Abort | GetTxnGasUnitPrice | GetTxnMaxGasUnits
-                    | GetGasRemaining | GetTxnSenderAddress | CreateAccount
-                    | GetTxnSequenceNumber | GetTxnPublicKey => vec![],
+                    FreezeRef | Pop | Ret | LdU8(_) | LdConst(_) | LdU128(_) | LdTrue | LdFalse
+                    | ReadRef | WriteRef | CastU8 | CastU64 | CastU128 | Add | Sub | Mul | Mod
+                    | Div | BitOr | BitAnd | Xor | Or | And | Not | Eq | Neq | Lt | Gt | Le
+                    | Ge | Abort | GetTxnGasUnitPrice | GetTxnMaxGasUnits | GetGasRemaining
+                    | GetTxnSenderAddress | CreateAccount | GetTxnSequenceNumber
+                    | GetTxnPublicKey => vec![],
                 }
             })
             .collect()
@@ -1,11 +1,14 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::IndexKind;
+use crate::{
+    file_format::{CodeOffset, FunctionDefinitionIndex, TableIndex},
+    IndexKind,
+};
 use libra_types::{
     account_address::AccountAddress,
     transaction::TransactionStatus,
-    vm_error::{StatusCode, VMStatus},
+    vm_error::{CodeLocation, StatusCode, VMStatus},
 };
 
 // We may want to eventually move this into the VM runtime since it is a semantic decision that
@@ -33,6 +36,9 @@ pub const ESEQUENCE_NUMBER_TOO_OLD: u64 = 3; // transaction sequence number is t
 pub const ESEQUENCE_NUMBER_TOO_NEW: u64 = 4; // transaction sequence number is too new
 pub const EACCOUNT_DOES_NOT_EXIST: u64 = 5; // transaction sender's account does not exist
 pub const ECANT_PAY_GAS_DEPOSIT: u64 = 6; // insufficient balance to pay for gas deposit
+pub const ENONCE_ALREADY_USED: u64 = 7; // transaction nonce has already been used
+pub const ENONCES_NOT_ENABLED: u64 = 8; // transaction sender doesn't accept nonce transactions
+pub const EACCOUNT_FROZEN: u64 = 9; // transaction sender's account is frozen
 
 /// Generic error codes. These codes don't have any special meaning for the VM, but they are useful
 /// conventions for debugging
@@ -80,6 +86,12 @@ pub fn convert_prologue_runtime_error(err: &VMStatus, txn_sender: &AccountAddres
             Some(ECANT_PAY_GAS_DEPOSIT) => {
                 VMStatus::new(StatusCode::INSUFFICIENT_BALANCE_FOR_TRANSACTION_FEE)
             }
+            // Nonce already used
+            Some(ENONCE_ALREADY_USED) => VMStatus::new(StatusCode::NONCE_ALREADY_USED),
+            // Nonce transactions not enabled by the sender
+            Some(ENONCES_NOT_ENABLED) => VMStatus::new(StatusCode::NONCES_NOT_ENABLED),
+            // Sender's account frozen
+            Some(EACCOUNT_FROZEN) => VMStatus::new(StatusCode::SENDING_ACCOUNT_FROZEN),
             // This should never happen...
             _ => err.clone(),
         }
@@ -122,12 +134,34 @@ pub fn verification_error(kind: IndexKind, idx: usize, err: StatusCode) -> VMSta
     VMStatus::new(err).with_message(msg)
 }
 
-pub fn append_err_info(status: VMStatus, kind: IndexKind, idx: usize) -> VMStatus {
+pub fn append_err_info(mut status: VMStatus, kind: IndexKind, idx: usize) -> VMStatus {
+    if kind == IndexKind::FunctionDefinition {
+        if let Some(ref mut location) = status.location {
+            if location.function_definition_index.is_none() {
+                location.function_definition_index = Some(idx as TableIndex);
+            }
+        }
+    }
     let msg = format!("at index {} while indexing {}", idx, kind);
     status.append_message_with_separator(' ', msg)
 }
 
 pub fn err_at_offset(status: StatusCode, offset: usize) -> VMStatus {
     let msg = format!("At offset {}", offset);
-    VMStatus::new(status).with_message(msg)
+    VMStatus::new(status)
+        .with_message(msg)
+        .with_location(CodeLocation {
+            code_offset: offset as CodeOffset,
+            ..CodeLocation::default()
+        })
+}
+
+/// Returns the function definition and code offset of a verification error built with
+/// `err_at_offset` and then tagged with `append_err_info` for a function definition.
+pub fn err_code_location(status: &VMStatus) -> Option<(FunctionDefinitionIndex, CodeOffset)> {
+    let location = status.location.as_ref()?;
+    Some((
+        FunctionDefinitionIndex::new(location.function_definition_index?),
+        location.code_offset,
+    ))
 }
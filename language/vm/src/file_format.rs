@@ -499,8 +499,12 @@ impl Kind {
 pub enum SignatureToken {
     /// Boolean, `true` or `false`.
     Bool,
+    /// Unsigned integers, 8 bits length.
+    U8,
     /// Unsigned integers, 64 bits length.
     U64,
+    /// Unsigned integers, 128 bits length.
+    U128,
     /// Strings, immutable, utf8 representation.
     String,
     /// ByteArray, variable size, immutable byte array.
@@ -528,7 +532,9 @@ impl Arbitrary for SignatureToken {
 
         let leaf = prop_oneof![
             Just(Bool),
+            Just(U8),
             Just(U64),
+            Just(U128),
             Just(String),
             Just(ByteArray),
             Just(Address),
@@ -557,7 +563,9 @@ impl ::std::fmt::Debug for SignatureToken {
     fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
         match self {
             SignatureToken::Bool => write!(f, "Bool"),
+            SignatureToken::U8 => write!(f, "U8"),
             SignatureToken::U64 => write!(f, "U64"),
+            SignatureToken::U128 => write!(f, "U128"),
             SignatureToken::String => write!(f, "String"),
             SignatureToken::ByteArray => write!(f, "ByteArray"),
             SignatureToken::Address => write!(f, "Address"),
@@ -612,7 +620,9 @@ impl SignatureToken {
         match self {
             Reference(_) => SignatureTokenKind::Reference,
             MutableReference(_) => SignatureTokenKind::MutableReference,
-            Bool | U64 | ByteArray | String | Address | Struct(_, _) => SignatureTokenKind::Value,
+            Bool | U8 | U64 | U128 | ByteArray | String | Address | Struct(_, _) => {
+                SignatureTokenKind::Value
+            }
             // TODO: This is a temporary hack to please the verifier. SignatureTokenKind will soon
             // be completely removed. `SignatureTokenView::kind()` should be used instead.
             TypeParameter(_) => SignatureTokenKind::Value,
@@ -628,7 +638,23 @@ impl SignatureToken {
         match self {
             Struct(sh_idx, _) => Some(*sh_idx),
             Reference(token) | MutableReference(token) => token.struct_index(),
-            Bool | U64 | ByteArray | String | Address | TypeParameter(_) => None,
+            Bool | U8 | U64 | U128 | ByteArray | String | Address | TypeParameter(_) => None,
+        }
+    }
+
+    /// Returns `true` if the `SignatureToken` is an unsigned integer type.
+    pub fn is_integer(&self) -> bool {
+        use SignatureToken::*;
+        match self {
+            U8 | U64 | U128 => true,
+            Bool
+            | String
+            | ByteArray
+            | Address
+            | Struct(_, _)
+            | Reference(_)
+            | MutableReference(_)
+            | TypeParameter(_) => false,
         }
     }
 
@@ -636,7 +662,7 @@ impl SignatureToken {
     pub fn is_primitive(&self) -> bool {
         use SignatureToken::*;
         match self {
-            Bool | U64 | String | ByteArray | Address => true,
+            Bool | U8 | U64 | U128 | String | ByteArray | Address => true,
             Struct(_, _) | Reference(_) | MutableReference(_) | TypeParameter(_) => false,
         }
     }
@@ -645,7 +671,7 @@ impl SignatureToken {
     ///
     /// Currently equality operations are only allowed on:
     /// - Bool
-    /// - U64
+    /// - U8, U64 and U128
     /// - String
     /// - ByteArray
     /// - Address
@@ -700,7 +726,9 @@ impl SignatureToken {
 
         match self {
             Bool => Bool,
+            U8 => U8,
             U64 => U64,
+            U128 => U128,
             String => String,
             ByteArray => ByteArray,
             Address => Address,
@@ -727,9 +755,8 @@ impl SignatureToken {
 
         match ty {
             // The primitive types & references have kind unrestricted.
-            Bool | U64 | String | ByteArray | Address | Reference(_) | MutableReference(_) => {
-                Kind::Unrestricted
-            }
+            Bool | U8 | U64 | U128 | String | ByteArray | Address | Reference(_)
+            | MutableReference(_) => Kind::Unrestricted,
 
             // To get the kind of a type parameter, we lookup its constraint in the formals.
             TypeParameter(idx) => type_formals[*idx as usize],
@@ -831,12 +858,44 @@ pub enum Bytecode {
     ///
     /// Stack transition: none
     Branch(CodeOffset),
+    /// Push a u8 constant onto the stack.
+    ///
+    /// Stack transition:
+    ///
+    /// ```... -> ..., u8_value```
+    LdU8(u8),
     /// Push integer constant onto the stack.
     ///
     /// Stack transition:
     ///
     /// ```... -> ..., u64_value```
     LdConst(u64),
+    /// Push a u128 constant onto the stack.
+    ///
+    /// Stack transition:
+    ///
+    /// ```... -> ..., u128_value```
+    LdU128(u128),
+    /// Convert the integer at the top of the stack into a u8.
+    /// The operation aborts the transaction if the value does not fit in a u8.
+    ///
+    /// Stack transition:
+    ///
+    /// ```..., integer_value -> ..., u8_value```
+    CastU8,
+    /// Convert the integer at the top of the stack into a u64.
+    /// The operation aborts the transaction if the value does not fit in a u64.
+    ///
+    /// Stack transition:
+    ///
+    /// ```..., integer_value -> ..., u64_value```
+    CastU64,
+    /// Convert the integer at the top of the stack into a u128.
+    ///
+    /// Stack transition:
+    ///
+    /// ```..., integer_value -> ..., u128_value```
+    CastU128,
     /// Push a string literal onto the stack. The string is loaded from the `UserStrings` via
     /// `UserStringIndex`.
     ///
@@ -996,58 +1055,60 @@ pub enum Bytecode {
     ///
     /// ```..., address_value -> ..., reference_value```
     ImmBorrowGlobal(StructDefinitionIndex, LocalsSignatureIndex),
-    /// Add the 2 u64 at the top of the stack and pushes the result on the stack.
+    /// Add the 2 integers at the top of the stack and pushes the result on the stack.
+    /// Both integers must have the same type (u8, u64 or u128), which is also the type of the
+    /// result.
     /// The operation aborts the transaction in case of overflow.
     ///
     /// Stack transition:
     ///
-    /// ```..., u64_value(1), u64_value(2) -> ..., u64_value```
+    /// ```..., int_value(1), int_value(2) -> ..., int_value```
     Add,
-    /// Subtract the 2 u64 at the top of the stack and pushes the result on the stack.
+    /// Subtract the 2 integers at the top of the stack and pushes the result on the stack.
     /// The operation aborts the transaction in case of underflow.
     ///
     /// Stack transition:
     ///
-    /// ```..., u64_value(1), u64_value(2) -> ..., u64_value```
+    /// ```..., int_value(1), int_value(2) -> ..., int_value```
     Sub,
-    /// Multiply the 2 u64 at the top of the stack and pushes the result on the stack.
+    /// Multiply the 2 integers at the top of the stack and pushes the result on the stack.
     /// The operation aborts the transaction in case of overflow.
     ///
     /// Stack transition:
     ///
-    /// ```..., u64_value(1), u64_value(2) -> ..., u64_value```
+    /// ```..., int_value(1), int_value(2) -> ..., int_value```
     Mul,
-    /// Perform a modulo operation on the 2 u64 at the top of the stack and pushes the
+    /// Perform a modulo operation on the 2 integers at the top of the stack and pushes the
     /// result on the stack.
     ///
     /// Stack transition:
     ///
-    /// ```..., u64_value(1), u64_value(2) -> ..., u64_value```
+    /// ```..., int_value(1), int_value(2) -> ..., int_value```
     Mod,
-    /// Divide the 2 u64 at the top of the stack and pushes the result on the stack.
+    /// Divide the 2 integers at the top of the stack and pushes the result on the stack.
     /// The operation aborts the transaction in case of "divide by 0".
     ///
     /// Stack transition:
     ///
-    /// ```..., u64_value(1), u64_value(2) -> ..., u64_value```
+    /// ```..., int_value(1), int_value(2) -> ..., int_value```
     Div,
-    /// Bitwise OR the 2 u64 at the top of the stack and pushes the result on the stack.
+    /// Bitwise OR the 2 integers at the top of the stack and pushes the result on the stack.
     ///
     /// Stack transition:
     ///
-    /// ```..., u64_value(1), u64_value(2) -> ..., u64_value```
+    /// ```..., int_value(1), int_value(2) -> ..., int_value```
     BitOr,
-    /// Bitwise AND the 2 u64 at the top of the stack and pushes the result on the stack.
+    /// Bitwise AND the 2 integers at the top of the stack and pushes the result on the stack.
     ///
     /// Stack transition:
     ///
-    /// ```..., u64_value(1), u64_value(2) -> ..., u64_value```
+    /// ```..., int_value(1), int_value(2) -> ..., int_value```
     BitAnd,
-    /// Bitwise XOR the 2 u64 at the top of the stack and pushes the result on the stack.
+    /// Bitwise XOR the 2 integers at the top of the stack and pushes the result on the stack.
     ///
     /// Stack transition:
     ///
-    /// ```..., u64_value(1), u64_value(2) -> ..., u64_value```
+    /// ```..., int_value(1), int_value(2) -> ..., int_value```
     Xor,
     /// Logical OR the 2 bool at the top of the stack and pushes the result on the stack.
     ///
@@ -1083,33 +1144,33 @@ pub enum Bytecode {
     ///
     /// ```..., value(1), value(2) -> ..., bool_value```
     Neq,
-    /// Perform a "less than" operation of the 2 u64 at the top of the stack and pushes the
+    /// Perform a "less than" operation of the 2 integers at the top of the stack and pushes the
     /// result on the stack.
     ///
     /// Stack transition:
     ///
-    /// ```..., u64_value(1), u64_value(2) -> ..., bool_value```
+    /// ```..., int_value(1), int_value(2) -> ..., bool_value```
     Lt,
-    /// Perform a "greater than" operation of the 2 u64 at the top of the stack and pushes the
+    /// Perform a "greater than" operation of the 2 integers at the top of the stack and pushes the
     /// result on the stack.
     ///
     /// Stack transition:
     ///
-    /// ```..., u64_value(1), u64_value(2) -> ..., bool_value```
+    /// ```..., int_value(1), int_value(2) -> ..., bool_value```
     Gt,
-    /// Perform a "less than or equal" operation of the 2 u64 at the top of the stack and pushes
-    /// the result on the stack.
+    /// Perform a "less than or equal" operation of the 2 integers at the top of the stack and
+    /// pushes the result on the stack.
     ///
     /// Stack transition:
     ///
-    /// ```..., u64_value(1), u64_value(2) -> ..., bool_value```
+    /// ```..., int_value(1), int_value(2) -> ..., bool_value```
     Le,
-    /// Perform a "greater than or equal" than operation of the 2 u64 at the top of the stack
+    /// Perform a "greater than or equal" than operation of the 2 integers at the top of the stack
     /// and pushes the result on the stack.
     ///
     /// Stack transition:
     ///
-    /// ```..., u64_value(1), u64_value(2) -> ..., bool_value```
+    /// ```..., int_value(1), int_value(2) -> ..., bool_value```
     Ge,
     /// Abort execution with errorcode
     ///
@@ -1187,7 +1248,7 @@ pub enum Bytecode {
 /// The number of bytecode instructions.
 /// This is necessary for checking that all instructions are covered since Rust
 /// does not provide a way of determining the number of variants of an enum.
-pub const NUMBER_OF_BYTECODE_INSTRUCTIONS: usize = 54;
+pub const NUMBER_OF_BYTECODE_INSTRUCTIONS: usize = 59;
 
 impl ::std::fmt::Debug for Bytecode {
     fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
@@ -1197,7 +1258,12 @@ impl ::std::fmt::Debug for Bytecode {
             Bytecode::BrTrue(a) => write!(f, "BrTrue({})", a),
             Bytecode::BrFalse(a) => write!(f, "BrFalse({})", a),
             Bytecode::Branch(a) => write!(f, "Branch({})", a),
+            Bytecode::LdU8(a) => write!(f, "LdU8({})", a),
             Bytecode::LdConst(a) => write!(f, "LdConst({})", a),
+            Bytecode::LdU128(a) => write!(f, "LdU128({})", a),
+            Bytecode::CastU8 => write!(f, "CastU8"),
+            Bytecode::CastU64 => write!(f, "CastU64"),
+            Bytecode::CastU128 => write!(f, "CastU128"),
             Bytecode::LdStr(a) => write!(f, "LdStr({})", a),
             Bytecode::LdByteArray(a) => write!(f, "LdByteArray({})", a),
             Bytecode::LdAddr(a) => write!(f, "LdAddr({})", a),
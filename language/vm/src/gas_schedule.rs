@@ -6,6 +6,11 @@
 //! It is important to note that the cost schedule defined in this file does not track hashing
 //! operations or other native operations; the cost of each native operation will be returned by the
 //! native function itself.
+//!
+//! The instruction cost table that the VM charges against is stored on chain (see the `GasSchedule`
+//! module in the standard library) and is read at the start of every block. The table compiled
+//! into the binary, [`DEFAULT_GAS_SCHEDULE`], is published at genesis and used whenever no
+//! on-chain schedule is available.
 use crate::{
     file_format::{
         AddressPoolIndex, ByteArrayPoolIndex, Bytecode, FieldDefinitionIndex, FunctionHandleIndex,
@@ -13,10 +18,15 @@ use crate::{
     },
     serializer::serialize_instruction,
 };
+use canonical_serialization::{
+    CanonicalDeserialize, CanonicalDeserializer, CanonicalSerialize, CanonicalSerializer,
+    SimpleDeserializer, SimpleSerializer,
+};
+use failure::prelude::*;
 use lazy_static::lazy_static;
 use libra_types::transaction::MAX_TRANSACTION_SIZE_IN_BYTES;
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap, HashSet},
     ops::{Add, Div, Mul, Sub},
     u64,
 };
@@ -192,9 +202,9 @@ lazy_static! {
 }
 
 /// The cost tables, keyed by the serialized form of the bytecode instruction.  We use the
-/// serialized form as opposed to the instruction enum itself as the key since this will be the
-/// on-chain representation of bytecode instructions in the future.
-#[derive(Debug)]
+/// serialized form as opposed to the instruction enum itself as the key since this is the
+/// on-chain representation of bytecode instructions.
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct CostTable {
     pub compute_table: HashMap<InstructionKey, GasUnits<GasCarrier>>,
     pub memory_table: HashMap<InstructionKey, GasUnits<GasCarrier>>,
@@ -233,6 +243,41 @@ impl CostTable {
         }
     }
 
+    /// Returns the `(instruction key, computational cost, memory cost)` entries of this table,
+    /// sorted by instruction key. This is the order used for the on-chain representation.
+    pub fn entries(&self) -> Vec<(InstructionKey, GasCarrier, GasCarrier)> {
+        let sorted: BTreeMap<_, _> = self
+            .compute_table
+            .iter()
+            .map(|(key, comp_cost)| (key.0, comp_cost.get()))
+            .collect();
+        sorted
+            .into_iter()
+            .map(|(key, comp_cost)| {
+                let key = InstructionKey(key);
+                let mem_cost = self
+                    .memory_table
+                    .get(&key)
+                    .map(|mem_cost| mem_cost.get())
+                    .unwrap_or(0);
+                (key, comp_cost, mem_cost)
+            })
+            .collect()
+    }
+
+    /// Serializes this table into the byte layout stored in the on-chain `GasSchedule.T`
+    /// resource.
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        SimpleSerializer::serialize(self)
+    }
+
+    /// Deserializes a table from the byte layout stored in the on-chain `GasSchedule.T` resource.
+    /// Fails if the table has an unknown or duplicate instruction key. Instructions missing from
+    /// the table, e.g. ones added after the table was published, are charged their default cost.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        SimpleDeserializer::deserialize(bytes)
+    }
+
     pub fn memory_gas(
         &self,
         instr: &Bytecode,
@@ -256,10 +301,63 @@ impl CostTable {
         assume!(compute_cost.is_some());
         compute_cost.unwrap().map2(size_provider, Mul::mul)
     }
+
+    /// Statically cost a bytecode instruction.
+    ///
+    /// Don't take into account current stack or memory size. Don't track whether references are to
+    /// global or local storage.
+    pub fn instruction_cost(
+        &self,
+        instr: &Bytecode,
+        size_provider: AbstractMemorySize<GasCarrier>,
+    ) -> GasCost {
+        GasCost {
+            instruction_gas: self.comp_gas(instr, size_provider),
+            memory_gas: self.memory_gas(instr, size_provider),
+        }
+    }
+}
+
+impl CanonicalSerialize for CostTable {
+    fn serialize(&self, serializer: &mut impl CanonicalSerializer) -> Result<()> {
+        let entries: Vec<_> = self
+            .entries()
+            .into_iter()
+            .map(|(key, comp_cost, mem_cost)| (key.0, comp_cost, mem_cost))
+            .collect();
+        serializer.encode_vec(&entries)?;
+        Ok(())
+    }
+}
+
+impl CanonicalDeserialize for CostTable {
+    fn deserialize(deserializer: &mut impl CanonicalDeserializer) -> Result<Self> {
+        let entries: Vec<(u8, u64, u64)> = deserializer.decode_vec()?;
+        let mut table = DEFAULT_GAS_SCHEDULE.clone();
+        let mut seen = HashSet::new();
+        for (key, comp_cost, mem_cost) in entries {
+            let key = InstructionKey(key);
+            ensure!(
+                table.compute_table.contains_key(&key),
+                "unknown instruction key {:#x} in cost table",
+                key.0
+            );
+            ensure!(
+                seen.insert(key),
+                "duplicate instruction key {:#x} in cost table",
+                key.0
+            );
+            table.compute_table.insert(key, GasUnits::new(comp_cost));
+            table.memory_table.insert(key, GasUnits::new(mem_cost));
+        }
+        Ok(table)
+    }
 }
 
 lazy_static! {
-    static ref GAS_SCHEDULE: CostTable = {
+    /// The cost table compiled into the binary. This is the table published on chain at genesis,
+    /// and the one used when no on-chain table can be found.
+    pub static ref DEFAULT_GAS_SCHEDULE: CostTable = {
         use Bytecode::*;
         // Arguments to the instructions don't matter -- these will be removed in the
         // `encode_instruction` function.
@@ -323,6 +421,11 @@ lazy_static! {
             (LdByteArray(ByteArrayPoolIndex::new(0)), 56, 1),
             (Gt, 46, 1),
             (Pack(StructDefinitionIndex::new(0), NO_TYPE_ACTUALS), 73, 1),
+            (LdU8(0), 29, 1),
+            (LdU128(0), 29, 1),
+            (CastU8, 29, 1),
+            (CastU64, 29, 1),
+            (CastU128, 29, 1),
         ];
         CostTable::new(instrs)
     };
@@ -337,20 +440,6 @@ pub struct GasCost {
     pub memory_gas: GasUnits<GasCarrier>,
 }
 
-/// Statically cost a bytecode instruction.
-///
-/// Don't take into account current stack or memory size. Don't track whether references are to
-/// global or local storage.
-pub fn static_cost_instr(
-    instr: &Bytecode,
-    size_provider: AbstractMemorySize<GasCarrier>,
-) -> GasCost {
-    GasCost {
-        instruction_gas: GAS_SCHEDULE.comp_gas(instr, size_provider),
-        memory_gas: GAS_SCHEDULE.memory_gas(instr, size_provider),
-    }
-}
-
 /// Computes the number of words rounded up
 pub fn words_in(size: AbstractMemorySize<GasCarrier>) -> AbstractMemorySize<GasCarrier> {
     precondition!(size.get() <= MAX_ABSTRACT_MEMORY_SIZE.get() - (WORD_SIZE.get() + 1));
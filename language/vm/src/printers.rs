@@ -83,6 +83,46 @@ pub trait TableAccess {
     fn get_locals_signature_at(&self, idx: LocalsSignatureIndex) -> Result<&LocalsSignature>;
 }
 
+/// Maps compiled bytecode back to the source it was produced from, so that the printers can
+/// annotate instructions and locals with e.g. Move IR line numbers.
+pub trait SourceLocator {
+    /// Returns a human readable source location for the instruction at `offset` in `function`.
+    fn code_location(
+        &self,
+        function: FunctionDefinitionIndex,
+        offset: CodeOffset,
+    ) -> Option<String>;
+
+    /// Returns the source name of the local at `local` in `function`.
+    fn local_name(&self, function: FunctionDefinitionIndex, local: LocalIndex) -> Option<String>;
+}
+
+/// Displays the function definitions of a `CompiledModule` with each instruction annotated with
+/// its source location.
+pub struct AnnotatedModule<'a, L> {
+    module: &'a CompiledModule,
+    locator: &'a L,
+}
+
+impl<'a, L: SourceLocator> AnnotatedModule<'a, L> {
+    pub fn new(module: &'a CompiledModule, locator: &'a L) -> Self {
+        Self { module, locator }
+    }
+}
+
+/// Displays the `main` function of a `CompiledScript` with each instruction annotated with its
+/// source location.
+pub struct AnnotatedScript<'a, L> {
+    script: &'a CompiledScript,
+    locator: &'a L,
+}
+
+impl<'a, L: SourceLocator> AnnotatedScript<'a, L> {
+    pub fn new(script: &'a CompiledScript, locator: &'a L) -> Self {
+        Self { script, locator }
+    }
+}
+
 impl TableAccess for CompiledScriptMut {
     fn get_field_def_at(&self, _idx: FieldDefinitionIndex) -> Result<&FieldDefinition> {
         bail!("no field definitions in scripts");
@@ -401,6 +441,49 @@ impl fmt::Display for CompiledModule {
     }
 }
 
+impl<'a, L: SourceLocator> fmt::Display for AnnotatedModule<'a, L> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let inner = self.module.as_inner();
+        writeln!(f, "CompiledModule: {{")?;
+        write!(f, "Function Definitions: [")?;
+        for (idx, function_def) in inner.function_defs.iter().enumerate() {
+            write!(f, "\n\t")?;
+            display_function_definition(function_def, inner, f)?;
+            if function_def.flags & CodeUnit::NATIVE == 0 {
+                let function = FunctionDefinitionIndex::new(idx as TableIndex);
+                display_annotated_code(
+                    &function_def.code,
+                    function,
+                    inner,
+                    self.locator,
+                    "\n\t\t",
+                    f,
+                )?;
+            }
+            write!(f, ",")?;
+        }
+        writeln!(f, "]")?;
+        writeln!(f, "}}")
+    }
+}
+
+impl<'a, L: SourceLocator> fmt::Display for AnnotatedScript<'a, L> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let inner = self.script.as_inner();
+        write!(f, "CompiledScript: {{\nMain:\n\t")?;
+        display_function_definition(&inner.main, inner, f)?;
+        display_annotated_code(
+            &inner.main.code,
+            CompiledScript::MAIN_INDEX,
+            inner,
+            self.locator,
+            "\n\t\t",
+            f,
+        )?;
+        writeln!(f, "\n}}")
+    }
+}
+
 fn display_struct_handle<T: TableAccess>(
     struct_: &StructHandle,
     tables: &T,
@@ -503,6 +586,33 @@ fn display_code<T: TableAccess>(
     Ok(())
 }
 
+fn display_annotated_code<T: TableAccess, L: SourceLocator>(
+    code: &CodeUnit,
+    function: FunctionDefinitionIndex,
+    tables: &T,
+    locator: &L,
+    indentation: &str,
+    f: &mut fmt::Formatter,
+) -> fmt::Result {
+    write!(f, "{}locals({}): ", indentation, code.locals,)?;
+    let locals = tables.get_locals_signature_at(code.locals).unwrap();
+    display_locals_signature(locals, tables, f)?;
+    write!(f, ",")?;
+    for idx in 0..locals.0.len() {
+        if let Some(name) = locator.local_name(function, idx as LocalIndex) {
+            write!(f, "{}local({}): {}", indentation, idx, name)?;
+        }
+    }
+    for (offset, bytecode) in code.code.iter().enumerate() {
+        write!(f, "{}", indentation)?;
+        display_bytecode(bytecode, tables, f)?;
+        if let Some(location) = locator.code_location(function, offset as CodeOffset) {
+            write!(f, " // {}", location)?;
+        }
+    }
+    Ok(())
+}
+
 fn display_address(addr: &AccountAddress, f: &mut fmt::Formatter) -> fmt::Result {
     let hex = format!("{:x}", addr);
     let mut v: VecDeque<char> = hex.chars().collect();
@@ -594,7 +704,9 @@ fn display_signature_token<T: TableAccess>(
 ) -> fmt::Result {
     match token {
         SignatureToken::Bool => write!(f, "Bool"),
+        SignatureToken::U8 => write!(f, "U8"),
         SignatureToken::U64 => write!(f, "Integer"),
+        SignatureToken::U128 => write!(f, "U128"),
         SignatureToken::String => write!(f, "String"),
         SignatureToken::ByteArray => write!(f, "ByteArray"),
         SignatureToken::Address => write!(f, "Address"),
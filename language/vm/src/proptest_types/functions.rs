@@ -399,6 +399,8 @@ impl BytecodeGen {
             // The numbers are relative weights, somewhat arbitrarily picked.
             9 => Self::just_bytecode_strategy(),
             1 => any::<u64>().prop_map(Bytecode::LdConst),
+            1 => any::<u8>().prop_map(Bytecode::LdU8),
+            1 => any::<u128>().prop_map(Bytecode::LdU128),
         ]
     }
 
@@ -429,6 +431,9 @@ impl BytecodeGen {
             Gt,
             Le,
             Ge,
+            CastU8,
+            CastU64,
+            CastU128,
             Abort,
             GetTxnGasUnitPrice,
             GetTxnMaxGasUnits,
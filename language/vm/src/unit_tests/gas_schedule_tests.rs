@@ -0,0 +1,83 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    file_format::{Bytecode, NUMBER_OF_BYTECODE_INSTRUCTIONS},
+    gas_schedule::*,
+};
+use canonical_serialization::SimpleSerializer;
+
+#[test]
+fn default_schedule_roundtrip() {
+    let bytes = DEFAULT_GAS_SCHEDULE
+        .to_bytes()
+        .expect("serialization should work");
+    let table = CostTable::from_bytes(&bytes).expect("deserialization should work");
+    assert_eq!(table, *DEFAULT_GAS_SCHEDULE);
+    assert_eq!(table.to_bytes().unwrap(), bytes);
+}
+
+#[test]
+fn entries_are_sorted_and_complete() {
+    let entries = DEFAULT_GAS_SCHEDULE.entries();
+    assert_eq!(entries.len(), NUMBER_OF_BYTECODE_INSTRUCTIONS);
+    assert!(entries.windows(2).all(|pair| (pair[0].0).0 < (pair[1].0).0));
+}
+
+#[test]
+fn modified_schedule_roundtrip() {
+    let entries: Vec<(u8, u64, u64)> = DEFAULT_GAS_SCHEDULE
+        .entries()
+        .into_iter()
+        .map(|(key, comp_cost, mem_cost)| (key.0, comp_cost * 2, mem_cost))
+        .collect();
+    let bytes: Vec<u8> = SimpleSerializer::serialize(&entries).unwrap();
+    let table = CostTable::from_bytes(&bytes).expect("deserialization should work");
+    let size = AbstractMemorySize::new(1);
+    assert_eq!(
+        table.comp_gas(&Bytecode::Add, size).get(),
+        DEFAULT_GAS_SCHEDULE.comp_gas(&Bytecode::Add, size).get() * 2
+    );
+}
+
+#[test]
+fn pad_incomplete_schedule() {
+    // A table published before `Add` was priced still decodes, charging the default cost.
+    let entries: Vec<(u8, u64, u64)> = DEFAULT_GAS_SCHEDULE
+        .entries()
+        .into_iter()
+        .filter(|(key, _, _)| *key != InstructionKey::new(&Bytecode::Add))
+        .map(|(key, comp_cost, mem_cost)| (key.0, comp_cost * 2, mem_cost))
+        .collect();
+    let bytes: Vec<u8> = SimpleSerializer::serialize(&entries).unwrap();
+    let table = CostTable::from_bytes(&bytes).expect("deserialization should work");
+    assert_eq!(table.entries().len(), NUMBER_OF_BYTECODE_INSTRUCTIONS);
+    let size = AbstractMemorySize::new(1);
+    assert_eq!(
+        table.comp_gas(&Bytecode::Add, size),
+        DEFAULT_GAS_SCHEDULE.comp_gas(&Bytecode::Add, size)
+    );
+    assert_eq!(
+        table.comp_gas(&Bytecode::Sub, size).get(),
+        DEFAULT_GAS_SCHEDULE.comp_gas(&Bytecode::Sub, size).get() * 2
+    );
+}
+
+#[test]
+fn reject_duplicate_and_unknown_keys() {
+    let entries: Vec<(u8, u64, u64)> = DEFAULT_GAS_SCHEDULE
+        .entries()
+        .into_iter()
+        .map(|(key, comp_cost, mem_cost)| (key.0, comp_cost, mem_cost))
+        .collect();
+
+    let mut duplicate = entries.clone();
+    duplicate[1].0 = duplicate[0].0;
+    let bytes: Vec<u8> = SimpleSerializer::serialize(&duplicate).unwrap();
+    CostTable::from_bytes(&bytes).unwrap_err();
+
+    let mut unknown = entries;
+    unknown[0].0 = 0xFF;
+    let bytes: Vec<u8> = SimpleSerializer::serialize(&unknown).unwrap();
+    CostTable::from_bytes(&bytes).unwrap_err();
+}
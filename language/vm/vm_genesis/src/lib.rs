@@ -17,7 +17,10 @@ use rand::{rngs::StdRng, SeedableRng};
 use state_view::StateView;
 use std::time::Duration;
 use stdlib::stdlib_modules;
-use vm::{access::ModuleAccess, transaction_metadata::TransactionMetadata};
+use vm::{
+    access::ModuleAccess, gas_schedule::DEFAULT_GAS_SCHEDULE,
+    transaction_metadata::TransactionMetadata,
+};
 use vm_cache_map::Arena;
 use vm_runtime::{
     code_cache::{
@@ -26,7 +29,8 @@ use vm_runtime::{
     },
     data_cache::BlockDataCache,
     txn_executor::{
-        TransactionExecutor, ACCOUNT_MODULE, BLOCK_MODULE, COIN_MODULE, VALIDATOR_SET_MODULE,
+        TransactionExecutor, ACCOUNT_MODULE, BLOCK_MODULE, COIN_MODULE, CURRENCY_MODULE,
+        GAS_SCHEDULE_MODULE, VALIDATOR_SET_MODULE,
     },
 };
 use vm_runtime_types::value::Value;
@@ -211,16 +215,40 @@ pub fn encode_genesis_transaction_with_validator(
             let mut txn_data = TransactionMetadata::default();
             txn_data.sender = genesis_addr;
 
-            let mut txn_executor = TransactionExecutor::new(&block_cache, &data_cache, txn_data);
+            let mut txn_executor = TransactionExecutor::new(
+                &block_cache,
+                &data_cache,
+                txn_data,
+                &DEFAULT_GAS_SCHEDULE,
+            );
             txn_executor.create_account(genesis_addr).unwrap();
             txn_executor
                 .create_account(account_config::core_code_address())
                 .unwrap();
+            txn_executor
+                .execute_function(&COIN_MODULE, &INITIALIZE, vec![])
+                .unwrap();
+            txn_executor
+                .execute_function(&CURRENCY_MODULE, &INITIALIZE, vec![])
+                .unwrap();
+            // Also initializes TransactionFee, whose withdraw capability is kept by Block.
             txn_executor
                 .execute_function(&BLOCK_MODULE, &INITIALIZE, vec![])
                 .unwrap();
             txn_executor
-                .execute_function(&COIN_MODULE, &INITIALIZE, vec![])
+                .execute_function(&ACCOUNT_MODULE, &INITIALIZE, vec![])
+                .unwrap();
+
+            // Publish the instruction cost table that the VM charges transactions against.
+            let gas_schedule = DEFAULT_GAS_SCHEDULE
+                .to_bytes()
+                .expect("Serializing the default cost table should not fail");
+            txn_executor
+                .execute_function(
+                    &GAS_SCHEDULE_MODULE,
+                    &INITIALIZE,
+                    vec![Value::byte_array(ByteArray::new(gas_schedule))],
+                )
                 .unwrap();
 
             txn_executor
@@ -9,23 +9,26 @@ use crate::{
     },
     counters::*,
     data_cache::BlockDataCache,
+    gas_schedule::load_gas_schedule,
     process_txn::{execute::ExecutedTransaction, validate::ValidationMode, ProcessTransaction},
+    txn_executor::TransactionExecutor,
 };
 use config::config::VMPublishingOption;
 use libra_types::{
-    transaction::{
-        SignatureCheckedTransaction, SignedTransaction, TransactionOutput, TransactionStatus,
-    },
+    account_config,
+    block_metadata::BlockMetadata,
+    transaction::{SignatureCheckedTransaction, Transaction, TransactionOutput, TransactionStatus},
     vm_error::{StatusCode, VMStatus},
     write_set::WriteSet,
 };
 use logger::prelude::*;
 use rayon::prelude::*;
 use state_view::StateView;
+use vm::{gas_schedule::CostTable, transaction_metadata::TransactionMetadata};
 use vm_cache_map::Arena;
 
 pub fn execute_block<'alloc>(
-    txn_block: Vec<SignedTransaction>,
+    txn_block: Vec<Transaction>,
     code_cache: &VMModuleCache<'alloc>,
     script_cache: &ScriptCache<'alloc>,
     data_view: &dyn StateView,
@@ -37,18 +40,7 @@ pub fn execute_block<'alloc>(
     let mode = if data_view.is_genesis() {
         // The genesis transaction must be in a block of its own.
         if txn_block.len() != 1 {
-            // XXX Need a way to return that an entire block failed.
-            return txn_block
-                .iter()
-                .map(|_| {
-                    TransactionOutput::new(
-                        WriteSet::default(),
-                        vec![],
-                        0,
-                        TransactionStatus::from(VMStatus::new(StatusCode::REJECTED_WRITE_SET)),
-                    )
-                })
-                .collect();
+            return discard_block(&txn_block, VMStatus::new(StatusCode::REJECTED_WRITE_SET));
         } else {
             ValidationMode::Genesis
         }
@@ -60,25 +52,37 @@ pub fn execute_block<'alloc>(
     let mut data_cache = BlockDataCache::new(data_view);
     let mut result = vec![];
 
-    let signature_verified_block: Vec<Result<SignatureCheckedTransaction, VMStatus>> = txn_block
+    // The gas schedule is read once per block, so updates to it take effect from the next block.
+    let gas_schedule = match load_gas_schedule(&data_cache) {
+        Ok(gas_schedule) => gas_schedule,
+        Err(vm_status) => return discard_block(&txn_block, vm_status),
+    };
+
+    let signature_verified_block: Vec<Result<PreprocessedTransaction, VMStatus>> = txn_block
         .into_par_iter()
-        .map(|txn| {
-            txn.check_signature()
-                .map_err(|_| VMStatus::new(StatusCode::INVALID_SIGNATURE))
-        })
+        .map(preprocess_transaction)
         .collect();
 
     for transaction in signature_verified_block {
         record_stats! {time_hist | TXN_TOTAL_TIME_TAKEN | {
                 let output = match transaction {
-                    Ok(t) => transaction_flow(
+                    Ok(PreprocessedTransaction::UserTransaction(t)) => transaction_flow(
                         t,
                         &module_cache,
                         script_cache,
                         &data_cache,
+                        &gas_schedule,
                         mode,
                         publishing_option,
                     ),
+                    Ok(PreprocessedTransaction::BlockMetadata(block_metadata)) => {
+                        process_block_metadata(
+                            &block_metadata,
+                            &module_cache,
+                            &data_cache,
+                            &gas_schedule,
+                        )
+                    }
                     Err(vm_status) => ExecutedTransaction::discard_error_output(vm_status),
                 };
                 report_execution_status(output.status());
@@ -96,6 +100,43 @@ pub fn execute_block<'alloc>(
     result
 }
 
+/// A transaction of the block that is ready to be executed.
+enum PreprocessedTransaction {
+    UserTransaction(SignatureCheckedTransaction),
+    BlockMetadata(BlockMetadata),
+}
+
+/// Check the signature of user transactions. Write set transactions are not supported in blocks;
+/// the genesis write set is submitted as a user transaction.
+fn preprocess_transaction(txn: Transaction) -> Result<PreprocessedTransaction, VMStatus> {
+    match txn {
+        Transaction::UserTransaction(txn) => txn
+            .check_signature()
+            .map(PreprocessedTransaction::UserTransaction)
+            .map_err(|_| VMStatus::new(StatusCode::INVALID_SIGNATURE)),
+        Transaction::BlockMetadata(block_metadata) => {
+            Ok(PreprocessedTransaction::BlockMetadata(block_metadata))
+        }
+        Transaction::WriteSet(_) => Err(VMStatus::new(StatusCode::REJECTED_WRITE_SET)),
+    }
+}
+
+/// Produce an output that discards every transaction in the block with `vm_status`.
+// XXX Need a way to return that an entire block failed.
+fn discard_block(txn_block: &[Transaction], vm_status: VMStatus) -> Vec<TransactionOutput> {
+    txn_block
+        .iter()
+        .map(|_| {
+            TransactionOutput::new(
+                WriteSet::default(),
+                vec![],
+                0,
+                TransactionStatus::from(vm_status.clone()),
+            )
+        })
+        .collect()
+}
+
 /// Process a transaction and emit a TransactionOutput.
 ///
 /// A successful execution will have `TransactionStatus::Keep` in the TransactionOutput and a
@@ -114,6 +155,7 @@ fn transaction_flow<'alloc, P>(
     module_cache: P,
     script_cache: &ScriptCache<'alloc>,
     data_cache: &BlockDataCache<'_>,
+    gas_schedule: &CostTable,
     mode: ValidationMode,
     publishing_option: &VMPublishingOption,
 ) -> TransactionOutput
@@ -121,7 +163,7 @@ where
     P: ModuleCache<'alloc>,
 {
     let arena = Arena::new();
-    let process_txn = ProcessTransaction::new(txn, &module_cache, data_cache, &arena);
+    let process_txn = ProcessTransaction::new(txn, &module_cache, data_cache, gas_schedule, &arena);
 
     let validated_txn = record_stats! {time_hist | TXN_VALIDATION_TIME_TAKEN | {
     match process_txn.validate(mode, publishing_option) {
@@ -159,3 +201,31 @@ where
     };
     output
 }
+
+/// Run the block prologue for the block described by `block_metadata` and emit a
+/// TransactionOutput.
+///
+/// The prologue is executed on behalf of the association, without charging gas. If it fails, the
+/// on-chain block resource is left unchanged and the output is discarded.
+fn process_block_metadata<'alloc, P>(
+    block_metadata: &BlockMetadata,
+    module_cache: P,
+    data_cache: &BlockDataCache<'_>,
+    gas_schedule: &CostTable,
+) -> TransactionOutput
+where
+    P: ModuleCache<'alloc>,
+{
+    let mut txn_data = TransactionMetadata::default();
+    txn_data.sender = account_config::association_address();
+
+    let mut txn_executor =
+        TransactionExecutor::new(module_cache, data_cache, txn_data, gas_schedule);
+    let result = txn_executor
+        .run_block_prologue(block_metadata)
+        .and_then(|()| txn_executor.make_write_set(vec![], Ok(())));
+    match result {
+        Ok(output) => output,
+        Err(vm_status) => ExecutedTransaction::discard_error_output(vm_status),
+    }
+}
@@ -10,7 +10,7 @@ use crate::{
         loaded_module::LoadedModule,
     },
 };
-use bytecode_verifier::VerifiedModule;
+use bytecode_verifier::{VerifiedModule, VerifierConfig};
 use libra_types::{
     language_storage::ModuleId,
     vm_error::{StatusCode, VMStatus},
@@ -45,7 +45,8 @@ pub trait ModuleCache<'alloc> {
     ///
     /// * `Ok(Some(FunctionRef))` if such function exists.
     /// * `Ok(None)` if such function doesn't exists.
-    /// * `Err(...)` for a verification issue in a resolved dependency or VM invariant violation.
+    /// * `Err(...)` for a verification issue in a resolved dependency, a call to a private
+    ///   function of another module or VM invariant violation.
     fn resolve_function_ref(
         &self,
         caller_module: &LoadedModule,
@@ -80,7 +81,8 @@ pub trait ModuleCache<'alloc> {
     fn cache_module(&self, module: VerifiedModule);
 
     /// Recache the list of previously resolved modules. Think of the cache as a generational
-    /// cache and we need to move modules across generations.
+    /// cache and we need to move modules across generations. Modules replace the versions already
+    /// cached under the same id.
     fn reclaim_cached_module(&self, v: Vec<LoadedModule>);
 }
 
@@ -156,8 +158,9 @@ impl<'alloc> VMModuleCache<'alloc> {
             None => return Ok(None),
         };
 
-        // Verify the module before using it.
-        let module = match VerifiedModule::new(module) {
+        // Verify the module before using it. Modules in storage were already metered when they
+        // were published, so the verifier budget is not applied again.
+        let module = match VerifiedModule::new_with_config(module, &VerifierConfig::unbounded()) {
             Ok(module) => module,
             Err((_, mut errors)) => {
                 // If there are errors there should be at least one otherwise there's an internal
@@ -209,6 +212,13 @@ impl<'alloc> VMModuleCache<'alloc> {
                     .function_defs_table
                     .get(callee_name)
                     .ok_or_else(|| VMStatus::new(StatusCode::LINKER_ERROR))?;
+                // Private functions can only be called from their own module. The VM does not
+                // verify scripts and modules against their dependencies, so this is enforced here.
+                if callee_module.self_id() != caller_module.self_id()
+                    && !callee_module.function_def_at(*callee_func_id).is_public()
+                {
+                    return Err(VMStatus::new(StatusCode::VISIBILITY_MISMATCH));
+                }
                 Ok(Some(FunctionRef::new(callee_module, *callee_func_id)))
             }
             Ok(None) => Ok(None),
@@ -251,7 +261,9 @@ impl<'alloc> VMModuleCache<'alloc> {
     ) -> VMResult<Option<Type>> {
         match tok {
             SignatureToken::Bool => Ok(Some(Type::Bool)),
+            SignatureToken::U8 => Ok(Some(Type::U8)),
             SignatureToken::U64 => Ok(Some(Type::U64)),
+            SignatureToken::U128 => Ok(Some(Type::U128)),
             SignatureToken::String => Ok(Some(Type::String)),
             SignatureToken::ByteArray => Ok(Some(Type::ByteArray)),
             SignatureToken::Address => Ok(Some(Type::Address)),
@@ -406,7 +418,8 @@ impl<'alloc> ModuleCache<'alloc> for VMModuleCache<'alloc> {
     fn reclaim_cached_module(&self, v: Vec<LoadedModule>) {
         for m in v.into_iter() {
             let module_id = m.self_id();
-            self.map.or_insert(module_id, m);
+            // A module published under an existing id replaces the old version.
+            self.map.insert(module_id, m);
         }
     }
 }
@@ -16,12 +16,14 @@ const TXN_EXECUTION_DISCARD: &str = "txn.execution.discard";
 const TXN_VERIFICATION_SUCCESS: &str = "txn.verification.success";
 const TXN_VERIFICATION_FAIL: &str = "txn.verification.fail";
 const TXN_BLOCK_COUNT: &str = "txn.block.count";
+const GAS_SCHEDULE_DECODE_FAILURE: &str = "gas_schedule.decode_failure";
 pub const TXN_TOTAL_TIME_TAKEN: &str = "txn_gas_total_time_taken";
 pub const TXN_VERIFICATION_TIME_TAKEN: &str = "txn_gas_verification_time_taken";
 pub const TXN_VALIDATION_TIME_TAKEN: &str = "txn_gas_validation_time_taken";
 pub const TXN_EXECUTION_TIME_TAKEN: &str = "txn_gas_execution_time_taken";
 pub const TXN_PROLOGUE_TIME_TAKEN: &str = "txn_gas_prologue_time_taken";
 pub const TXN_EPILOGUE_TIME_TAKEN: &str = "txn_gas_epilogue_time_taken";
+pub const BLOCK_PROLOGUE_TIME_TAKEN: &str = "block_prologue_time_taken";
 pub const TXN_EXECUTION_GAS_USAGE: &str = "txn_gas_execution_gas_usage";
 pub const TXN_TOTAL_GAS_USAGE: &str = "txn_gas_total_gas_usage";
 
@@ -31,6 +33,8 @@ lazy_static::lazy_static! {
 
     static ref VERIFIED_TRANSACTION: IntCounter = VM_COUNTERS.counter(TXN_VERIFICATION_SUCCESS);
     static ref BLOCK_TRANSACTION_COUNT: IntGauge = VM_COUNTERS.gauge(TXN_BLOCK_COUNT);
+    static ref GAS_SCHEDULE_DECODE_FAILURE_COUNT: IntCounter =
+        VM_COUNTERS.counter(GAS_SCHEDULE_DECODE_FAILURE);
 }
 
 /// Wrapper around time::Instant.
@@ -46,6 +50,11 @@ pub fn report_block_count(count: usize) {
     }
 }
 
+/// Reports that the on-chain gas schedule could not be decoded and the default one was used.
+pub fn report_gas_schedule_decode_failure() {
+    GAS_SCHEDULE_DECODE_FAILURE_COUNT.inc();
+}
+
 // All statistics gather operations for the time taken/gas usage should go through this macro. This
 // gives us the ability to turn these metrics on and off easily from one place.
 #[macro_export]
@@ -165,6 +174,9 @@ fn get_validation_status(validation_status: StatusCode) -> &'static str {
         StatusCode::EXCEEDED_MAX_TRANSACTION_SIZE => "ExceededMaxTransactionSize",
         StatusCode::UNKNOWN_SCRIPT => "UnknownScript",
         StatusCode::UNKNOWN_MODULE => "UnknownModule",
+        StatusCode::NONCE_ALREADY_USED => "NonceAlreadyUsed",
+        StatusCode::NONCES_NOT_ENABLED => "NoncesNotEnabled",
+        StatusCode::SENDING_ACCOUNT_FROZEN => "SendingAccountFrozen",
         StatusCode::MAX_GAS_UNITS_EXCEEDS_MAX_GAS_UNITS_BOUND
         | StatusCode::MAX_GAS_UNITS_BELOW_MIN_TRANSACTION_GAS_UNITS
         | StatusCode::GAS_UNIT_PRICE_BELOW_MIN_BOUND
@@ -0,0 +1,379 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Instruction and branch coverage of Move code.
+//!
+//! When the `coverage` feature is enabled, the interpreter records every block of instructions it
+//! executes into a process wide `CoverageMap`. The map can be flushed to a file so that coverage
+//! is aggregated across all the tests of a run, and turned into a `ModuleCoverageReport` listing
+//! the functions and basic blocks of a module that were never executed.
+
+use bytecode_verifier::control_flow_graph::{ControlFlowGraph, VMControlFlowGraph};
+use libra_types::{
+    account_address::AccountAddress,
+    identifier::{IdentStr, Identifier},
+    language_storage::ModuleId,
+};
+use std::{
+    collections::BTreeMap,
+    fmt, fs,
+    io::{self, ErrorKind},
+    path::Path,
+    str::FromStr,
+    sync::Mutex,
+};
+use vm::{
+    access::ModuleAccess,
+    file_format::{Bytecode, CodeOffset, CompiledModule},
+};
+
+/// The environment variable naming the file test harnesses flush the collected coverage to.
+pub const COVERAGE_FILE_ENV_VAR: &str = "MOVE_COVERAGE_FILE";
+
+lazy_static! {
+    static ref COVERAGE: Mutex<CoverageMap> = Mutex::new(CoverageMap::default());
+}
+
+/// Coverage of a single function.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct FunctionCoverage {
+    /// Number of times each instruction was executed, by code offset.
+    pub instructions: BTreeMap<CodeOffset, u64>,
+    /// Number of times each edge out of a conditional branch was followed, keyed by the offset of
+    /// the branch and the offset of the instruction executed next.
+    pub branches: BTreeMap<(CodeOffset, CodeOffset), u64>,
+}
+
+/// Coverage of all the functions executed by the VM.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CoverageMap {
+    pub functions: BTreeMap<(ModuleId, Identifier), FunctionCoverage>,
+}
+
+impl CoverageMap {
+    /// Returns the coverage of the given function, if it was ever executed.
+    pub fn function(&self, module: &ModuleId, function: &IdentStr) -> Option<&FunctionCoverage> {
+        self.functions.get(&(module.clone(), function.to_owned()))
+    }
+
+    /// Adds the hit counts of `other` to this map.
+    pub fn merge(&mut self, other: CoverageMap) {
+        for (key, other) in other.functions {
+            let coverage = self.functions.entry(key).or_default();
+            for (offset, count) in other.instructions {
+                *coverage.instructions.entry(offset).or_insert(0) += count;
+            }
+            for (edge, count) in other.branches {
+                *coverage.branches.entry(edge).or_insert(0) += count;
+            }
+        }
+    }
+
+    /// Records the execution of the instructions of `code` from `start` to `end`, both included.
+    ///
+    /// Conditional branches before `end` fell through. `next` is the offset control was transferred
+    /// to by the instruction at `end`, if it completed.
+    pub fn record_block(
+        &mut self,
+        module: ModuleId,
+        function: &IdentStr,
+        code: &[Bytecode],
+        start: CodeOffset,
+        end: CodeOffset,
+        next: Option<CodeOffset>,
+    ) {
+        let coverage = self
+            .functions
+            .entry((module, function.to_owned()))
+            .or_default();
+        for offset in start..=end {
+            *coverage.instructions.entry(offset).or_insert(0) += 1;
+            let target = match code[offset as usize] {
+                Bytecode::BrTrue(_) | Bytecode::BrFalse(_) if offset != end => offset + 1,
+                Bytecode::BrTrue(_) | Bytecode::BrFalse(_) => match next {
+                    Some(next) => next,
+                    None => continue,
+                },
+                _ => continue,
+            };
+            *coverage.branches.entry((offset, target)).or_insert(0) += 1;
+        }
+    }
+
+    /// Reads a coverage map previously written with `write_to_file`. A missing file is read as an
+    /// empty map.
+    pub fn read_from_file(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(s) => s
+                .parse::<CoverageMap>()
+                .map_err(|e| io::Error::new(ErrorKind::InvalidData, e)),
+            Err(ref e) if e.kind() == ErrorKind::NotFound => Ok(CoverageMap::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn write_to_file(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+}
+
+/// One line per instruction and per branch edge:
+///
+/// ```text
+/// instr <address> <module> <function> <offset> <count>
+/// branch <address> <module> <function> <offset> <target> <count>
+/// ```
+impl fmt::Display for CoverageMap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for ((module, function), coverage) in &self.functions {
+            for (offset, count) in &coverage.instructions {
+                writeln!(
+                    f,
+                    "instr {:x} {} {} {} {}",
+                    module.address(),
+                    module.name(),
+                    function,
+                    offset,
+                    count
+                )?;
+            }
+            for ((offset, target), count) in &coverage.branches {
+                writeln!(
+                    f,
+                    "branch {:x} {} {} {} {} {}",
+                    module.address(),
+                    module.name(),
+                    function,
+                    offset,
+                    target,
+                    count
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for CoverageMap {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        let mut map = CoverageMap::default();
+        for line in s.lines().filter(|line| !line.trim().is_empty()) {
+            let parse_error = || format!("malformed coverage entry '{}'", line);
+            let words: Vec<_> = line.split_whitespace().collect();
+            if words.len() < 4 {
+                return Err(parse_error());
+            }
+            let numbers = words
+                .iter()
+                .skip(4)
+                .map(|word| word.parse::<u64>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| parse_error())?;
+            let address = AccountAddress::from_str(words[1]).map_err(|_| parse_error())?;
+            let module = Identifier::new(words[2]).map_err(|_| parse_error())?;
+            let function = Identifier::new(words[3]).map_err(|_| parse_error())?;
+            let coverage = map
+                .functions
+                .entry((ModuleId::new(address, module), function))
+                .or_default();
+            match (words[0], numbers.as_slice()) {
+                ("instr", [offset, count]) if *offset <= u64::from(CodeOffset::max_value()) => {
+                    *coverage
+                        .instructions
+                        .entry(*offset as CodeOffset)
+                        .or_insert(0) += count;
+                }
+                ("branch", [offset, target, count])
+                    if *offset <= u64::from(CodeOffset::max_value())
+                        && *target <= u64::from(CodeOffset::max_value()) =>
+                {
+                    *coverage
+                        .branches
+                        .entry((*offset as CodeOffset, *target as CodeOffset))
+                        .or_insert(0) += count;
+                }
+                _ => return Err(parse_error()),
+            }
+        }
+        Ok(map)
+    }
+}
+
+/// Records a block of instructions executed by the interpreter.
+#[cfg(feature = "coverage")]
+pub(crate) fn record_block(
+    module: ModuleId,
+    function: &IdentStr,
+    code: &[Bytecode],
+    start: CodeOffset,
+    end: CodeOffset,
+    next: Option<CodeOffset>,
+) {
+    COVERAGE
+        .lock()
+        .unwrap()
+        .record_block(module, function, code, start, end, next);
+}
+
+/// Returns the coverage collected so far and resets the collector.
+pub fn take_coverage() -> CoverageMap {
+    std::mem::replace(&mut *COVERAGE.lock().unwrap(), CoverageMap::default())
+}
+
+/// Moves the coverage collected so far into the file at `path`, adding it to the coverage already
+/// recorded there.
+pub fn flush_coverage(path: &Path) -> io::Result<()> {
+    let mut collected = COVERAGE.lock().unwrap();
+    if collected.functions.is_empty() {
+        return Ok(());
+    }
+    let mut map = CoverageMap::read_from_file(path)?;
+    map.merge(std::mem::replace(&mut *collected, CoverageMap::default()));
+    map.write_to_file(path)
+}
+
+/// Coverage of a single function definition.
+#[derive(Clone, Debug)]
+pub struct FunctionCoverageReport {
+    pub name: Identifier,
+    pub instructions: usize,
+    pub covered_instructions: usize,
+    pub branches: usize,
+    pub covered_branches: usize,
+    /// Code offset ranges, both ends included, of the basic blocks that were never executed.
+    pub uncovered_blocks: Vec<(CodeOffset, CodeOffset)>,
+}
+
+impl FunctionCoverageReport {
+    pub fn is_covered(&self) -> bool {
+        self.covered_instructions > 0
+    }
+}
+
+/// Coverage of the functions defined in a module.
+#[derive(Clone, Debug)]
+pub struct ModuleCoverageReport {
+    pub module: ModuleId,
+    pub functions: Vec<FunctionCoverageReport>,
+}
+
+impl ModuleCoverageReport {
+    /// Builds the report of `module` out of the collected coverage. Native functions are skipped.
+    pub fn new(module: &CompiledModule, coverage: &CoverageMap) -> Self {
+        let module_id = module.self_id();
+        let empty = FunctionCoverage::default();
+        let mut functions = vec![];
+        for function_def in module.function_defs() {
+            if function_def.is_native() {
+                continue;
+            }
+            let handle = module.function_handle_at(function_def.function);
+            let name = module.identifier_at(handle.name);
+            let function_coverage = coverage.function(&module_id, name).unwrap_or(&empty);
+            let code = &function_def.code.code;
+            let cfg = VMControlFlowGraph::new(code);
+
+            let mut branches = 0;
+            let mut covered_branches = 0;
+            for (offset, instr) in code.iter().enumerate() {
+                let offset = offset as CodeOffset;
+                if let Bytecode::BrTrue(target) | Bytecode::BrFalse(target) = instr {
+                    for edge in &[(offset, *target), (offset, offset + 1)] {
+                        branches += 1;
+                        if function_coverage.branches.contains_key(edge) {
+                            covered_branches += 1;
+                        }
+                    }
+                }
+            }
+
+            let mut uncovered_blocks: Vec<_> = cfg
+                .blocks()
+                .iter()
+                .filter(|block_id| {
+                    cfg.instr_indexes(block_id)
+                        .all(|offset| !function_coverage.instructions.contains_key(&offset))
+                })
+                .map(|block_id| (cfg.block_start(block_id), cfg.block_end(block_id)))
+                .collect();
+            uncovered_blocks.sort();
+
+            functions.push(FunctionCoverageReport {
+                name: name.to_owned(),
+                instructions: code.len(),
+                covered_instructions: (0..code.len())
+                    .filter(|offset| {
+                        function_coverage
+                            .instructions
+                            .contains_key(&(*offset as CodeOffset))
+                    })
+                    .count(),
+                branches,
+                covered_branches,
+                uncovered_blocks,
+            });
+        }
+        ModuleCoverageReport {
+            module: module_id,
+            functions,
+        }
+    }
+
+    pub fn instructions(&self) -> (usize, usize) {
+        self.functions.iter().fold((0, 0), |(covered, total), f| {
+            (covered + f.covered_instructions, total + f.instructions)
+        })
+    }
+
+    pub fn branches(&self) -> (usize, usize) {
+        self.functions.iter().fold((0, 0), |(covered, total), f| {
+            (covered + f.covered_branches, total + f.branches)
+        })
+    }
+}
+
+fn percentage((covered, total): (usize, usize)) -> f64 {
+    if total == 0 {
+        100.0
+    } else {
+        covered as f64 * 100.0 / total as f64
+    }
+}
+
+impl fmt::Display for ModuleCoverageReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "Module {}.{}: {:.1}% instructions, {:.1}% branches",
+            self.module.address(),
+            self.module.name(),
+            percentage(self.instructions()),
+            percentage(self.branches()),
+        )?;
+        for function in &self.functions {
+            if !function.is_covered() {
+                writeln!(f, "    {}: not covered", function.name)?;
+                continue;
+            }
+            write!(
+                f,
+                "    {}: {:.1}% instructions, {:.1}% branches",
+                function.name,
+                percentage((function.covered_instructions, function.instructions)),
+                percentage((function.covered_branches, function.branches)),
+            )?;
+            if !function.uncovered_blocks.is_empty() {
+                let blocks: Vec<_> = function
+                    .uncovered_blocks
+                    .iter()
+                    .map(|(start, end)| format!("[{}-{}]", start, end))
+                    .collect();
+                write!(f, ", uncovered blocks: {}", blocks.join(" "))?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::loaded_data::{function::FunctionReference, loaded_module::LoadedModule};
+use libra_types::identifier::IdentStr;
 use std::{fmt, marker::PhantomData};
 use vm::{
     errors::{Location, VMResult},
@@ -45,6 +46,10 @@ where
         self.function.module()
     }
 
+    pub fn function_name(&self) -> &'txn IdentStr {
+        self.function.name()
+    }
+
     pub fn copy_loc(&self, idx: LocalIndex) -> VMResult<Value> {
         self.locals.copy_loc(idx as usize)
     }
@@ -0,0 +1,45 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+//! Loading of the on-chain gas schedule.
+
+use crate::{
+    counters::report_gas_schedule_decode_failure, data_cache::RemoteCache,
+    identifier::create_access_path,
+};
+use canonical_serialization::{CanonicalDeserializer, SimpleDeserializer};
+use libra_types::account_config;
+use logger::prelude::*;
+use vm::{
+    errors::VMResult,
+    gas_schedule::{CostTable, DEFAULT_GAS_SCHEDULE},
+};
+
+/// Load the instruction cost table published in the `GasSchedule.T` resource under the
+/// association account.
+///
+/// If no table has been published yet (e.g. when executing the genesis transaction), the table
+/// compiled into the binary is returned instead. The same table is used if the published one
+/// cannot be decoded: `GasSchedule.set_instruction_schedule` rejects such tables, and discarding
+/// every block because of one would halt the chain.
+pub fn load_gas_schedule(data_cache: &dyn RemoteCache) -> VMResult<CostTable> {
+    let access_path = create_access_path(
+        &account_config::association_address(),
+        account_config::gas_schedule_struct_tag(),
+    );
+    let blob = match data_cache.get(&access_path)? {
+        Some(blob) => blob,
+        None => return Ok(DEFAULT_GAS_SCHEDULE.clone()),
+    };
+    // `GasSchedule.T` has a single bytearray field that holds the serialized table.
+    Ok(SimpleDeserializer::new(&blob)
+        .decode_bytes()
+        .and_then(|table_bytes| CostTable::from_bytes(&table_bytes))
+        .unwrap_or_else(|err| {
+            crit!(
+                "[VM] Unable to decode the on-chain gas schedule, using the default: {}",
+                err
+            );
+            report_gas_schedule_decode_failure();
+            DEFAULT_GAS_SCHEDULE.clone()
+        }))
+}
@@ -122,7 +122,9 @@ mod runtime;
 mod unit_tests;
 
 pub mod code_cache;
+pub mod coverage;
 pub mod data_cache;
+pub mod gas_schedule;
 pub mod identifier;
 pub mod loaded_data;
 pub mod txn_executor;
@@ -138,7 +140,7 @@ pub use txn_executor::execute_function;
 
 use config::config::VMConfig;
 use libra_types::{
-    transaction::{SignedTransaction, TransactionOutput},
+    transaction::{SignedTransaction, Transaction, TransactionOutput},
     vm_error::VMStatus,
 };
 use state_view::StateView;
@@ -163,9 +165,11 @@ pub trait VMExecutor {
     // There are some cache invalidation issues around transactions publishing code that need to be
     // sorted out before that's possible.
 
-    /// Executes a block of transactions and returns output for each one of them.
+    /// Executes a block of transactions and returns output for each one of them. A block
+    /// metadata transaction updates the on-chain block resource before the transactions that
+    /// follow it are executed.
     fn execute_block(
-        transactions: Vec<SignedTransaction>,
+        transactions: Vec<Transaction>,
         config: &VMConfig,
         state_view: &dyn StateView,
     ) -> Vec<TransactionOutput>;
@@ -1,7 +1,9 @@
 use crate::{
     code_cache::module_cache::ModuleCache,
+    loaded_data::loaded_module::LoadedModule,
     process_txn::verify::{VerTxn, VerifiedTransaction, VerifiedTransactionState},
 };
+use bytecode_verifier::{CompatibilityChecker, VerifiedModule};
 use libra_types::{
     transaction::{TransactionOutput, TransactionPayload, TransactionStatus},
     vm_error::{StatusCode, StatusType, VMStatus},
@@ -10,7 +12,7 @@ use libra_types::{
 use logger::prelude::*;
 use vm::{
     access::ModuleAccess,
-    errors::{vm_error, Location},
+    errors::{vm_error, Location, VMResult},
 };
 
 /// Represents a transaction that has been executed.
@@ -35,6 +37,25 @@ impl ExecutedTransaction {
     }
 }
 
+/// Checks that `new_module` can replace `old_module`, the version published under the same
+/// `ModuleId`.
+fn check_module_update(old_module: &LoadedModule, new_module: &VerifiedModule) -> VMResult<()> {
+    let errors = CompatibilityChecker::new(old_module.as_module(), new_module.as_inner()).check();
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        warn!(
+            "[VM] VM error incompatible update of module {:?}: {:?}",
+            new_module.self_id(),
+            errors
+        );
+        Err(vm_error(
+            Location::default(),
+            StatusCode::INCOMPATIBLE_MODULE_UPDATE,
+        ))
+    }
+}
+
 fn execute<'alloc, 'txn, P>(
     mut verified_txn: VerifiedTransaction<'alloc, 'txn, P>,
 ) -> TransactionOutput
@@ -74,6 +95,14 @@ where
                 // if it is not already cached.
                 match txn_executor.module_cache().get_loaded_module(&module_id) {
                     Ok(None) => (), // No module with this name exists. safe to publish one
+                    Ok(Some(old_module)) => {
+                        // A module with this name already exists. The new version replaces it
+                        // only if it doesn't break code linked against the old version or make
+                        // resources published by the old version unreadable.
+                        if let Err(status) = check_module_update(old_module, &module) {
+                            return txn_executor.failed_transaction_cleanup(Err(status));
+                        }
+                    }
                     Err(ref err) if err.is(StatusType::InvariantViolation) => {
                         error!(
                             "[VM] VM internal error while checking for duplicate module {:?}: {:?}",
@@ -81,16 +110,9 @@ where
                         );
                         return ExecutedTransaction::discard_error_output(err.clone());
                     }
-                    Ok(Some(_)) | Err(_) => {
-                        // A module with this name already exists (the error case is when the module
-                        // couldn't be verified, but it still exists so we should fail similarly).
-                        // It is not safe to publish another one; it would clobber the old module.
-                        // This would break code that links against the module and make published
-                        // resources from the old module inaccessible (or worse, accessible and not
-                        // typesafe).
-                        //
-                        // We are currently developing a versioning scheme for safe updates of
-                        // modules and resources.
+                    Err(_) => {
+                        // A module with this name already exists but couldn't be verified, so it
+                        // can't be compared with the new version.
                         warn!("[VM] VM error duplicate module {:?}", module_id);
                         return txn_executor.failed_transaction_cleanup(Err(vm_error(
                             Location::default(),
@@ -150,6 +172,14 @@ where
             // if it is not already cached.
             match txn_executor.module_cache().get_loaded_module(&module_id) {
                 Ok(None) => (), // No module with this name exists. safe to publish one
+                Ok(Some(old_module)) => {
+                    // A module with this name already exists. The new version replaces it only
+                    // if it doesn't break code linked against the old version or make resources
+                    // published by the old version unreadable.
+                    if let Err(status) = check_module_update(old_module, &ver_module) {
+                        return txn_executor.failed_transaction_cleanup(Err(status));
+                    }
+                }
                 Err(ref err) if err.is(StatusType::InvariantViolation) => {
                     error!(
                         "[VM] VM internal error while checking for duplicate module {:?}: {:?}",
@@ -157,16 +187,9 @@ where
                     );
                     return ExecutedTransaction::discard_error_output(err.clone());
                 }
-                Ok(Some(_)) | Err(_) => {
-                    // A module with this name already exists (the error case is when the module
-                    // couldn't be verified, but it still exists so we should fail similarly).
-                    // It is not safe to publish another one; it would clobber the old module.
-                    // This would break code that links against the module and make published
-                    // resources from the old module inaccessible (or worse, accessible and not
-                    // typesafe).
-                    //
-                    // We are currently developing a versioning scheme for safe updates of
-                    // modules and resources.
+                Err(_) => {
+                    // A module with this name already exists but couldn't be verified, so it can't
+                    // be compared with the new version.
                     warn!("[VM] VM error duplicate module {:?}", module_id);
                     return txn_executor.failed_transaction_cleanup(Err(vm_error(
                         Location::default(),
@@ -174,6 +197,9 @@ where
                     )));
                 }
             }
+            // Cache the module so that, if it replaces an older version, the following
+            // transactions of the block use the new one.
+            txn_executor.module_cache().cache_module(ver_module);
             let module_bytes = module.into_inner();
             txn_executor.transaction_cleanup(vec![(module_id, module_bytes)])
         }
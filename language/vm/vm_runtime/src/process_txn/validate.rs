@@ -17,7 +17,7 @@ use libra_types::{
 use logger::prelude::*;
 use vm::{
     errors::convert_prologue_runtime_error,
-    gas_schedule::{self, AbstractMemorySize, GasAlgebra, GasCarrier},
+    gas_schedule::{self, AbstractMemorySize, CostTable, GasAlgebra, GasCarrier},
     transaction_metadata::TransactionMetadata,
 };
 use vm_cache_map::Arena;
@@ -74,6 +74,7 @@ where
             txn,
             module_cache,
             data_cache,
+            gas_schedule,
             allocator,
             ..
         } = process_txn;
@@ -84,6 +85,7 @@ where
                     &txn,
                     module_cache,
                     data_cache,
+                    gas_schedule,
                     allocator,
                     mode,
                     || {
@@ -109,6 +111,7 @@ where
                     &txn,
                     module_cache,
                     data_cache,
+                    gas_schedule,
                     allocator,
                     mode,
                     || {
@@ -127,6 +130,7 @@ where
                     &txn,
                     module_cache,
                     data_cache,
+                    gas_schedule,
                     allocator,
                     mode,
                     || {
@@ -192,6 +196,7 @@ where
         txn: &SignatureCheckedTransaction,
         module_cache: P,
         data_cache: &'txn dyn RemoteCache,
+        gas_schedule: &'txn CostTable,
         allocator: &'txn Arena<LoadedModule>,
         mode: ValidationMode,
         payload_check: impl Fn() -> Result<(), VMStatus>,
@@ -300,8 +305,13 @@ where
         payload_check()?;
 
         let metadata = TransactionMetadata::new(&txn);
-        let mut txn_state =
-            ValidatedTransactionState::new(metadata, module_cache, data_cache, allocator);
+        let mut txn_state = ValidatedTransactionState::new(
+            metadata,
+            module_cache,
+            data_cache,
+            gas_schedule,
+            allocator,
+        );
 
         // Run the prologue to ensure that clients have enough gas and aren't tricking us by
         // sending us garbage.
@@ -350,11 +360,13 @@ where
         metadata: TransactionMetadata,
         module_cache: P,
         data_cache: &'txn dyn RemoteCache,
+        gas_schedule: &'txn CostTable,
         allocator: &'txn Arena<LoadedModule>,
     ) -> Self {
         // This temporary cache is used for modules published by a single transaction.
         let txn_module_cache = TransactionModuleCache::new(module_cache, allocator);
-        let txn_executor = TransactionExecutor::new(txn_module_cache, data_cache, metadata);
+        let txn_executor =
+            TransactionExecutor::new(txn_module_cache, data_cache, metadata, gas_schedule);
         Self { txn_executor }
     }
 }
@@ -9,17 +9,20 @@ use crate::{
         script_cache::ScriptCache,
     },
     counters::report_verification_status,
-    data_cache::BlockDataCache,
+    data_cache::{BlockDataCache, RemoteCache},
+    gas_schedule::load_gas_schedule,
     loaded_data::loaded_module::LoadedModule,
     process_txn::{validate::ValidationMode, ProcessTransaction},
 };
 use config::config::{VMConfig, VMPublishingOption};
 use libra_types::{
-    transaction::{SignedTransaction, TransactionOutput},
+    transaction::{SignedTransaction, Transaction, TransactionOutput, Version},
     vm_error::{StatusCode, VMStatus},
 };
 use logger::prelude::*;
 use state_view::StateView;
+use std::sync::{Arc, Mutex};
+use vm::{errors::VMResult, gas_schedule::CostTable};
 use vm_cache_map::Arena;
 
 /// An instantiation of the MoveVM.
@@ -30,10 +33,13 @@ use vm_cache_map::Arena;
 ///   in the whitelist, the VM will just reject it in `verify_transaction`.
 /// * Custom scripts, which will allow arbitrary valid scripts, but no module publishing
 /// * Open script and module publishing
+/// `gas_schedule` is the gas schedule last loaded by `verify_transaction`, together with the
+/// ledger version it was loaded at.
 pub struct VMRuntime<'alloc> {
     code_cache: VMModuleCache<'alloc>,
     script_cache: ScriptCache<'alloc>,
     publishing_option: VMPublishingOption,
+    gas_schedule: Mutex<Option<(Version, Arc<CostTable>)>>,
 }
 
 impl<'alloc> VMRuntime<'alloc> {
@@ -44,9 +50,35 @@ impl<'alloc> VMRuntime<'alloc> {
             code_cache: VMModuleCache::new(allocator),
             script_cache: ScriptCache::new(allocator),
             publishing_option: config.publishing_options.clone(),
+            gas_schedule: Mutex::new(None),
         }
     }
 
+    /// Returns the gas schedule to validate transactions against `data_view` with. The schedule
+    /// is only loaded again once the version of the ledger `data_view` reads changes.
+    fn validation_gas_schedule(
+        &self,
+        data_view: &dyn StateView,
+        data_cache: &dyn RemoteCache,
+    ) -> VMResult<Arc<CostTable>> {
+        let version = match data_view.version() {
+            Some(version) => version,
+            None => return load_gas_schedule(data_cache).map(Arc::new),
+        };
+        let mut cached = self
+            .gas_schedule
+            .lock()
+            .expect("gas schedule lock should not be poisoned");
+        if let Some((cached_version, gas_schedule)) = &*cached {
+            if *cached_version == version {
+                return Ok(Arc::clone(gas_schedule));
+            }
+        }
+        let gas_schedule = Arc::new(load_gas_schedule(data_cache)?);
+        *cached = Some((version, Arc::clone(&gas_schedule)));
+        Ok(gas_schedule)
+    }
+
     /// Determine if a transaction is valid. Will return `None` if the transaction is accepted,
     /// `Some(Err)` if the VM rejects it, with `Err` as an error code. We verify the following
     /// items:
@@ -71,6 +103,10 @@ impl<'alloc> VMRuntime<'alloc> {
         let module_cache =
             BlockModuleCache::new(&self.code_cache, ModuleFetcherImpl::new(data_view));
         let data_cache = BlockDataCache::new(data_view);
+        let gas_schedule = match self.validation_gas_schedule(data_view, &data_cache) {
+            Ok(gas_schedule) => gas_schedule,
+            Err(vm_status) => return Some(vm_status),
+        };
 
         let arena = Arena::new();
         let signature_verified_txn = match txn.check_signature() {
@@ -78,8 +114,13 @@ impl<'alloc> VMRuntime<'alloc> {
             Err(_) => return Some(VMStatus::new(StatusCode::INVALID_SIGNATURE)),
         };
 
-        let process_txn =
-            ProcessTransaction::new(signature_verified_txn, module_cache, &data_cache, &arena);
+        let process_txn = ProcessTransaction::new(
+            signature_verified_txn,
+            module_cache,
+            &data_cache,
+            &gas_schedule,
+            &arena,
+        );
         let mode = if data_view.is_genesis() {
             ValidationMode::Genesis
         } else {
@@ -109,7 +150,7 @@ impl<'alloc> VMRuntime<'alloc> {
     /// transaction output.
     pub fn execute_block_transactions(
         &self,
-        txn_block: Vec<SignedTransaction>,
+        txn_block: Vec<Transaction>,
         data_view: &dyn StateView,
     ) -> Vec<TransactionOutput> {
         execute_block(
@@ -19,6 +19,7 @@ use libra_types::{
     access_path::AccessPath,
     account_address::AccountAddress,
     account_config,
+    block_metadata::BlockMetadata,
     byte_array::ByteArray,
     contract_event::ContractEvent,
     event::EventKey,
@@ -27,7 +28,7 @@ use libra_types::{
     transaction::{
         TransactionArgument, TransactionOutput, TransactionStatus, MAX_TRANSACTION_SIZE_IN_BYTES,
     },
-    vm_error::{StatusCode, StatusType, VMStatus},
+    vm_error::{CodeLocation, StatusCode, StatusType, VMStatus},
     write_set::WriteSet,
 };
 use std::{collections::VecDeque, convert::TryFrom};
@@ -35,14 +36,15 @@ use vm::{
     access::ModuleAccess,
     errors::*,
     file_format::{Bytecode, CodeOffset, CompiledScript, StructDefinitionIndex},
-    gas_schedule::{AbstractMemorySize, GasAlgebra, GasUnits},
+    gas_schedule::{AbstractMemorySize, CostTable, GasAlgebra, GasUnits, DEFAULT_GAS_SCHEDULE},
     transaction_metadata::TransactionMetadata,
     vm_string::VMString,
 };
 use vm_cache_map::Arena;
 use vm_runtime_types::{
     native_functions::dispatch::{dispatch_native_function, NativeReturnStatus},
-    value::{ReferenceValue, Struct, Value},
+    native_structs::{vector::NativeVector, NativeStructValue},
+    value::{IntegerValue, ReferenceValue, Struct, Value},
 };
 
 // Metadata needed for resolving the account module.
@@ -56,6 +58,15 @@ lazy_static! {
     /// The ModuleId for the LibraCoin module
     pub static ref COIN_MODULE: ModuleId =
         { ModuleId::new(account_config::core_code_address(), Identifier::new("LibraCoin").unwrap()) };
+    /// The ModuleId for the Currency module
+    pub static ref CURRENCY_MODULE: ModuleId =
+        { ModuleId::new(account_config::core_code_address(), Identifier::new("Currency").unwrap()) };
+    /// The ModuleId for the GasSchedule module
+    pub static ref GAS_SCHEDULE_MODULE: ModuleId =
+        { ModuleId::new(account_config::core_code_address(), account_config::gas_schedule_module_name().to_owned()) };
+    /// The ModuleId for the TransactionFee module
+    pub static ref TRANSACTION_FEE_MODULE: ModuleId =
+        { ModuleId::new(account_config::core_code_address(), Identifier::new("TransactionFee").unwrap()) };
     /// The ModuleId for the Event
     pub static ref EVENT_MODULE: ModuleId =
         { ModuleId::new(account_config::core_code_address(), Identifier::new("Event").unwrap()) };
@@ -69,6 +80,7 @@ lazy_static! {
 lazy_static! {
     static ref PROLOGUE_NAME: Identifier = Identifier::new("prologue").unwrap();
     static ref EPILOGUE_NAME: Identifier = Identifier::new("epilogue").unwrap();
+    static ref GET_CURRENT_HEIGHT_NAME: Identifier = Identifier::new("get_current_height").unwrap();
     static ref CREATE_ACCOUNT_NAME: Identifier = Identifier::new("make").unwrap();
     static ref ACCOUNT_STRUCT_NAME: Identifier = Identifier::new("T").unwrap();
     static ref EMIT_EVENT_NAME: Identifier = Identifier::new("write_to_event_store").unwrap();
@@ -101,7 +113,7 @@ where
 
     #[cfg(not(any(test, feature = "instruction_synthesis")))]
     execution_stack: ExecutionStack<'alloc, 'txn, P>,
-    gas_meter: GasMeter,
+    gas_meter: GasMeter<'txn>,
     txn_data: TransactionMetadata,
     event_data: Vec<ContractEvent>,
     data_view: TransactionDataCache<'txn>,
@@ -115,15 +127,17 @@ where
     /// Create a new `TransactionExecutor` to execute a single transaction. `module_cache` is the
     /// cache that stores the modules previously read from the blockchain. `data_cache` is the cache
     /// that holds read-only connection to the state store as well as the changes made by previous
-    /// transactions within the same block.
+    /// transactions within the same block. `gas_schedule` is the instruction cost table that gas
+    /// is charged against.
     pub fn new(
         module_cache: P,
         data_cache: &'txn dyn RemoteCache,
         txn_data: TransactionMetadata,
+        gas_schedule: &'txn CostTable,
     ) -> Self {
         TransactionExecutor {
             execution_stack: ExecutionStack::new(module_cache),
-            gas_meter: GasMeter::new(txn_data.max_gas_amount()),
+            gas_meter: GasMeter::new(txn_data.max_gas_amount(), gas_schedule),
             txn_data,
             event_data: Vec::new(),
             data_view: TransactionDataCache::new(data_cache),
@@ -155,12 +169,35 @@ where
         }
     }
 
-    fn binop_int<F, T>(&mut self, f: F) -> VMResult<()>
+    fn binop_int<F>(&mut self, f: F) -> VMResult<()>
     where
-        Option<T>: From<Value>,
-        F: FnOnce(T, T) -> Option<u64>,
+        F: FnOnce(IntegerValue, IntegerValue) -> Option<IntegerValue>,
+    {
+        self.binop(|lhs, rhs| f(lhs, rhs).map(IntegerValue::into_value))
+    }
+
+    fn binop_cmp<F>(&mut self, f: F) -> VMResult<()>
+    where
+        F: FnOnce(IntegerValue, IntegerValue) -> Option<bool>,
     {
-        self.binop(|lhs, rhs| f(lhs, rhs).map(Value::u64))
+        self.binop(|lhs, rhs| f(lhs, rhs).map(Value::bool))
+    }
+
+    /// Convert the integer at the top of the stack with `f`, which returns `None` if the value
+    /// does not fit in the target type.
+    fn cast<F>(&mut self, f: F) -> VMResult<()>
+    where
+        F: FnOnce(IntegerValue) -> Option<Value>,
+    {
+        let operand = self.execution_stack.pop_as::<IntegerValue>()?;
+        if let Some(v) = f(operand) {
+            self.execution_stack.push(v)
+        } else {
+            Err(vm_error(
+                self.execution_stack.location()?,
+                StatusCode::ARITHMETIC_ERROR,
+            ))
+        }
     }
 
     fn binop_bool<F, T>(&mut self, f: F) -> VMResult<()>
@@ -175,14 +212,66 @@ where
     /// Ok(offset) when the instruction sequence hit a branch, either by calling into a new
     /// function, branches, function return, etc. The return value will be the pc for the next
     /// instruction to be executed.
-    #[allow(clippy::cognitive_complexity)]
+    ///
+    /// Errors raised while executing the block are annotated with the location of the failing
+    /// instruction.
     pub fn execute_block(
         &mut self,
         code: &[Bytecode],
         beginning_offset: CodeOffset,
     ) -> VMResult<CodeOffset> {
         let mut pc = beginning_offset;
-        for instruction in &code[beginning_offset as usize..] {
+        #[cfg(feature = "coverage")]
+        let function = self
+            .execution_stack
+            .top_frame()
+            .map(|frame| (frame.module().self_id(), frame.function_name()));
+        let result = self.execute_instructions(code, &mut pc);
+        #[cfg(feature = "coverage")]
+        {
+            if let (Ok((module, function_name)), Some(last)) = (function, code.len().checked_sub(1))
+            {
+                crate::coverage::record_block(
+                    module,
+                    function_name,
+                    code,
+                    beginning_offset,
+                    std::cmp::min(pc, last as CodeOffset),
+                    result.as_ref().ok().cloned(),
+                );
+            }
+        }
+        result.map_err(|err| self.with_abort_location(err, pc))
+    }
+
+    /// Attach the location of the instruction at `code_offset` in the current frame to `err`,
+    /// unless a callee already recorded a more precise one. A location recorded by the verifier,
+    /// e.g. for a dependency that failed verification when it was loaded, is replaced.
+    fn with_abort_location(&self, err: VMStatus, code_offset: CodeOffset) -> VMStatus {
+        if let Some(location) = &err.location {
+            if location.call_stack_depth > 0 {
+                return err;
+            }
+        }
+        match self.execution_stack.top_frame() {
+            Ok(frame) => err.with_location(CodeLocation {
+                module: Some(frame.module().self_id()),
+                function: Some(frame.function_name().to_owned()),
+                function_definition_index: None,
+                code_offset,
+                call_stack_depth: self.execution_stack.call_stack_height() as u64,
+            }),
+            Err(_) => err,
+        }
+    }
+
+    #[allow(clippy::cognitive_complexity)]
+    fn execute_instructions(
+        &mut self,
+        code: &[Bytecode],
+        pc: &mut CodeOffset,
+    ) -> VMResult<CodeOffset> {
+        for instruction in &code[*pc as usize..] {
             // FIXME: Once we add in memory ops, we will need to pass in the current memory size to
             // this function.
             self.gas_meter.calculate_and_consume(
@@ -215,9 +304,18 @@ where
                     }
                 }
                 Bytecode::Branch(offset) => return Ok(*offset),
+                Bytecode::LdU8(int_const) => {
+                    self.execution_stack.push(Value::u8(*int_const))?;
+                }
                 Bytecode::LdConst(int_const) => {
                     self.execution_stack.push(Value::u64(*int_const))?;
                 }
+                Bytecode::LdU128(int_const) => {
+                    self.execution_stack.push(Value::u128(*int_const))?;
+                }
+                Bytecode::CastU8 => self.cast(|i| i.cast_u8().map(Value::u8))?,
+                Bytecode::CastU64 => self.cast(|i| i.cast_u64().map(Value::u64))?,
+                Bytecode::CastU128 => self.cast(|i| Some(Value::u128(i.cast_u128())))?,
                 Bytecode::LdAddr(idx) => {
                     let top_frame = self.execution_stack.top_frame()?;
                     let addr_ref = top_frame.module().address_at(*idx);
@@ -332,7 +430,7 @@ where
                     // Call stack is not reconstructed for a native call, so we just
                     // proceed on to next instruction.
                     } else {
-                        self.execution_stack.top_frame_mut()?.save_pc(pc);
+                        self.execution_stack.top_frame_mut()?.save_pc(*pc);
                         self.execution_stack.push_call(callee_function_ref)?;
                         // Call stack is reconstructed, the next instruction to execute will be the
                         // first instruction of the callee function. Thus we should break here to
@@ -383,20 +481,20 @@ where
                     reference.write_ref(value);
                 }
                 // Arithmetic Operations
-                Bytecode::Add => self.binop_int(u64::checked_add)?,
-                Bytecode::Sub => self.binop_int(u64::checked_sub)?,
-                Bytecode::Mul => self.binop_int(u64::checked_mul)?,
-                Bytecode::Mod => self.binop_int(u64::checked_rem)?,
-                Bytecode::Div => self.binop_int(u64::checked_div)?,
-                Bytecode::BitOr => self.binop_int(|l: u64, r| Some(l | r))?,
-                Bytecode::BitAnd => self.binop_int(|l: u64, r| Some(l & r))?,
-                Bytecode::Xor => self.binop_int(|l: u64, r| Some(l ^ r))?,
+                Bytecode::Add => self.binop_int(IntegerValue::checked_add)?,
+                Bytecode::Sub => self.binop_int(IntegerValue::checked_sub)?,
+                Bytecode::Mul => self.binop_int(IntegerValue::checked_mul)?,
+                Bytecode::Mod => self.binop_int(IntegerValue::checked_rem)?,
+                Bytecode::Div => self.binop_int(IntegerValue::checked_div)?,
+                Bytecode::BitOr => self.binop_int(IntegerValue::bit_or)?,
+                Bytecode::BitAnd => self.binop_int(IntegerValue::bit_and)?,
+                Bytecode::Xor => self.binop_int(IntegerValue::bit_xor)?,
                 Bytecode::Or => self.binop_bool(|l, r| l || r)?,
                 Bytecode::And => self.binop_bool(|l, r| l && r)?,
-                Bytecode::Lt => self.binop_bool(|l: u64, r| l < r)?,
-                Bytecode::Gt => self.binop_bool(|l: u64, r| l > r)?,
-                Bytecode::Le => self.binop_bool(|l: u64, r| l <= r)?,
-                Bytecode::Ge => self.binop_bool(|l: u64, r| l >= r)?,
+                Bytecode::Lt => self.binop_cmp(IntegerValue::lt)?,
+                Bytecode::Gt => self.binop_cmp(IntegerValue::gt)?,
+                Bytecode::Le => self.binop_cmp(IntegerValue::le)?,
+                Bytecode::Ge => self.binop_cmp(IntegerValue::ge)?,
                 Bytecode::Abort => {
                     let error_code = self.execution_stack.pop_as::<u64>()?;
                     return Err(
@@ -437,7 +535,7 @@ where
                 }
                 Bytecode::GetTxnPublicKey => {
                     self.execution_stack.push(Value::byte_array(ByteArray::new(
-                        self.txn_data.public_key().to_bytes().to_vec(),
+                        self.txn_data.public_key().to_vec(),
                     )))?;
                 }
                 Bytecode::MutBorrowGlobal(idx, _) | Bytecode::ImmBorrowGlobal(idx, _) => {
@@ -536,7 +634,7 @@ where
                         .push(Value::u64(self.gas_meter.remaining_gas().get()))?;
                 }
             }
-            pc += 1;
+            *pc += 1;
         }
 
         if cfg!(test) || cfg!(feature = "instruction_synthesis") {
@@ -624,6 +722,42 @@ where
         }
     }
 
+    /// Run the prologue of a block by calling into `PROLOGUE_NAME` function stored in the
+    /// `BLOCK_MODULE` on chain. The new block is the one following the current block on chain.
+    /// Block prologues are system transactions, so no gas is charged for them.
+    pub(crate) fn run_block_prologue(&mut self, block_metadata: &BlockMetadata) -> VMResult<()> {
+        record_stats! {time_hist | BLOCK_PROLOGUE_TIME_TAKEN | {
+                self.gas_meter.disable_metering();
+                let result = self.execute_block_prologue(block_metadata);
+                self.gas_meter.enable_metering();
+                result
+            }
+        }
+    }
+
+    fn execute_block_prologue(&mut self, block_metadata: &BlockMetadata) -> VMResult<()> {
+        self.execute_function(&BLOCK_MODULE, &GET_CURRENT_HEIGHT_NAME, vec![])?;
+        let height = self
+            .pop_stack()?
+            .value_as::<u64>()
+            .ok_or_else(|| VMStatus::new(StatusCode::TYPE_MISMATCH))?;
+        let voters = block_metadata
+            .voters()
+            .into_iter()
+            .map(Value::address)
+            .collect();
+        let next_height = height
+            .checked_add(1)
+            .ok_or_else(|| VMStatus::new(StatusCode::ARITHMETIC_ERROR))?;
+        let args = vec![
+            Value::u64(next_height),
+            Value::u64(block_metadata.timestamp_usec()),
+            Value::address(block_metadata.proposer()),
+            Value::native_struct(NativeStructValue::Vector(NativeVector::new(voters))),
+        ];
+        self.execute_function(&BLOCK_MODULE, &PROLOGUE_NAME, args)
+    }
+
     /// Generate the TransactionOutput on failure. There can be two possibilities:
     /// 1. The transaction encounters some runtime error, such as out of gas, arithmetic overflow,
     /// etc. In this scenario, we are going to keep this transaction and charge proper gas to the
@@ -830,7 +964,7 @@ pub fn execute_function(
     }
     let mut vm = TransactionExecutor {
         execution_stack: ExecutionStack::new(&module_cache),
-        gas_meter: GasMeter::new(txn_metadata.max_gas_amount()),
+        gas_meter: GasMeter::new(txn_metadata.max_gas_amount(), &*DEFAULT_GAS_SCHEDULE),
         txn_data: txn_metadata,
         event_data: Vec::new(),
         data_view: TransactionDataCache::new(data_cache),
@@ -0,0 +1,125 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::coverage::{CoverageMap, ModuleCoverageReport};
+use compiler::Compiler;
+use libra_types::account_address::AccountAddress;
+use vm::{
+    access::ModuleAccess,
+    file_format::{Bytecode, CodeOffset, CompiledModule},
+};
+
+fn compile_module(s: &str) -> CompiledModule {
+    let compiler = Compiler {
+        address: AccountAddress::default(),
+        skip_stdlib_deps: true,
+        ..Compiler::default()
+    };
+    compiler
+        .into_compiled_program(s)
+        .expect("Failed to compile program")
+        .modules
+        .pop()
+        .unwrap()
+}
+
+fn code_of<'a>(module: &'a CompiledModule, name: &str) -> &'a [Bytecode] {
+    let def = module
+        .function_defs()
+        .iter()
+        .find(|def| {
+            module
+                .identifier_at(module.function_handle_at(def.function).name)
+                .as_str()
+                == name
+        })
+        .unwrap();
+    &def.code.code
+}
+
+const MODULE: &str = "
+    modules:
+    module M {
+        public f(x: bool): u64 {
+            if (move(x)) {
+                return 1;
+            }
+            return 2;
+        }
+        public g() {
+            return;
+        }
+    }
+    script:
+    main() {
+        return;
+    }
+";
+
+#[test]
+fn report_uncovered_functions_and_blocks() {
+    let module = compile_module(MODULE);
+    let code = code_of(&module, "f");
+    let (branch, target) = code
+        .iter()
+        .enumerate()
+        .find_map(|(offset, instr)| match instr {
+            Bytecode::BrTrue(target) | Bytecode::BrFalse(target) => {
+                Some((offset as CodeOffset, *target))
+            }
+            _ => None,
+        })
+        .unwrap();
+
+    // run `f` once, following the jump of its only conditional branch
+    let mut coverage = CoverageMap::default();
+    let end = (target..code.len() as CodeOffset)
+        .find(|offset| code[*offset as usize] == Bytecode::Ret)
+        .unwrap();
+    coverage.record_block(module.self_id(), &ident("f"), code, 0, branch, Some(target));
+    coverage.record_block(module.self_id(), &ident("f"), code, target, end, Some(0));
+
+    let report = ModuleCoverageReport::new(&module, &coverage);
+    let f = report
+        .functions
+        .iter()
+        .find(|f| f.name == ident("f"))
+        .unwrap();
+    assert!(f.is_covered());
+    assert_eq!(f.branches, 2);
+    assert_eq!(f.covered_branches, 1);
+    assert_eq!(f.uncovered_blocks.len(), 1);
+    assert_eq!(f.uncovered_blocks[0].0, branch + 1);
+    assert!(f.covered_instructions < f.instructions);
+
+    let g = report
+        .functions
+        .iter()
+        .find(|f| f.name == ident("g"))
+        .unwrap();
+    assert!(!g.is_covered());
+    assert!(report.to_string().contains("g: not covered"));
+}
+
+#[test]
+fn merge_and_roundtrip() {
+    let module = compile_module(MODULE);
+    let code = code_of(&module, "g");
+
+    let mut coverage = CoverageMap::default();
+    coverage.record_block(module.self_id(), &ident("g"), code, 0, 0, Some(0));
+    let mut other = coverage.clone();
+    other.merge(coverage.clone());
+    assert_eq!(
+        other
+            .function(&module.self_id(), &ident("g"))
+            .unwrap()
+            .instructions[&0],
+        2
+    );
+
+    let parsed = other.to_string().parse::<CoverageMap>().unwrap();
+    assert_eq!(parsed, other);
+    "instr 00 M g".parse::<CoverageMap>().unwrap_err();
+}
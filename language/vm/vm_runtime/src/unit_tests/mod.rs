@@ -3,6 +3,7 @@
 
 use libra_types::identifier::Identifier;
 
+mod coverage_tests;
 mod identifier_prop_tests;
 mod module_cache_tests;
 mod runtime_tests;
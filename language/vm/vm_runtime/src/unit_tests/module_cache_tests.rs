@@ -24,7 +24,7 @@ use libra_types::{
 use vm::{
     access::ModuleAccess,
     file_format::*,
-    gas_schedule::{GasAlgebra, GasUnits},
+    gas_schedule::{GasAlgebra, GasUnits, DEFAULT_GAS_SCHEDULE},
 };
 use vm_cache_map::Arena;
 use vm_runtime_types::loaded_data::{struct_def::StructDef, types::Type};
@@ -244,6 +244,36 @@ fn test_loader_cross_modules() {
     assert_eq!(func2.code_definition(), vec![Bytecode::Ret].as_slice());
 }
 
+#[test]
+fn test_loader_private_function_cross_modules() {
+    let script = test_script();
+    // Make func2 private.
+    let mut module = test_module("module").into_inner().into_inner();
+    module.function_defs[1].flags = 0;
+    let module = VerifiedModule::new(module.freeze().unwrap()).unwrap();
+
+    let allocator = Arena::new();
+    let loaded_program = VMModuleCache::new(&allocator);
+    loaded_program.cache_module(module);
+
+    let owned_entry_module = script.into_module();
+    let loaded_main = LoadedModule::new(owned_entry_module);
+    let entry_func = FunctionRef::new(&loaded_main, CompiledScript::MAIN_INDEX);
+    let entry_module = entry_func.module();
+    assert!(loaded_program
+        .resolve_function_ref(entry_module, FunctionHandleIndex::new(1))
+        .unwrap()
+        .is_some());
+    assert_eq!(
+        loaded_program
+            .resolve_function_ref(entry_module, FunctionHandleIndex::new(2))
+            .err()
+            .unwrap()
+            .major_status,
+        StatusCode::VISIBILITY_MISMATCH
+    );
+}
+
 #[test]
 fn test_cache_with_storage() {
     let allocator = Arena::new();
@@ -527,7 +557,7 @@ fn test_same_module_struct_resolution() {
     {
         let module_id = ModuleId::new(AccountAddress::default(), ident("M1"));
         let module_ref = block_cache.get_loaded_module(&module_id).unwrap().unwrap();
-        let gas = GasMeter::new(GasUnits::new(100_000_000));
+        let gas = GasMeter::new(GasUnits::new(100_000_000), &DEFAULT_GAS_SCHEDULE);
         let struct_x = block_cache
             .resolve_struct_def(module_ref, StructDefinitionIndex::new(0), &gas)
             .unwrap()
@@ -577,7 +607,7 @@ fn test_multi_module_struct_resolution() {
             .unwrap()
             .unwrap();
 
-        let gas = GasMeter::new(GasUnits::new(100_000_000));
+        let gas = GasMeter::new(GasUnits::new(100_000_000), &DEFAULT_GAS_SCHEDULE);
         let struct_t = block_cache
             .resolve_struct_def(module2_ref, StructDefinitionIndex::new(0), &gas)
             .unwrap()
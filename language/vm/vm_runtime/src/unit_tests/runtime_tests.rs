@@ -27,7 +27,7 @@ use vm::{
         FunctionSignatureIndex, IdentifierIndex, LocalsSignature, LocalsSignatureIndex,
         ModuleHandle, ModuleHandleIndex, SignatureToken, UserStringIndex, NO_TYPE_ACTUALS,
     },
-    gas_schedule::{AbstractMemorySize, GasAlgebra, GasPrice, GasUnits},
+    gas_schedule::{AbstractMemorySize, GasAlgebra, GasPrice, GasUnits, DEFAULT_GAS_SCHEDULE},
     transaction_metadata::TransactionMetadata,
 };
 use vm_cache_map::Arena;
@@ -190,8 +190,12 @@ fn test_simple_instruction_transition() {
     let loaded_main = LoadedModule::new(main_module);
     let entry_func = FunctionRef::new(&loaded_main, CompiledScript::MAIN_INDEX);
     let data_cache = FakeDataCache::new();
-    let mut vm =
-        TransactionExecutor::new(module_cache, &data_cache, TransactionMetadata::default());
+    let mut vm = TransactionExecutor::new(
+        module_cache,
+        &data_cache,
+        TransactionMetadata::default(),
+        &DEFAULT_GAS_SCHEDULE,
+    );
     vm.execution_stack
         .push_frame(entry_func)
         .expect("push to empty execution stack should succeed");
@@ -390,8 +394,12 @@ fn test_arith_instructions() {
     let entry_func = FunctionRef::new(&loaded_main, CompiledScript::MAIN_INDEX);
     let data_cache = FakeDataCache::new();
 
-    let mut vm =
-        TransactionExecutor::new(module_cache, &data_cache, TransactionMetadata::default());
+    let mut vm = TransactionExecutor::new(
+        module_cache,
+        &data_cache,
+        TransactionMetadata::default(),
+        &DEFAULT_GAS_SCHEDULE,
+    );
 
     vm.execution_stack
         .push_frame(entry_func)
@@ -637,6 +645,41 @@ fn fake_module_with_calls(sigs: Vec<(Vec<SignatureToken>, FunctionSignature)>) -
     VerifiedModule::bypass_verifier_DANGEROUS_FOR_TESTING_ONLY(compiled_module)
 }
 
+#[test]
+fn test_abort_location() {
+    let allocator = Arena::new();
+    let module_cache = VMModuleCache::new(&allocator);
+    let main_module = fake_script().into_module();
+    let loaded_main = LoadedModule::new(main_module);
+    let entry_func = FunctionRef::new(&loaded_main, CompiledScript::MAIN_INDEX);
+    let data_cache = FakeDataCache::new();
+
+    let mut vm = TransactionExecutor::new(
+        module_cache,
+        &data_cache,
+        TransactionMetadata::default(),
+        &DEFAULT_GAS_SCHEDULE,
+    );
+
+    vm.execution_stack
+        .push_frame(entry_func)
+        .expect("push to empty execution stack should succeed");
+    vm.execution_stack
+        .set_stack(vec![Value::u64(7), Value::u64(0)]);
+
+    let code = vec![Bytecode::Pop, Bytecode::Abort];
+    let status = vm
+        .execute_block(code.as_slice(), 0)
+        .expect_err("abort should return an error");
+    assert_eq!(status.major_status, StatusCode::ABORTED);
+    assert_eq!(status.sub_status, Some(7));
+
+    let location = status.location.expect("abort should record its location");
+    assert_eq!(location.module, Some(loaded_main.self_id()));
+    assert_eq!(location.code_offset, 1);
+    assert_eq!(location.call_stack_depth, 1);
+}
+
 #[test]
 fn test_call() {
     // Note that to pass verification, none of the signatures need to have duplicates.
@@ -695,8 +738,12 @@ fn test_call() {
             .unwrap()
     };
     let data_cache = FakeDataCache::new();
-    let mut vm =
-        TransactionExecutor::new(module_cache, &data_cache, TransactionMetadata::default());
+    let mut vm = TransactionExecutor::new(
+        module_cache,
+        &data_cache,
+        TransactionMetadata::default(),
+        &DEFAULT_GAS_SCHEDULE,
+    );
     vm.execution_stack
         .push_frame(fake_func)
         .expect("push to empty execution stack should succeed");
@@ -773,7 +820,8 @@ fn test_transaction_info() {
         }
     };
     let data_cache = FakeDataCache::new();
-    let mut vm = TransactionExecutor::new(module_cache, &data_cache, txn_info);
+    let mut vm =
+        TransactionExecutor::new(module_cache, &data_cache, txn_info, &DEFAULT_GAS_SCHEDULE);
 
     vm.execution_stack
         .push_frame(entry_func)
@@ -128,6 +128,18 @@ where
     {
         self.or_insert_with_transform(key, insert, |value_ref| value_ref)
     }
+
+    /// Insert the value, replacing the value present if any.
+    ///
+    /// References to the replaced value that were handed out before stay valid, since the value
+    /// is still owned by the arena. They keep pointing to the replaced value.
+    ///
+    /// Returns the address of the inserted value.
+    pub fn insert(&self, key: K, value: V) -> &'a V {
+        let alloc_value: &'a V = self.alloc.alloc(value);
+        self.map.insert(key, alloc_value);
+        alloc_value
+    }
 }
 
 #[test]
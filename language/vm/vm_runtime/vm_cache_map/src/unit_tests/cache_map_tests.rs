@@ -42,6 +42,24 @@ proptest! {
         }
     }
 
+    #[test]
+    fn insert_replaces(kv_lists in hash_map(".*", vec(".*", 1..4), 0..100)) {
+        let arena = Arena::new();
+        let map = CacheRefMap::new(&arena);
+        for (key, values) in kv_lists {
+            let first = values[0].clone();
+            let first_ref = map.or_insert(key.clone(), first.clone());
+
+            // Further values for the same key replace the previous one.
+            for value in values.into_iter().skip(1) {
+                prop_assert_eq!(map.insert(key.clone(), value.clone()), &value);
+                prop_assert_eq!(map.get(&key), Some(&value));
+            }
+            // References to replaced values stay valid.
+            prop_assert_eq!(first_ref, &first);
+        }
+    }
+
     #[test]
     fn or_insert_many_threads(kv_lists in hash_map(".*", vec(".*", NUM_THREADS), 0..50)) {
         // Try inserting to the list concurrently with NUM_THREADS threads.
@@ -14,7 +14,9 @@ mod type_prop_tests;
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub enum Type {
     Bool,
+    U8,
     U64,
+    U128,
     String,
     ByteArray,
     Address,
@@ -57,6 +59,8 @@ impl CanonicalSerialize for Type {
                 serializer.encode_u16(*idx)?;
                 serializer
             }
+            U8 => serializer.encode_u8(0x0A)?,
+            U128 => serializer.encode_u8(0x0B)?,
         };
         Ok(())
     }
@@ -79,6 +83,8 @@ impl CanonicalDeserialize for Type {
             0x07 => Reference(Box::new(Type::deserialize(deserializer)?)),
             0x08 => MutableReference(Box::new(Type::deserialize(deserializer)?)),
             0x09 => TypeVariable(u16::deserialize(deserializer)?),
+            0x0A => U8,
+            0x0B => U128,
             other => bail!(
                 "Error while deserializing type: found unexpected tag {:#x}",
                 other
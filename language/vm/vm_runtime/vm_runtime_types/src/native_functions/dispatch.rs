@@ -1,7 +1,7 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use super::{hash, primitive_helpers, signature};
+use super::{gas_schedule, hash, primitive_helpers, signature};
 use crate::{
     native_structs::{dispatch::dispatch_native_struct, vector::NativeVector},
     value::Value,
@@ -118,6 +118,17 @@ lazy_static! {
             vec![ByteArray],
             vec![ByteArray]
         );
+        add!(m, addr, "Hash", "keccak_256",
+            hash::native_keccak_256,
+            vec![ByteArray],
+            vec![ByteArray]
+        );
+        // GasSchedule
+        add!(m, addr, "GasSchedule", "is_valid_instruction_schedule",
+            gas_schedule::native_is_valid_instruction_schedule,
+            vec![ByteArray],
+            vec![Bool]
+        );
         // Signature
         add!(m, addr, "Signature", "ed25519_verify",
             signature::native_ed25519_signature_verification,
@@ -129,6 +140,16 @@ lazy_static! {
             vec![ByteArray, ByteArray, ByteArray, ByteArray],
             vec![U64]
         );
+        add!(m, addr, "Signature", "ed25519_multisig_verify",
+            signature::native_ed25519_multisig_verification,
+            vec![ByteArray, ByteArray, ByteArray, U64, ByteArray],
+            vec![Bool]
+        );
+        add!(m, addr, "Signature", "bls12381_verify",
+            signature::native_bls12381_signature_verification,
+            vec![ByteArray, ByteArray, ByteArray],
+            vec![Bool]
+        );
         // AddressUtil
         add!(m, addr, "AddressUtil", "address_to_bytes",
             primitive_helpers::native_address_to_bytes,
@@ -213,6 +234,39 @@ lazy_static! {
             ],
             vec![]
         );
+        add!(m, addr, "Vector", "append",
+            NativeVector::native_append,
+            vec![Kind::All],
+            vec![
+                MutableReference(Box::new(tstruct(addr, "Vector", "T", vec![TypeParameter(0)]))),
+                tstruct(addr, "Vector", "T", vec![TypeParameter(0)]),
+            ],
+            vec![]
+        );
+        add!(m, addr, "Vector", "reverse",
+            NativeVector::native_reverse,
+            vec![Kind::All],
+            vec![MutableReference(Box::new(tstruct(addr, "Vector", "T", vec![TypeParameter(0)])))],
+            vec![]
+        );
+        add!(m, addr, "Vector", "contains",
+            NativeVector::native_contains,
+            vec![Kind::Unrestricted],
+            vec![
+                Reference(Box::new(tstruct(addr, "Vector", "T", vec![TypeParameter(0)]))),
+                Reference(Box::new(TypeParameter(0))),
+            ],
+            vec![Bool]
+        );
+        add!(m, addr, "Vector", "index_of",
+            NativeVector::native_index_of,
+            vec![Kind::Unrestricted],
+            vec![
+                Reference(Box::new(tstruct(addr, "Vector", "T", vec![TypeParameter(0)]))),
+                Reference(Box::new(TypeParameter(0))),
+            ],
+            vec![Bool, U64]
+        );
         // Event
         add!(m, addr, "Event", "write_to_event_store",
             |_| { NativeReturnStatus::InvalidArguments },
@@ -0,0 +1,27 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use super::dispatch::NativeReturnStatus;
+use crate::value::Value;
+use libra_types::byte_array::ByteArray;
+use std::collections::VecDeque;
+use vm::gas_schedule::CostTable;
+
+const VALIDATE_SCHEDULE_COST: u64 = 1;
+
+/// Checks that a serialized instruction cost table can be decoded by the VM, so that a table
+/// the VM could not load is never published on chain.
+pub fn native_is_valid_instruction_schedule(mut arguments: VecDeque<Value>) -> NativeReturnStatus {
+    if arguments.len() != 1 {
+        return NativeReturnStatus::InvalidArguments;
+    }
+    let schedule = pop_arg!(arguments, ByteArray);
+    let cost = VALIDATE_SCHEDULE_COST * schedule.len() as u64;
+
+    let is_valid = CostTable::from_bytes(schedule.as_bytes()).is_ok();
+    let return_values = vec![Value::bool(is_valid)];
+    NativeReturnStatus::Success {
+        cost,
+        return_values,
+    }
+}
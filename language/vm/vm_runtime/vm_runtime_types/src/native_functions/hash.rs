@@ -7,9 +7,11 @@ use crypto::HashValue;
 use libra_types::byte_array::ByteArray;
 use sha2::{Digest, Sha256};
 use std::collections::VecDeque;
+use tiny_keccak::Keccak;
 
 const SHA2_COST: u64 = 30;
 const SHA3_COST: u64 = 30;
+const KECCAK_COST: u64 = 30;
 
 pub fn native_sha2_256(mut arguments: VecDeque<Value>) -> NativeReturnStatus {
     if arguments.len() != 1 {
@@ -40,3 +42,23 @@ pub fn native_sha3_256(mut arguments: VecDeque<Value>) -> NativeReturnStatus {
         return_values,
     }
 }
+
+/// Computes the original (pre-standardization) Keccak-256 digest, as used by Ethereum, which
+/// differs from SHA3-256 only in its padding.
+pub fn native_keccak_256(mut arguments: VecDeque<Value>) -> NativeReturnStatus {
+    if arguments.len() != 1 {
+        return NativeReturnStatus::InvalidArguments;
+    }
+    let hash_arg = pop_arg!(arguments, ByteArray);
+    let cost = KECCAK_COST * hash_arg.len() as u64;
+
+    let mut keccak = Keccak::new_keccak256();
+    keccak.update(hash_arg.as_bytes());
+    let mut hash_vec = vec![0u8; 32];
+    keccak.finalize(&mut hash_vec);
+    let return_values = vec![Value::byte_array(ByteArray::new(hash_vec))];
+    NativeReturnStatus::Success {
+        cost,
+        return_values,
+    }
+}
@@ -5,6 +5,7 @@ use super::dispatch::NativeReturnStatus;
 use crate::value::Value;
 use bit_vec::BitVec;
 use crypto::{
+    bls12381::{BLS12381PublicKey, BLS12381Signature},
     ed25519::{self, Ed25519PublicKey, Ed25519Signature},
     traits::*,
     HashValue,
@@ -15,6 +16,7 @@ use std::{collections::VecDeque, convert::TryFrom};
 // TODO: Talk to Crypto to determine these costs
 const ED25519_COST: u64 = 35;
 const BATCH_ED25519_COST: u64 = 30;
+const BLS12381_COST: u64 = 80;
 
 const BITMAP_SIZE: usize = 32;
 
@@ -38,6 +40,11 @@ const BITMAP_PUBLIC_KEY_SIZE_FAILURE: u64 = DEFAULT_ERROR_CODE + 7;
 const OVERSIZED_PUBLIC_KEY_SIZE_FAILURE: u64 = DEFAULT_ERROR_CODE + 8;
 /// Concatenated Ed25519 public keys should be a multiple of 32 bytes
 const INVALID_PUBLIC_KEY_SIZE_FAILURE: u64 = DEFAULT_ERROR_CODE + 9;
+/// Threshold is zero or larger than the number of public keys
+const INVALID_THRESHOLD_FAILURE: u64 = DEFAULT_ERROR_CODE + 10;
+
+/// Starting error code number for BLS12-381
+const BLS12381_ERROR_CODE: u64 = 0x0B15_2381;
 
 pub fn native_ed25519_signature_verification(mut arguments: VecDeque<Value>) -> NativeReturnStatus {
     if arguments.len() != 3 {
@@ -76,6 +83,112 @@ pub fn native_ed25519_signature_verification(mut arguments: VecDeque<Value>) ->
     }
 }
 
+/// Verify that the keys selected by `bitmap` signed `message` and that at least `threshold` of
+/// them did so. Signatures are matched to keys in bitmap order.
+pub fn native_ed25519_multisig_verification(mut arguments: VecDeque<Value>) -> NativeReturnStatus {
+    if arguments.len() != 5 {
+        return NativeReturnStatus::InvalidArguments;
+    }
+    let message = pop_arg!(arguments, ByteArray);
+    let threshold = pop_arg!(arguments, u64);
+    let public_keys = pop_arg!(arguments, ByteArray);
+    let signatures = pop_arg!(arguments, ByteArray);
+    let bitmap = pop_arg!(arguments, ByteArray);
+
+    let bitvec = BitVec::from_bytes(bitmap.as_bytes());
+    let num_of_sigs = match sanity_check(&bitvec, &signatures, &public_keys, ED25519_COST) {
+        Ok(num_of_sigs) => num_of_sigs,
+        Err(e) => return e,
+    };
+    let cost = ED25519_COST * num_of_sigs * message.len() as u64;
+
+    let num_of_keys = (public_keys.len() / 32) as u64;
+    if threshold == 0 || threshold > num_of_keys {
+        return NativeReturnStatus::Aborted {
+            cost,
+            error_code: INVALID_THRESHOLD_FAILURE,
+        };
+    }
+
+    let signatures = match signatures
+        .as_bytes()
+        .chunks(64)
+        .map(Ed25519Signature::try_from)
+        .collect::<::std::result::Result<Vec<_>, _>>()
+    {
+        Ok(signatures) => signatures,
+        Err(_) => {
+            return NativeReturnStatus::Aborted {
+                cost,
+                error_code: SIGNATURE_DESERIALIZATION_FAILURE,
+            }
+        }
+    };
+    let keys = match public_keys
+        .as_bytes()
+        .chunks(32)
+        .map(Ed25519PublicKey::try_from)
+        .collect::<::std::result::Result<Vec<_>, _>>()
+    {
+        Ok(keys) => keys,
+        Err(_) => {
+            return NativeReturnStatus::Aborted {
+                cost,
+                error_code: PUBLIC_KEY_DESERIALIZATION_FAILURE,
+            }
+        }
+    };
+
+    let bool_value = num_of_sigs >= threshold
+        && matching_keys_and_signatures(num_of_sigs, bitvec, signatures, keys)
+            .iter()
+            .all(|(key, sig)| sig.verify_arbitrary_msg(message.as_bytes(), key).is_ok());
+    let return_values = vec![Value::bool(bool_value)];
+    NativeReturnStatus::Success {
+        cost,
+        return_values,
+    }
+}
+
+pub fn native_bls12381_signature_verification(
+    mut arguments: VecDeque<Value>,
+) -> NativeReturnStatus {
+    if arguments.len() != 3 {
+        return NativeReturnStatus::InvalidArguments;
+    }
+    let msg = pop_arg!(arguments, ByteArray);
+    let pubkey = pop_arg!(arguments, ByteArray);
+    let signature = pop_arg!(arguments, ByteArray);
+
+    let cost = BLS12381_COST * msg.len() as u64;
+
+    let sig = match BLS12381Signature::try_from(signature.as_bytes()) {
+        Ok(sig) => sig,
+        Err(_) => {
+            return NativeReturnStatus::Aborted {
+                cost,
+                error_code: BLS12381_ERROR_CODE,
+            }
+        }
+    };
+    let pk = match BLS12381PublicKey::try_from(pubkey.as_bytes()) {
+        Ok(pk) => pk,
+        Err(_) => {
+            return NativeReturnStatus::Aborted {
+                cost,
+                error_code: BLS12381_ERROR_CODE,
+            }
+        }
+    };
+
+    let bool_value = sig.verify_arbitrary_msg(msg.as_bytes(), &pk).is_ok();
+    let return_values = vec![Value::bool(bool_value)];
+    NativeReturnStatus::Success {
+        cost,
+        return_values,
+    }
+}
+
 /// Batch verify a collection of signatures using a bitmap for matching signatures to keys.
 pub fn native_ed25519_threshold_signature_verification(
     mut arguments: VecDeque<Value>,
@@ -4,7 +4,10 @@ use crate::{
 };
 use canonical_serialization::*;
 use failure::prelude::*;
-use vm::gas_schedule::{AbstractMemorySize, GasCarrier};
+use vm::{
+    errors::VMResult,
+    gas_schedule::{AbstractMemorySize, GasCarrier},
+};
 
 #[derive(Debug, Eq, PartialEq, Clone, Copy)]
 pub enum NativeStructTag {
@@ -45,6 +48,20 @@ impl NativeStructValue {
         }
     }
 
+    /// Structural equality, used by the `Eq` and `Neq` bytecodes.
+    pub(crate) fn equals(&self, other: &NativeStructValue) -> VMResult<bool> {
+        match (self, other) {
+            (NativeStructValue::Vector(v1), NativeStructValue::Vector(v2)) => v1.equals(v2),
+        }
+    }
+
+    /// Copy the native value, including all the values it contains.
+    pub(crate) fn copy_value(&self) -> VMResult<NativeStructValue> {
+        match self {
+            NativeStructValue::Vector(v) => Ok(NativeStructValue::Vector(v.copy_value()?)),
+        }
+    }
+
     /// Normal code should always know what type this value has. This is made available only for
     /// tests.
     #[allow(non_snake_case)]
@@ -6,7 +6,10 @@ use crate::{
 };
 use libra_types::vm_error::sub_status::NFE_VECTOR_ERROR_BASE;
 use std::{collections::VecDeque, ops::Add};
-use vm::gas_schedule::{AbstractMemorySize, GasAlgebra, GasCarrier, STRUCT_SIZE};
+use vm::{
+    errors::VMResult,
+    gas_schedule::{AbstractMemorySize, GasAlgebra, GasCarrier, STRUCT_SIZE},
+};
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct NativeVector(pub(crate) Vec<MutVal>);
@@ -18,6 +21,13 @@ const PUSH_BACK_COST: u64 = 30; // TODO: determine experimentally
 const POP_COST: u64 = 30; // TODO: determine experimentally
 const DESTROY_EMPTY_VEC_COST: u64 = 30; // TODO: determine experimentally
 const SWAP_COST: u64 = 30; // TODO: determine experimentally
+const APPEND_COST: u64 = 30; // TODO: determine experimentally
+const REVERSE_COST: u64 = 30; // TODO: determine experimentally
+const CONTAINS_COST: u64 = 30; // TODO: determine experimentally
+const INDEX_OF_COST: u64 = 30; // TODO: determine experimentally
+
+// Charged on top of the base cost for every element moved or compared by the linear natives.
+const PER_ELEMENT_COST: u64 = 1; // TODO: determine experimentally
 
 pub const INDEX_OUT_OF_BOUNDS: u64 = NFE_VECTOR_ERROR_BASE + 1;
 pub const POP_EMPTY_VEC: u64 = NFE_VECTOR_ERROR_BASE + 2;
@@ -36,6 +46,25 @@ fn get_vector(v: &NativeStructValue) -> Option<&NativeVector> {
     }
 }
 
+/// Return the index of the first element of the vector behind `reference` equal to `elem`,
+/// together with the number of elements compared to find it.
+fn find_element(
+    reference: &ReferenceValue,
+    elem: &Value,
+) -> Option<VMResult<(Option<usize>, usize)>> {
+    reference.read_native_struct(|native_val| {
+        let vector = get_vector(native_val)?;
+        for (idx, e) in vector.0.iter().enumerate() {
+            match e.equals_value(elem) {
+                Ok(true) => return Some(Ok((Some(idx), idx + 1))),
+                Ok(false) => (),
+                Err(err) => return Some(Err(err)),
+            }
+        }
+        Some(Ok((None, vector.0.len())))
+    })
+}
+
 macro_rules! get_vector_ref {
     ($args: expr) => {
         match $args
@@ -49,6 +78,12 @@ macro_rules! get_vector_ref {
 }
 
 impl NativeVector {
+    /// Creates a vector holding `values`, for passing a vector built outside of Move as an
+    /// argument to a Move function.
+    pub fn new(values: Vec<Value>) -> Self {
+        NativeVector(values.into_iter().map(MutVal::new).collect())
+    }
+
     pub fn native_empty(_args: VecDeque<Value>) -> NativeReturnStatus {
         NativeReturnStatus::Success {
             return_values: vec![Value::native_struct(NativeStructValue::Vector(
@@ -193,6 +228,85 @@ impl NativeVector {
             .unwrap_or(NativeReturnStatus::InvalidArguments)
     }
 
+    pub fn native_append(mut args: VecDeque<Value>) -> NativeReturnStatus {
+        if args.len() != 2 {
+            return NativeReturnStatus::InvalidArguments;
+        }
+        let reference = get_vector_ref!(args);
+        let other = match pop_arg!(args, NativeStructValue) {
+            NativeStructValue::Vector(v) => v,
+        };
+        let cost = APPEND_COST + PER_ELEMENT_COST * other.0.len() as u64;
+        reference
+            .mutate_native_struct(|native_val| {
+                get_mut_vector(native_val)?.0.extend(other.0);
+                Some(())
+            })
+            .map(|_| NativeReturnStatus::Success {
+                cost,
+                return_values: vec![],
+            })
+            .unwrap_or(NativeReturnStatus::InvalidArguments)
+    }
+
+    pub fn native_reverse(mut args: VecDeque<Value>) -> NativeReturnStatus {
+        if args.len() != 1 {
+            return NativeReturnStatus::InvalidArguments;
+        }
+        let reference = get_vector_ref!(args);
+        reference
+            .mutate_native_struct(|native_val| {
+                let vector = get_mut_vector(native_val)?;
+                vector.0.reverse();
+                Some(vector.0.len())
+            })
+            .map(|len| NativeReturnStatus::Success {
+                cost: REVERSE_COST + PER_ELEMENT_COST * len as u64,
+                return_values: vec![],
+            })
+            .unwrap_or(NativeReturnStatus::InvalidArguments)
+    }
+
+    pub fn native_contains(mut args: VecDeque<Value>) -> NativeReturnStatus {
+        if args.len() != 2 {
+            return NativeReturnStatus::InvalidArguments;
+        }
+        let reference = get_vector_ref!(args);
+        let elem = match pop_arg!(args, ReferenceValue).read_ref() {
+            Ok(v) => v,
+            Err(_) => return NativeReturnStatus::InvalidArguments,
+        };
+        match find_element(&reference, &elem) {
+            Some(Ok((idx, compared))) => NativeReturnStatus::Success {
+                cost: CONTAINS_COST + PER_ELEMENT_COST * compared as u64,
+                return_values: vec![Value::bool(idx.is_some())],
+            },
+            _ => NativeReturnStatus::InvalidArguments,
+        }
+    }
+
+    pub fn native_index_of(mut args: VecDeque<Value>) -> NativeReturnStatus {
+        if args.len() != 2 {
+            return NativeReturnStatus::InvalidArguments;
+        }
+        let reference = get_vector_ref!(args);
+        let elem = match pop_arg!(args, ReferenceValue).read_ref() {
+            Ok(v) => v,
+            Err(_) => return NativeReturnStatus::InvalidArguments,
+        };
+        match find_element(&reference, &elem) {
+            // The index is meaningless when the element is not found and is reported as 0.
+            Some(Ok((idx, compared))) => NativeReturnStatus::Success {
+                cost: INDEX_OF_COST + PER_ELEMENT_COST * compared as u64,
+                return_values: vec![
+                    Value::bool(idx.is_some()),
+                    Value::u64(idx.unwrap_or(0) as u64),
+                ],
+            },
+            _ => NativeReturnStatus::InvalidArguments,
+        }
+    }
+
     pub(crate) fn get(&self, idx: u64) -> Option<MutVal> {
         self.0.get(idx as usize).map(MutVal::clone)
     }
@@ -201,4 +315,24 @@ impl NativeVector {
             .iter()
             .fold(*STRUCT_SIZE, |acc, vl| acc.map2(vl.size(), Add::add))
     }
+
+    pub(crate) fn equals(&self, other: &NativeVector) -> VMResult<bool> {
+        if self.0.len() != other.0.len() {
+            return Ok(false);
+        }
+        for (v1, v2) in self.0.iter().zip(&other.0) {
+            if !v1.equals(v2)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    pub(crate) fn copy_value(&self) -> VMResult<NativeVector> {
+        let mut elems = vec![];
+        for elem in &self.0 {
+            elems.push(MutVal::new(elem.copy_value()?));
+        }
+        Ok(NativeVector(elems))
+    }
 }
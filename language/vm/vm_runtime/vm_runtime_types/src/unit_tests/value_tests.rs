@@ -403,3 +403,72 @@ fn test_references() {
         Value::struct_(struct_outer),
     );
 }
+
+#[test]
+fn test_vectors() {
+    use crate::native_structs::{vector::NativeVector, NativeStructType};
+
+    let make_vector = |elems: Vec<Value>| {
+        Value::native_struct(NativeStructValue::Vector(NativeVector(
+            elems.into_iter().map(MutVal::new).collect(),
+        )))
+    };
+    let make_pair =
+        |n: u64, b: bool| Value::struct_(Struct::new(vec![Value::u64(n), Value::bool(b)]));
+
+    // structural equality over vectors of structs
+    let vec1 = make_vector(vec![make_pair(1, true), make_pair(2, false)]);
+    let vec2 = make_vector(vec![make_pair(1, true), make_pair(2, false)]);
+    let vec3 = make_vector(vec![make_pair(1, true), make_pair(3, false)]);
+    let vec4 = make_vector(vec![make_pair(1, true)]);
+    assert!(vec1.equals(&vec2).expect("vectors must be comparable"));
+    assert!(!vec1.equals(&vec3).expect("vectors must be comparable"));
+    assert!(!vec1.equals(&vec4).expect("vectors must be comparable"));
+    assert!(make_vector(vec![])
+        .equals(&make_vector(vec![]))
+        .expect("vectors must be comparable"));
+
+    // a copy does not share its elements with the original
+    let mut locals = Locals::new(2);
+    locals
+        .store_loc(0, vec1.clone())
+        .expect("local 0 must exist");
+    let copy = locals.copy_loc(0).expect("local 0 must be valid");
+    locals.store_loc(1, copy).expect("local 1 must exist");
+    let elem_ref = locals
+        .borrow_loc(1)
+        .expect("local 1 must exist")
+        .value_as::<ReferenceValue>()
+        .expect("value must be a reference")
+        .get_native_struct_reference(|native_val| match native_val {
+            NativeStructValue::Vector(v) => v.get(0),
+        })
+        .expect("element 0 must exist");
+    elem_ref
+        .value_as::<ReferenceValue>()
+        .expect("value must be a reference")
+        .write_ref(make_pair(10, false));
+    assert_eq!(locals.move_loc(0).expect("local 0 must be valid"), vec1);
+    assert_eq!(
+        locals.move_loc(1).expect("local 1 must be valid"),
+        make_vector(vec![make_pair(10, false), make_pair(2, false)]),
+    );
+
+    // a resource holding a vector of structs survives a serialization round trip
+    let pair_def = StructDef::new(vec![Type::U64, Type::Bool]);
+    let resource_def = StructDef::new(vec![
+        Type::U64,
+        Type::Struct(StructDef::Native(NativeStructType::new_vec(Type::Struct(
+            pair_def,
+        )))),
+    ]);
+    let resource = Value::struct_(Struct::new(vec![Value::u64(42), vec1]));
+    let blob = resource
+        .simple_serialize()
+        .expect("resource must serialize");
+    let deserialized =
+        Value::simple_deserialize(&blob, resource_def).expect("resource must deserialize");
+    assert!(resource
+        .equals(&deserialized)
+        .expect("resources must be comparable"));
+}
@@ -1,10 +1,15 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::value::{Struct, Value};
+use crate::{
+    native_structs::{vector::NativeVector, NativeStructValue},
+    value::{Struct, Value},
+};
 use canonical_serialization::SimpleDeserializer;
 use libra_types::{
-    account_address::AccountAddress, account_config::AccountResource, byte_array::ByteArray,
+    account_address::AccountAddress,
+    account_config::{AccountResource, CurrencyBalancesResource},
+    byte_array::ByteArray,
 };
 
 #[test]
@@ -17,14 +22,29 @@ fn account_type() {
     let sequence_number = 32u64;
     let sent_events_key = ByteArray::new(AccountAddress::random().to_vec());
     let recv_events_key = ByteArray::new(AccountAddress::random().to_vec());
+    let key_rotation_events_count = 4u64;
+    let key_rotation_events_key = ByteArray::new(AccountAddress::random().to_vec());
+    let event_handle = |count: u64| {
+        Value::struct_(Struct::new(vec![
+            Value::u64(count),
+            Value::byte_array(ByteArray::new(AccountAddress::random().to_vec())),
+        ]))
+    };
 
     let mut account_fields: Vec<Value> = Vec::new();
+    account_fields.push(event_handle(1));
     account_fields.push(Value::byte_array(authentication_key.clone()));
     let mut coin_fields: Vec<Value> = Vec::new();
     coin_fields.push(Value::u64(balance));
     account_fields.push(Value::struct_(Struct::new(coin_fields)));
     account_fields.push(Value::bool(false));
     account_fields.push(Value::bool(false));
+    account_fields.push(Value::bool(true));
+    account_fields.push(event_handle(0));
+    account_fields.push(Value::struct_(Struct::new(vec![
+        Value::u64(key_rotation_events_count),
+        Value::byte_array(key_rotation_events_key.clone()),
+    ])));
     account_fields.push(Value::struct_(Struct::new(vec![
         Value::u64(received_events_count),
         Value::byte_array(recv_events_key.clone()),
@@ -34,6 +54,7 @@ fn account_type() {
         Value::byte_array(sent_events_key.clone()),
     ])));
     account_fields.push(Value::u64(sequence_number));
+    account_fields.push(event_handle(0));
 
     let account = Value::struct_(Struct::new(account_fields));
     let blob = &account.simple_serialize().expect("blob must serialize");
@@ -42,6 +63,7 @@ fn account_type() {
         SimpleDeserializer::deserialize(blob).expect("must deserialize");
     assert_eq!(*account_resource.authentication_key(), authentication_key);
     assert_eq!(account_resource.balance(), balance);
+    assert!(account_resource.is_frozen());
     assert_eq!(
         account_resource.sent_events().key().as_bytes(),
         sent_events_key.as_bytes()
@@ -55,5 +77,70 @@ fn account_type() {
         recv_events_key.as_bytes()
     );
     assert_eq!(account_resource.sent_events().count(), sent_events_count);
+    assert_eq!(
+        account_resource.key_rotation_events().count(),
+        key_rotation_events_count
+    );
+    assert_eq!(
+        account_resource.key_rotation_events().key().as_bytes(),
+        key_rotation_events_key.as_bytes()
+    );
+    assert_eq!(account_resource.account_creation_events().count(), 1);
     assert_eq!(account_resource.sequence_number(), sequence_number);
 }
+
+#[test]
+fn currency_balances_type() {
+    // mimic a CurrencyBalances
+    let usd = ByteArray::new(b"USD".to_vec());
+    let eur = ByteArray::new(b"EUR".to_vec());
+    let received_events_count = 8u64;
+    let sent_events_count = 16u64;
+    let sent_events_key = ByteArray::new(AccountAddress::random().to_vec());
+    let recv_events_key = ByteArray::new(AccountAddress::random().to_vec());
+
+    let coins = vec![
+        Value::struct_(Struct::new(vec![
+            Value::byte_array(usd.clone()),
+            Value::u64(100),
+        ])),
+        Value::struct_(Struct::new(vec![
+            Value::byte_array(eur.clone()),
+            Value::u64(0),
+        ])),
+    ];
+    let balances_fields = vec![
+        Value::native_struct(NativeStructValue::Vector(NativeVector::new(coins))),
+        Value::struct_(Struct::new(vec![
+            Value::u64(received_events_count),
+            Value::byte_array(recv_events_key.clone()),
+        ])),
+        Value::struct_(Struct::new(vec![
+            Value::u64(sent_events_count),
+            Value::byte_array(sent_events_key.clone()),
+        ])),
+    ];
+
+    let balances = Value::struct_(Struct::new(balances_fields));
+    let blob = &balances.simple_serialize().expect("blob must serialize");
+
+    let balances_resource: CurrencyBalancesResource =
+        SimpleDeserializer::deserialize(blob).expect("must deserialize");
+    assert_eq!(balances_resource.coins().len(), 2);
+    assert_eq!(balances_resource.balance(usd.as_bytes()), Some(100));
+    assert_eq!(balances_resource.balance(eur.as_bytes()), Some(0));
+    assert_eq!(balances_resource.balance(b"GBP"), None);
+    assert_eq!(
+        balances_resource.received_events().count(),
+        received_events_count
+    );
+    assert_eq!(
+        balances_resource.received_events().key().as_bytes(),
+        recv_events_key.as_bytes()
+    );
+    assert_eq!(balances_resource.sent_events().count(), sent_events_count);
+    assert_eq!(
+        balances_resource.sent_events().key().as_bytes(),
+        sent_events_key.as_bytes()
+    );
+}
@@ -54,7 +54,9 @@ enum ValueImpl {
     /// Locals are invalid on entry of a function and when moved out.
     Invalid,
     // Primitive types
+    U8(u8),
     U64(u64),
+    U128(u128),
     Address(AccountAddress),
     Bool(bool),
     ByteArray(ByteArray),
@@ -82,6 +84,17 @@ enum ValueImpl {
 #[derive(PartialEq, Eq, Debug, Clone)]
 pub struct Value(ValueImpl);
 
+/// An unsigned integer of any of the widths supported by Move.
+///
+/// Arithmetic, bitwise and comparison bytecodes operate on two integers of the same type, so a
+/// `IntegerValue` is what those bytecodes pop off the stack.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum IntegerValue {
+    U8(u8),
+    U64(u64),
+    U128(u128),
+}
+
 /// Internal representation for a reference or a mutable value.
 /// This is quite a core type for the mechanics of references.
 #[derive(PartialEq, Eq, Debug, Clone)]
@@ -153,6 +166,11 @@ impl ValueImpl {
         match self {
             ValueImpl::Invalid => Err(VMStatus::new(StatusCode::INTERNAL_TYPE_ERROR)),
             ValueImpl::PromotedReference(reference) => reference.copy_value(),
+            // Aggregates own their fields through shared cells, so a copy has to allocate fresh
+            // cells for the whole value tree. Otherwise writes through a reference to the copy
+            // would be visible in the original.
+            ValueImpl::Struct(s) => Ok(Value::struct_(s.copy_value()?)),
+            ValueImpl::NativeStruct(s) => Ok(Value::native_struct(s.copy_value()?)),
             _ => Ok(Value(self.clone())),
         }
     }
@@ -166,7 +184,11 @@ impl ValueImpl {
 
     fn size(&self) -> AbstractMemorySize<GasCarrier> {
         match self {
-            ValueImpl::Invalid | ValueImpl::U64(_) | ValueImpl::Bool(_) => *CONST_SIZE,
+            ValueImpl::Invalid
+            | ValueImpl::U8(_)
+            | ValueImpl::U64(_)
+            | ValueImpl::U128(_)
+            | ValueImpl::Bool(_) => *CONST_SIZE,
             ValueImpl::Address(_) => AbstractMemorySize::new(ADDRESS_LENGTH as u64),
             // Possible debate topic: Should we charge based upon the size of the string.
             // At this moment, we take the view that you should be charged as though you are
@@ -190,18 +212,20 @@ impl ValueImpl {
             // TODO: this does not look right to me....
             (ValueImpl::Invalid, ValueImpl::Invalid) => Ok(true),
             // values
+            (ValueImpl::U8(u1), ValueImpl::U8(u2)) => Ok(u1 == u2),
             (ValueImpl::U64(u1), ValueImpl::U64(u2)) => Ok(u1 == u2),
+            (ValueImpl::U128(u1), ValueImpl::U128(u2)) => Ok(u1 == u2),
             (ValueImpl::Bool(b1), ValueImpl::Bool(b2)) => Ok(b1 == b2),
             (ValueImpl::Address(a1), ValueImpl::Address(a2)) => Ok(a1 == a2),
             (ValueImpl::ByteArray(ba1), ValueImpl::ByteArray(ba2)) => Ok(ba1 == ba2),
             (ValueImpl::String(s1), ValueImpl::String(s2)) => Ok(s1 == s2),
             (ValueImpl::Struct(s1), ValueImpl::Struct(s2)) => s1.equals(s2),
+            (ValueImpl::NativeStruct(s1), ValueImpl::NativeStruct(s2)) => s1.equals(s2),
             // references
             (ValueImpl::Reference(ref1), ValueImpl::Reference(ref2)) => ref1.equals(ref2),
             (ValueImpl::GlobalRef(gr1), ValueImpl::GlobalRef(gr2)) => gr1.equals(gr2),
             (ValueImpl::GlobalRef(gr), ValueImpl::Reference(reference)) => gr.equals_ref(reference),
             (ValueImpl::Reference(reference), ValueImpl::GlobalRef(gr)) => gr.equals_ref(reference),
-            // Should we allow comparing native structs?
             _ => Err(VMStatus::new(StatusCode::INTERNAL_TYPE_ERROR)),
         }
     }
@@ -213,7 +237,9 @@ impl ValueImpl {
     fn to_type_FOR_TESTING(&self) -> Type {
         match self {
             ValueImpl::Invalid => unreachable!("Cannot ask type of invalid location"),
+            ValueImpl::U8(_) => Type::U8,
             ValueImpl::U64(_) => Type::U64,
+            ValueImpl::U128(_) => Type::U128,
             ValueImpl::Address(_) => Type::Address,
             ValueImpl::Bool(_) => Type::Bool,
             ValueImpl::ByteArray(_) => Type::ByteArray,
@@ -237,11 +263,21 @@ impl Value {
         Value(value)
     }
 
+    /// Return a `Value` representing a `u8` in the VM.
+    pub fn u8(value: u8) -> Self {
+        Value(ValueImpl::U8(value))
+    }
+
     /// Return a `Value` representing a `u64` in the VM.
     pub fn u64(value: u64) -> Self {
         Value(ValueImpl::U64(value))
     }
 
+    /// Return a `Value` representing a `u128` in the VM.
+    pub fn u128(value: u128) -> Self {
+        Value(ValueImpl::U128(value))
+    }
+
     /// Return a `Value` representing an `AccountAddress` in the VM.
     pub fn address(address: AccountAddress) -> Self {
         Value(ValueImpl::Address(address))
@@ -335,6 +371,15 @@ impl Value {
 // (e.g. Add) the values popped from the stack are expected to be u64 and should fail otherwise.
 //
 
+impl From<Value> for Option<u8> {
+    fn from(value: Value) -> Option<u8> {
+        match value.0 {
+            ValueImpl::U8(i) => Some(i),
+            _ => None,
+        }
+    }
+}
+
 impl From<Value> for Option<u64> {
     fn from(value: Value) -> Option<u64> {
         match value.0 {
@@ -344,6 +389,26 @@ impl From<Value> for Option<u64> {
     }
 }
 
+impl From<Value> for Option<u128> {
+    fn from(value: Value) -> Option<u128> {
+        match value.0 {
+            ValueImpl::U128(i) => Some(i),
+            _ => None,
+        }
+    }
+}
+
+impl From<Value> for Option<IntegerValue> {
+    fn from(value: Value) -> Option<IntegerValue> {
+        match value.0 {
+            ValueImpl::U8(i) => Some(IntegerValue::U8(i)),
+            ValueImpl::U64(i) => Some(IntegerValue::U64(i)),
+            ValueImpl::U128(i) => Some(IntegerValue::U128(i)),
+            _ => None,
+        }
+    }
+}
+
 impl From<Value> for Option<bool> {
     fn from(value: Value) -> Option<bool> {
         match value.0 {
@@ -426,6 +491,122 @@ impl From<Value> for Option<GlobalRef> {
     }
 }
 
+/// Evaluate `$body` on two integers of the same type, with `$l` and `$r` bound to the operands.
+/// `$body` returns an `Option` of the operands' type. Evaluates to `None` if the integers have
+/// different types.
+macro_rules! integer_binop {
+    ($lhs:expr, $rhs:expr, |$l:ident, $r:ident| $body:expr) => {
+        match ($lhs, $rhs) {
+            (IntegerValue::U8($l), IntegerValue::U8($r)) => $body.map(IntegerValue::U8),
+            (IntegerValue::U64($l), IntegerValue::U64($r)) => $body.map(IntegerValue::U64),
+            (IntegerValue::U128($l), IntegerValue::U128($r)) => $body.map(IntegerValue::U128),
+            _ => None,
+        }
+    };
+}
+
+/// Compare two integers of the same type. Evaluates to `None` if the integers have different
+/// types.
+macro_rules! integer_cmp {
+    ($lhs:expr, $rhs:expr, $op:tt) => {
+        match ($lhs, $rhs) {
+            (IntegerValue::U8(l), IntegerValue::U8(r)) => Some(l $op r),
+            (IntegerValue::U64(l), IntegerValue::U64(r)) => Some(l $op r),
+            (IntegerValue::U128(l), IntegerValue::U128(r)) => Some(l $op r),
+            _ => None,
+        }
+    };
+}
+
+/// Implementation of the integer bytecodes.
+///
+/// All operations return `None` when the operands have different types, which the bytecode
+/// verifier rules out, or when the result cannot be represented in the operands' type.
+impl IntegerValue {
+    pub fn checked_add(self, other: IntegerValue) -> Option<IntegerValue> {
+        integer_binop!(self, other, |l, r| l.checked_add(r))
+    }
+
+    pub fn checked_sub(self, other: IntegerValue) -> Option<IntegerValue> {
+        integer_binop!(self, other, |l, r| l.checked_sub(r))
+    }
+
+    pub fn checked_mul(self, other: IntegerValue) -> Option<IntegerValue> {
+        integer_binop!(self, other, |l, r| l.checked_mul(r))
+    }
+
+    pub fn checked_rem(self, other: IntegerValue) -> Option<IntegerValue> {
+        integer_binop!(self, other, |l, r| l.checked_rem(r))
+    }
+
+    pub fn checked_div(self, other: IntegerValue) -> Option<IntegerValue> {
+        integer_binop!(self, other, |l, r| l.checked_div(r))
+    }
+
+    pub fn bit_or(self, other: IntegerValue) -> Option<IntegerValue> {
+        integer_binop!(self, other, |l, r| Some(l | r))
+    }
+
+    pub fn bit_and(self, other: IntegerValue) -> Option<IntegerValue> {
+        integer_binop!(self, other, |l, r| Some(l & r))
+    }
+
+    pub fn bit_xor(self, other: IntegerValue) -> Option<IntegerValue> {
+        integer_binop!(self, other, |l, r| Some(l ^ r))
+    }
+
+    pub fn lt(self, other: IntegerValue) -> Option<bool> {
+        integer_cmp!(self, other, <)
+    }
+
+    pub fn gt(self, other: IntegerValue) -> Option<bool> {
+        integer_cmp!(self, other, >)
+    }
+
+    pub fn le(self, other: IntegerValue) -> Option<bool> {
+        integer_cmp!(self, other, <=)
+    }
+
+    pub fn ge(self, other: IntegerValue) -> Option<bool> {
+        integer_cmp!(self, other, >=)
+    }
+
+    /// `CastU8` bytecode. Returns `None` if the value does not fit in a `u8`.
+    pub fn cast_u8(self) -> Option<u8> {
+        match self {
+            IntegerValue::U8(i) => Some(i),
+            IntegerValue::U64(i) => u8::try_from(i).ok(),
+            IntegerValue::U128(i) => u8::try_from(i).ok(),
+        }
+    }
+
+    /// `CastU64` bytecode. Returns `None` if the value does not fit in a `u64`.
+    pub fn cast_u64(self) -> Option<u64> {
+        match self {
+            IntegerValue::U8(i) => Some(u64::from(i)),
+            IntegerValue::U64(i) => Some(i),
+            IntegerValue::U128(i) => u64::try_from(i).ok(),
+        }
+    }
+
+    /// `CastU128` bytecode. Every unsigned integer fits in a `u128`.
+    pub fn cast_u128(self) -> u128 {
+        match self {
+            IntegerValue::U8(i) => u128::from(i),
+            IntegerValue::U64(i) => u128::from(i),
+            IntegerValue::U128(i) => i,
+        }
+    }
+
+    pub fn into_value(self) -> Value {
+        match self {
+            IntegerValue::U8(i) => Value::u8(i),
+            IntegerValue::U64(i) => Value::u64(i),
+            IntegerValue::U128(i) => Value::u128(i),
+        }
+    }
+}
+
 impl MutVal {
     pub(crate) fn new(v: Value) -> Self {
         MutVal(Rc::new(RefCell::new(v.0)))
@@ -442,7 +623,7 @@ impl MutVal {
         }
     }
 
-    fn copy_value(&self) -> VMResult<Value> {
+    pub(crate) fn copy_value(&self) -> VMResult<Value> {
         self.peek().copy_value()
     }
 
@@ -464,10 +645,14 @@ impl MutVal {
         self.peek().to_type_FOR_TESTING()
     }
 
-    fn equals(&self, v2: &MutVal) -> VMResult<bool> {
+    pub(crate) fn equals(&self, v2: &MutVal) -> VMResult<bool> {
         self.peek().equals(&v2.peek())
     }
 
+    pub(crate) fn equals_value(&self, v2: &Value) -> VMResult<bool> {
+        self.peek().equals(&v2.0)
+    }
+
     fn mutate_native_struct<T, F>(&self, op: F) -> Option<T>
     where
         F: FnOnce(&mut NativeStructValue) -> Option<T>,
@@ -524,6 +709,14 @@ impl Struct {
             .fold(*STRUCT_SIZE, |acc, vl| acc.map2(vl.size(), Add::add))
     }
 
+    fn copy_value(&self) -> VMResult<Struct> {
+        let mut fields = vec![];
+        for field in &self.0 {
+            fields.push(MutVal::new(field.copy_value()?));
+        }
+        Ok(Struct(fields))
+    }
+
     fn equals(&self, s2: &Struct) -> VMResult<bool> {
         if self.0.len() != s2.0.len() {
             return Err(VMStatus::new(StatusCode::INTERNAL_TYPE_ERROR));
@@ -958,7 +1151,9 @@ pub(crate) fn deserialize_value(
 ) -> VMResult<Value> {
     match ty {
         Type::Bool => deserializer.decode_bool().map(Value::bool),
+        Type::U8 => deserializer.decode_u8().map(Value::u8),
         Type::U64 => deserializer.decode_u64().map(Value::u64),
+        Type::U128 => deserializer.decode_u128().map(Value::u128),
         Type::String => {
             if let Ok(bytes) = deserializer.decode_bytes() {
                 if let Ok(s) = VMString::from_utf8(bytes) {
@@ -1003,9 +1198,15 @@ fn deserialize_struct(
 impl CanonicalSerialize for ValueImpl {
     fn serialize(&self, serializer: &mut impl CanonicalSerializer) -> Result<()> {
         match self {
+            ValueImpl::U8(val) => {
+                serializer.encode_u8(*val)?;
+            }
             ValueImpl::U64(val) => {
                 serializer.encode_u64(*val)?;
             }
+            ValueImpl::U128(val) => {
+                serializer.encode_u128(*val)?;
+            }
             ValueImpl::Address(addr) => {
                 // TODO: this is serializing as a vector but we want just raw bytes
                 // however the AccountAddress story is a bit difficult to work with right now
@@ -18,7 +18,10 @@ use config::config::NodeConfig;
 use libra_mempool_shared_proto::{
     proto::mempool_status::MempoolAddTransactionStatusCode, MempoolAddTransactionStatus,
 };
-use libra_types::{account_address::AccountAddress, transaction::SignedTransaction};
+use libra_types::{
+    account_address::AccountAddress,
+    transaction::{nonce_from_sequence_number, SignedTransaction},
+};
 use logger::prelude::*;
 use lru_cache::LruCache;
 use std::{cmp::max, collections::HashSet, convert::TryFrom};
@@ -64,7 +67,11 @@ impl Mempool {
         self.metrics_cache.remove(&(*sender, sequence_number));
         OP_COUNTERS.inc(&format!("remove_transaction.{}", is_rejected));
 
-        if is_rejected {
+        if nonce_from_sequence_number(sequence_number).is_some() {
+            // nonce transactions don't change the sequence number of the account
+            self.transactions
+                .remove_nonce_transaction(&sender, sequence_number);
+        } else if is_rejected {
             debug!(
                 "[Mempool] transaction is rejected: {}:{}",
                 sender, sequence_number
@@ -187,11 +194,12 @@ impl Mempool {
                 continue;
             }
             let mut seq = txn.sequence_number;
+            let is_nonce_txn = nonce_from_sequence_number(seq).is_some();
             let account_sequence_number = self.sequence_number_cache.get_mut(&txn.address);
             let seen_previous = seq > 0 && seen.contains(&(txn.address, seq - 1));
-            // include transaction if it's "next" for given account or
+            // include transaction if it's a nonce transaction, if it's "next" for given account or
             // we've already sent its ancestor to Consensus
-            if seen_previous || account_sequence_number == Some(&mut seq) {
+            if is_nonce_txn || seen_previous || account_sequence_number == Some(&mut seq) {
                 let ptr = TxnPointer::from(txn);
                 seen.insert(ptr);
                 result.push(ptr);
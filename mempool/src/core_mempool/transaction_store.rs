@@ -16,10 +16,14 @@ use failure::prelude::*;
 use libra_mempool_shared_proto::{
     proto::mempool_status::MempoolAddTransactionStatusCode, MempoolAddTransactionStatus,
 };
-use libra_types::{account_address::AccountAddress, transaction::SignedTransaction};
+use libra_types::{
+    account_address::AccountAddress,
+    transaction::{nonce_from_sequence_number, SignedTransaction, NONCE_FLAG},
+};
 use logger::prelude::*;
 use std::{
     collections::HashMap,
+    mem,
     ops::Bound,
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
@@ -196,6 +200,7 @@ impl TransactionStore {
     /// supposed to be included in both PriorityIndex (ordering for Consensus) and
     /// TimelineIndex (txns for SharedMempool)
     /// Other txns are considered to be "non-ready" and should be added to ParkingLotIndex
+    /// Nonce transactions don't depend on the sequence number of the account and are always ready
     fn process_ready_transactions(
         &mut self,
         address: &AccountAddress,
@@ -213,7 +218,10 @@ impl TransactionStore {
             }
 
             let mut parking_lot_txns = 0;
-            for (_, txn) in txns.range_mut((Bound::Excluded(sequence_number), Bound::Unbounded)) {
+            for (_, txn) in txns.range_mut((
+                Bound::Excluded(sequence_number),
+                Bound::Excluded(NONCE_FLAG),
+            )) {
                 match txn.timeline_state {
                     TimelineState::Ready(_) => {}
                     _ => {
@@ -222,6 +230,14 @@ impl TransactionStore {
                     }
                 }
             }
+
+            for (_, txn) in txns.range_mut(NONCE_FLAG..) {
+                self.priority_index.insert(txn);
+
+                if txn.timeline_state == TimelineState::NotReady {
+                    self.timeline_index.insert(txn);
+                }
+            }
             debug!("[Mempool] txns for account {:?}. Current sequence_number: {}, length: {}, parking lot: {}",
                 address, current_sequence_number, txns.len(), parking_lot_txns,
             );
@@ -253,9 +269,29 @@ impl TransactionStore {
         self.process_ready_transactions(account, account_sequence_number);
     }
 
+    /// handles commit or rejection of a nonce transaction
+    /// other transactions of the account don't depend on it, so only this one is removed
+    pub(crate) fn remove_nonce_transaction(
+        &mut self,
+        account: &AccountAddress,
+        sequence_number: u64,
+    ) {
+        if let Some(txn) = self
+            .transactions
+            .get_mut(&account)
+            .and_then(|txns| txns.remove(&sequence_number))
+        {
+            self.index_remove(&txn);
+        }
+    }
+
     pub(crate) fn reject_transaction(&mut self, account: &AccountAddress, _sequence_number: u64) {
-        if let Some(txns) = self.transactions.remove(&account) {
-            for transaction in txns.values() {
+        if let Some(txns) = self.transactions.get_mut(&account) {
+            // nonce transactions don't depend on the rejected transaction and can stay
+            let nonce_txns = txns.split_off(&NONCE_FLAG);
+            let txns_for_removal = mem::replace(txns, nonce_txns);
+
+            for transaction in txns_for_removal.values() {
                 self.index_remove(&transaction);
             }
         }
@@ -329,10 +365,16 @@ impl TransactionStore {
         for key in index.gc(now) {
             if let Some(txns) = self.transactions.get_mut(&key.address) {
                 // mark all following transactions as non-ready
-                for (_, t) in txns.range((Bound::Excluded(key.sequence_number), Bound::Unbounded)) {
-                    self.parking_lot_index.insert(&t);
-                    self.priority_index.remove(&t);
-                    self.timeline_index.remove(&t);
+                // no transaction depends on a nonce transaction
+                if nonce_from_sequence_number(key.sequence_number).is_none() {
+                    for (_, t) in txns.range((
+                        Bound::Excluded(key.sequence_number),
+                        Bound::Excluded(NONCE_FLAG),
+                    )) {
+                        self.parking_lot_index.insert(&t);
+                        self.priority_index.remove(&t);
+                        self.timeline_index.remove(&t);
+                    }
                 }
                 if let Some(txn) = txns.remove(&key.sequence_number) {
                     let is_active = self.priority_index.contains(&txn);
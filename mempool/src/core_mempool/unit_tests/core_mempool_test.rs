@@ -10,7 +10,7 @@ use crate::core_mempool::{
 };
 use config::config::NodeConfigHelpers;
 use libra_mempool_shared_proto::proto::mempool_status::MempoolAddTransactionStatusCode;
-use libra_types::transaction::SignedTransaction;
+use libra_types::transaction::{sequence_number_from_nonce, SignedTransaction};
 use std::{collections::HashSet, time::Duration};
 
 #[test]
@@ -215,6 +215,55 @@ fn test_commit_callback() {
     assert_eq!(pool.get_block(1, HashSet::new())[0], txns[0]);
 }
 
+#[test]
+fn test_nonce_transactions_are_ready() {
+    let mut pool = setup_mempool().0;
+    // transaction 6 is parked while nonce transactions don't wait for any sequence number
+    add_txns_to_mempool(
+        &mut pool,
+        vec![
+            TestTransaction::new(1, 6, 1),
+            TestTransaction::new(1, sequence_number_from_nonce(7), 1),
+            TestTransaction::new(1, sequence_number_from_nonce(3), 1),
+        ],
+    );
+
+    let mut block: Vec<_> = pool
+        .get_block(10, HashSet::new())
+        .iter()
+        .filter_map(SignedTransaction::nonce)
+        .collect();
+    block.sort();
+    assert_eq!(block, vec![3, 7]);
+    let (timeline, _) = pool.read_timeline(0, 10);
+    assert_eq!(timeline.len(), 2);
+}
+
+#[test]
+fn test_remove_nonce_transaction() {
+    let mut pool = setup_mempool().0;
+    let txns = add_txns_to_mempool(
+        &mut pool,
+        vec![
+            TestTransaction::new(1, 0, 1),
+            TestTransaction::new(1, sequence_number_from_nonce(1), 1),
+            TestTransaction::new(1, sequence_number_from_nonce(2), 1),
+        ],
+    );
+
+    // committing a nonce transaction only removes this transaction and doesn't affect the
+    // sequence number of the account
+    pool.remove_transaction(&txns[1].sender(), txns[1].sequence_number(), false);
+    let block = pool.get_block(10, HashSet::new());
+    assert_eq!(block.len(), 2);
+    assert!(block.contains(&txns[0]));
+    assert!(block.contains(&txns[2]));
+
+    // rejecting a transaction with a sequence number doesn't remove nonce transactions
+    pool.remove_transaction(&txns[0].sender(), txns[0].sequence_number(), true);
+    assert_eq!(pool.get_block(10, HashSet::new()), vec![txns[2].clone()]);
+}
+
 #[test]
 fn test_sequence_number_cache() {
     // checks potential race where StateDB is lagging
@@ -114,7 +114,7 @@ impl LibraDB {
             ),
             (LEDGER_COUNTERS_CF_NAME, ColumnFamilyOptions::default()),
             (STALE_NODE_INDEX_CF_NAME, ColumnFamilyOptions::default()),
-            (SIGNED_TRANSACTION_CF_NAME, ColumnFamilyOptions::default()),
+            (TRANSACTION_CF_NAME, ColumnFamilyOptions::default()),
             (
                 TRANSACTION_ACCUMULATOR_CF_NAME,
                 ColumnFamilyOptions::default(),
@@ -420,14 +420,21 @@ impl LibraDB {
         zip_eq(first_version..=last_version, txns_to_commit)
             .map(|(ver, txn_to_commit)| {
                 self.transaction_store
-                    .put_transaction(ver, txn_to_commit.signed_txn(), &mut cs)
+                    .put_transaction(ver, txn_to_commit.transaction(), &mut cs)
             })
             .collect::<Result<()>>()?;
 
         // Transaction accumulator updates. Get result root hash.
         let txn_infos = izip!(txns_to_commit, state_root_hashes, event_root_hashes)
             .map(|(t, s, e)| {
-                TransactionInfo::new(t.signed_txn().hash(), s, e, t.gas_used(), t.major_status())
+                TransactionInfo::new(
+                    t.transaction().hash(),
+                    s,
+                    e,
+                    t.gas_used(),
+                    t.major_status(),
+                    t.location().cloned(),
+                )
             })
             .collect::<Vec<_>>();
         assert_eq!(txn_infos.len(), txns_to_commit.len());
@@ -701,7 +708,11 @@ impl LibraDB {
                 .get_transaction_info_with_proof(version, ledger_version)?;
             SignedTransactionProof::new(txn_info_accumulator_proof, txn_info)
         };
-        let signed_transaction = self.transaction_store.get_transaction(version)?;
+        let signed_transaction = self
+            .transaction_store
+            .get_transaction(version)?
+            .as_signed_user_txn()?
+            .clone();
 
         // If events were requested, also fetch those.
         let events = if fetch_events {
@@ -17,7 +17,7 @@ use libra_types::{
     crypto_proxies::LedgerInfoWithSignatures,
     ledger_info::LedgerInfo,
     proof::SparseMerkleLeafNode,
-    transaction::{RawTransaction, Script, TransactionInfo, TransactionToCommit},
+    transaction::{RawTransaction, Script, Transaction, TransactionInfo, TransactionToCommit},
     vm_error::StatusCode,
 };
 use rand::{
@@ -56,11 +56,12 @@ fn gen_mock_genesis() -> (
         .collect::<HashMap<_, _>>();
 
     let txn_to_commit = TransactionToCommit::new(
-        signed_txn,
+        Transaction::UserTransaction(signed_txn),
         account_states.clone(),
         vec![], /* events */
         0,      /* gas_used */
         StatusCode::EXECUTED,
+        None,
     );
 
     // The genesis state tree has a single leaf node, so the root hash is the hash of that node.
@@ -71,6 +72,7 @@ fn gen_mock_genesis() -> (
         *ACCUMULATOR_PLACEHOLDER_HASH,
         0,
         StatusCode::EXECUTED,
+        None,
     );
 
     let ledger_info = LedgerInfo::new(
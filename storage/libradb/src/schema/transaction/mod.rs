@@ -1,9 +1,9 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-//! This module defines physical storage schema for signed transactions.
+//! This module defines physical storage schema for transactions.
 //!
-//! Serialized signed transaction bytes identified by version.
+//! Serialized transaction bytes identified by version.
 //! ```text
 //! |<--key-->|<--value-->|
 //! | version | txn bytes |
@@ -12,10 +12,10 @@
 //! `Version` is serialized in big endian so that records in RocksDB will be in order of it's
 //! numeric value.
 
-use crate::schema::{ensure_slice_len_eq, SIGNED_TRANSACTION_CF_NAME};
+use crate::schema::{ensure_slice_len_eq, TRANSACTION_CF_NAME};
 use byteorder::{BigEndian, ReadBytesExt};
 use failure::prelude::*;
-use libra_types::transaction::{SignedTransaction, Version};
+use libra_types::transaction::{Transaction, Version};
 use prost::Message;
 use prost_ext::MessageExt;
 use schemadb::{
@@ -25,14 +25,9 @@ use schemadb::{
 use std::convert::TryInto;
 use std::mem::size_of;
 
-define_schema!(
-    SignedTransactionSchema,
-    Version,
-    SignedTransaction,
-    SIGNED_TRANSACTION_CF_NAME
-);
+define_schema!(TransactionSchema, Version, Transaction, TRANSACTION_CF_NAME);
 
-impl KeyCodec<SignedTransactionSchema> for Version {
+impl KeyCodec<TransactionSchema> for Version {
     fn encode_key(&self) -> Result<Vec<u8>> {
         Ok(self.to_be_bytes().to_vec())
     }
@@ -43,14 +38,14 @@ impl KeyCodec<SignedTransactionSchema> for Version {
     }
 }
 
-impl ValueCodec<SignedTransactionSchema> for SignedTransaction {
+impl ValueCodec<TransactionSchema> for Transaction {
     fn encode_value(&self) -> Result<Vec<u8>> {
-        let event: libra_types::proto::types::SignedTransaction = self.clone().into();
-        Ok(event.to_vec()?)
+        let txn: libra_types::proto::types::Transaction = self.clone().into();
+        Ok(txn.to_vec()?)
     }
 
     fn decode_value(data: &[u8]) -> Result<Self> {
-        libra_types::proto::types::SignedTransaction::decode(data)?.try_into()
+        libra_types::proto::types::Transaction::decode(data)?.try_into()
     }
 }
 
@@ -2,13 +2,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::*;
-use libra_types::transaction::SignedTransaction;
+use libra_types::transaction::Transaction;
 use proptest::prelude::*;
 use schemadb::schema::assert_encode_decode;
 
 proptest! {
     #[test]
-    fn test_encode_decode(txn in any::<SignedTransaction>()) {
-        assert_encode_decode::<SignedTransactionSchema>(&0u64, &txn);
+    fn test_encode_decode(txn in any::<Transaction>()) {
+        assert_encode_decode::<TransactionSchema>(&0u64, &txn);
     }
 }
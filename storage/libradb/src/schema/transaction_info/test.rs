@@ -3,7 +3,10 @@
 
 use super::*;
 use crypto::HashValue;
-use libra_types::{transaction::TransactionInfo, vm_error::StatusCode};
+use libra_types::{
+    transaction::TransactionInfo,
+    vm_error::{CodeLocation, StatusCode},
+};
 use schemadb::schema::assert_encode_decode;
 
 #[test]
@@ -13,7 +16,12 @@ fn test_encode_decode() {
         HashValue::random(),
         HashValue::random(),
         7,
-        StatusCode::EXECUTED,
+        StatusCode::ABORTED,
+        Some(CodeLocation {
+            code_offset: 3,
+            call_stack_depth: 2,
+            ..CodeLocation::default()
+        }),
     );
     assert_encode_decode::<TransactionInfoSchema>(&0u64, &txn_info);
 }
@@ -1,9 +1,9 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-//! This file defines transaction store APIs that are related to committed signed transactions.
+//! This file defines transaction store APIs that are related to committed transactions.
 
-use super::schema::signed_transaction::*;
+use super::schema::transaction::*;
 use crate::{
     change_set::ChangeSet, errors::LibraDbError,
     schema::transaction_by_account::TransactionByAccountSchema,
@@ -11,7 +11,7 @@ use crate::{
 use failure::prelude::*;
 use libra_types::{
     account_address::AccountAddress,
-    transaction::{SignedTransaction, Version},
+    transaction::{Transaction, Version},
 };
 use schemadb::DB;
 use std::sync::Arc;
@@ -44,29 +44,30 @@ impl TransactionStore {
         Ok(None)
     }
 
-    /// Get signed transaction given `version`
-    pub fn get_transaction(&self, version: Version) -> Result<SignedTransaction> {
+    /// Get transaction given `version`
+    pub fn get_transaction(&self, version: Version) -> Result<Transaction> {
         self.db
-            .get::<SignedTransactionSchema>(&version)?
+            .get::<TransactionSchema>(&version)?
             .ok_or_else(|| LibraDbError::NotFound(format!("Txn {}", version)).into())
     }
 
-    /// Save signed transaction at `version`
+    /// Save transaction at `version`. Only user transactions are indexed by their sender.
     pub fn put_transaction(
         &self,
         version: Version,
-        signed_transaction: &SignedTransaction,
+        transaction: &Transaction,
         cs: &mut ChangeSet,
     ) -> Result<()> {
-        cs.batch.put::<TransactionByAccountSchema>(
-            &(
-                signed_transaction.sender(),
-                signed_transaction.sequence_number(),
-            ),
-            &version,
-        )?;
-        cs.batch
-            .put::<SignedTransactionSchema>(&version, signed_transaction)?;
+        if let Transaction::UserTransaction(signed_transaction) = transaction {
+            cs.batch.put::<TransactionByAccountSchema>(
+                &(
+                    signed_transaction.sender(),
+                    signed_transaction.sequence_number(),
+                ),
+                &version,
+            )?;
+        }
+        cs.batch.put::<TransactionSchema>(&version, transaction)?;
 
         Ok(())
     }
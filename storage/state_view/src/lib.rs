@@ -4,7 +4,7 @@
 //! This crate defines [`trait StateView`](StateView).
 
 use failure::prelude::*;
-use libra_types::access_path::AccessPath;
+use libra_types::{access_path::AccessPath, transaction::Version};
 
 /// `StateView` is a trait that defines a read-only snapshot of the global state. It is passed to
 /// the VM for transaction execution, during which the VM is guaranteed to read anything at the
@@ -19,4 +19,11 @@ pub trait StateView {
     /// VM needs this method to know whether the current state view is for genesis state creation.
     /// Currently TransactionPayload::WriteSet is only valid for genesis state creation.
     fn is_genesis(&self) -> bool;
+
+    /// Returns the committed version of the ledger this view reads, or `None` if the view also
+    /// reads state that has not been committed. The VM uses it to cache data derived from the
+    /// state, such as the gas schedule.
+    fn version(&self) -> Option<Version> {
+        None
+    }
 }
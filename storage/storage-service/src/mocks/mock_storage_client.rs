@@ -188,6 +188,11 @@ fn get_mock_response_item(request_item: &ProtoRequestItem) -> Result<ProtoRespon
                     libra_types::byte_array::ByteArray::new(vec![]),
                     false,
                     false,
+                    false,
+                    EventHandle::random_handle(0),
+                    EventHandle::random_handle(0),
+                    EventHandle::random_handle(0),
+                    EventHandle::random_handle(0),
                     EventHandle::random_handle(0),
                     EventHandle::random_handle(0),
                 );
@@ -208,6 +213,7 @@ fn get_mock_response_item(request_item: &ProtoRequestItem) -> Result<ProtoRespon
                         HashValue::zero(),
                         0,
                         StatusCode::UNKNOWN_STATUS,
+                        None,
                     );
                     let transaction_info_to_account_proof =
                         libra_types::proof::SparseMerkleProof::new(None, vec![]);
@@ -259,7 +265,7 @@ fn get_mock_txn_data(
     start_seq: u64,
     end_seq: u64,
 ) -> (
-    Vec<libra_types::proto::types::SignedTransaction>,
+    Vec<libra_types::proto::types::Transaction>,
     Vec<TransactionInfo>,
 ) {
     let mut seed_rng = OsRng::new().expect("can't access OsRng");
@@ -269,9 +275,8 @@ fn get_mock_txn_data(
     let mut txns = vec![];
     let mut infos = vec![];
     for i in start_seq..=end_seq {
-        let signed_txn =
-            get_test_signed_txn(address, i, priv_key.clone(), pub_key.clone(), None).into();
-        txns.push(signed_txn);
+        let signed_txn = get_test_signed_txn(address, i, priv_key.clone(), pub_key.clone(), None);
+        txns.push(libra_types::transaction::Transaction::UserTransaction(signed_txn).into());
 
         let info = get_transaction_info().into();
         infos.push(info);
@@ -290,5 +295,6 @@ fn get_transaction_info() -> libra_types::transaction::TransactionInfo {
         HashValue::zero(),
         0,
         StatusCode::UNKNOWN_STATUS,
+        None,
     )
 }
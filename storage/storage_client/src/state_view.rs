@@ -177,4 +177,13 @@ impl<'a> StateView for VerifiedStateView<'a> {
     fn is_genesis(&self) -> bool {
         self.latest_persistent_version.is_none()
     }
+
+    fn version(&self) -> Option<Version> {
+        // The speculative state only matches the persistent one if nothing was executed on top.
+        if self.speculative_state.root_hash() == self.latest_persistent_state_root {
+            self.latest_persistent_version
+        } else {
+            None
+        }
+    }
 }
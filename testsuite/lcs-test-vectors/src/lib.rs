@@ -0,0 +1,554 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Golden test vectors for the canonical serialization (LCS) of the types in `libra_types`.
+//!
+//! The vectors are checked in at `vectors/test_vectors.json` so that implementations of LCS in
+//! other languages can test against them. Each vector has a name, the Rust type it was serialized
+//! from, a description of the value and the hex encoding of its serialization. The values are
+//! fixed, so the vectors only change when the serialization of a type does. In that case, run
+//! `cargo run -p lcs-test-vectors -- --update` and review the differences it prints.
+
+use canonical_serialization::{
+    CanonicalDeserialize, CanonicalSerialize, SimpleDeserializer, SimpleSerializer,
+};
+use crypto::{
+    ed25519::{Ed25519PublicKey, Ed25519Signature},
+    multi_ed25519::{MultiEd25519PublicKey, MultiEd25519Signature},
+    x25519::X25519StaticPublicKey,
+    HashValue,
+};
+use failure::prelude::*;
+use libra_types::{
+    access_path::AccessPath,
+    account_address::{AccountAddress, ADDRESS_LENGTH},
+    account_config::AccountResource,
+    block_metadata::BlockMetadata,
+    byte_array::ByteArray,
+    contract_event::ContractEvent,
+    event::{EventHandle, EventKey, EVENT_KEY_LENGTH},
+    identifier::Identifier,
+    language_storage::{ModuleId, StructTag},
+    ledger_info::LedgerInfo,
+    transaction::{
+        Module, Program, RawTransaction, Script, SignedTransaction, TransactionArgument,
+        TransactionInfo, TransactionPayload,
+    },
+    validator_public_keys::ValidatorPublicKeys,
+    validator_set::ValidatorSet,
+    vm_error::StatusCode,
+    write_set::{WriteOp, WriteSet, WriteSetMut},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    convert::TryFrom,
+    fs,
+    path::Path,
+    time::Duration,
+};
+
+/// The checked-in test vectors.
+pub const TEST_VECTORS_PATH: &str =
+    concat!(env!("CARGO_MANIFEST_DIR"), "/vectors/test_vectors.json");
+
+// The public keys of the first two test vectors of RFC 8032, and their signatures of the empty
+// message.
+const PUBLIC_KEY_1: &str = "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a";
+const SIGNATURE_1: &str =
+    "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590\
+     a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b";
+const PUBLIC_KEY_2: &str = "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c";
+const SIGNATURE_2: &str =
+    "30cfcc460a3e51b55ac3e7daf88dbbde2f66c76b1b8e6fe424568f222d25940563360b9c52\
+     7840b6b7d784a5a13fa383661a0db2734ab5e66eacedd150af6603";
+const X25519_PUBLIC_KEY: &str = "1cf579aba45a10ba1d1ef06d91fca2aa9ed0a1150515653155405d0b18cb9a67";
+
+/// A test vector as stored in the vectors file.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct EncodedVector {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub type_name: String,
+    pub description: String,
+    pub hex: String,
+}
+
+/// The serialization of a value of a `libra_types` type.
+pub struct TestVector {
+    pub name: &'static str,
+    pub type_name: &'static str,
+    pub description: &'static str,
+    pub bytes: Vec<u8>,
+    /// Deserializes the bytes and serializes the value again, for the types which can be
+    /// deserialized.
+    reencode: Option<fn(&[u8]) -> Result<Vec<u8>>>,
+}
+
+impl TestVector {
+    fn new<T: CanonicalSerialize + CanonicalDeserialize>(
+        name: &'static str,
+        type_name: &'static str,
+        description: &'static str,
+        value: &T,
+    ) -> Self {
+        Self {
+            reencode: Some(reencode::<T>),
+            ..Self::serialize_only(name, type_name, description, value)
+        }
+    }
+
+    fn serialize_only<T: CanonicalSerialize>(
+        name: &'static str,
+        type_name: &'static str,
+        description: &'static str,
+        value: &T,
+    ) -> Self {
+        Self {
+            name,
+            type_name,
+            description,
+            bytes: SimpleSerializer::<Vec<u8>>::serialize(value)
+                .unwrap_or_else(|e| panic!("Failed to serialize {}: {}", name, e)),
+            reencode: None,
+        }
+    }
+
+    pub fn encode(&self) -> EncodedVector {
+        EncodedVector {
+            name: self.name.to_string(),
+            type_name: self.type_name.to_string(),
+            description: self.description.to_string(),
+            hex: hex::encode(&self.bytes),
+        }
+    }
+
+    /// Checks that deserializing the vector and serializing the value again gives back the same
+    /// bytes. Does nothing for the types which cannot be deserialized.
+    pub fn check_round_trip(&self) -> Result<()> {
+        if let Some(reencode) = self.reencode {
+            let bytes = reencode(&self.bytes)?;
+            ensure!(
+                bytes == self.bytes,
+                "{} serialized again to {}",
+                self.name,
+                hex::encode(&bytes)
+            );
+        }
+        Ok(())
+    }
+
+    /// Deserializes `bytes` as the type of the vector. Returns `None` for the types which cannot
+    /// be deserialized.
+    pub fn deserialize(&self, bytes: &[u8]) -> Option<Result<()>> {
+        self.reencode.map(|reencode| reencode(bytes).map(|_| ()))
+    }
+}
+
+fn reencode<T: CanonicalSerialize + CanonicalDeserialize>(bytes: &[u8]) -> Result<Vec<u8>> {
+    let mut deserializer = SimpleDeserializer::new(bytes);
+    let value = T::deserialize(&mut deserializer)?;
+    ensure!(
+        deserializer.is_empty(),
+        "{} bytes left after deserializing",
+        deserializer.len() - deserializer.position() as usize
+    );
+    SimpleSerializer::<Vec<u8>>::serialize(&value)
+}
+
+/// Reads a file of test vectors.
+pub fn read_vectors(path: impl AsRef<Path>) -> Result<Vec<EncodedVector>> {
+    let path = path.as_ref();
+    let contents = fs::read_to_string(path)
+        .map_err(|e| format_err!("Unable to read {}: {}", path.display(), e))?;
+    Ok(serde_json::from_str(&contents)?)
+}
+
+/// Writes a file of test vectors.
+pub fn write_vectors(path: impl AsRef<Path>, vectors: &[EncodedVector]) -> Result<()> {
+    let mut contents = serde_json::to_string_pretty(vectors)?;
+    contents.push('\n');
+    fs::write(path, contents)?;
+    Ok(())
+}
+
+/// Describes the vectors which are added, removed or changed in `new` compared to `old`.
+pub fn diff(old: &[EncodedVector], new: &[EncodedVector]) -> Vec<String> {
+    let old_vectors: BTreeMap<_, _> = old.iter().map(|v| (v.name.as_str(), v)).collect();
+    let new_vectors: BTreeMap<_, _> = new.iter().map(|v| (v.name.as_str(), v)).collect();
+    let names: BTreeSet<_> = old_vectors.keys().chain(new_vectors.keys()).collect();
+    names
+        .into_iter()
+        .filter_map(
+            |name| match (old_vectors.get(name), new_vectors.get(name)) {
+                (Some(old), Some(new)) if old == new => None,
+                (Some(old), Some(new)) => Some(format!(
+                    "changed {}:\n  - {} {}: {}\n  + {} {}: {}",
+                    name,
+                    old.type_name,
+                    old.description,
+                    old.hex,
+                    new.type_name,
+                    new.description,
+                    new.hex
+                )),
+                (Some(old), None) => Some(format!("removed {} ({})", name, old.type_name)),
+                (None, Some(new)) => Some(format!(
+                    "added {}:\n  + {} {}: {}",
+                    name, new.type_name, new.description, new.hex
+                )),
+                (None, None) => None,
+            },
+        )
+        .collect()
+}
+
+fn address(byte: u8) -> AccountAddress {
+    AccountAddress::new([byte; ADDRESS_LENGTH])
+}
+
+fn hash(byte: u8) -> HashValue {
+    HashValue::new([byte; HashValue::LENGTH])
+}
+
+fn event_key(byte: u8) -> EventKey {
+    EventKey::new([byte; EVENT_KEY_LENGTH])
+}
+
+fn identifier(name: &str) -> Identifier {
+    Identifier::new(name).expect("valid identifier")
+}
+
+fn public_key(key: &str) -> Ed25519PublicKey {
+    Ed25519PublicKey::try_from(hex::decode(key).expect("valid hex").as_slice())
+        .expect("valid public key")
+}
+
+fn signature(signature: &str) -> Ed25519Signature {
+    Ed25519Signature::try_from(hex::decode(signature).expect("valid hex").as_slice())
+        .expect("valid signature")
+}
+
+fn script() -> Script {
+    Script::new(b"move".to_vec(), vec![TransactionArgument::U64(9)])
+}
+
+fn raw_transaction() -> RawTransaction {
+    RawTransaction::new(
+        address(0x11),
+        32,
+        TransactionPayload::Script(script()),
+        10_000,
+        20,
+        Duration::from_secs(86_400),
+    )
+}
+
+fn write_set() -> WriteSet {
+    WriteSetMut::new(vec![
+        (
+            AccessPath::new(address(0x11), b"/a".to_vec()),
+            WriteOp::Value(vec![0xca, 0xfe]),
+        ),
+        (
+            AccessPath::new(address(0x22), b"/b".to_vec()),
+            WriteOp::Deletion,
+        ),
+    ])
+    .freeze()
+    .expect("valid write set")
+}
+
+fn validator_public_keys() -> ValidatorPublicKeys {
+    ValidatorPublicKeys::new(
+        address(0x11),
+        public_key(PUBLIC_KEY_1),
+        10,
+        public_key(PUBLIC_KEY_2),
+        X25519StaticPublicKey::try_from(
+            hex::decode(X25519_PUBLIC_KEY)
+                .expect("valid hex")
+                .as_slice(),
+        )
+        .expect("valid public key"),
+    )
+}
+
+/// All the test vectors, in the order of the vectors file.
+pub fn test_vectors() -> Vec<TestVector> {
+    let program = Program::new(
+        b"move".to_vec(),
+        vec![vec![0xca], vec![0xfe, 0xd0]],
+        vec![TransactionArgument::String("cafe".to_string())],
+    );
+    let module = Module::new(vec![0xca, 0xfe, 0xd0, 0x0d]);
+    let multi_ed25519_public_key =
+        MultiEd25519PublicKey::new(vec![public_key(PUBLIC_KEY_1), public_key(PUBLIC_KEY_2)], 2)
+            .expect("valid public key");
+    let multi_ed25519_signature = MultiEd25519Signature::new(vec![
+        (signature(SIGNATURE_1), 0),
+        (signature(SIGNATURE_2), 1),
+    ])
+    .expect("valid signature");
+    let mut previous_block_votes = BTreeMap::new();
+    previous_block_votes.insert(address(0x22), signature(SIGNATURE_2));
+    previous_block_votes.insert(address(0x11), signature(SIGNATURE_1));
+
+    vec![
+        TestVector::new(
+            "account_address",
+            "AccountAddress",
+            "0x1111111111111111111111111111111111111111111111111111111111111111",
+            &address(0x11),
+        ),
+        TestVector::new(
+            "byte_array",
+            "ByteArray",
+            "0xcafed00d",
+            &ByteArray::new(vec![0xca, 0xfe, 0xd0, 0x0d]),
+        ),
+        TestVector::new(
+            "identifier",
+            "Identifier",
+            "LibraAccount",
+            &identifier("LibraAccount"),
+        ),
+        TestVector::new(
+            "module_id",
+            "ModuleId",
+            "address 0x0000000000000000000000000000000000000000000000000000000000000000, \
+             name LibraAccount",
+            &ModuleId::new(address(0), identifier("LibraAccount")),
+        ),
+        TestVector::new(
+            "struct_tag",
+            "StructTag",
+            "0x0000000000000000000000000000000000000000000000000000000000000000::LibraAccount::T\
+             <0x0000000000000000000000000000000000000000000000000000000000000000::LibraCoin::T>",
+            &StructTag {
+                address: address(0),
+                module: identifier("LibraAccount"),
+                name: identifier("T"),
+                type_params: vec![StructTag {
+                    address: address(0),
+                    module: identifier("LibraCoin"),
+                    name: identifier("T"),
+                    type_params: vec![],
+                }],
+            },
+        ),
+        TestVector::new(
+            "access_path",
+            "AccessPath",
+            "address 0x1111111111111111111111111111111111111111111111111111111111111111, \
+             path \"/a\"",
+            &AccessPath::new(address(0x11), b"/a".to_vec()),
+        ),
+        TestVector::new(
+            "event_key",
+            "EventKey",
+            "0x2222222222222222222222222222222222222222222222222222222222222222",
+            &event_key(0x22),
+        ),
+        TestVector::new(
+            "event_handle",
+            "EventHandle",
+            "key 0x2222222222222222222222222222222222222222222222222222222222222222, count 5",
+            &EventHandle::new(event_key(0x22), 5),
+        ),
+        TestVector::serialize_only(
+            "contract_event",
+            "ContractEvent",
+            "key 0x2222222222222222222222222222222222222222222222222222222222222222, \
+             sequence number 7, data 0xcafe",
+            &ContractEvent::new(event_key(0x22), 7, vec![0xca, 0xfe]),
+        ),
+        TestVector::new(
+            "write_op_deletion",
+            "WriteOp",
+            "Deletion",
+            &WriteOp::Deletion,
+        ),
+        TestVector::new(
+            "write_op_value",
+            "WriteOp",
+            "Value 0xcafe",
+            &WriteOp::Value(vec![0xca, 0xfe]),
+        ),
+        TestVector::new(
+            "write_set",
+            "WriteSet",
+            "[(access path 0x1111111111111111111111111111111111111111111111111111111111111111 \
+             \"/a\", Value 0xcafe), (access path \
+             0x2222222222222222222222222222222222222222222222222222222222222222 \"/b\", Deletion)]",
+            &write_set(),
+        ),
+        TestVector::new(
+            "transaction_argument_u64",
+            "TransactionArgument",
+            "U64 9213671392124193148",
+            &TransactionArgument::U64(9_213_671_392_124_193_148),
+        ),
+        TestVector::new(
+            "transaction_argument_address",
+            "TransactionArgument",
+            "Address 0x1111111111111111111111111111111111111111111111111111111111111111",
+            &TransactionArgument::Address(address(0x11)),
+        ),
+        TestVector::new(
+            "transaction_argument_string",
+            "TransactionArgument",
+            "String \"Hello, World!\"",
+            &TransactionArgument::String("Hello, World!".to_string()),
+        ),
+        TestVector::new(
+            "transaction_argument_byte_array",
+            "TransactionArgument",
+            "ByteArray 0xcafed00d",
+            &TransactionArgument::ByteArray(ByteArray::new(vec![0xca, 0xfe, 0xd0, 0x0d])),
+        ),
+        TestVector::new(
+            "program",
+            "Program",
+            "code \"move\", args [String \"cafe\"], modules [0xca, 0xfed0]",
+            &program,
+        ),
+        TestVector::new("script", "Script", "code \"move\", args [U64 9]", &script()),
+        TestVector::new("module", "Module", "code 0xcafed00d", &module),
+        TestVector::new(
+            "transaction_payload_program",
+            "TransactionPayload",
+            "Program, as in the program vector",
+            &TransactionPayload::Program(program),
+        ),
+        TestVector::new(
+            "transaction_payload_write_set",
+            "TransactionPayload",
+            "WriteSet, as in the write_set vector",
+            &TransactionPayload::WriteSet(write_set()),
+        ),
+        TestVector::new(
+            "transaction_payload_script",
+            "TransactionPayload",
+            "Script, as in the script vector",
+            &TransactionPayload::Script(script()),
+        ),
+        TestVector::new(
+            "transaction_payload_module",
+            "TransactionPayload",
+            "Module, as in the module vector",
+            &TransactionPayload::Module(module),
+        ),
+        TestVector::new(
+            "raw_transaction",
+            "RawTransaction",
+            "sender 0x1111111111111111111111111111111111111111111111111111111111111111, \
+             sequence number 32, Script payload as in the script vector, max gas amount 10000, \
+             gas unit price 20, expiration time 86400 seconds",
+            &raw_transaction(),
+        ),
+        TestVector::new(
+            "signed_transaction",
+            "SignedTransaction",
+            "the raw_transaction vector, with an Ed25519 authenticator of the first RFC 8032 test \
+             key and its signature of the empty message",
+            &SignedTransaction::new(
+                raw_transaction(),
+                public_key(PUBLIC_KEY_1),
+                signature(SIGNATURE_1),
+            ),
+        ),
+        TestVector::new(
+            "signed_transaction_multi_ed25519",
+            "SignedTransaction",
+            "the raw_transaction vector, with a 2-of-2 MultiEd25519 authenticator of the first \
+             two RFC 8032 test keys and their signatures of the empty message",
+            &SignedTransaction::new_multisig(
+                raw_transaction(),
+                multi_ed25519_public_key,
+                multi_ed25519_signature,
+            ),
+        ),
+        TestVector::serialize_only(
+            "transaction_info",
+            "TransactionInfo",
+            "signed transaction hash 0x33..33, state root hash 0x44..44, event root hash \
+             0x55..55, gas used 1000, major status EXECUTED (4001), no location",
+            &TransactionInfo::new(
+                hash(0x33),
+                hash(0x44),
+                hash(0x55),
+                1000,
+                StatusCode::EXECUTED,
+                None,
+            ),
+        ),
+        TestVector::new(
+            "validator_public_keys",
+            "ValidatorPublicKeys",
+            "account 0x1111111111111111111111111111111111111111111111111111111111111111, \
+             consensus key of the first RFC 8032 test, voting power 10, network signing key of \
+             the second RFC 8032 test, network identity key of the X25519 private key 0x77..77",
+            &validator_public_keys(),
+        ),
+        TestVector::new(
+            "validator_set",
+            "ValidatorSet",
+            "the validator_public_keys vector",
+            &ValidatorSet::new(vec![validator_public_keys()]),
+        ),
+        TestVector::serialize_only(
+            "ledger_info",
+            "LedgerInfo",
+            "version 100, transaction accumulator hash 0x33..33, consensus data hash 0x44..44, \
+             consensus block id 0x55..55, epoch 2, timestamp 1000000 usecs, no next validator set",
+            &LedgerInfo::new(100, hash(0x33), hash(0x44), hash(0x55), 2, 1_000_000, None),
+        ),
+        TestVector::serialize_only(
+            "ledger_info_with_validator_set",
+            "LedgerInfo",
+            "the ledger_info vector, with the validator_set vector as next validator set",
+            &LedgerInfo::new(
+                100,
+                hash(0x33),
+                hash(0x44),
+                hash(0x55),
+                2,
+                1_000_000,
+                Some(ValidatorSet::new(vec![validator_public_keys()])),
+            ),
+        ),
+        TestVector::new(
+            "account_resource",
+            "AccountResource",
+            "balance 1000, sequence number 3, authentication key 0x66..66, delegated key \
+             rotation capability false, delegated withdrawal capability true, not frozen, sent \
+             events key 0x22..22 count 1, received events key 0x23..23 count 2, account \
+             creation events key 0x24..24 count 1, key rotation events key 0x25..25 count 0, key \
+             rotation capability events key 0x26..26 count 0, withdrawal capability events key \
+             0x27..27 count 1",
+            &AccountResource::new(
+                1000,
+                3,
+                ByteArray::new(vec![0x66; 32]),
+                false,
+                true,
+                false,
+                EventHandle::new(event_key(0x22), 1),
+                EventHandle::new(event_key(0x23), 2),
+                EventHandle::new(event_key(0x24), 1),
+                EventHandle::new(event_key(0x25), 0),
+                EventHandle::new(event_key(0x26), 0),
+                EventHandle::new(event_key(0x27), 1),
+            ),
+        ),
+        TestVector::new(
+            "block_metadata",
+            "BlockMetadata",
+            "id 0x33..33, timestamp 1000000 usecs, votes of 0x11..11 and 0x22..22 with the \
+             signatures of the empty message by the first and second RFC 8032 test keys, \
+             proposer 0x11..11",
+            &BlockMetadata::new(hash(0x33), 1_000_000, previous_block_votes, address(0x11)),
+        ),
+    ]
+}
@@ -0,0 +1,58 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use failure::prelude::*;
+use lcs_test_vectors::{
+    diff, read_vectors, test_vectors, write_vectors, EncodedVector, TestVector, TEST_VECTORS_PATH,
+};
+use std::{path::PathBuf, process};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+#[structopt(
+    name = "lcs-test-vectors",
+    about = "Regenerates the canonical serialization test vectors and reports how they differ \
+             from the checked-in ones."
+)]
+struct Args {
+    /// Overwrite the test vectors with the regenerated ones
+    #[structopt(long)]
+    pub update: bool,
+    /// The file of test vectors, instead of the checked-in one
+    #[structopt(long, parse(from_os_str))]
+    pub path: Option<PathBuf>,
+}
+
+fn main() -> Result<()> {
+    let args = Args::from_args();
+    let path = args
+        .path
+        .unwrap_or_else(|| PathBuf::from(TEST_VECTORS_PATH));
+
+    let generated: Vec<EncodedVector> = test_vectors().iter().map(TestVector::encode).collect();
+    let existing = if path.exists() {
+        read_vectors(&path)?
+    } else {
+        vec![]
+    };
+    let differences = diff(&existing, &generated);
+    for difference in &differences {
+        println!("{}", difference);
+    }
+
+    if args.update {
+        write_vectors(&path, &generated)?;
+        println!(
+            "Wrote {} test vectors to {}",
+            generated.len(),
+            path.display()
+        );
+        return Ok(());
+    }
+    if differences.is_empty() {
+        println!("The {} test vectors are up to date", generated.len());
+        return Ok(());
+    }
+    println!("Run with --update to overwrite the test vectors");
+    process::exit(1);
+}
@@ -0,0 +1,82 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use lcs_test_vectors::{diff, read_vectors, test_vectors, TestVector, TEST_VECTORS_PATH};
+use std::collections::BTreeSet;
+
+fn vector(name: &str) -> TestVector {
+    test_vectors()
+        .into_iter()
+        .find(|vector| vector.name == name)
+        .expect("vector should exist")
+}
+
+#[test]
+fn checked_in_vectors_are_up_to_date() {
+    let checked_in = read_vectors(TEST_VECTORS_PATH).unwrap();
+    let generated: Vec<_> = test_vectors().iter().map(TestVector::encode).collect();
+    let differences = diff(&checked_in, &generated);
+    assert!(
+        differences.is_empty(),
+        "The serialization changed, run `cargo run -p lcs-test-vectors -- --update` if this is \
+         intended:\n{}",
+        differences.join("\n")
+    );
+    // The file keeps the order of the vectors.
+    assert_eq!(checked_in, generated);
+}
+
+#[test]
+fn vector_names_are_unique() {
+    let vectors = test_vectors();
+    let names: BTreeSet<_> = vectors.iter().map(|vector| vector.name).collect();
+    assert_eq!(names.len(), vectors.len());
+}
+
+#[test]
+fn vectors_round_trip() {
+    for vector in test_vectors() {
+        if let Err(e) = vector.check_round_trip() {
+            panic!("{} does not round trip: {}", vector.name, e);
+        }
+    }
+}
+
+#[test]
+fn non_canonical_encodings_are_rejected() {
+    let block_metadata = vector("block_metadata");
+    assert!(block_metadata
+        .deserialize(&block_metadata.bytes)
+        .unwrap()
+        .is_ok());
+
+    // Swap the two votes, which come after the id and the timestamp and the length of the map.
+    let votes_start = 4 + 32 + 8 + 4;
+    let vote_len = (4 + 32) + (4 + 64);
+    let mut unsorted = block_metadata.bytes.clone();
+    unsorted[votes_start..votes_start + 2 * vote_len].rotate_left(vote_len);
+    assert!(block_metadata.deserialize(&unsorted).unwrap().is_err());
+
+    // Trailing bytes.
+    let raw_transaction = vector("raw_transaction");
+    let mut trailing = raw_transaction.bytes.clone();
+    trailing.push(0);
+    assert!(raw_transaction.deserialize(&trailing).unwrap().is_err());
+
+    // A length prefix longer than the remaining bytes.
+    let account_address = vector("account_address");
+    let mut overlong = account_address.bytes.clone();
+    overlong[0] = 33;
+    assert!(account_address.deserialize(&overlong).unwrap().is_err());
+
+    // A boolean other than 0 or 1.
+    let account_resource = vector("account_resource");
+    let mut invalid_bool = account_resource.bytes.clone();
+    invalid_bool[4 + 32 + 8] = 2;
+    assert!(account_resource
+        .deserialize(&invalid_bool)
+        .unwrap()
+        .is_err());
+
+    assert!(vector("ledger_info").deserialize(&[]).is_none());
+}
@@ -56,6 +56,7 @@ macro_rules! proto_fuzz_target {
 
 // List fuzz target modules here.
 mod admission_control;
+mod canonical_round_trip;
 mod compiled_module;
 mod consensus_proposal;
 mod inner_signed_transaction;
@@ -72,6 +73,7 @@ lazy_static! {
             Box::new(vm_value::ValueTarget::default()),
             Box::new(consensus_proposal::ConsensusProposal::default()),
             Box::new(admission_control::AdmissionControlSubmitTransactionRequest::default()),
+            Box::new(canonical_round_trip::CanonicalRoundTrip::default()),
         ];
         targets.into_iter().map(|target| (target.name(), target)).collect()
     };
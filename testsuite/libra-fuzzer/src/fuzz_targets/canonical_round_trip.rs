@@ -0,0 +1,87 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::FuzzTargetImpl;
+use canonical_serialization::{
+    CanonicalDeserialize, CanonicalSerialize, SimpleDeserializer, SimpleSerializer,
+};
+use libra_types::{
+    account_config::AccountResource, block_metadata::BlockMetadata, transaction::SignedTransaction,
+    validator_set::ValidatorSet,
+};
+use proptest::prelude::*;
+use proptest_helpers::ValueGenerator;
+use std::fmt::Debug;
+
+/// The number of types fuzzed, selected by the first byte of the input.
+const NUM_TYPES: u8 = 4;
+
+#[derive(Clone, Debug, Default)]
+pub struct CanonicalRoundTrip;
+
+impl FuzzTargetImpl for CanonicalRoundTrip {
+    fn name(&self) -> &'static str {
+        module_name!()
+    }
+
+    fn description(&self) -> &'static str {
+        "LCS decode(encode(x)) == x, and only canonical encodings are accepted"
+    }
+
+    fn generate(&self, idx: usize, gen: &mut ValueGenerator) -> Option<Vec<u8>> {
+        let selector = (idx % NUM_TYPES as usize) as u8;
+        let bytes = match selector {
+            0 => serialize(&gen.generate(any::<SignedTransaction>())),
+            1 => serialize(&gen.generate(any::<BlockMetadata>())),
+            2 => serialize(&gen.generate(any::<ValidatorSet>())),
+            _ => serialize(&gen.generate(any::<AccountResource>())),
+        };
+        let mut data = vec![selector];
+        data.extend(bytes);
+        Some(data)
+    }
+
+    fn fuzz(&self, data: &[u8]) {
+        if data.is_empty() {
+            return;
+        }
+        let data_bytes = &data[1..];
+        match data[0] % NUM_TYPES {
+            0 => round_trip::<SignedTransaction>(data_bytes),
+            1 => round_trip::<BlockMetadata>(data_bytes),
+            2 => round_trip::<ValidatorSet>(data_bytes),
+            _ => round_trip::<AccountResource>(data_bytes),
+        }
+    }
+}
+
+fn serialize(value: &impl CanonicalSerialize) -> Vec<u8> {
+    SimpleSerializer::serialize(value).expect("serialization should work")
+}
+
+/// Any input the deserializer accepts must be the canonical encoding of the value it returns, so
+/// that serializing this value gives back the same bytes.
+fn round_trip<T>(data: &[u8])
+where
+    T: CanonicalSerialize + CanonicalDeserialize + Debug,
+{
+    let mut deserializer = SimpleDeserializer::new(data);
+    // Errors are OK -- the input may be invalid.
+    let value = match T::deserialize(&mut deserializer) {
+        Ok(value) => value,
+        Err(_) => return,
+    };
+    // The bytes following the value are not part of its encoding.
+    let consumed = &data[..deserializer.position() as usize];
+
+    let bytes = serialize(&value);
+    assert_eq!(
+        bytes.as_slice(),
+        consumed,
+        "non-canonical encoding of {:?} was accepted",
+        value
+    );
+    let decoded: T =
+        SimpleDeserializer::deserialize(&bytes).expect("deserializing a serialized value failed");
+    assert_eq!(serialize(&decoded), bytes);
+}
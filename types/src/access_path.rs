@@ -45,9 +45,7 @@ use crate::{
     language_storage::{ModuleId, ResourceKey, StructTag},
     validator_set::validator_set_path,
 };
-use canonical_serialization::{
-    CanonicalDeserialize, CanonicalDeserializer, CanonicalSerialize, CanonicalSerializer,
-};
+use canonical_serialization::{CanonicalDeserialize, CanonicalSerialize};
 use crypto::hash::{CryptoHash, HashValue};
 use failure::prelude::*;
 use hex;
@@ -203,7 +201,19 @@ lazy_static! {
         AccessPath::new(association_address(), validator_set_path());
 }
 
-#[derive(Clone, Eq, PartialEq, Default, Hash, Serialize, Deserialize, Ord, PartialOrd)]
+#[derive(
+    Clone,
+    Eq,
+    PartialEq,
+    Default,
+    Hash,
+    Serialize,
+    Deserialize,
+    Ord,
+    PartialOrd,
+    CanonicalSerialize,
+    CanonicalDeserialize,
+)]
 #[cfg_attr(any(test, feature = "testing"), derive(Arbitrary))]
 pub struct AccessPath {
     pub address: AccountAddress,
@@ -327,24 +337,6 @@ impl fmt::Display for AccessPath {
     }
 }
 
-impl CanonicalSerialize for AccessPath {
-    fn serialize(&self, serializer: &mut impl CanonicalSerializer) -> Result<()> {
-        serializer
-            .encode_struct(&self.address)?
-            .encode_bytes(&self.path)?;
-        Ok(())
-    }
-}
-
-impl CanonicalDeserialize for AccessPath {
-    fn deserialize(deserializer: &mut impl CanonicalDeserializer) -> Result<Self> {
-        let address = deserializer.decode_struct::<AccountAddress>()?;
-        let path = deserializer.decode_bytes()?;
-
-        Ok(Self { address, path })
-    }
-}
-
 impl TryFrom<crate::proto::types::AccessPath> for AccessPath {
     type Error = Error;
 
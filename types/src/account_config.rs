@@ -28,6 +28,16 @@ lazy_static! {
     // Account
     static ref ACCOUNT_MODULE_NAME: Identifier = Identifier::new("LibraAccount").unwrap();
     static ref ACCOUNT_STRUCT_NAME: Identifier = Identifier::new("T").unwrap();
+    static ref CURRENCY_BALANCES_STRUCT_NAME: Identifier =
+        Identifier::new("CurrencyBalances").unwrap();
+
+    // GasSchedule
+    static ref GAS_SCHEDULE_MODULE_NAME: Identifier = Identifier::new("GasSchedule").unwrap();
+    static ref GAS_SCHEDULE_STRUCT_NAME: Identifier = Identifier::new("T").unwrap();
+
+    // Block
+    static ref BLOCK_MODULE_NAME: Identifier = Identifier::new("Block").unwrap();
+    static ref BLOCK_STRUCT_NAME: Identifier = Identifier::new("T").unwrap();
 }
 
 pub fn coin_module_name() -> &'static IdentStr {
@@ -46,6 +56,26 @@ pub fn account_struct_name() -> &'static IdentStr {
     &*ACCOUNT_STRUCT_NAME
 }
 
+pub fn currency_balances_struct_name() -> &'static IdentStr {
+    &*CURRENCY_BALANCES_STRUCT_NAME
+}
+
+pub fn gas_schedule_module_name() -> &'static IdentStr {
+    &*GAS_SCHEDULE_MODULE_NAME
+}
+
+pub fn gas_schedule_struct_name() -> &'static IdentStr {
+    &*GAS_SCHEDULE_STRUCT_NAME
+}
+
+pub fn block_module_name() -> &'static IdentStr {
+    &*BLOCK_MODULE_NAME
+}
+
+pub fn block_struct_name() -> &'static IdentStr {
+    &*BLOCK_STRUCT_NAME
+}
+
 pub fn core_code_address() -> AccountAddress {
     AccountAddress::default()
 }
@@ -69,6 +99,39 @@ pub fn account_struct_tag() -> StructTag {
     }
 }
 
+/// The struct tag of the resource holding the balances of an account in currencies other than
+/// LibraCoin. It is only published under accounts that accept such currencies.
+pub fn currency_balances_struct_tag() -> StructTag {
+    StructTag {
+        address: core_code_address(),
+        module: account_module_name().to_owned(),
+        name: currency_balances_struct_name().to_owned(),
+        type_params: vec![],
+    }
+}
+
+/// The struct tag of the resource holding the on-chain gas schedule. The resource is published
+/// under the [association address](association_address).
+pub fn gas_schedule_struct_tag() -> StructTag {
+    StructTag {
+        address: core_code_address(),
+        module: gas_schedule_module_name().to_owned(),
+        name: gas_schedule_struct_name().to_owned(),
+        type_params: vec![],
+    }
+}
+
+/// The struct tag of the resource holding the height and timestamp of the current block. The
+/// resource is published under the [association address](association_address).
+pub fn block_struct_tag() -> StructTag {
+    StructTag {
+        address: core_code_address(),
+        module: block_module_name().to_owned(),
+        name: block_struct_name().to_owned(),
+        type_params: vec![],
+    }
+}
+
 /// A Rust representation of an Account resource.
 /// This is not how the Account is represented in the VM but it's a convenient representation.
 #[derive(Debug, Default)]
@@ -79,8 +142,13 @@ pub struct AccountResource {
     authentication_key: ByteArray,
     delegated_key_rotation_capability: bool,
     delegated_withdrawal_capability: bool,
+    is_frozen: bool,
     sent_events: EventHandle,
     received_events: EventHandle,
+    account_creation_events: EventHandle,
+    key_rotation_events: EventHandle,
+    key_rotation_capability_events: EventHandle,
+    withdrawal_capability_events: EventHandle,
 }
 
 impl AccountResource {
@@ -91,8 +159,13 @@ impl AccountResource {
         authentication_key: ByteArray,
         delegated_key_rotation_capability: bool,
         delegated_withdrawal_capability: bool,
+        is_frozen: bool,
         sent_events: EventHandle,
         received_events: EventHandle,
+        account_creation_events: EventHandle,
+        key_rotation_events: EventHandle,
+        key_rotation_capability_events: EventHandle,
+        withdrawal_capability_events: EventHandle,
     ) -> Self {
         AccountResource {
             balance,
@@ -100,8 +173,13 @@ impl AccountResource {
             authentication_key,
             delegated_key_rotation_capability,
             delegated_withdrawal_capability,
+            is_frozen,
             sent_events,
             received_events,
+            account_creation_events,
+            key_rotation_events,
+            key_rotation_capability_events,
+            withdrawal_capability_events,
         }
     }
 
@@ -139,6 +217,26 @@ impl AccountResource {
         &self.received_events
     }
 
+    /// Return the account_creation_events handle for the given AccountResource
+    pub fn account_creation_events(&self) -> &EventHandle {
+        &self.account_creation_events
+    }
+
+    /// Return the key_rotation_events handle for the given AccountResource
+    pub fn key_rotation_events(&self) -> &EventHandle {
+        &self.key_rotation_events
+    }
+
+    /// Return the key_rotation_capability_events handle for the given AccountResource
+    pub fn key_rotation_capability_events(&self) -> &EventHandle {
+        &self.key_rotation_capability_events
+    }
+
+    /// Return the withdrawal_capability_events handle for the given AccountResource
+    pub fn withdrawal_capability_events(&self) -> &EventHandle {
+        &self.withdrawal_capability_events
+    }
+
     /// Return the delegated_key_rotation_capability field for the given AccountResource
     pub fn delegated_key_rotation_capability(&self) -> bool {
         self.delegated_key_rotation_capability
@@ -149,11 +247,24 @@ impl AccountResource {
         self.delegated_withdrawal_capability
     }
 
+    /// Return the is_frozen field for the given AccountResource
+    pub fn is_frozen(&self) -> bool {
+        self.is_frozen
+    }
+
     pub fn get_event_handle_by_query_path(&self, query_path: &[u8]) -> Result<&EventHandle> {
         if *ACCOUNT_RECEIVED_EVENT_PATH == query_path {
             Ok(&self.received_events)
         } else if *ACCOUNT_SENT_EVENT_PATH == query_path {
             Ok(&self.sent_events)
+        } else if *ACCOUNT_CREATION_EVENT_PATH == query_path {
+            Ok(&self.account_creation_events)
+        } else if *ACCOUNT_KEY_ROTATION_EVENT_PATH == query_path {
+            Ok(&self.key_rotation_events)
+        } else if *ACCOUNT_KEY_ROTATION_CAPABILITY_EVENT_PATH == query_path {
+            Ok(&self.key_rotation_capability_events)
+        } else if *ACCOUNT_WITHDRAWAL_CAPABILITY_EVENT_PATH == query_path {
+            Ok(&self.withdrawal_capability_events)
         } else {
             bail!("Unrecognized query path: {:?}", query_path);
         }
@@ -165,26 +276,36 @@ impl CanonicalSerialize for AccountResource {
         // TODO(drussi): the order in which these fields are serialized depends on some
         // implementation details in the VM.
         serializer
+            .encode_struct(&self.account_creation_events)?
             .encode_struct(&self.authentication_key)?
             .encode_u64(self.balance)?
             .encode_bool(self.delegated_key_rotation_capability)?
             .encode_bool(self.delegated_withdrawal_capability)?
+            .encode_bool(self.is_frozen)?
+            .encode_struct(&self.key_rotation_capability_events)?
+            .encode_struct(&self.key_rotation_events)?
             .encode_struct(&self.received_events)?
             .encode_struct(&self.sent_events)?
-            .encode_u64(self.sequence_number)?;
+            .encode_u64(self.sequence_number)?
+            .encode_struct(&self.withdrawal_capability_events)?;
         Ok(())
     }
 }
 
 impl CanonicalDeserialize for AccountResource {
     fn deserialize(deserializer: &mut impl CanonicalDeserializer) -> Result<Self> {
+        let account_creation_events = deserializer.decode_struct()?;
         let authentication_key = deserializer.decode_struct()?;
         let balance = deserializer.decode_u64()?;
         let delegated_key_rotation_capability = deserializer.decode_bool()?;
         let delegated_withdrawal_capability = deserializer.decode_bool()?;
+        let is_frozen = deserializer.decode_bool()?;
+        let key_rotation_capability_events = deserializer.decode_struct()?;
+        let key_rotation_events = deserializer.decode_struct()?;
         let received_events = deserializer.decode_struct()?;
         let sent_events = deserializer.decode_struct()?;
         let sequence_number = deserializer.decode_u64()?;
+        let withdrawal_capability_events = deserializer.decode_struct()?;
 
         Ok(AccountResource {
             balance,
@@ -192,8 +313,13 @@ impl CanonicalDeserialize for AccountResource {
             authentication_key,
             delegated_key_rotation_capability,
             delegated_withdrawal_capability,
+            is_frozen,
             sent_events,
             received_events,
+            account_creation_events,
+            key_rotation_events,
+            key_rotation_capability_events,
+            withdrawal_capability_events,
         })
     }
 }
@@ -216,6 +342,16 @@ pub fn account_resource_path() -> Vec<u8> {
     AccessPath::resource_access_vec(&account_struct_tag(), &Accesses::empty())
 }
 
+/// Return the path to the CurrencyBalances resource of an account.
+pub fn currency_balances_resource_path() -> Vec<u8> {
+    AccessPath::resource_access_vec(&currency_balances_struct_tag(), &Accesses::empty())
+}
+
+/// Return the path to the Block resource. It is published under the association address.
+pub fn block_resource_path() -> Vec<u8> {
+    AccessPath::resource_access_vec(&block_struct_tag(), &Accesses::empty())
+}
+
 lazy_static! {
     /// The path to the sent event counter for an Account resource.
     /// It can be used to query the event DB for the given event.
@@ -232,6 +368,166 @@ lazy_static! {
         path.extend_from_slice(b"/received_events_count/");
         path
     };
+
+    /// The path to the account creation event counter for an Account resource.
+    pub static ref ACCOUNT_CREATION_EVENT_PATH: Vec<u8> = {
+        let mut path = account_resource_path();
+        path.extend_from_slice(b"/account_creation_events_count/");
+        path
+    };
+
+    /// The path to the key rotation event counter for an Account resource.
+    pub static ref ACCOUNT_KEY_ROTATION_EVENT_PATH: Vec<u8> = {
+        let mut path = account_resource_path();
+        path.extend_from_slice(b"/key_rotation_events_count/");
+        path
+    };
+
+    /// The path to the key rotation capability event counter for an Account resource.
+    pub static ref ACCOUNT_KEY_ROTATION_CAPABILITY_EVENT_PATH: Vec<u8> = {
+        let mut path = account_resource_path();
+        path.extend_from_slice(b"/key_rotation_capability_events_count/");
+        path
+    };
+
+    /// The path to the withdrawal capability event counter for an Account resource.
+    pub static ref ACCOUNT_WITHDRAWAL_CAPABILITY_EVENT_PATH: Vec<u8> = {
+        let mut path = account_resource_path();
+        path.extend_from_slice(b"/withdrawal_capability_events_count/");
+        path
+    };
+}
+
+/// A Rust representation of a coin of a currency other than LibraCoin (a Currency.T resource).
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testing"), derive(Arbitrary))]
+pub struct CurrencyCoin {
+    currency_code: ByteArray,
+    value: u64,
+}
+
+impl CurrencyCoin {
+    pub fn new(currency_code: ByteArray, value: u64) -> Self {
+        CurrencyCoin {
+            currency_code,
+            value,
+        }
+    }
+
+    /// Return the code of the currency of the coin
+    pub fn currency_code(&self) -> &ByteArray {
+        &self.currency_code
+    }
+
+    /// Return the value of the coin
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+}
+
+impl CanonicalSerialize for CurrencyCoin {
+    fn serialize(&self, serializer: &mut impl CanonicalSerializer) -> Result<()> {
+        serializer
+            .encode_struct(&self.currency_code)?
+            .encode_u64(self.value)?;
+        Ok(())
+    }
+}
+
+impl CanonicalDeserialize for CurrencyCoin {
+    fn deserialize(deserializer: &mut impl CanonicalDeserializer) -> Result<Self> {
+        let currency_code = deserializer.decode_struct()?;
+        let value = deserializer.decode_u64()?;
+        Ok(CurrencyCoin::new(currency_code, value))
+    }
+}
+
+/// A Rust representation of the CurrencyBalances resource, which holds one coin per currency
+/// accepted by the account.
+#[derive(Debug, Default)]
+#[cfg_attr(any(test, feature = "testing"), derive(Arbitrary))]
+pub struct CurrencyBalancesResource {
+    coins: Vec<CurrencyCoin>,
+    received_events: EventHandle,
+    sent_events: EventHandle,
+}
+
+impl CurrencyBalancesResource {
+    pub fn new(
+        coins: Vec<CurrencyCoin>,
+        received_events: EventHandle,
+        sent_events: EventHandle,
+    ) -> Self {
+        CurrencyBalancesResource {
+            coins,
+            received_events,
+            sent_events,
+        }
+    }
+
+    /// Given an account map (typically from storage) retrieves the CurrencyBalances resource
+    /// associated, if the account accepts any currency.
+    pub fn make_from(account_map: &BTreeMap<Vec<u8>, Vec<u8>>) -> Result<Option<Self>> {
+        account_map
+            .get(&currency_balances_resource_path())
+            .map(|bytes| SimpleDeserializer::deserialize(bytes))
+            .transpose()
+    }
+
+    /// Return the coins held by the account, one per accepted currency
+    pub fn coins(&self) -> &[CurrencyCoin] {
+        &self.coins
+    }
+
+    /// Return the balance of the account in `currency_code`, or None if the account doesn't
+    /// accept the currency
+    pub fn balance(&self, currency_code: &[u8]) -> Option<u64> {
+        self.coins
+            .iter()
+            .find(|coin| coin.currency_code.as_bytes() == currency_code)
+            .map(CurrencyCoin::value)
+    }
+
+    /// Return the received_events handle for the given CurrencyBalancesResource
+    pub fn received_events(&self) -> &EventHandle {
+        &self.received_events
+    }
+
+    /// Return the sent_events handle for the given CurrencyBalancesResource
+    pub fn sent_events(&self) -> &EventHandle {
+        &self.sent_events
+    }
+}
+
+impl CanonicalSerialize for CurrencyBalancesResource {
+    fn serialize(&self, mut serializer: &mut impl CanonicalSerializer) -> Result<()> {
+        // The VM serializes vectors as their length followed by their elements, see ValidatorSet.
+        serializer = serializer.encode_u32(self.coins.len() as u32)?;
+        for coin in &self.coins {
+            serializer = serializer.encode_struct(coin)?;
+        }
+        serializer
+            .encode_struct(&self.received_events)?
+            .encode_struct(&self.sent_events)?;
+        Ok(())
+    }
+}
+
+impl CanonicalDeserialize for CurrencyBalancesResource {
+    fn deserialize(deserializer: &mut impl CanonicalDeserializer) -> Result<Self> {
+        let size = deserializer.decode_u32()?;
+        let mut coins = vec![];
+        for _i in 0..size {
+            coins.push(deserializer.decode_struct::<CurrencyCoin>()?);
+        }
+        let received_events = deserializer.decode_struct()?;
+        let sent_events = deserializer.decode_struct()?;
+        Ok(CurrencyBalancesResource::new(
+            coins,
+            received_events,
+            sent_events,
+        ))
+    }
 }
 
 /// Generic struct that represents an Account event.
@@ -261,3 +557,108 @@ impl AccountEvent {
         self.amount
     }
 }
+
+/// Struct that represents a CreateAccountEvent, emitted to the new account.
+#[derive(Debug, Default)]
+pub struct CreateAccountEvent {
+    creator: AccountAddress,
+}
+
+impl CreateAccountEvent {
+    pub fn try_from(bytes: &[u8]) -> Result<CreateAccountEvent> {
+        let mut deserializer = SimpleDeserializer::new(bytes);
+        let creator = deserializer.decode_struct()?;
+        Ok(Self { creator })
+    }
+
+    /// Get the account that sent the transaction creating the account
+    pub fn creator(&self) -> AccountAddress {
+        self.creator
+    }
+}
+
+/// Struct that represents a KeyRotationEvent.
+#[derive(Debug, Default)]
+pub struct KeyRotationEvent {
+    new_authentication_key: ByteArray,
+    rotated_by: AccountAddress,
+}
+
+impl KeyRotationEvent {
+    pub fn try_from(bytes: &[u8]) -> Result<KeyRotationEvent> {
+        let mut deserializer = SimpleDeserializer::new(bytes);
+        let new_authentication_key = deserializer.decode_struct()?;
+        let rotated_by = deserializer.decode_struct()?;
+        Ok(Self {
+            new_authentication_key,
+            rotated_by,
+        })
+    }
+
+    /// Get the new authentication key of the account
+    pub fn new_authentication_key(&self) -> &ByteArray {
+        &self.new_authentication_key
+    }
+
+    /// Get the account that rotated the key: the account itself or the holder of its key
+    /// rotation capability
+    pub fn rotated_by(&self) -> AccountAddress {
+        self.rotated_by
+    }
+}
+
+/// Generic struct that represents a capability event.
+/// Both KeyRotationCapabilityEvent and WithdrawalCapabilityEvent are representable with this
+/// struct. They tell whether the capability was extracted from or restored to the account, and
+/// who did it.
+#[derive(Debug, Default)]
+pub struct CapabilityEvent {
+    delegated: bool,
+    sender: AccountAddress,
+}
+
+impl CapabilityEvent {
+    pub fn try_from(bytes: &[u8]) -> Result<CapabilityEvent> {
+        let mut deserializer = SimpleDeserializer::new(bytes);
+        let delegated = deserializer.decode_bool()?;
+        let sender = deserializer.decode_struct()?;
+        Ok(Self { delegated, sender })
+    }
+
+    /// True if the capability was extracted from the account, false if it was restored
+    pub fn delegated(&self) -> bool {
+        self.delegated
+    }
+
+    /// Get the account that extracted or restored the capability
+    pub fn sender(&self) -> AccountAddress {
+        self.sender
+    }
+}
+
+/// Struct that represents the Block resource: the height and timestamp of the current block.
+/// The withdraw capability for the transaction fees that the resource also holds is not exposed.
+#[derive(Debug, Default)]
+pub struct BlockResource {
+    height: u64,
+    timestamp: u64,
+}
+
+impl BlockResource {
+    pub fn try_from(bytes: &[u8]) -> Result<BlockResource> {
+        let mut deserializer = SimpleDeserializer::new(bytes);
+        let height = deserializer.decode_u64()?;
+        let timestamp = deserializer.decode_u64()?;
+        Ok(Self { height, timestamp })
+    }
+
+    /// Get the height of the current block
+    pub fn height(&self) -> u64 {
+        self.height
+    }
+
+    /// Get the timestamp of the current block, in microseconds since the Unix epoch
+    pub fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+}
@@ -19,7 +19,7 @@ use std::collections::BTreeMap;
 /// 3. Once that special resource is modified, the other user transactions can read the consensus
 ///    info by calling into the read method of that resource, which would thus give users the
 ///    information such as the current leader.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct BlockMetadata {
     id: HashValue,
     timestamp_usec: u64,
@@ -45,6 +45,23 @@ impl BlockMetadata {
         }
     }
 
+    pub fn id(&self) -> HashValue {
+        self.id
+    }
+
+    pub fn timestamp_usec(&self) -> u64 {
+        self.timestamp_usec
+    }
+
+    /// The validators that voted for the previous block, in increasing order of address.
+    pub fn voters(&self) -> Vec<AccountAddress> {
+        self.previous_block_votes.keys().cloned().collect()
+    }
+
+    pub fn proposer(&self) -> AccountAddress {
+        self.proposer
+    }
+
     pub fn into_inner(self) -> Result<(ByteArray, u64, ByteArray, AccountAddress)> {
         let id = ByteArray::new(self.id.to_vec());
         let vote_maps = {
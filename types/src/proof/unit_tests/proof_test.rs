@@ -13,9 +13,7 @@ use crate::{
         SparseMerkleLeafNode, SparseMerkleProof, TestAccumulatorInternalNode,
         TransactionAccumulatorInternalNode,
     },
-    transaction::{
-        RawTransaction, Script, SignedTransaction, TransactionInfo, TransactionListWithProof,
-    },
+    transaction::{RawTransaction, Script, Transaction, TransactionInfo, TransactionListWithProof},
     vm_error::StatusCode,
 };
 use crypto::{
@@ -283,6 +281,7 @@ fn test_verify_signed_transaction() {
         event_root1_hash,
         /* gas_used = */ 0,
         /* major_status = */ StatusCode::EXECUTED,
+        /* location = */ None,
     );
     let txn_info1_hash = txn_info1.hash();
 
@@ -383,6 +382,7 @@ fn test_verify_account_state_and_event() {
         event_root_hash,
         /* gas_used = */ 0,
         /* major_status = */ StatusCode::EXECUTED,
+        /* location = */ None,
     );
     let txn_info2_hash = txn_info2.hash();
 
@@ -477,46 +477,44 @@ fn test_verify_account_state_and_event() {
 
 // Return a variable length of transaction_and_info list with a random range within [0,
 // list_length).
-fn arb_signed_txn_list_and_range(
-) -> impl Strategy<Value = (Vec<(SignedTransaction, TransactionInfo)>, usize, usize)> {
-    vec(
-        (any::<SignedTransaction>(), any::<TransactionInfo>()),
-        0..100,
-    )
-    .prop_flat_map(|list| {
-        let len = list.len();
-        (Just(list), 0..std::cmp::max(len, 1))
-    })
-    .prop_flat_map(|(list, start)| {
-        let len = list.len();
-        (Just(list), Just(start), start..std::cmp::max(len, 1))
-    })
-    .prop_map(|(list, start, end)| {
-        let final_list = list
-            .into_iter()
-            .map(|(txn, txn_info)| {
-                let txn_hash = txn.hash();
-                (
-                    txn,
-                    TransactionInfo::new(
-                        txn_hash,
-                        txn_info.state_root_hash(),
-                        txn_info.event_root_hash(),
-                        txn_info.gas_used(),
-                        txn_info.major_status(),
-                    ),
-                )
-            })
-            .collect::<Vec<_>>();
-        (final_list, start, end)
-    })
+fn arb_txn_list_and_range(
+) -> impl Strategy<Value = (Vec<(Transaction, TransactionInfo)>, usize, usize)> {
+    vec((any::<Transaction>(), any::<TransactionInfo>()), 0..100)
+        .prop_flat_map(|list| {
+            let len = list.len();
+            (Just(list), 0..std::cmp::max(len, 1))
+        })
+        .prop_flat_map(|(list, start)| {
+            let len = list.len();
+            (Just(list), Just(start), start..std::cmp::max(len, 1))
+        })
+        .prop_map(|(list, start, end)| {
+            let final_list = list
+                .into_iter()
+                .map(|(txn, txn_info)| {
+                    let txn_hash = txn.hash();
+                    (
+                        txn,
+                        TransactionInfo::new(
+                            txn_hash,
+                            txn_info.state_root_hash(),
+                            txn_info.event_root_hash(),
+                            txn_info.gas_used(),
+                            txn_info.major_status(),
+                            txn_info.location().cloned(),
+                        ),
+                    )
+                })
+                .collect::<Vec<_>>();
+            (final_list, start, end)
+        })
 }
 
 proptest! {
     #![proptest_config(ProptestConfig::with_cases(20))]
 
     #[test]
-    fn test_transaction_list_with_proof((txn_and_infos, first_version, last_version) in arb_signed_txn_list_and_range()) {
+    fn test_transaction_list_with_proof((txn_and_infos, first_version, last_version) in arb_txn_list_and_range()) {
         let mut root_hash = *ACCUMULATOR_PLACEHOLDER_HASH;
 
         let txn_list_with_proof =
@@ -15,11 +15,11 @@ use crate::{
     proof::{AccumulatorConsistencyProof, AccumulatorProof},
     transaction::{
         Module, Program, RawTransaction, Script, SignatureCheckedTransaction, SignedTransaction,
-        TransactionArgument, TransactionInfo, TransactionListWithProof, TransactionPayload,
-        TransactionStatus, TransactionToCommit, Version,
+        Transaction, TransactionArgument, TransactionInfo, TransactionListWithProof,
+        TransactionPayload, TransactionStatus, TransactionToCommit, Version,
     },
     validator_change::ValidatorChangeEventWithProof,
-    vm_error::{StatusCode, VMStatus},
+    vm_error::{CodeLocation, StatusCode, VMStatus},
     write_set::{WriteOp, WriteSet, WriteSetMut},
 };
 use crypto::{
@@ -421,6 +421,9 @@ impl Arbitrary for StatusCode {
             Just(StatusCode::MAX_GAS_UNITS_BELOW_MIN_TRANSACTION_GAS_UNITS),
             Just(StatusCode::GAS_UNIT_PRICE_BELOW_MIN_BOUND),
             Just(StatusCode::GAS_UNIT_PRICE_ABOVE_MAX_BOUND),
+            Just(StatusCode::NONCE_ALREADY_USED),
+            Just(StatusCode::NONCES_NOT_ENABLED),
+            Just(StatusCode::SENDING_ACCOUNT_FROZEN),
         ]
         .boxed()
     }
@@ -608,6 +611,7 @@ struct AccountResourceGen {
     balance: u64,
     delegated_key_rotation_capability: bool,
     delegated_withdrawal_capability: bool,
+    is_frozen: bool,
 }
 
 impl AccountResourceGen {
@@ -624,8 +628,13 @@ impl AccountResourceGen {
             ByteArray::new(account_info.public_key.to_bytes().to_vec()),
             self.delegated_key_rotation_capability,
             self.delegated_withdrawal_capability,
+            self.is_frozen,
             account_info.sent_event_handle.clone(),
             account_info.received_event_handle.clone(),
+            EventHandle::new_from_address(&account_info.address, 2),
+            EventHandle::new_from_address(&account_info.address, 3),
+            EventHandle::new_from_address(&account_info.address, 4),
+            EventHandle::new_from_address(&account_info.address, 5),
         )
     }
 }
@@ -720,6 +729,8 @@ pub struct TransactionToCommitGen {
     gas_used: u64,
     /// Transaction status
     major_status: StatusCode,
+    /// Location of the status
+    location: Option<CodeLocation>,
 }
 
 impl TransactionToCommitGen {
@@ -747,11 +758,12 @@ impl TransactionToCommitGen {
             .collect();
 
         TransactionToCommit::new(
-            signed_txn,
+            Transaction::UserTransaction(signed_txn),
             account_states,
             events,
             self.gas_used,
             self.major_status,
+            self.location,
         )
     }
 }
@@ -777,9 +789,17 @@ impl Arbitrary for TransactionToCommitGen {
             vec((any::<Index>(), any::<AccountStateBlobGen>()), 0..=1),
             any::<u64>(),
             any::<StatusCode>(),
+            any::<Option<CodeLocation>>(),
         )
             .prop_map(
-                |(sender, event_emitters, mut touched_accounts, gas_used, major_status)| {
+                |(
+                    sender,
+                    event_emitters,
+                    mut touched_accounts,
+                    gas_used,
+                    major_status,
+                    location,
+                )| {
                     // To reflect change of account/event sequence numbers, txn sender account and
                     // event emitter accounts must be updated.
                     let (sender_index, sender_blob_gen, txn_gen) = sender;
@@ -797,6 +817,7 @@ impl Arbitrary for TransactionToCommitGen {
                         account_state_gens: touched_accounts,
                         gas_used,
                         major_status,
+                        location,
                     }
                 },
             )
@@ -806,10 +827,24 @@ impl Arbitrary for TransactionToCommitGen {
     type Strategy = BoxedStrategy<Self>;
 }
 
+impl Arbitrary for Transaction {
+    type Parameters = ();
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            any::<SignedTransaction>().prop_map(Transaction::UserTransaction),
+            any::<WriteSet>().prop_map(Transaction::WriteSet),
+            any::<BlockMetadata>().prop_map(Transaction::BlockMetadata),
+        ]
+        .boxed()
+    }
+
+    type Strategy = BoxedStrategy<Self>;
+}
+
 fn arb_transaction_list_with_proof() -> impl Strategy<Value = TransactionListWithProof> {
     vec(
         (
-            any::<SignedTransaction>(),
+            any::<Transaction>(),
             any::<TransactionInfo>(),
             vec(any::<ContractEvent>(), 0..10),
         ),
@@ -11,7 +11,7 @@ use crate::{
         get_accumulator_root_hash, verify_signed_transaction, verify_transaction_list,
         AccumulatorProof, SignedTransactionProof,
     },
-    vm_error::{StatusCode, StatusType, VMStatus},
+    vm_error::{CodeLocation, StatusCode, StatusType, VMStatus},
     write_set::WriteSet,
 };
 use canonical_serialization::{
@@ -22,8 +22,9 @@ use crypto::{
     ed25519::*,
     hash::{
         CryptoHash, CryptoHasher, EventAccumulatorHasher, RawTransactionHasher,
-        SignedTransactionHasher, TransactionInfoHasher,
+        SignedTransactionHasher, TransactionHasher, TransactionInfoHasher,
     },
+    multi_ed25519::{MultiEd25519PublicKey, MultiEd25519Signature},
     traits::*,
     HashValue,
 };
@@ -57,6 +58,27 @@ pub type Version = u64; // Height - also used for MVCC in StateDB
 
 pub const MAX_TRANSACTION_SIZE_IN_BYTES: usize = 4096;
 
+/// A transaction whose sequence number has this bit set is a nonce transaction: instead of having
+/// to use the next sequence number of its sender, it carries a nonce in the remaining bits that
+/// can be used only once. Nonce transactions can be executed in any order, and are only accepted
+/// by accounts that enabled them.
+pub const NONCE_FLAG: u64 = 1 << 63;
+
+/// Returns the nonce carried by `sequence_number` if it belongs to a nonce transaction.
+pub fn nonce_from_sequence_number(sequence_number: u64) -> Option<u64> {
+    if sequence_number & NONCE_FLAG == 0 {
+        None
+    } else {
+        Some(sequence_number & !NONCE_FLAG)
+    }
+}
+
+/// Returns the sequence number of a nonce transaction using `nonce`.
+pub fn sequence_number_from_nonce(nonce: u64) -> u64 {
+    assert!(nonce < NONCE_FLAG, "nonce {} is out of range", nonce);
+    nonce | NONCE_FLAG
+}
+
 /// RawTransaction is the portion of a transaction that a client signs
 #[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
 pub struct RawTransaction {
@@ -272,88 +294,30 @@ impl CanonicalDeserialize for RawTransaction {
     }
 }
 
-#[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(
+    Clone,
+    Debug,
+    Hash,
+    Eq,
+    PartialEq,
+    Serialize,
+    Deserialize,
+    CanonicalSerialize,
+    CanonicalDeserialize,
+)]
+// The canonical indexes of `Module` and `Script` do not follow their declaration order.
 pub enum TransactionPayload {
     /// A regular programmatic transaction that is executed by the VM.
     Program(Program),
     WriteSet(WriteSet),
     /// A transaction that publishes code.
+    #[canonical(index = 3)]
     Module(Module),
     /// A transaction that executes code.
+    #[canonical(index = 2)]
     Script(Script),
 }
 
-impl CanonicalSerialize for TransactionPayload {
-    fn serialize(&self, serializer: &mut impl CanonicalSerializer) -> Result<()> {
-        match self {
-            TransactionPayload::Program(program) => {
-                serializer.encode_u32(TransactionPayloadType::Program as u32)?;
-                serializer.encode_struct(program)?;
-            }
-            TransactionPayload::WriteSet(write_set) => {
-                serializer.encode_u32(TransactionPayloadType::WriteSet as u32)?;
-                serializer.encode_struct(write_set)?;
-            }
-            TransactionPayload::Script(script) => {
-                serializer.encode_u32(TransactionPayloadType::Script as u32)?;
-                serializer.encode_struct(script)?;
-            }
-            TransactionPayload::Module(module) => {
-                serializer.encode_u32(TransactionPayloadType::Module as u32)?;
-                serializer.encode_struct(module)?;
-            }
-        };
-        Ok(())
-    }
-}
-
-impl CanonicalDeserialize for TransactionPayload {
-    fn deserialize(deserializer: &mut impl CanonicalDeserializer) -> Result<Self> {
-        let decoded_payload_type = deserializer.decode_u32()?;
-        let payload_type = TransactionPayloadType::from_u32(decoded_payload_type);
-        match payload_type {
-            Some(TransactionPayloadType::Program) => {
-                Ok(TransactionPayload::Program(deserializer.decode_struct()?))
-            }
-            Some(TransactionPayloadType::WriteSet) => {
-                Ok(TransactionPayload::WriteSet(deserializer.decode_struct()?))
-            }
-            Some(TransactionPayloadType::Script) => {
-                Ok(TransactionPayload::Script(deserializer.decode_struct()?))
-            }
-            Some(TransactionPayloadType::Module) => {
-                Ok(TransactionPayload::Module(deserializer.decode_struct()?))
-            }
-            None => Err(format_err!(
-                "ParseError: Unable to decode TransactionPayloadType, found {}",
-                decoded_payload_type
-            )),
-        }
-    }
-}
-
-#[derive(Clone, Debug, Hash, Eq, PartialEq)]
-enum TransactionPayloadType {
-    Program = 0,
-    WriteSet = 1,
-    Script = 2,
-    Module = 3,
-}
-
-impl TransactionPayloadType {
-    fn from_u32(value: u32) -> Option<TransactionPayloadType> {
-        match value {
-            0 => Some(TransactionPayloadType::Program),
-            1 => Some(TransactionPayloadType::WriteSet),
-            2 => Some(TransactionPayloadType::Script),
-            3 => Some(TransactionPayloadType::Module),
-            _ => None,
-        }
-    }
-}
-
-impl ::std::marker::Copy for TransactionPayloadType {}
-
 /// A transaction that has been signed.
 ///
 /// A `SignedTransaction` is a single transaction that can be atomically executed. Clients submit
@@ -367,12 +331,10 @@ pub struct SignedTransaction {
     /// The raw transaction
     raw_txn: RawTransaction,
 
-    /// Sender's public key. When checking the signature, we first need to check whether this key
-    /// is indeed the pre-image of the pubkey hash stored under sender's account.
-    public_key: Ed25519PublicKey,
-
-    /// Signature of the transaction that correspond to the public key
-    signature: Ed25519Signature,
+    /// Sender's public key and signature of the transaction. When checking the signature, we
+    /// first need to check whether the key is indeed the pre-image of the pubkey hash stored under
+    /// sender's account.
+    authenticator: TransactionAuthenticator,
 
     /// The transaction length is used by the VM to limit the size of transactions
     transaction_length: usize,
@@ -409,11 +371,10 @@ impl fmt::Debug for SignedTransaction {
             f,
             "SignedTransaction {{ \n \
              {{ raw_txn: {:#?}, \n \
-             public_key: {:#?}, \n \
-             signature: {:#?}, \n \
+             authenticator: {:#?}, \n \
              }} \n \
              }}",
-            self.raw_txn, self.public_key, self.signature,
+            self.raw_txn, self.authenticator,
         )
     }
 }
@@ -423,25 +384,48 @@ impl SignedTransaction {
         raw_txn: RawTransaction,
         public_key: Ed25519PublicKey,
         signature: Ed25519Signature,
+    ) -> SignedTransaction {
+        Self::new_with_authenticator(
+            raw_txn,
+            TransactionAuthenticator::Ed25519 {
+                public_key,
+                signature,
+            },
+        )
+    }
+
+    /// Creates a transaction signed by at least `threshold` keys of a multi-signature key.
+    pub fn new_multisig(
+        raw_txn: RawTransaction,
+        public_key: MultiEd25519PublicKey,
+        signature: MultiEd25519Signature,
+    ) -> SignedTransaction {
+        Self::new_with_authenticator(
+            raw_txn,
+            TransactionAuthenticator::MultiEd25519 {
+                public_key,
+                signature,
+            },
+        )
+    }
+
+    pub fn new_with_authenticator(
+        raw_txn: RawTransaction,
+        authenticator: TransactionAuthenticator,
     ) -> SignedTransaction {
         let transaction_length = SimpleSerializer::<Vec<u8>>::serialize(&raw_txn)
             .expect("Unable to serialize RawTransaction")
             .len();
 
         SignedTransaction {
-            raw_txn: raw_txn.clone(),
-            public_key,
-            signature,
+            raw_txn,
+            authenticator,
             transaction_length,
         }
     }
 
-    pub fn public_key(&self) -> Ed25519PublicKey {
-        self.public_key.clone()
-    }
-
-    pub fn signature(&self) -> Ed25519Signature {
-        self.signature.clone()
+    pub fn authenticator(&self) -> &TransactionAuthenticator {
+        &self.authenticator
     }
 
     pub fn sender(&self) -> AccountAddress {
@@ -456,6 +440,11 @@ impl SignedTransaction {
         self.raw_txn.sequence_number
     }
 
+    /// Returns the nonce of this transaction if it is a nonce transaction.
+    pub fn nonce(&self) -> Option<u64> {
+        nonce_from_sequence_number(self.raw_txn.sequence_number)
+    }
+
     pub fn payload(&self) -> &TransactionPayload {
         &self.raw_txn.payload
     }
@@ -479,8 +468,7 @@ impl SignedTransaction {
     /// Checks that the signature of given transaction. Returns `Ok(SignatureCheckedTransaction)` if
     /// the signature is valid.
     pub fn check_signature(self) -> Result<SignatureCheckedTransaction> {
-        self.public_key
-            .verify_signature(&self.raw_txn.hash(), &self.signature)?;
+        self.authenticator.verify_signature(&self.raw_txn.hash())?;
         Ok(SignatureCheckedTransaction(self))
     }
 
@@ -488,12 +476,10 @@ impl SignedTransaction {
         format!(
             "SignedTransaction {{ \n \
              raw_txn: {}, \n \
-             public_key: {:#?}, \n \
-             signature: {:#?}, \n \
+             authenticator: {:#?}, \n \
              }}",
             self.raw_txn.format_for_client(get_transaction_name),
-            self.public_key,
-            self.signature,
+            self.authenticator,
         )
     }
 }
@@ -641,8 +627,7 @@ impl CanonicalSerialize for SignedTransaction {
     fn serialize(&self, serializer: &mut impl CanonicalSerializer) -> Result<()> {
         serializer
             .encode_struct(&self.raw_txn)?
-            .encode_struct(&self.public_key)?
-            .encode_struct(&self.signature)?;
+            .encode_struct(&self.authenticator)?;
         Ok(())
     }
 }
@@ -653,10 +638,107 @@ impl CanonicalDeserialize for SignedTransaction {
         Self: Sized,
     {
         let raw_txn: RawTransaction = deserializer.decode_struct()?;
-        let public_key: Ed25519PublicKey = deserializer.decode_struct()?;
-        let signature: Ed25519Signature = deserializer.decode_struct()?;
+        let authenticator: TransactionAuthenticator = deserializer.decode_struct()?;
 
-        Ok(SignedTransaction::new(raw_txn, public_key, signature))
+        Ok(SignedTransaction::new_with_authenticator(
+            raw_txn,
+            authenticator,
+        ))
+    }
+}
+
+/// The public key of the sender of a transaction along with the signature of the transaction by
+/// that key.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum TransactionAuthenticator {
+    /// A single Ed25519 signature.
+    Ed25519 {
+        public_key: Ed25519PublicKey,
+        signature: Ed25519Signature,
+    },
+    /// A K-of-N multi-signature, valid if at least K of the N keys signed the transaction.
+    MultiEd25519 {
+        public_key: MultiEd25519PublicKey,
+        signature: MultiEd25519Signature,
+    },
+}
+
+impl TransactionAuthenticator {
+    /// Checks that the signature of `message` is valid for the public key.
+    pub fn verify_signature(&self, message: &HashValue) -> Result<()> {
+        match self {
+            TransactionAuthenticator::Ed25519 {
+                public_key,
+                signature,
+            } => public_key.verify_signature(message, signature),
+            TransactionAuthenticator::MultiEd25519 {
+                public_key,
+                signature,
+            } => public_key.verify_signature(message, signature),
+        }
+    }
+
+    /// The bytes of the public key. The authentication key of the sender must be their SHA3-256
+    /// hash. Single and multi-signature keys have different lengths, so no account can be
+    /// authenticated by both kinds of keys.
+    pub fn public_key_bytes(&self) -> Vec<u8> {
+        match self {
+            TransactionAuthenticator::Ed25519 { public_key, .. } => public_key.to_bytes().to_vec(),
+            TransactionAuthenticator::MultiEd25519 { public_key, .. } => public_key.to_bytes(),
+        }
+    }
+
+    /// The bytes of the signature.
+    pub fn signature_bytes(&self) -> Vec<u8> {
+        match self {
+            TransactionAuthenticator::Ed25519 { signature, .. } => signature.to_bytes().to_vec(),
+            TransactionAuthenticator::MultiEd25519 { signature, .. } => signature.to_bytes(),
+        }
+    }
+}
+
+/// The authenticator is encoded without a variant tag so that single-signature transactions keep
+/// the `public_key` followed by `signature` layout they had before multi-signatures existed. The
+/// variant is recovered from the length of the public key, which is never
+/// `ED25519_PUBLIC_KEY_LENGTH` for a multi-signature key (`32 * N + 1` bytes).
+impl CanonicalSerialize for TransactionAuthenticator {
+    fn serialize(&self, serializer: &mut impl CanonicalSerializer) -> Result<()> {
+        match self {
+            TransactionAuthenticator::Ed25519 {
+                public_key,
+                signature,
+            } => serializer
+                .encode_struct(public_key)?
+                .encode_struct(signature)?,
+            TransactionAuthenticator::MultiEd25519 {
+                public_key,
+                signature,
+            } => serializer
+                .encode_struct(public_key)?
+                .encode_struct(signature)?,
+        };
+        Ok(())
+    }
+}
+
+impl CanonicalDeserialize for TransactionAuthenticator {
+    fn deserialize(deserializer: &mut impl CanonicalDeserializer) -> Result<Self>
+    where
+        Self: Sized,
+    {
+        let public_key_bytes = deserializer.decode_bytes()?;
+        let signature_bytes = deserializer.decode_bytes()?;
+        if public_key_bytes.len() == ED25519_PUBLIC_KEY_LENGTH {
+            Ok(TransactionAuthenticator::Ed25519 {
+                public_key: Ed25519PublicKey::try_from(&public_key_bytes[..])?,
+                signature: Ed25519Signature::try_from(&signature_bytes[..])?,
+            })
+        } else {
+            Ok(TransactionAuthenticator::MultiEd25519 {
+                public_key: MultiEd25519PublicKey::try_from(&public_key_bytes[..])?,
+                signature: MultiEd25519Signature::try_from(&signature_bytes[..])?,
+            })
+        }
     }
 }
 
@@ -770,12 +852,17 @@ impl TryFrom<crate::proto::types::TransactionInfo> for TransactionInfo {
         let gas_used = proto_txn_info.gas_used;
         let major_status =
             StatusCode::try_from(proto_txn_info.major_status).unwrap_or(StatusCode::UNKNOWN_STATUS);
+        let location = proto_txn_info
+            .location
+            .map(CodeLocation::try_from)
+            .transpose()?;
         Ok(TransactionInfo::new(
             signed_txn_hash,
             state_root_hash,
             event_root_hash,
             gas_used,
             major_status,
+            location,
         ))
     }
 }
@@ -788,6 +875,7 @@ impl From<TransactionInfo> for crate::proto::types::TransactionInfo {
             event_root_hash: txn_info.event_root_hash.to_vec(),
             gas_used: txn_info.gas_used,
             major_status: txn_info.major_status.into(),
+            location: txn_info.location.map(Into::into),
         }
     }
 }
@@ -814,6 +902,10 @@ pub struct TransactionInfo {
     /// particularly high fidelity in the presence of sub statuses but, the major status does
     /// determine whether or not the transaction is applied to the global state or not.
     major_status: StatusCode,
+
+    /// Where in Move code execution stopped, if the major status has a location, e.g. the
+    /// function that aborted.
+    location: Option<CodeLocation>,
 }
 
 impl TransactionInfo {
@@ -825,6 +917,7 @@ impl TransactionInfo {
         event_root_hash: HashValue,
         gas_used: u64,
         major_status: StatusCode,
+        location: Option<CodeLocation>,
     ) -> TransactionInfo {
         TransactionInfo {
             signed_transaction_hash,
@@ -832,6 +925,7 @@ impl TransactionInfo {
             event_root_hash,
             gas_used,
             major_status,
+            location,
         }
     }
 
@@ -860,6 +954,11 @@ impl TransactionInfo {
     pub fn major_status(&self) -> StatusCode {
         self.major_status
     }
+
+    /// Returns where in Move code execution stopped, if the major status has a location.
+    pub fn location(&self) -> Option<&CodeLocation> {
+        self.location.as_ref()
+    }
 }
 
 impl CanonicalSerialize for TransactionInfo {
@@ -869,7 +968,8 @@ impl CanonicalSerialize for TransactionInfo {
             .encode_bytes(self.state_root_hash.as_ref())?
             .encode_bytes(self.event_root_hash.as_ref())?
             .encode_u64(self.gas_used)?
-            .encode_u64(self.major_status.into())?;
+            .encode_u64(self.major_status.into())?
+            .encode_optional(&self.location)?;
         Ok(())
     }
 }
@@ -888,32 +988,35 @@ impl CryptoHash for TransactionInfo {
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct TransactionToCommit {
-    signed_txn: SignedTransaction,
+    transaction: Transaction,
     account_states: HashMap<AccountAddress, AccountStateBlob>,
     events: Vec<ContractEvent>,
     gas_used: u64,
     major_status: StatusCode,
+    location: Option<CodeLocation>,
 }
 
 impl TransactionToCommit {
     pub fn new(
-        signed_txn: SignedTransaction,
+        transaction: Transaction,
         account_states: HashMap<AccountAddress, AccountStateBlob>,
         events: Vec<ContractEvent>,
         gas_used: u64,
         major_status: StatusCode,
+        location: Option<CodeLocation>,
     ) -> Self {
         TransactionToCommit {
-            signed_txn,
+            transaction,
             account_states,
             events,
             gas_used,
             major_status,
+            location,
         }
     }
 
-    pub fn signed_txn(&self) -> &SignedTransaction {
-        &self.signed_txn
+    pub fn transaction(&self) -> &Transaction {
+        &self.transaction
     }
 
     pub fn account_states(&self) -> &HashMap<AccountAddress, AccountStateBlob> {
@@ -931,15 +1034,19 @@ impl TransactionToCommit {
     pub fn major_status(&self) -> StatusCode {
         self.major_status
     }
+
+    pub fn location(&self) -> Option<&CodeLocation> {
+        self.location.as_ref()
+    }
 }
 
 impl TryFrom<crate::proto::types::TransactionToCommit> for TransactionToCommit {
     type Error = Error;
 
     fn try_from(proto: crate::proto::types::TransactionToCommit) -> Result<Self> {
-        let signed_txn = proto
-            .signed_txn
-            .ok_or_else(|| format_err!("Missing signed_transaction"))?
+        let transaction = proto
+            .transaction
+            .ok_or_else(|| format_err!("Missing transaction"))?
             .try_into()?;
         let num_account_states = proto.account_states.len();
         let account_states = proto
@@ -964,13 +1071,15 @@ impl TryFrom<crate::proto::types::TransactionToCommit> for TransactionToCommit {
         let gas_used = proto.gas_used;
         let major_status =
             StatusCode::try_from(proto.major_status).unwrap_or(StatusCode::UNKNOWN_STATUS);
+        let location = proto.location.map(CodeLocation::try_from).transpose()?;
 
         Ok(TransactionToCommit {
-            signed_txn,
+            transaction,
             account_states,
             events,
             gas_used,
             major_status,
+            location,
         })
     }
 }
@@ -978,7 +1087,7 @@ impl TryFrom<crate::proto::types::TransactionToCommit> for TransactionToCommit {
 impl From<TransactionToCommit> for crate::proto::types::TransactionToCommit {
     fn from(txn: TransactionToCommit) -> Self {
         Self {
-            signed_txn: Some(txn.signed_txn.into()),
+            transaction: Some(txn.transaction.into()),
             account_states: txn
                 .account_states
                 .into_iter()
@@ -990,6 +1099,7 @@ impl From<TransactionToCommit> for crate::proto::types::TransactionToCommit {
             events: txn.events.into_iter().map(Into::into).collect(),
             gas_used: txn.gas_used,
             major_status: txn.major_status.into(),
+            location: txn.location.map(Into::into),
         }
     }
 }
@@ -1001,7 +1111,7 @@ impl From<TransactionToCommit> for crate::proto::types::TransactionToCommit {
 /// 3. The list has 2+ transactions/transaction_infos. The both proofs must exist.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct TransactionListWithProof {
-    pub transaction_and_infos: Vec<(SignedTransaction, TransactionInfo)>,
+    pub transaction_and_infos: Vec<(Transaction, TransactionInfo)>,
     pub events: Option<Vec<Vec<ContractEvent>>>,
     pub first_transaction_version: Option<Version>,
     pub proof_of_first_transaction: Option<AccumulatorProof>,
@@ -1011,7 +1121,7 @@ pub struct TransactionListWithProof {
 impl TransactionListWithProof {
     /// Constructor.
     pub fn new(
-        transaction_and_infos: Vec<(SignedTransaction, TransactionInfo)>,
+        transaction_and_infos: Vec<(Transaction, TransactionInfo)>,
         events: Option<Vec<Vec<ContractEvent>>>,
         first_transaction_version: Option<Version>,
         proof_of_first_transaction: Option<AccumulatorProof>,
@@ -1124,7 +1234,7 @@ impl TryFrom<crate::proto::types::TransactionListWithProof> for TransactionListW
             itertools::zip_eq(proto.transactions.into_iter(), proto.infos.into_iter())
                 .map(|(txn, info)| {
                     Ok((
-                        SignedTransaction::try_from(txn)?,
+                        Transaction::try_from(txn)?,
                         TransactionInfo::try_from(info)?,
                     ))
                 })
@@ -1192,6 +1302,7 @@ impl From<TransactionListWithProof> for crate::proto::types::TransactionListWith
 /// We suppress the clippy warning here as we would expect most of the transaction to be user
 /// transaction.
 #[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
 pub enum Transaction {
     /// Transaction submitted by the user. e.g: P2P payment transaction, publishing module
     /// transaction, etc.
@@ -1206,3 +1317,49 @@ pub enum Transaction {
     /// Transaction to update the block metadata resource at the beginning of a block.
     BlockMetadata(BlockMetadata),
 }
+
+impl Transaction {
+    /// Returns the user transaction, or an error for a transaction generated by the system.
+    pub fn as_signed_user_txn(&self) -> Result<&SignedTransaction> {
+        match self {
+            Transaction::UserTransaction(txn) => Ok(txn),
+            _ => Err(format_err!("Not a user transaction.")),
+        }
+    }
+}
+
+impl CryptoHash for Transaction {
+    type Hasher = TransactionHasher;
+
+    /// User transactions keep the hash of the `SignedTransaction`, so that the proofs of
+    /// `SignedTransactionWithProof` can be checked against the same `TransactionInfo`.
+    fn hash(&self) -> HashValue {
+        match self {
+            Transaction::UserTransaction(txn) => txn.hash(),
+            _ => {
+                let mut state = Self::Hasher::default();
+                state.write(
+                    &SimpleSerializer::<Vec<u8>>::serialize(self)
+                        .expect("Serialization should work."),
+                );
+                state.finish()
+            }
+        }
+    }
+}
+
+impl TryFrom<crate::proto::types::Transaction> for Transaction {
+    type Error = Error;
+
+    fn try_from(proto: crate::proto::types::Transaction) -> Result<Self> {
+        SimpleDeserializer::deserialize(&proto.transaction)
+    }
+}
+
+impl From<Transaction> for crate::proto::types::Transaction {
+    fn from(txn: Transaction) -> Self {
+        let transaction =
+            SimpleSerializer::<Vec<u8>>::serialize(&txn).expect("Unable to serialize Transaction");
+        Self { transaction }
+    }
+}
@@ -1,14 +1,13 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use canonical_serialization::{
-    CanonicalDeserialize, CanonicalDeserializer, CanonicalSerialize, CanonicalSerializer,
-};
-use failure::prelude::*;
+use canonical_serialization::{CanonicalDeserialize, CanonicalSerialize};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[derive(Clone, Hash, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(
+    Clone, Hash, Eq, PartialEq, Serialize, Deserialize, CanonicalSerialize, CanonicalDeserialize,
+)]
 pub struct Module {
     code: Vec<u8>,
 }
@@ -34,17 +33,3 @@ impl fmt::Debug for Module {
             .finish()
     }
 }
-
-impl CanonicalSerialize for Module {
-    fn serialize(&self, serializer: &mut impl CanonicalSerializer) -> Result<()> {
-        serializer.encode_vec(&self.code)?;
-        Ok(())
-    }
-}
-
-impl CanonicalDeserialize for Module {
-    fn deserialize(deserializer: &mut impl CanonicalDeserializer) -> Result<Self> {
-        let code: Vec<u8> = deserializer.decode_vec()?;
-        Ok(Module::new(code))
-    }
-}
@@ -2,14 +2,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::transaction::transaction_argument::TransactionArgument;
-use canonical_serialization::{
-    CanonicalDeserialize, CanonicalDeserializer, CanonicalSerialize, CanonicalSerializer,
-};
-use failure::prelude::*;
+use canonical_serialization::{CanonicalDeserialize, CanonicalSerialize};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[derive(Clone, Hash, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(
+    Clone, Hash, Eq, PartialEq, Serialize, Deserialize, CanonicalSerialize, CanonicalDeserialize,
+)]
 pub struct Program {
     code: Vec<u8>,
     args: Vec<TransactionArgument>,
@@ -50,22 +49,3 @@ impl fmt::Debug for Program {
             .finish()
     }
 }
-
-impl CanonicalSerialize for Program {
-    fn serialize(&self, serializer: &mut impl CanonicalSerializer) -> Result<()> {
-        serializer.encode_vec(&self.code)?;
-        serializer.encode_vec(&self.args)?;
-        serializer.encode_vec(&self.modules)?;
-        Ok(())
-    }
-}
-
-impl CanonicalDeserialize for Program {
-    fn deserialize(deserializer: &mut impl CanonicalDeserializer) -> Result<Self> {
-        let code: Vec<u8> = deserializer.decode_vec()?;
-        let args: Vec<TransactionArgument> = deserializer.decode_vec()?;
-        let modules: Vec<Vec<u8>> = deserializer.decode_vec()?;
-
-        Ok(Program::new(code, modules, args))
-    }
-}
@@ -2,17 +2,16 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::transaction::transaction_argument::TransactionArgument;
-use canonical_serialization::{
-    CanonicalDeserialize, CanonicalDeserializer, CanonicalSerialize, CanonicalSerializer,
-};
-use failure::prelude::*;
+use canonical_serialization::{CanonicalDeserialize, CanonicalSerialize};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
 #[allow(dead_code)]
 pub const SCRIPT_HASH_LENGTH: usize = 32;
 
-#[derive(Clone, Hash, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(
+    Clone, Hash, Eq, PartialEq, Serialize, Deserialize, CanonicalSerialize, CanonicalDeserialize,
+)]
 pub struct Script {
     code: Vec<u8>,
     args: Vec<TransactionArgument>,
@@ -44,19 +43,3 @@ impl fmt::Debug for Script {
             .finish()
     }
 }
-
-impl CanonicalSerialize for Script {
-    fn serialize(&self, serializer: &mut impl CanonicalSerializer) -> Result<()> {
-        serializer.encode_vec(&self.code)?;
-        serializer.encode_vec(&self.args)?;
-        Ok(())
-    }
-}
-
-impl CanonicalDeserialize for Script {
-    fn deserialize(deserializer: &mut impl CanonicalDeserializer) -> Result<Self> {
-        let code: Vec<u8> = deserializer.decode_vec()?;
-        let args: Vec<TransactionArgument> = deserializer.decode_vec()?;
-        Ok(Script::new(code, args))
-    }
-}
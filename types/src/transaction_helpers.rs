@@ -25,7 +25,7 @@ pub fn get_signed_transactions_digest(signed_txns: &[ProtoSignedTransaction]) ->
         let signed_txn: SignedTransaction =
             SimpleDeserializer::deserialize(&transaction.signed_txn)
                 .expect("Unable to deserialize SignedTransaction");
-        signatures.extend_from_slice(&signed_txn.signature().to_bytes());
+        signatures.extend_from_slice(&signed_txn.authenticator().signature_bytes());
     }
     signatures.test_only_hash()
 }
@@ -8,8 +8,14 @@ use crate::{
 use canonical_serialization::{
     CanonicalDeserializer, CanonicalSerializer, SimpleDeserializer, SimpleSerializer,
 };
-use crypto::ed25519::*;
+use crypto::{
+    ed25519::*,
+    hash::CryptoHash,
+    multi_ed25519::{MultiEd25519PublicKey, MultiEd25519Signature},
+    traits::{SigningKey, ValidKey},
+};
 use proptest::prelude::*;
+use rand::{rngs::StdRng, SeedableRng};
 use std::convert::TryFrom;
 
 #[test]
@@ -34,6 +40,54 @@ fn test_invalid_signature() {
         .expect_err("signature checking should fail");
 }
 
+#[test]
+fn test_multisig() {
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    let keypairs: Vec<_> = (0..3).map(|_| compat::generate_keypair(&mut rng)).collect();
+    let public_key = MultiEd25519PublicKey::new(
+        keypairs
+            .iter()
+            .map(|(_, public_key)| public_key.clone())
+            .collect(),
+        2,
+    )
+    .unwrap();
+    let raw_txn = RawTransaction::new_script(
+        AccountAddress::from_public_key(&public_key),
+        0,
+        Script::new(vec![], vec![]),
+        0,
+        0,
+        std::time::Duration::new(0, 0),
+    );
+    let sign = |indexes: &[u8]| {
+        let signatures = indexes
+            .iter()
+            .map(|i| (keypairs[*i as usize].0.sign_message(&raw_txn.hash()), *i))
+            .collect();
+        SignedTransaction::new_multisig(
+            raw_txn.clone(),
+            public_key.clone(),
+            MultiEd25519Signature::new(signatures).unwrap(),
+        )
+    };
+
+    let txn = sign(&[0, 2]);
+    let bytes = SimpleSerializer::<Vec<u8>>::serialize(&txn).unwrap();
+    let deserialized: SignedTransaction = SimpleDeserializer::deserialize(&bytes).unwrap();
+    assert_eq!(deserialized, txn);
+    assert_eq!(
+        txn.authenticator().public_key_bytes(),
+        public_key.to_bytes()
+    );
+    txn.check_signature()
+        .expect("signature checking should succeed");
+
+    sign(&[1])
+        .check_signature()
+        .expect_err("signature checking should fail below the threshold");
+}
+
 proptest! {
     #[test]
     fn test_sig(raw_txn in any::<RawTransaction>(), (sk1, pk1) in compat::keypair_strategy()) {
@@ -1,7 +1,8 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::vm_error::{StatusCode, VMStatus};
+use crate::vm_error::{CodeLocation, StatusCode, VMStatus};
+use proptest::prelude::*;
 use prost_ext::test_helpers::assert_protobuf_encode_decode;
 use std::convert::TryFrom;
 
@@ -19,3 +20,13 @@ fn status_roundtrip() {
         assert_protobuf_encode_decode::<crate::proto::types::VmStatus, VMStatus>(&status);
     }
 }
+
+proptest! {
+    #[test]
+    fn status_with_location_roundtrip(location in any::<CodeLocation>()) {
+        let status = VMStatus::new(StatusCode::ABORTED)
+            .with_sub_status(7)
+            .with_location(location);
+        assert_protobuf_encode_decode::<crate::proto::types::VmStatus, VMStatus>(&status);
+    }
+}
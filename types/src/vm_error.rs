@@ -3,6 +3,8 @@
 
 #![allow(clippy::unit_arg)]
 
+use crate::{identifier::Identifier, language_storage::ModuleId};
+use canonical_serialization::{CanonicalSerialize, CanonicalSerializer};
 use failure::prelude::*;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 #[cfg(any(test, feature = "testing"))]
@@ -58,6 +60,71 @@ pub struct VMStatus {
     /// The optional message. Useful for verification errors, and for returning information in
     /// validation.
     pub message: Option<String>,
+
+    /// The optional location in Move code the status refers to: where execution stopped, e.g.
+    /// the function that aborted or overflowed, or the instruction a verification error is about.
+    pub location: Option<CodeLocation>,
+}
+
+/// A point in Move code.
+///
+/// The VM records the module, the function name and the call stack depth of the code it was
+/// executing. The bytecode verifier only knows the function definition index within the module
+/// being verified, once it has been added to the error.
+#[derive(Clone, Default, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Serialize, Deserialize)]
+#[cfg_attr(any(test, feature = "testing"), derive(Arbitrary))]
+#[cfg_attr(any(test, feature = "testing"), proptest(no_params))]
+pub struct CodeLocation {
+    /// The module that defines the function, if known.
+    pub module: Option<ModuleId>,
+
+    /// The name of the function, if known.
+    pub function: Option<Identifier>,
+
+    /// The index of the function definition in its module, if known.
+    pub function_definition_index: Option<u16>,
+
+    /// The offset of the instruction in the function's code.
+    pub code_offset: u16,
+
+    /// The number of frames on the call stack, counting the entry function as 1. It is 0 if the
+    /// location was not recorded while executing code.
+    pub call_stack_depth: u64,
+}
+
+impl fmt::Display for CodeLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(module) = &self.module {
+            write!(f, "{}::{}::", module.address(), module.name())?;
+        }
+        match (&self.function, self.function_definition_index) {
+            (Some(function), _) => write!(f, "{}", function)?,
+            (None, Some(idx)) => write!(f, "function #{}", idx)?,
+            (None, None) => write!(f, "<unknown function>")?,
+        }
+        write!(f, " at offset {}", self.code_offset)?;
+        if self.call_stack_depth > 0 {
+            write!(f, " (call stack depth {})", self.call_stack_depth)?;
+        }
+        Ok(())
+    }
+}
+
+impl CanonicalSerialize for CodeLocation {
+    fn serialize(&self, serializer: &mut impl CanonicalSerializer) -> Result<()> {
+        serializer
+            .encode_optional(&self.module)?
+            .encode_optional(&self.function)?;
+        // Encoded like an optional value: there is no CanonicalSerialize for u16.
+        match self.function_definition_index {
+            Some(idx) => serializer.encode_bool(true)?.encode_u16(idx)?,
+            None => serializer.encode_bool(false)?,
+        };
+        serializer
+            .encode_u16(self.code_offset)?
+            .encode_u64(self.call_stack_depth)?;
+        Ok(())
+    }
 }
 
 /// A status type is one of 5 different variants, along with a fallback variant in the case that we
@@ -99,6 +166,10 @@ impl fmt::Display for VMStatus {
             status = format!("{} and message {}", status, msg);
         }
 
+        if let Some(ref location) = self.location {
+            status = format!("{} in {}", status, location);
+        }
+
         write!(f, "{}", status)
     }
 }
@@ -112,6 +183,7 @@ impl VMStatus {
             major_status,
             sub_status: None,
             message: None,
+            location: None,
         }
     }
 
@@ -127,6 +199,12 @@ impl VMStatus {
         self
     }
 
+    /// Adds the location in Move code the status refers to to the VM status.
+    pub fn with_location(mut self, location: CodeLocation) -> Self {
+        self.location = Some(location);
+        self
+    }
+
     /// Mutates the VMStatus sub status field to be the new `sub_status` passed in.
     pub fn set_sub_status(&mut self, sub_status: u64) {
         self.sub_status = Some(sub_status);
@@ -218,6 +296,10 @@ impl TryFrom<crate::proto::types::VmStatus> for VMStatus {
             status.set_message(proto.message);
         }
 
+        if let Some(location) = proto.location {
+            status.location = Some(CodeLocation::try_from(location)?);
+        }
+
         Ok(status)
     }
 }
@@ -244,10 +326,60 @@ impl From<VMStatus> for crate::proto::types::VmStatus {
             proto_status.message = string;
         }
 
+        proto_status.location = status.location.map(Into::into);
+
         proto_status
     }
 }
 
+impl TryFrom<crate::proto::types::CodeLocation> for CodeLocation {
+    type Error = Error;
+
+    fn try_from(proto: crate::proto::types::CodeLocation) -> Result<Self> {
+        let max_index = u32::from(std::u16::MAX);
+        if proto.function_definition_index > max_index {
+            bail!(
+                "Function definition index {} is out of range",
+                proto.function_definition_index
+            );
+        }
+        if proto.code_offset > max_index {
+            bail!("Code offset {} is out of range", proto.code_offset);
+        }
+        Ok(Self {
+            module: proto.module.map(ModuleId::try_from).transpose()?,
+            function: if proto.function.is_empty() {
+                None
+            } else {
+                Some(Identifier::new(proto.function)?)
+            },
+            function_definition_index: if proto.has_function_definition_index {
+                Some(proto.function_definition_index as u16)
+            } else {
+                None
+            },
+            code_offset: proto.code_offset as u16,
+            call_stack_depth: proto.call_stack_depth,
+        })
+    }
+}
+
+impl From<CodeLocation> for crate::proto::types::CodeLocation {
+    fn from(location: CodeLocation) -> Self {
+        Self {
+            module: location.module.map(Into::into),
+            function: location
+                .function
+                .map(Identifier::into_string)
+                .unwrap_or_default(),
+            code_offset: location.code_offset.into(),
+            call_stack_depth: location.call_stack_depth,
+            has_function_definition_index: location.function_definition_index.is_some(),
+            function_definition_index: location.function_definition_index.unwrap_or(0).into(),
+        }
+    }
+}
+
 #[allow(non_camel_case_types)]
 #[derive(
     Clone,
@@ -309,6 +441,13 @@ pub enum StatusCode {
     // Gas unit price submitted with the transaction is above the maximum
     // gas price set in the VM.
     GAS_UNIT_PRICE_ABOVE_MAX_BOUND = 16,
+    // The nonce of a nonce transaction has already been used, or is below the smallest nonce
+    // the sender still accepts.
+    NONCE_ALREADY_USED = 17,
+    // The sender of a nonce transaction has not enabled nonce transactions.
+    NONCES_NOT_ENABLED = 18,
+    // The account sending the transaction has been frozen.
+    SENDING_ACCOUNT_FROZEN = 19,
 
     // When a code module/script is published it is verified. These are the
     // possible errors that can arise from the verification process.
@@ -395,6 +534,8 @@ pub enum StatusCode {
     LOOP_IN_INSTANTIATION_GRAPH = 1076,
     UNUSED_LOCALS_SIGNATURE = 1077,
     UNUSED_TYPE_SIGNATURE = 1078,
+    // The verifier did more work on a module than its budget allows.
+    VERIFIER_BUDGET_EXCEEDED = 1079,
 
     // These are errors that the VM might raise if a violation of internal
     // invariants takes place.
@@ -458,6 +599,9 @@ pub enum StatusCode {
     EXECUTION_STACK_OVERFLOW = 4020,
     CALL_STACK_OVERFLOW = 4021,
     NATIVE_FUNCTION_ERROR = 4022,
+    // The sender is trying to publish a new version of a module `M` that breaks code linked
+    // against the version already published, or values of its types stored on chain.
+    INCOMPATIBLE_MODULE_UPDATE = 4023,
 
     // A reserved status to represent an unknown vm status.
     UNKNOWN_STATUS = std::u64::MAX,